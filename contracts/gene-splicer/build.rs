@@ -0,0 +1,79 @@
+//! Generates `INTERFACE_HASH`, the SHA-256 of the contract's public interface
+//!
+//! The interface is every entrypoint signature (`pub fn name(env: Env, ...)`,
+//! the same convention the capabilities test relies on) and every
+//! `#[contracttype]`, `#[contractevent]` and `#[contracterror]` definition.
+//! Comments and whitespace are dropped and the entries sorted, so reformatting
+//! or moving code between modules keeps the hash; changing a signature or a
+//! type doesn't.
+
+use sha2::{Digest, Sha256};
+use std::{env, fs, path::Path};
+
+const TYPE_ATTRIBUTES: &[&str] = &["#[contracttype]", "#[contractevent]", "#[contracterror]"];
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+
+    let mut entries = Vec::new();
+    let mut paths: Vec<_> = fs::read_dir("src")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.file_name().unwrap() != "test.rs")
+        .collect();
+    paths.sort();
+    for path in paths {
+        let source = strip_comments(&fs::read_to_string(&path).unwrap());
+        entries.extend(entrypoints(&source));
+        entries.extend(type_definitions(&source));
+    }
+    entries.sort();
+
+    let hash: [u8; 32] = Sha256::digest(entries.join("\n").as_bytes()).into();
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("interface_hash.rs");
+    fs::write(
+        out,
+        format!("pub const INTERFACE_HASH: [u8; 32] = {:?};\n", hash),
+    )
+    .unwrap();
+}
+
+fn strip_comments(source: &str) -> String {
+    source
+        .lines()
+        .map(|line| line.find("//").map_or(line, |i| &line[..i]))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn normalize(item: &str) -> String {
+    item.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Signatures of `pub fn name(env: Env, ...)` methods, up to their opening brace
+fn entrypoints(source: &str) -> Vec<String> {
+    let mut signatures = Vec::new();
+    for (i, _) in source.match_indices("\n    pub fn ") {
+        let rest = &source[i + 1..];
+        let open = rest.find('(').unwrap();
+        let args = rest[open + 1..].trim_start();
+        if args.starts_with("env: Env") || args.starts_with("_env: Env") {
+            let body = rest.find('{').unwrap();
+            signatures.push(normalize(&rest[..body]));
+        }
+    }
+    signatures
+}
+
+/// Top-level items carrying a contract type attribute, through their closing brace
+fn type_definitions(source: &str) -> Vec<String> {
+    let mut definitions = Vec::new();
+    for attribute in TYPE_ATTRIBUTES {
+        for (i, _) in source.match_indices(attribute) {
+            let rest = &source[i..];
+            let end = rest.find("\n}").unwrap() + 2;
+            definitions.push(normalize(&rest[..end]));
+        }
+    }
+    definitions
+}
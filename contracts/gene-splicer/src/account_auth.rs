@@ -0,0 +1,56 @@
+//! Structured auth arguments for owner actions
+//!
+//! Owner actions call `require_auth_for_args` with a short, fixed tuple rather
+//! than the entrypoint's own arguments. A classic account signs the tuple like
+//! any invocation. A custom account contract (smart wallet, passkey wallet)
+//! receives it as the `args` of the contract context passed to
+//! `__check_auth`. It can then apply its own policy per action type, for
+//! example allowing finalization but not burning, without decoding each
+//! entrypoint's parameters. The context's `fn_name` is still the entrypoint.
+//!
+//! The first element always names the action:
+//!
+//! | Action     | Args                                        | Entrypoints                                    |
+//! |------------|---------------------------------------------|------------------------------------------------|
+//! | `finalize` | (`finalize`, cartridge_id: u32)             | finalize_splice, finalize_splice_or_report, dev_finalize, request_finalization, finalize_with_permit without a permit |
+//! | `list`     | (`list`, cartridge_id: u32, price: i128)    | sell_finalization_right; revoke_finalization_right with price 0 |
+//! | `burn`     | (`burn`, creature_id: u32)                  | retire_creature                                |
+//! | `export`   | (`export`, creature_id: u32)                | export_asset                                   |
+//! | `transfer` | (`transfer`, id: u32, to: Address)          | transfer_cartridge, transfer_creature          |
+//! | `approve`  | (`approve`, id: u32, spender: Address)      | approve                                        |
+//!
+//! Entrypoints that spend the caller's tokens (minting, buying) keep plain
+//! `require_auth`, so the wallet sees the token transfer as a sub-invocation.
+
+use soroban_sdk::{symbol_short, Address, Env, IntoVal};
+
+/// Require `finalizer`'s auth for finalizing `cartridge_id`
+pub(crate) fn require_finalize(env: &Env, finalizer: &Address, cartridge_id: u32) {
+    finalizer.require_auth_for_args((symbol_short!("finalize"), cartridge_id).into_val(env));
+}
+
+/// Require `owner`'s auth for listing `cartridge_id`'s finalization right at `price` (0 withdraws it)
+pub(crate) fn require_list(env: &Env, owner: &Address, cartridge_id: u32, price: i128) {
+    owner.require_auth_for_args((symbol_short!("list"), cartridge_id, price).into_val(env));
+}
+
+/// Require `owner`'s auth for permanently retiring `creature_id`
+pub(crate) fn require_burn(env: &Env, owner: &Address, creature_id: u32) {
+    owner.require_auth_for_args((symbol_short!("burn"), creature_id).into_val(env));
+}
+
+/// Require `owner`'s auth for exporting `creature_id` to another deployment
+pub(crate) fn require_export(env: &Env, owner: &Address, creature_id: u32) {
+    owner.require_auth_for_args((symbol_short!("export"), creature_id).into_val(env));
+}
+
+/// Require `owner`'s auth for sending cartridge or creature `id` to `to`
+pub(crate) fn require_transfer(env: &Env, owner: &Address, id: u32, to: &Address) {
+    owner.require_auth_for_args((symbol_short!("transfer"), id, to.clone()).into_val(env));
+}
+
+/// Require `owner`'s auth for approving `spender` to transfer `token_id`
+pub(crate) fn require_approve(env: &Env, owner: &Address, token_id: u32, spender: &Address) {
+    owner
+        .require_auth_for_args((symbol_short!("approve"), token_id, spender.clone()).into_val(env));
+}
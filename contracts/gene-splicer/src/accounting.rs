@@ -0,0 +1,195 @@
+//! Liabilities ledger and stranded-token recovery
+//!
+//! Subsystems that hold balances at the contract address on behalf of users
+//! record what they owe per token here, so the solvency report covers every
+//! pool by construction. Only the burn reward pool exists today; the insurance,
+//! bounty, escrow and refund pools are reserved for subsystems that will hold
+//! funds the same way, and always report zero until one credits them.
+//! Anything the contract holds above the summed liabilities is free balance —
+//! typically tokens sent to the contract by mistake — and only that part can
+//! be withdrawn by the admin.
+
+use crate::{
+    configured, events, reentrancy,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, contracttype, panic_with_error, token, Address,
+    Env, Vec,
+};
+
+/// Pool accounting failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AccountingError {
+    InsufficientFreeBalance = 18, // Withdrawal would dip into tracked pool liabilities
+    LiabilityOutOfRange = 107,    // A pool released more than it owes, or its liability overflowed
+}
+
+/// Accounted pools whose balances are owed to users
+/// Only BurnRewards is credited today; the others are reserved (see the module docs)
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Pool {
+    Insurance,
+    Bounty,
+    Escrow,
+    Refund,
+    BurnRewards,
+}
+
+/// Every pool, in the order liabilities are summed
+pub const POOLS: [Pool; 5] = [
+    Pool::Insurance,
+    Pool::Bounty,
+    Pool::Escrow,
+    Pool::Refund,
+    Pool::BurnRewards,
+];
+
+/// What one pool owes, as listed in a SolvencyReport
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolLiability {
+    pub pool: Pool,
+    pub amount: i128,
+}
+
+/// Contract XLM holdings against what its pools owe
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolvencyReport {
+    pub balance: i128,                   // Actual XLM balance of the contract address
+    pub liabilities: Vec<PoolLiability>, // One entry per pool, in POOLS order
+    pub total_liabilities: i128,
+    pub free_balance: i128, // balance - total_liabilities; the most admin_withdraw_token can take
+}
+
+/// Event emitted when the admin recovers stranded tokens
+#[contractevent]
+pub struct StrandedTokensWithdrawn {
+    pub token: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub remaining_free: i128, // Free balance left after the withdrawal
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Withdraw tokens held by the contract that no pool accounts for (Owner only)
+    /// Refuses any amount that would leave the balance below tracked liabilities
+    pub fn admin_withdraw_token(env: Env, token: Address, to: Address, amount: i128) {
+        reentrancy::enter(&env);
+        require_role(&env, Role::Owner);
+
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let free = Self::get_free_balance(env.clone(), token.clone());
+        if amount > free {
+            panic_with_error!(&env, AccountingError::InsufficientFreeBalance);
+        }
+
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+
+        events::publish(&env, |nonce| StrandedTokensWithdrawn {
+            token,
+            to,
+            amount,
+            remaining_free: free - amount,
+            nonce,
+        });
+        reentrancy::exit(&env);
+    }
+
+    /// Get the amount a single pool owes in `token`
+    pub fn get_liability(env: Env, pool: Pool, token: Address) -> i128 {
+        read_liability(&env, pool, &token)
+    }
+
+    /// Get the summed liabilities of every pool in `token`
+    pub fn get_total_liabilities(env: Env, token: Address) -> i128 {
+        total_liabilities(&env, &token)
+    }
+
+    /// Get the contract's balance of `token` not backing any liability
+    /// Negative if the contract is insolvent for that token
+    pub fn get_free_balance(env: Env, token: Address) -> i128 {
+        let balance = token::Client::new(&env, &token).balance(&env.current_contract_address());
+        balance - total_liabilities(&env, &token)
+    }
+
+    /// Get the XLM solvency report: actual balance, each pool's liability and the free balance
+    pub fn get_solvency(env: Env) -> Result<SolvencyReport, Error> {
+        let xlm_token: Address = configured(&env, &DataKey::XlmToken)?;
+        let balance = token::Client::new(&env, &xlm_token).balance(&env.current_contract_address());
+
+        let mut liabilities = Vec::new(&env);
+        let mut total_liabilities = 0;
+        for pool in POOLS {
+            let amount = read_liability(&env, pool, &xlm_token);
+            total_liabilities += amount;
+            liabilities.push_back(PoolLiability { pool, amount });
+        }
+
+        Ok(SolvencyReport {
+            balance,
+            liabilities,
+            total_liabilities,
+            free_balance: balance - total_liabilities,
+        })
+    }
+}
+
+/// Record that `pool` now owes `amount` more of `token`
+/// Called by pool subsystems after receiving funds at the contract address
+pub(crate) fn add_liability(env: &Env, pool: Pool, token: &Address, amount: i128) {
+    let current = read_liability(env, pool, token);
+    write_liability(
+        env,
+        pool,
+        token,
+        current
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, AccountingError::LiabilityOutOfRange)),
+    );
+}
+
+/// Record that `pool` paid out `amount` of `token`
+/// Called by pool subsystems before sending funds from the contract address
+pub(crate) fn release_liability(env: &Env, pool: Pool, token: &Address, amount: i128) {
+    let current = read_liability(env, pool, token);
+    if amount > current {
+        panic_with_error!(env, AccountingError::LiabilityOutOfRange);
+    }
+    write_liability(env, pool, token, current - amount);
+}
+
+fn total_liabilities(env: &Env, token: &Address) -> i128 {
+    POOLS
+        .iter()
+        .map(|pool| read_liability(env, *pool, token))
+        .sum()
+}
+
+fn read_liability(env: &Env, pool: Pool, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Liability(pool, token.clone()))
+        .unwrap_or(0)
+}
+
+fn write_liability(env: &Env, pool: Pool, token: &Address, amount: i128) {
+    let key = DataKey::Liability(pool, token.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
@@ -0,0 +1,96 @@
+//! Single-call account bootstrap for game sessions
+//!
+//! At login the game client needs the player's creatures, the cartridges
+//! still waiting on them, their points and inbox, and the contract-wide state
+//! that decides what they can do next. `bootstrap` assembles all of it from
+//! the per-user indexes in one simulated call instead of about eight reads.
+//!
+//! Loading is capped at MAX_BOOTSTRAP_ENTRIES cartridge and creature records.
+//! Both sections walk their index newest first. Pending cartridges may use up
+//! to half of the budget, and owned creatures get the rest. A section that
+//! stops early reports a cursor: the entries at positions below it in
+//! `get_user_cartridges` / `get_user_creatures` were not loaded, and the
+//! client fetches them with `get_cartridge_status` / `get_creature_thumbs`.
+//! A cursor of 0 means the section is complete.
+//!
+//! Sections for subsystems that aren't configured come back empty or at their
+//! defaults, so the struct has the same shape on every deployment.
+
+use crate::{
+    cartridge_status::{CartridgeState, CartridgeStatus},
+    CreatureThumb, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, MAX_BOOTSTRAP_ENTRIES,
+};
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+/// A cartridge of the user's that still needs finalizing
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAction {
+    pub cartridge_id: u32,
+    pub status: CartridgeStatus, // NotReady or Ready, with the timings to display
+}
+
+/// Everything a game client reads about an account at login
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bootstrap {
+    pub creatures: Vec<CreatureThumb>, // Owned creatures, newest first
+    pub creatures_cursor: u32,         // Unloaded positions in get_user_creatures (0 = complete)
+    pub pending: Vec<PendingAction>,   // Unfinalized cartridges, newest first
+    pub pending_cursor: u32,           // Unscanned positions in get_user_cartridges (0 = complete)
+    pub points: u64,
+    pub inbox_count: u32, // Notifications waiting in read_inbox
+    pub gene_table_epoch: u32,
+    pub mint_paused: bool,
+    pub finalize_paused: bool,
+    pub maintenance_start: Option<u64>, // Upcoming or running maintenance window (see get_maintenance)
+    pub maintenance_end: Option<u64>,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get a user's creatures, pending cartridges, balances and the contract state in one call
+    /// Loads at most Limits.max_bootstrap_entries records; see the cursors for the rest
+    pub fn bootstrap(env: Env, user: Address) -> Bootstrap {
+        let cartridge_ids = Self::get_user_cartridges(env.clone(), user.clone());
+        let mut pending = Vec::new(&env);
+        let mut pending_cursor = cartridge_ids.len();
+        let mut loaded = 0;
+        while pending_cursor > 0 && loaded < MAX_BOOTSTRAP_ENTRIES / 2 {
+            pending_cursor -= 1;
+            loaded += 1;
+            let cartridge_id = cartridge_ids.get_unchecked(pending_cursor);
+            let status = Self::get_cartridge_status(env.clone(), cartridge_id);
+            if status.state != CartridgeState::Finalized {
+                pending.push_back(PendingAction {
+                    cartridge_id,
+                    status,
+                });
+            }
+        }
+
+        let creature_ids = Self::get_user_creatures(env.clone(), user.clone());
+        let mut newest_first = Vec::new(&env);
+        let mut creatures_cursor = creature_ids.len();
+        while creatures_cursor > 0 && loaded < MAX_BOOTSTRAP_ENTRIES {
+            creatures_cursor -= 1;
+            loaded += 1;
+            newest_first.push_back(creature_ids.get_unchecked(creatures_cursor));
+        }
+
+        let maintenance = Self::get_maintenance(env.clone());
+        Bootstrap {
+            creatures: Self::get_creature_thumbs(env.clone(), newest_first),
+            creatures_cursor,
+            pending,
+            pending_cursor,
+            points: Self::get_points(env.clone(), user.clone()),
+            inbox_count: Self::read_inbox(env.clone(), user).len(),
+            gene_table_epoch: Self::get_gene_table_epoch(env.clone()),
+            mint_paused: Self::is_mint_paused(env.clone()),
+            finalize_paused: Self::is_finalize_paused(env.clone()),
+            maintenance_start: maintenance.as_ref().map(|window| window.start),
+            maintenance_end: maintenance.map(|window| window.end),
+        }
+    }
+}
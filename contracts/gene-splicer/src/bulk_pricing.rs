@@ -0,0 +1,147 @@
+//! Wholesale pricing for batch mints
+//!
+//! A tier table maps a minimum cartridge count to a discount in basis points.
+//! A batch gets the discount of the highest tier its count reaches. By default,
+//! 5+ cartridges get 10% off and 15+ get 20%. Tiers must be sorted by strictly
+//! increasing count with non-decreasing discounts, so buying more never costs
+//! more per unit.
+//!
+//! Should another discount apply to the same mint, the buyer gets whichever is
+//! better, never both.
+//!
+//! `splice_genome_batch` mints up to MAX_SPLICE_BATCH cartridges at the quoted
+//! total in one token transfer. Each cartridge rolls its own skin; all share one
+//! future drand round, so one beacon finalizes the whole batch.
+//!
+//! Its events follow the contract-wide batch rule (see the crate docs): up to
+//! BATCH_EVENT_THRESHOLD cartridges emit one `CartridgeMinted` each, larger
+//! batches a single `BatchCartridgeMinted` carrying every skin ID and the
+//! quoted unit price, which keeps a ten-cartridge mint from emitting ten events.
+
+use crate::{
+    collections, config_log, fee_rounding, reentrancy,
+    roles::{require_role, Role},
+    DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, MAX_SPLICE_BATCH,
+    ORIGINAL_COLLECTION,
+};
+use soroban_sdk::{contracterror, contractimpl, contracttype, panic_with_error, Address, Env, Vec};
+
+/// Most tiers the table can hold
+pub const MAX_BULK_TIERS: u32 = 8;
+
+/// Discounts are expressed in basis points of the mint fee
+const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Bulk pricing and batch mint failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BulkPricingError {
+    InvalidDiscountTiers = 48, // Bulk tiers unsorted, non-monotone, over 100% or too many
+    InvalidBatchSize = 105,    // splice_genome_batch count is 0 or above Limits.max_splice_batch
+}
+
+/// Price of a batch of `count` cartridges
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchQuote {
+    pub count: u32,
+    pub discount_bps: u32, // Discount of the tier the count reaches (0 below every tier)
+    pub unit_price: i128,  // Effective price per cartridge, in stroops
+    pub total: i128,       // unit_price * count
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Replace the bulk discount tiers, as (min_count, discount_bps) pairs (Owner only)
+    /// Counts must strictly increase, discounts must not decrease and stay at most 10_000 bps
+    pub fn set_bulk_discounts(env: Env, tiers: Vec<(u32, u32)>) {
+        let actor = require_role(&env, Role::Owner);
+        if tiers.len() > MAX_BULK_TIERS {
+            panic_with_error!(&env, BulkPricingError::InvalidDiscountTiers);
+        }
+        let mut previous: Option<(u32, u32)> = None;
+        for (min_count, discount_bps) in tiers.iter() {
+            let unordered =
+                previous.is_some_and(|(count, bps)| min_count <= count || discount_bps < bps);
+            if min_count == 0 || discount_bps > BPS_DENOMINATOR || unordered {
+                panic_with_error!(&env, BulkPricingError::InvalidDiscountTiers);
+            }
+            previous = Some((min_count, discount_bps));
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::BulkDiscounts, &tiers);
+        config_log::record(&env, &actor, "bulk_discounts", tiers);
+    }
+
+    /// Get the bulk discount tiers, or the default 5+ at 10% / 15+ at 20%
+    pub fn get_bulk_discounts(env: Env) -> Vec<(u32, u32)> {
+        env.storage()
+            .instance()
+            .get(&DataKey::BulkDiscounts)
+            .unwrap_or_else(|| Vec::from_array(&env, [(5, 1_000), (15, 2_000)]))
+    }
+
+    /// Quote the fee for minting `count` cartridges in one batch
+    pub fn quote_batch_fee(env: Env, count: u32) -> BatchQuote {
+        quote(&env, count)
+    }
+
+    /// Mint `count` cartridges (1 to Limits.max_splice_batch) for the quoted batch total
+    /// Returns the new cartridge IDs, which are contiguous and share one drand round
+    pub fn splice_genome_batch(env: Env, user: Address, count: u32) -> Vec<u32> {
+        reentrancy::enter(&env);
+        user.require_auth();
+        if count == 0 || count > MAX_SPLICE_BATCH {
+            panic_with_error!(&env, BulkPricingError::InvalidBatchSize);
+        }
+
+        let quote = quote(&env, count);
+        Self::charge_mint_fee(&env, &user, quote.total);
+        let skin_count = collections::skin_count(&env, ORIGINAL_COLLECTION);
+        let (splice_round, dev_entropy) = Self::assign_splice_round(&env, count);
+        let mut cartridge_ids = Vec::new(&env);
+        let mut skin_ids = Vec::new(&env);
+        for _ in 0..count {
+            let skin_id = Self::select_skin(&env, skin_count);
+            let cartridge = Self::store_cartridge(
+                &env,
+                &user,
+                ORIGINAL_COLLECTION,
+                skin_id,
+                splice_round,
+                dev_entropy,
+            );
+            cartridge_ids.push_back(cartridge.id);
+            skin_ids.push_back(skin_id);
+        }
+        Self::list_cartridges(&env, &user, &cartridge_ids);
+
+        let first_id = cartridge_ids.first_unchecked();
+        Self::publish_cartridges_minted(&env, &user, first_id, &skin_ids, quote.unit_price);
+        reentrancy::exit(&env);
+        cartridge_ids
+    }
+}
+
+/// Price `count` cartridges at the discount of the highest tier they reach
+pub(crate) fn quote(env: &Env, count: u32) -> BatchQuote {
+    let discount_bps = GeneSplicer::get_bulk_discounts(env.clone())
+        .iter()
+        .filter(|(min_count, _)| count >= *min_count)
+        .map(|(_, discount_bps)| discount_bps)
+        .last()
+        .unwrap_or(0);
+    let unit_price = fee_rounding::apply(
+        env,
+        GeneSplicer::get_mint_fee(env.clone()) * (BPS_DENOMINATOR - discount_bps) as i128
+            / BPS_DENOMINATOR as i128,
+    );
+    BatchQuote {
+        count,
+        discount_bps,
+        unit_price,
+        total: unit_price * count as i128,
+    }
+}
@@ -0,0 +1,206 @@
+//! XLM rewards for burning (retiring) creatures
+//!
+//! Retiring a creature pays its owner a small XLM reward by rarity, summed
+//! over the three genes, on top of the retirement points. Rewards come out of
+//! the `BurnRewards` accounting pool, which anyone can top up with
+//! `fund_burn_rewards` (typically the Owner, from collected fees). Payouts in
+//! one epoch (`BURN_REWARD_EPOCH` seconds of ledger time) are capped by the
+//! schedule's epoch budget, so the pool can't be drained in one go.
+//!
+//! A reward that would exceed the epoch budget, or that the pool can't cover,
+//! is skipped with a `BurnRewardSkipped` event; the retirement itself still
+//! goes through.
+
+use crate::{
+    accounting::{self, Pool},
+    config_log, events, reentrancy, require_configured,
+    roles::{require_role, Role},
+    Creature, DataKey, Error, GeneRarity, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+    TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contractevent, contractimpl, contracttype, panic_with_error, token, Address, Env, Symbol,
+};
+
+/// Length of a burn reward budget epoch in seconds (7 days)
+pub const BURN_REWARD_EPOCH: u64 = 604_800;
+
+/// Default reward per gene of each rarity, in stroops (0.1 / 0.3 / 1 XLM)
+pub const DEFAULT_BURN_REWARD_NORMAL: i128 = 1_000_000;
+pub const DEFAULT_BURN_REWARD_RARE: i128 = 3_000_000;
+pub const DEFAULT_BURN_REWARD_LEGENDARY: i128 = 10_000_000;
+
+/// Default cap on rewards paid per epoch, in stroops (100 XLM)
+pub const DEFAULT_BURN_REWARD_EPOCH_BUDGET: i128 = 1_000_000_000;
+
+/// Per-gene burn rewards and the epoch budget, in stroops
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BurnRewardSchedule {
+    pub normal: i128,
+    pub rare: i128,
+    pub legendary: i128,
+    pub epoch_budget: i128, // Most paid out across all burns in one epoch (0 = rewards off)
+}
+
+/// Event emitted when the burn reward pool is topped up
+#[contractevent]
+pub struct BurnRewardsFunded {
+    pub from: Address,
+    pub amount: i128,
+    pub pool_balance: i128, // Pool balance after the top-up
+    pub nonce: u64,
+}
+
+/// Event emitted when a burn pays its reward
+#[contractevent]
+pub struct BurnRewardPaid {
+    pub creature_id: u32,
+    pub owner: Address,
+    pub amount: i128,
+    pub epoch: u64,
+    pub nonce: u64,
+}
+
+/// Event emitted when a burn's reward is skipped; the burn itself succeeded
+#[contractevent]
+pub struct BurnRewardSkipped {
+    pub creature_id: u32,
+    pub owner: Address,
+    pub amount: i128,   // Reward the burn would have paid
+    pub reason: Symbol, // "budget" (epoch budget exhausted) or "pool" (pool can't cover it)
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set the per-gene burn rewards and the epoch budget (Owner only)
+    pub fn set_burn_reward_schedule(env: Env, schedule: BurnRewardSchedule) {
+        let actor = require_role(&env, Role::Owner);
+        if schedule.normal < 0
+            || schedule.rare < 0
+            || schedule.legendary < 0
+            || schedule.epoch_budget < 0
+        {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::BurnRewardSchedule, &schedule);
+        config_log::record(&env, &actor, "burn_reward_schedule", schedule);
+    }
+
+    /// Get the per-gene burn rewards and the epoch budget
+    pub fn get_burn_reward_schedule(env: Env) -> BurnRewardSchedule {
+        env.storage()
+            .instance()
+            .get(&DataKey::BurnRewardSchedule)
+            .unwrap_or(BurnRewardSchedule {
+                normal: DEFAULT_BURN_REWARD_NORMAL,
+                rare: DEFAULT_BURN_REWARD_RARE,
+                legendary: DEFAULT_BURN_REWARD_LEGENDARY,
+                epoch_budget: DEFAULT_BURN_REWARD_EPOCH_BUDGET,
+            })
+    }
+
+    /// Move `amount` stroops of XLM from `from` into the burn reward pool
+    pub fn fund_burn_rewards(env: Env, from: Address, amount: i128) {
+        reentrancy::enter(&env);
+        from.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+
+        let xlm_token: Address = require_configured(&env, &DataKey::XlmToken);
+        token::Client::new(&env, &xlm_token).transfer(
+            &from,
+            env.current_contract_address(),
+            &amount,
+        );
+        accounting::add_liability(&env, Pool::BurnRewards, &xlm_token, amount);
+
+        let pool_balance = Self::get_liability(env.clone(), Pool::BurnRewards, xlm_token);
+        events::publish(&env, |nonce| BurnRewardsFunded {
+            from,
+            amount,
+            pool_balance,
+            nonce,
+        });
+        reentrancy::exit(&env);
+    }
+
+    /// Get the current burn reward epoch
+    pub fn get_burn_reward_epoch(env: Env) -> u64 {
+        env.ledger().timestamp() / BURN_REWARD_EPOCH
+    }
+
+    /// Get the burn rewards paid out in `epoch`, in stroops
+    pub fn get_burn_reward_spent(env: Env, epoch: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BurnRewardSpent(epoch))
+            .unwrap_or(0)
+    }
+}
+
+/// Pay the owner of a just-retired creature its burn reward, or skip it with an event
+pub(crate) fn pay(env: &Env, creature: &Creature, owner: &Address) {
+    let schedule = GeneSplicer::get_burn_reward_schedule(env.clone());
+    let amount = reward(&schedule, creature);
+    if amount == 0 {
+        return;
+    }
+
+    let epoch = GeneSplicer::get_burn_reward_epoch(env.clone());
+    let spent = GeneSplicer::get_burn_reward_spent(env.clone(), epoch);
+    let xlm_token: Address = require_configured(env, &DataKey::XlmToken);
+    let pool = GeneSplicer::get_liability(env.clone(), Pool::BurnRewards, xlm_token.clone());
+    let skipped = if spent + amount > schedule.epoch_budget {
+        Some("budget")
+    } else if amount > pool {
+        Some("pool")
+    } else {
+        None
+    };
+    if let Some(reason) = skipped {
+        events::publish(env, |nonce| BurnRewardSkipped {
+            creature_id: creature.id,
+            owner: owner.clone(),
+            amount,
+            reason: Symbol::new(env, reason),
+            nonce,
+        });
+        return;
+    }
+
+    let key = DataKey::BurnRewardSpent(epoch);
+    env.storage().persistent().set(&key, &(spent + amount));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    accounting::release_liability(env, Pool::BurnRewards, &xlm_token, amount);
+    token::Client::new(env, &xlm_token).transfer(&env.current_contract_address(), owner, &amount);
+
+    events::publish(env, |nonce| BurnRewardPaid {
+        creature_id: creature.id,
+        owner: owner.clone(),
+        amount,
+        epoch,
+        nonce,
+    });
+}
+
+fn reward(schedule: &BurnRewardSchedule, creature: &Creature) -> i128 {
+    [
+        &creature.head_gene,
+        &creature.body_gene,
+        &creature.legs_gene,
+    ]
+    .iter()
+    .map(|gene| match gene.rarity {
+        GeneRarity::Normal => schedule.normal,
+        GeneRarity::Rare => schedule.rare,
+        GeneRarity::Legendary => schedule.legendary,
+    })
+    .sum()
+}
@@ -0,0 +1,316 @@
+//! Machine-readable auth and fund-flow summary of every entrypoint
+//!
+//! Wallet security reviewers can query `get_capabilities` instead of reading
+//! the source. The table below is maintained by hand; a unit test cross-checks
+//! it against the contract's entrypoints, so adding a function without listing
+//! it here fails the test suite.
+
+use crate::{GeneSplicer, GeneSplicerArgs, GeneSplicerClient};
+use soroban_sdk::{contractimpl, contracttype, Env, Symbol, Vec};
+
+/// What calling an entrypoint requires and what it can change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FunctionCapability {
+    pub name: Symbol,
+    pub user_auth: bool,         // Requires auth from a user-supplied address (owner, buyer, relayer, voter)
+    pub admin_auth: bool,        // Requires auth from the Owner (stored admin)
+    pub operator_auth: bool,     // Requires auth from the Operator (the Owner while none is assigned)
+    pub moves_tokens: bool,      // Calls a token contract to transfer funds
+    pub mutates_ownership: bool, // Creates NFTs or changes who owns / will own them
+}
+
+struct Capability {
+    name: &'static str,
+    user_auth: bool,
+    admin_auth: bool,
+    operator_auth: bool,
+    moves_tokens: bool,
+    mutates_ownership: bool,
+}
+
+const fn cap(
+    name: &'static str,
+    user_auth: bool,
+    admin_auth: bool,
+    moves_tokens: bool,
+    mutates_ownership: bool,
+) -> Capability {
+    Capability {
+        name,
+        user_auth,
+        admin_auth,
+        operator_auth: false,
+        moves_tokens,
+        mutates_ownership,
+    }
+}
+
+/// Read-only or permissionless: no auth, no funds, no ownership changes
+const fn view(name: &'static str) -> Capability {
+    cap(name, false, false, false, false)
+}
+
+/// Owner-only configuration change
+const fn owner(name: &'static str) -> Capability {
+    cap(name, false, true, false, false)
+}
+
+/// Operational change allowed to the Operator
+const fn operator(name: &'static str) -> Capability {
+    Capability {
+        operator_auth: true,
+        ..cap(name, false, false, false, false)
+    }
+}
+
+#[rustfmt::skip]
+//  name                                        user   admin  tokens owner
+const CAPABILITIES: &[Capability] = &[
+    view("__constructor"),
+    cap("splice_genome",                        true,  false, true,  true),
+    cap("splice_and_list",                      true,  false, true,  true),
+    cap("splice_genome_with_points",            true,  false, true,  true),
+    cap("splice_genome_ref",                    true,  false, true,  true),
+    cap("splice_genome_batch",                  true,  false, true,  true),
+    cap("mint_companion",                       true,  false, true,  true),
+    cap("splice_collection",                    true,  false, true,  true),
+    cap("storefront_mint",                      true,  false, false, true),
+    cap("settle_storefront",                    true,  false, true,  false),
+    cap("claim_free_mint",                      true,  false, false, true),
+    cap("finalize_splice",                      true,  false, true,  true),
+    cap("finalize_with_permit",                 true,  false, true,  true),
+    cap("finalize_splice_or_report",            true,  false, true,  true),
+    cap("finalize_with_entropy",                true,  false, true,  true),
+    cap("process_queue",                        false, false, false, true),
+    cap("dev_finalize",                         true,  false, false, true),
+    cap("dev_splice_and_finalize",              true,  false, true,  true),
+    cap("register_session_key",                 true,  false, false, false),
+    cap("sell_finalization_right",              true,  false, false, false),
+    cap("revoke_finalization_right",            true,  false, false, false),
+    cap("buy_finalization_right",               true,  false, true,  true),
+    cap("cast_vote",                            true,  false, false, false),
+    cap("admin_withdraw_token",                 false, true,  true,  false),
+    owner("set_admin"),
+    owner("set_operator"),
+    owner("upgrade"),
+    owner("set_skin_count"),
+    owner("set_skin_cap"),
+    owner("set_drand_public_key"),
+    owner("set_drand_chain_hash"),
+    owner("set_derivation_version"),
+    owner("set_free_mint_attestor"),
+    owner("set_verification_share"),
+    owner("set_metadata_operator"),
+    owner("set_max_assignments_per_round"),
+    owner("set_layer_table"),
+    owner("set_reveal_time"),
+    owner("set_points_rate"),
+    owner("set_round_substitution"),
+    owner("finalize_configuration"),
+    owner("set_bulk_discounts"),
+    owner("create_collection"),
+    owner("register_consumer"),
+    owner("unregister_consumer"),
+    owner("set_consumer_read_fee"),
+    owner("set_companion_fee"),
+    owner("set_fee_rounding"),
+    owner("set_mint_fee"),
+    owner("set_treasury"),
+    owner("set_max_finalization_lag"),
+    owner("set_max_randomness_age"),
+    owner("set_trusted_source"),
+    owner("set_burn_reward_schedule"),
+    owner("register_storefront"),
+    owner("suspend_storefront"),
+    owner("commit_gene_table"),
+    owner("set_game_operator"),
+    owner("schedule_maintenance"),
+    owner("cancel_maintenance"),
+    owner("bootstrap_config"),
+    owner("apply_gene_table"),
+    owner("trigger_reveal"),
+    owner("approve_metadata_correction"),
+    operator("set_mint_paused"),
+    operator("set_finalize_paused"),
+    operator("set_paused"),
+    operator("set_free_mint_enabled"),
+    operator("create_proposal"),
+    operator("snapshot_weights"),
+    operator("create_drawing"),
+    operator("set_feature_enabled"),
+    operator("repair"),
+    view("extend_ttl"),
+    view("gc"),
+    view("begin_verification"),
+    view("resolve_drawing"),
+    cap("complete_verification",                true,  false, false, false),
+    cap("submit_entropy",                       true,  false, false, false),
+    cap("anchor_metadata",                      true,  false, false, false),
+    cap("request_metadata_correction",          true,  false, false, false),
+    cap("clear_inbox",                          true,  false, false, false),
+    cap("request_finalization",                 true,  false, false, false),
+    cap("set_privacy",                          true,  false, false, false),
+    cap("substitute_round",                     true,  false, false, false),
+    cap("update_collection",                    true,  false, false, false),
+    cap("retire_creature",                      true,  false, true,  false),
+    cap("export_asset",                         true,  false, false, true),
+    cap("import_asset",                         true,  false, false, true),
+    cap("transfer_cartridge",                   true,  false, false, true),
+    cap("transfer_creature",                    true,  false, false, true),
+    cap("transfer_from",                        true,  false, false, true),
+    cap("approve",                              true,  false, false, false),
+    cap("revoke_approval",                      true,  false, false, false),
+    cap("fund_burn_rewards",                    true,  false, true,  false),
+    cap("grant_xp",                             true,  false, false, false),
+    cap("consume_randomness",                   true,  false, true,  false),
+    view("is_round_verified"),
+    view("diagnose_verification"),
+    view("admin"),
+    view("operator"),
+    view("get_cartridge"),
+    view("has_cartridge"),
+    view("is_finalized"),
+    view("is_finalized_fast"),
+    view("get_finalized_bitmap"),
+    view("repair_finalized_bitmap"),
+    view("get_cartridges_batch"),
+    view("get_user_cartridges"),
+    view("get_user_cartridges_page"),
+    view("get_user_cartridge_count"),
+    view("get_cartridges_range"),
+    view("lookup_by_ref"),
+    view("get_companion"),
+    view("get_companion_parent"),
+    view("get_approved"),
+    view("check_integrity"),
+    view("get_entropy"),
+    view("get_companion_fee"),
+    view("get_fee_rounding"),
+    view("get_mint_fee"),
+    view("get_treasury"),
+    view("get_total_cartridges"),
+    view("get_pending_details"),
+    view("get_creature"),
+    view("has_creature"),
+    view("get_creatures_batch"),
+    view("get_creature_thumbs"),
+    view("get_creatures_by_gene"),
+    view("get_creatures_by_gene_count"),
+    view("get_user_creatures"),
+    view("get_user_creatures_page"),
+    view("get_user_creature_count"),
+    view("is_mint_paused"),
+    view("is_finalize_paused"),
+    view("get_skin_supply"),
+    view("get_available_skins"),
+    view("get_skin_count"),
+    view("get_drand_public_key"),
+    view("get_drand_chain_hash"),
+    view("get_derivation_version"),
+    view("get_cartridge_derivation_version"),
+    view("get_session_key"),
+    view("permit_message"),
+    view("free_mint_message"),
+    view("is_free_mint_enabled"),
+    view("is_free_mint_eligible"),
+    view("get_free_mint_count"),
+    view("get_finalization_right_price"),
+    view("get_voting_weight"),
+    view("get_snapshot_weight"),
+    view("tally"),
+    view("get_proposal"),
+    view("get_liability"),
+    view("get_total_liabilities"),
+    view("get_free_balance"),
+    view("get_solvency"),
+    view("get_limits"),
+    view("get_config_log"),
+    view("get_verification_share"),
+    view("get_verification_cost_cap"),
+    view("get_round_verifier"),
+    view("get_round_reimbursed"),
+    view("get_max_assignments_per_round"),
+    view("get_round_assignments"),
+    view("get_reveal_time"),
+    view("is_revealed"),
+    view("get_layer_table"),
+    view("get_render_layers"),
+    view("get_metadata"),
+    view("get_metadata_operator"),
+    view("get_metadata_hash"),
+    view("get_metadata_correction"),
+    view("get_metadata_provenance"),
+    view("get_dev_mode"),
+    view("is_production_ready"),
+    view("get_capabilities"),
+    view("read_inbox"),
+    view("get_points_rate"),
+    view("get_points"),
+    view("get_round_substitution_delay"),
+    view("get_max_round_substitution"),
+    view("is_configuration_final"),
+    view("get_configuration_finalized_at"),
+    view("are_operations_locked"),
+    view("get_last_finalize_error"),
+    view("get_bulk_discounts"),
+    view("quote_batch_fee"),
+    view("get_collection"),
+    view("get_collection_count"),
+    view("get_collection_minted"),
+    view("get_collection_cartridges"),
+    view("is_consumer_registered"),
+    view("get_consumer_read_fee"),
+    view("get_max_finalization_lag"),
+    view("get_max_randomness_age"),
+    view("get_cartridge_status"),
+    view("get_storefront"),
+    view("get_storefront_debt"),
+    view("is_creature_retired"),
+    view("get_gene_table_commitment"),
+    view("get_gene_table_epoch"),
+    view("get_gene_table"),
+    view("get_event_nonce"),
+    view("can_finalize"),
+    view("get_game_operator"),
+    view("get_creature_xp"),
+    view("get_level"),
+    view("get_finalization_receipt"),
+    view("get_maintenance"),
+    view("bootstrap"),
+    view("get_drawing"),
+    view("get_features"),
+    view("is_private"),
+    view("get_queue_position"),
+    view("get_queue_length"),
+    view("get_provenance_hash"),
+    view("get_locks"),
+    view("get_burn_reward_schedule"),
+    view("get_burn_reward_epoch"),
+    view("get_burn_reward_spent"),
+    view("confirm_export"),
+    view("is_trusted_source"),
+    view("get_import_origin"),
+    view("verify_provenance_segment"),
+    view("interface_hash"),
+    view("assert_interface"),
+];
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get the auth requirements and side effects of every entrypoint
+    pub fn get_capabilities(env: Env) -> Vec<FunctionCapability> {
+        let mut capabilities = Vec::new(&env);
+        for entry in CAPABILITIES {
+            capabilities.push_back(FunctionCapability {
+                name: Symbol::new(&env, entry.name),
+                user_auth: entry.user_auth,
+                admin_auth: entry.admin_auth,
+                operator_auth: entry.operator_auth,
+                moves_tokens: entry.moves_tokens,
+                mutates_ownership: entry.mutates_ownership,
+            });
+        }
+        capabilities
+    }
+}
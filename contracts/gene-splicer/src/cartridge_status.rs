@@ -0,0 +1,112 @@
+//! Cartridge lifecycle state, shared by the status getter and finalization
+//!
+//! A cartridge is NotReady until its drand round is emitted, Ready from then
+//! until it is finalized, and Finalized after that. Round timing comes from the
+//! drand genesis and period constants. dev_entropy cartridges are Ready from
+//! mint, since their round was already public. `state` is the one place this is
+//! decided. Finalization refuses anything but Ready through `check_ready`, so
+//! the UI's view and the contract's checks can't disagree.
+//!
+//! A Ready cartridge becomes overdue once it has waited longer than the
+//! configurable maximum finalization lag. Overdue cartridges can still be
+//! finalized; the lag only tells the UI when to nudge the holder.
+
+use crate::{
+    config_log, drand_round_time,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge,
+};
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Env};
+
+/// Default seconds a cartridge may sit ready before it counts as overdue (7 days)
+pub const DEFAULT_MAX_FINALIZATION_LAG: u64 = 604_800;
+
+/// Where a cartridge is in its lifecycle
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CartridgeState {
+    NotReady,  // Drand round not emitted yet
+    Ready,     // Round emitted, can be finalized
+    Finalized, // Turned into a Creature
+}
+
+/// A cartridge's state with the timings a UI needs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CartridgeStatus {
+    pub state: CartridgeState,
+    pub ready_since: Option<u64>, // When the cartridge became finalizable; None while NotReady
+    pub seconds_until_ready: Option<u64>, // Wait left while NotReady
+    pub seconds_overdue: Option<u64>, // Time Ready past the maximum finalization lag, once exceeded
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set how long a cartridge may sit ready before it counts as overdue, in seconds (Owner only)
+    pub fn set_max_finalization_lag(env: Env, seconds: u64) {
+        let actor = require_role(&env, Role::Owner);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxFinalizationLag, &seconds);
+        config_log::record(&env, &actor, "max_finalization_lag", seconds);
+    }
+
+    /// Get how long a cartridge may sit ready before it counts as overdue (seconds)
+    pub fn get_max_finalization_lag(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxFinalizationLag)
+            .unwrap_or(DEFAULT_MAX_FINALIZATION_LAG)
+    }
+
+    /// Get a cartridge's lifecycle state and timings at the current ledger time
+    pub fn get_cartridge_status(env: Env, cartridge_id: u32) -> CartridgeStatus {
+        let cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Cartridge(cartridge_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CartridgeNotFound));
+
+        let now = env.ledger().timestamp();
+        let ready_at = ready_at(&cartridge);
+        let state = state(&env, &cartridge);
+        let lag = Self::get_max_finalization_lag(env.clone());
+        CartridgeStatus {
+            state,
+            ready_since: (state != CartridgeState::NotReady).then_some(ready_at),
+            seconds_until_ready: (state == CartridgeState::NotReady).then(|| ready_at - now),
+            seconds_overdue: (state == CartridgeState::Ready)
+                .then(|| now.saturating_sub(ready_at.saturating_add(lag)))
+                .filter(|overdue| *overdue > 0),
+        }
+    }
+}
+
+/// The cartridge's state at the current ledger time
+pub(crate) fn state(env: &Env, cartridge: &GenomeCartridge) -> CartridgeState {
+    if cartridge.finalized {
+        CartridgeState::Finalized
+    } else if env.ledger().timestamp() < ready_at(cartridge) {
+        CartridgeState::NotReady
+    } else {
+        CartridgeState::Ready
+    }
+}
+
+/// The error finalizing the cartridge now would fail with, if it isn't Ready
+pub(crate) fn check_ready(env: &Env, cartridge: &GenomeCartridge) -> Result<(), Error> {
+    match state(env, cartridge) {
+        CartridgeState::Ready => Ok(()),
+        CartridgeState::NotReady => Err(Error::RoundNotReady),
+        CartridgeState::Finalized => Err(Error::AlreadyFinalized),
+    }
+}
+
+/// When the cartridge's round is emitted; dev_entropy rounds were public at mint
+fn ready_at(cartridge: &GenomeCartridge) -> u64 {
+    if cartridge.dev_entropy {
+        cartridge.created_at
+    } else {
+        drand_round_time(cartridge.splice_round)
+    }
+}
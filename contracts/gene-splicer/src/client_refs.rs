@@ -0,0 +1,51 @@
+//! Client-chosen mint references
+//!
+//! A mobile client that loses the transaction result (app killed mid-submit)
+//! can't tell which cartridge its mint produced. `splice_genome_ref` mints
+//! like `splice_genome` and remembers the cartridge ID under a 32-byte
+//! reference the client picked before submitting, so `lookup_by_ref` can
+//! recover it afterwards.
+//!
+//! References live in temporary storage for CLIENT_REF_TTL ledgers and are
+//! unique while they live: minting again with a live reference fails with
+//! ClientRefInUse. Once expired, a reference looks unused and may be reused.
+//! Clients should pick references at random; a lookup only says which
+//! cartridge was minted, so check its owner before trusting it.
+
+use crate::{gc, reentrancy, DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient};
+use soroban_sdk::{contractimpl, panic_with_error, Address, BytesN, Env};
+
+/// Ledgers a client reference is kept (~3 days)
+pub const CLIENT_REF_TTL: u32 = 51_840;
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Mint a cartridge like splice_genome, recording its ID under `client_ref`
+    /// Fails with ClientRefInUse while the reference is still recorded. Returns the cartridge ID
+    pub fn splice_genome_ref(env: Env, user: Address, client_ref: BytesN<32>) -> u32 {
+        reentrancy::enter(&env);
+        user.require_auth();
+
+        let key = DataKey::ClientRef(client_ref);
+        if env.storage().temporary().has(&key) {
+            panic_with_error!(&env, Error::ClientRefInUse);
+        }
+        let cartridge_id = Self::splice_authorized(&env, user, Self::get_mint_fee(env.clone()));
+
+        env.storage().temporary().set(&key, &cartridge_id);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, CLIENT_REF_TTL, CLIENT_REF_TTL);
+        let expiry = env.ledger().sequence() + CLIENT_REF_TTL;
+        gc::track(&env, "client_ref", key, expiry.into());
+        reentrancy::exit(&env);
+        cartridge_id
+    }
+
+    /// Get the cartridge minted with `client_ref`, while the reference is recorded
+    pub fn lookup_by_ref(env: Env, client_ref: BytesN<32>) -> Option<u32> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::ClientRef(client_ref))
+    }
+}
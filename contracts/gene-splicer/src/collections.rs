@@ -0,0 +1,260 @@
+//! Themed collections sharing one deployment
+//!
+//! Seasonal and partner drops run as collections next to the original one
+//! instead of as separate contracts. Each collection has its own mint fee,
+//! skin count and supply cap, and an admin who can retune them. Cartridge and
+//! creature IDs stay global: every cartridge records its `collection_id`, and
+//! the creature it becomes inherits it.
+//!
+//! Collection 0 is the original collection. It is configured by the contract's
+//! own settings (`set_mint_fee`, `set_skin_count`), has no cap, and keeps every
+//! cartridge minted before collections existed, so those need no migration.
+//! Created collections are numbered from 1 and keep a mint counter plus an
+//! index of their cartridge IDs, chunked like the gene index.
+//!
+//! Skin IDs and skin caps are shared across collections; a collection with a
+//! skin count of N draws from skins 0..N of the same art table.
+
+use crate::{
+    config_log, reentrancy, require_configured,
+    roles::{require_role, Role},
+    DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, MAX_COLLECTION_PAGE, TTL_EXTEND_TO,
+    TTL_THRESHOLD,
+};
+use soroban_sdk::{contracterror, contractimpl, contracttype, panic_with_error, Address, Env, Vec};
+
+/// Cartridge IDs per collection index chunk entry
+pub const COLLECTION_INDEX_CHUNK: u32 = 100;
+
+/// The original collection, configured by the contract-wide settings
+pub const ORIGINAL_COLLECTION: u32 = 0;
+
+/// Collection lookup and configuration failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum CollectionError {
+    CollectionNotFound = 50,      // No created collection with this ID
+    CollectionSoldOut = 51,       // Collection has minted its supply cap
+    InvalidCollectionParams = 52, // Negative fee, no skins, or cap below the cartridges minted
+}
+
+/// Mint rules of a collection
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollectionParams {
+    pub fee: i128,       // Mint fee in stroops (0 = free)
+    pub skin_count: u64, // Skins 0..skin_count the collection draws from
+    pub supply_cap: u32, // Most cartridges the collection can mint (0 = uncapped)
+}
+
+/// A collection created with `create_collection`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Collection {
+    pub id: u32,
+    pub admin: Address, // May retune the collection's params
+    pub params: CollectionParams,
+    pub created_at: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Create a collection run by `admin` (Owner only)
+    /// Returns the collection ID, starting at 1
+    pub fn create_collection(env: Env, admin: Address, params: CollectionParams) -> u32 {
+        let actor = require_role(&env, Role::Owner);
+        require_valid(&env, &params, 0);
+
+        let collection_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextCollectionId)
+            .unwrap_or(1);
+        let collection = Collection {
+            id: collection_id,
+            admin,
+            params,
+            created_at: env.ledger().timestamp(),
+        };
+        write_collection(&env, &collection);
+        env.storage().instance().set(
+            &DataKey::NextCollectionId,
+            &collection_id
+                .checked_add(1)
+                .expect("Collection ID overflow"),
+        );
+        config_log::record(&env, &actor, "collection", collection);
+        collection_id
+    }
+
+    /// Replace a collection's params (collection admin)
+    /// The supply cap can't drop below the cartridges already minted
+    pub fn update_collection(env: Env, collection_id: u32, params: CollectionParams) {
+        let mut collection = read_collection(&env, collection_id);
+        collection.admin.require_auth();
+        require_valid(
+            &env,
+            &params,
+            Self::get_collection_minted(env.clone(), collection_id),
+        );
+
+        collection.params = params;
+        write_collection(&env, &collection);
+        let actor = collection.admin.clone();
+        config_log::record(&env, &actor, "collection", collection);
+    }
+
+    /// Mint a cartridge in `collection_id` at that collection's fee
+    /// Collection 0 is the original collection and behaves exactly like splice_genome
+    pub fn splice_collection(env: Env, user: Address, collection_id: u32) -> u32 {
+        reentrancy::enter(&env);
+        user.require_auth();
+        let cartridge_id = if collection_id == ORIGINAL_COLLECTION {
+            Self::splice_authorized(&env, user, Self::get_mint_fee(env.clone()))
+        } else {
+            let collection = read_collection(&env, collection_id);
+            let cap = collection.params.supply_cap;
+            if cap > 0 && Self::get_collection_minted(env.clone(), collection_id) >= cap {
+                panic_with_error!(&env, CollectionError::CollectionSoldOut);
+            }
+            Self::charge_mint_fee(&env, &user, collection.params.fee);
+            Self::mint_cartridge(&env, user, collection_id)
+        };
+        reentrancy::exit(&env);
+        cartridge_id
+    }
+
+    /// Get a created collection (None for the original collection 0 and unknown IDs)
+    pub fn get_collection(env: Env, collection_id: u32) -> Option<Collection> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Collection(collection_id))
+    }
+
+    /// Get the number of collections created, not counting the original one
+    pub fn get_collection_count(env: Env) -> u32 {
+        let next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextCollectionId)
+            .unwrap_or(1);
+        next_id - 1
+    }
+
+    /// Get the number of cartridges minted in a collection
+    /// For collection 0 this is every cartridge not minted in a created collection
+    pub fn get_collection_minted(env: Env, collection_id: u32) -> u32 {
+        if collection_id == ORIGINAL_COLLECTION {
+            let in_created: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::CreatedCollectionsMinted)
+                .unwrap_or(0);
+            return Self::get_total_cartridges(env.clone()) - in_created;
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::CollectionMinted(collection_id))
+            .unwrap_or(0)
+    }
+
+    /// Page through a created collection's cartridge IDs, in mint order
+    /// Returns at most `limit` IDs (capped at Limits.max_collection_page) starting at position `offset`
+    /// The original collection isn't indexed; its cartridges are the global IDs outside every created collection
+    pub fn get_collection_cartridges(
+        env: Env,
+        collection_id: u32,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u32> {
+        read_collection(&env, collection_id);
+        let count = Self::get_collection_minted(env.clone(), collection_id);
+        let end = offset
+            .saturating_add(limit.min(MAX_COLLECTION_PAGE))
+            .min(count);
+
+        let mut page = Vec::new(&env);
+        let mut position = offset;
+        while position < end {
+            let chunk_index = position / COLLECTION_INDEX_CHUNK;
+            let chunk = read_chunk(&env, collection_id, chunk_index);
+            let chunk_end = end.min((chunk_index + 1) * COLLECTION_INDEX_CHUNK);
+            for i in position..chunk_end {
+                page.push_back(chunk.get(i % COLLECTION_INDEX_CHUNK).unwrap());
+            }
+            position = chunk_end;
+        }
+        page
+    }
+}
+
+/// Skin count new cartridges in `collection_id` draw from
+pub(crate) fn skin_count(env: &Env, collection_id: u32) -> u64 {
+    if collection_id == ORIGINAL_COLLECTION {
+        return require_configured(env, &DataKey::CartridgeSkinCount);
+    }
+    read_collection(env, collection_id).params.skin_count
+}
+
+/// Count a newly minted cartridge against its collection and append it to the collection index
+pub(crate) fn record_mint(env: &Env, collection_id: u32, cartridge_id: u32) {
+    if collection_id == ORIGINAL_COLLECTION {
+        return;
+    }
+    let count_key = DataKey::CollectionMinted(collection_id);
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+    let chunk_index = count / COLLECTION_INDEX_CHUNK;
+    let chunk_key = DataKey::CollectionCartridgeChunk(collection_id, chunk_index);
+    let mut chunk = read_chunk(env, collection_id, chunk_index);
+    chunk.push_back(cartridge_id);
+
+    env.storage().persistent().set(&chunk_key, &chunk);
+    env.storage()
+        .persistent()
+        .extend_ttl(&chunk_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    env.storage().persistent().set(&count_key, &(count + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&count_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+    let in_created: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::CreatedCollectionsMinted)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::CreatedCollectionsMinted, &(in_created + 1));
+}
+
+fn require_valid(env: &Env, params: &CollectionParams, minted: u32) {
+    let below_minted = params.supply_cap > 0 && params.supply_cap < minted;
+    if params.fee < 0 || params.skin_count == 0 || below_minted {
+        panic_with_error!(env, CollectionError::InvalidCollectionParams);
+    }
+}
+
+fn read_collection(env: &Env, collection_id: u32) -> Collection {
+    GeneSplicer::get_collection(env.clone(), collection_id)
+        .unwrap_or_else(|| panic_with_error!(env, CollectionError::CollectionNotFound))
+}
+
+fn write_collection(env: &Env, collection: &Collection) {
+    let key = DataKey::Collection(collection.id);
+    env.storage().persistent().set(&key, collection);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+fn read_chunk(env: &Env, collection_id: u32, chunk_index: u32) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CollectionCartridgeChunk(
+            collection_id,
+            chunk_index,
+        ))
+        .unwrap_or(Vec::new(env))
+}
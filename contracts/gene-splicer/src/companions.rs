@@ -0,0 +1,131 @@
+//! Companion cartridges for creature holders
+//!
+//! The owner of a creature can mint one companion cartridge for it, for the
+//! companion fee. The companion is an ordinary cartridge in the original
+//! collection, finalized like any other, but it stays linked to its parent
+//! creature for good: `get_companion` and `get_companion_parent` traverse the
+//! link both ways, and it survives either side changing hands: holding the
+//! parent is only checked at mint time.
+//!
+//! Each creature gets at most one companion, and a companion can't be the
+//! parent of another. Retired creatures and creatures under any lock (such as
+//! an export) can't take companions.
+
+use crate::{
+    config_log, events, features, fee_rounding, locks, reentrancy, retirement,
+    roles::{require_role, Role},
+    Creature, DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, ORIGINAL_COLLECTION,
+    TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contracterror, contractevent, contractimpl, panic_with_error, Address, Env};
+
+/// Default companion mint fee in stroops (0.5 XLM)
+pub const DEFAULT_COMPANION_FEE: i128 = 5_000_000;
+
+/// Companion mint failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum CompanionError {
+    CompanionAlreadyMinted = 90, // The parent creature already has its companion
+    CompanionCannotBeParent = 91, // mint_companion with a companion as the parent
+}
+
+/// Event emitted alongside CartridgeMinted when the cartridge is a companion
+#[contractevent]
+pub struct CompanionMinted {
+    pub cartridge_id: u32,
+    pub parent_id: u32,
+    pub owner: Address,
+    pub fee: i128,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Mint the companion cartridge of a creature `owner` holds, paying the companion fee
+    /// Returns the companion's cartridge ID
+    pub fn mint_companion(env: Env, owner: Address, parent_creature_id: u32) -> u32 {
+        features::require(&env, "companions");
+        reentrancy::enter(&env);
+        owner.require_auth();
+
+        let parent: Creature = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Creature(parent_creature_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CreatureNotFound));
+        if parent.owner != owner {
+            panic_with_error!(&env, Error::NotCreatureOwner);
+        }
+        if Self::get_companion_parent(env.clone(), parent_creature_id).is_some() {
+            panic_with_error!(&env, CompanionError::CompanionCannotBeParent);
+        }
+        if Self::get_companion(env.clone(), parent_creature_id).is_some() {
+            panic_with_error!(&env, CompanionError::CompanionAlreadyMinted);
+        }
+        retirement::assert_active(&env, parent_creature_id);
+        if let Err(error) = locks::require_unlocked(&env, parent_creature_id) {
+            panic_with_error!(&env, error);
+        }
+
+        let fee = fee_rounding::apply(&env, Self::get_companion_fee(env.clone()));
+        Self::charge_mint_fee(&env, &owner, fee);
+        let cartridge_id = Self::mint_cartridge(&env, owner.clone(), ORIGINAL_COLLECTION);
+        link(&env, DataKey::Companion(parent_creature_id), cartridge_id);
+        link(
+            &env,
+            DataKey::CompanionParent(cartridge_id),
+            parent_creature_id,
+        );
+
+        events::publish(&env, |nonce| CompanionMinted {
+            cartridge_id,
+            parent_id: parent_creature_id,
+            owner,
+            fee,
+            nonce,
+        });
+        reentrancy::exit(&env);
+        cartridge_id
+    }
+
+    /// Get the companion cartridge minted for a creature, if any
+    pub fn get_companion(env: Env, parent_creature_id: u32) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Companion(parent_creature_id))
+    }
+
+    /// Get the parent creature of a companion cartridge (None if it isn't a companion)
+    pub fn get_companion_parent(env: Env, cartridge_id: u32) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CompanionParent(cartridge_id))
+    }
+
+    /// Set the companion mint fee in stroops (Owner only, 0 = free)
+    pub fn set_companion_fee(env: Env, fee: i128) {
+        let actor = require_role(&env, Role::Owner);
+        if fee < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        env.storage().instance().set(&DataKey::CompanionFee, &fee);
+        config_log::record(&env, &actor, "companion_fee", fee);
+    }
+
+    /// Get the companion mint fee in stroops
+    pub fn get_companion_fee(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CompanionFee)
+            .unwrap_or(DEFAULT_COMPANION_FEE)
+    }
+}
+
+fn link(env: &Env, key: DataKey, id: u32) {
+    env.storage().persistent().set(&key, &id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
@@ -0,0 +1,156 @@
+//! One-call configuration of a fresh deployment
+//!
+//! Standing up an environment used to take the constructor plus a dozen
+//! setter transactions. `bootstrap_config` takes every Owner-configurable
+//! section at once and applies the present ones through the regular setters,
+//! so each keeps its own validation, config log entry and events. Any failure
+//! reverts the whole call: a deployment is configured completely or not at all.
+//!
+//! It is only available before the first cartridge is minted. After that,
+//! configuration goes through the individual setters. The Operator section is
+//! applied last, because the Operator takes over operational entrypoints as
+//! soon as it is assigned.
+
+use crate::{events, roles, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, LayerRule};
+use soroban_sdk::{
+    contractevent, contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Symbol, Vec,
+};
+
+/// Every Owner-configurable section of a deployment; None leaves a section at its default
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FullBootstrap {
+    pub skin_count: Option<u64>,
+    pub skin_caps: Option<Vec<(u32, Option<u32>)>>, // (skin ID, cap) as for set_skin_cap
+    pub drand_chain_hash: Option<BytesN<32>>,
+    pub derivation_version: Option<u32>,
+    pub max_assignments_per_round: Option<u32>,
+    pub round_substitution_delay: Option<u64>, // Either half alone keeps the other's current value
+    pub round_substitution_max_offset: Option<u64>,
+    pub max_finalization_lag: Option<u64>,
+    pub max_randomness_age: Option<u64>,
+    pub bulk_discounts: Option<Vec<(u32, u32)>>,
+    pub points_rate: Option<u64>,
+    pub consumer_read_fee: Option<i128>,
+    pub verification_share: Option<i128>, // Either half alone keeps the other's current value
+    pub verification_cost_cap: Option<i128>,
+    pub layer_table: Option<Vec<LayerRule>>,
+    pub reveal_time: Option<u64>,
+    pub game_operator: Option<Address>,
+    pub metadata_operator: Option<Address>,
+    pub operator: Option<Address>,
+}
+
+/// Event emitted once per bootstrap, naming the sections that were applied
+#[contractevent]
+pub struct BootstrapApplied {
+    pub sections: Vec<Symbol>,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Apply a full deployment configuration atomically (Owner only)
+    /// Refused with BootstrapClosed once any cartridge has been minted
+    pub fn bootstrap_config(env: Env, config: FullBootstrap) {
+        let sections = roles::as_owner(&env, || {
+            if Self::get_total_cartridges(env.clone()) > 0 {
+                panic_with_error!(&env, Error::BootstrapClosed);
+            }
+
+            let mut sections = Vec::new(&env);
+            let mut applied = |name: &str| sections.push_back(Symbol::new(&env, name));
+            if let Some(count) = config.skin_count {
+                Self::set_skin_count(env.clone(), count);
+                applied("skin_count");
+            }
+            if let Some(caps) = config.skin_caps {
+                for (skin_id, cap) in caps.iter() {
+                    Self::set_skin_cap(env.clone(), skin_id, cap);
+                }
+                applied("skin_caps");
+            }
+            if let Some(hash) = config.drand_chain_hash {
+                Self::set_drand_chain_hash(env.clone(), hash);
+                applied("drand_chain_hash");
+            }
+            if let Some(version) = config.derivation_version {
+                Self::set_derivation_version(env.clone(), version);
+                applied("derivation_version");
+            }
+            if let Some(max) = config.max_assignments_per_round {
+                Self::set_max_assignments_per_round(env.clone(), max);
+                applied("max_assignments_per_round");
+            }
+            if config.round_substitution_delay.is_some()
+                || config.round_substitution_max_offset.is_some()
+            {
+                Self::set_round_substitution(
+                    env.clone(),
+                    config
+                        .round_substitution_delay
+                        .unwrap_or_else(|| Self::get_round_substitution_delay(env.clone())),
+                    config
+                        .round_substitution_max_offset
+                        .unwrap_or_else(|| Self::get_max_round_substitution(env.clone())),
+                );
+                applied("round_substitution");
+            }
+            if let Some(seconds) = config.max_finalization_lag {
+                Self::set_max_finalization_lag(env.clone(), seconds);
+                applied("max_finalization_lag");
+            }
+            if let Some(seconds) = config.max_randomness_age {
+                Self::set_max_randomness_age(env.clone(), seconds);
+                applied("max_randomness_age");
+            }
+            if let Some(tiers) = config.bulk_discounts {
+                Self::set_bulk_discounts(env.clone(), tiers);
+                applied("bulk_discounts");
+            }
+            if let Some(rate) = config.points_rate {
+                Self::set_points_rate(env.clone(), rate);
+                applied("points_rate");
+            }
+            if let Some(fee) = config.consumer_read_fee {
+                Self::set_consumer_read_fee(env.clone(), fee);
+                applied("consumer_read_fee");
+            }
+            if config.verification_share.is_some() || config.verification_cost_cap.is_some() {
+                Self::set_verification_share(
+                    env.clone(),
+                    config
+                        .verification_share
+                        .unwrap_or_else(|| Self::get_verification_share(env.clone())),
+                    config
+                        .verification_cost_cap
+                        .unwrap_or_else(|| Self::get_verification_cost_cap(env.clone())),
+                );
+                applied("verification_share");
+            }
+            if let Some(rules) = config.layer_table {
+                Self::set_layer_table(env.clone(), rules);
+                applied("layer_table");
+            }
+            if let Some(time) = config.reveal_time {
+                Self::set_reveal_time(env.clone(), Some(time));
+                applied("reveal_time");
+            }
+            if let Some(operator) = config.game_operator {
+                Self::set_game_operator(env.clone(), Some(operator));
+                applied("game_operator");
+            }
+            if let Some(operator) = config.metadata_operator {
+                Self::set_metadata_operator(env.clone(), Some(operator));
+                applied("metadata_operator");
+            }
+            if let Some(operator) = config.operator {
+                Self::set_operator(env.clone(), Some(operator));
+                applied("operator");
+            }
+            sections
+        });
+
+        events::publish(&env, |nonce| BootstrapApplied { sections, nonce });
+    }
+}
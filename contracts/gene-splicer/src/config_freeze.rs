@@ -0,0 +1,100 @@
+//! Irreversible configuration freeze
+//!
+//! After `finalize_configuration`, the collection's rules can no longer change:
+//! fees, skins and caps, drand key and network, derivation version, layer table,
+//! reveal time and the WASM itself are fixed for good. Every configuration
+//! change goes through `config_log::record`, which calls `require_mutable`, so
+//! a setter added later is frozen too. Because a panic reverts the whole
+//! invocation, checking at record time is as strong as checking up front.
+//!
+//! Some changes stay available afterwards:
+//! - Role rotation (`admin`, `operator`) always works. A new Owner inherits the
+//!   freeze and can't undo it.
+//! - Operational switches (pausing, maintenance windows, feature switches,
+//!   the free mint program, storefront suspension) keep working unless the
+//!   freeze was declared with `lock_operations`.
+
+use crate::{
+    events,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{contractevent, contractimpl, panic_with_error, Env};
+
+/// Changes that remain possible after the freeze
+const ROLE_CHANGES: &[&str] = &["admin", "operator"];
+
+/// Changes that remain possible after the freeze unless operations were locked too
+const OPERATIONAL_CHANGES: &[&str] = &[
+    "mint_paused",
+    "finalize_paused",
+    "free_mint_enabled",
+    "storefront_suspended",
+    "maintenance",
+    "feature",
+];
+
+/// Event emitted once when the Owner permanently freezes the configuration
+#[contractevent]
+pub struct ConfigurationFrozen {
+    pub finalized_at: u64,
+    pub operations_locked: bool,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Permanently freeze the configuration (Owner only, irreversible)
+    /// With `lock_operations`, pausing and the free mint switch are frozen as well
+    pub fn finalize_configuration(env: Env, lock_operations: bool) {
+        require_role(&env, Role::Owner);
+        if Self::is_configuration_final(env.clone()) {
+            panic_with_error!(&env, Error::ConfigurationFinalized);
+        }
+        let finalized_at = env.ledger().timestamp();
+        env.storage()
+            .instance()
+            .set(&DataKey::ConfigurationFinalizedAt, &finalized_at);
+        env.storage()
+            .instance()
+            .set(&DataKey::OperationsLocked, &lock_operations);
+        events::publish(&env, |nonce| ConfigurationFrozen {
+            finalized_at,
+            operations_locked: lock_operations,
+            nonce,
+        });
+    }
+
+    /// Whether `finalize_configuration` has been called
+    pub fn is_configuration_final(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .has(&DataKey::ConfigurationFinalizedAt)
+    }
+
+    /// Get the ledger timestamp at which the configuration was frozen
+    pub fn get_configuration_finalized_at(env: Env) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ConfigurationFinalizedAt)
+    }
+
+    /// Whether operational switches were frozen along with the configuration
+    pub fn are_operations_locked(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::OperationsLocked)
+            .unwrap_or(false)
+    }
+}
+
+/// Refuse `change` with ConfigurationFinalized if the freeze covers it
+pub(crate) fn require_mutable(env: &Env, change: &str) {
+    if !GeneSplicer::is_configuration_final(env.clone()) || ROLE_CHANGES.contains(&change) {
+        return;
+    }
+    if OPERATIONAL_CHANGES.contains(&change) && !GeneSplicer::are_operations_locked(env.clone()) {
+        return;
+    }
+    panic_with_error!(env, Error::ConfigurationFinalized);
+}
@@ -0,0 +1,86 @@
+//! On-chain changelog of admin configuration changes
+//!
+//! Every admin mutation goes through `record`, which both appends to a bounded
+//! log (the last `MAX_CONFIG_LOG` entries) and emits `ConfigChanged`, so the
+//! queryable history and the event stream can't drift apart. Each entry stores
+//! SHA256 of the new value's XDR rather than the value itself.
+
+use crate::{
+    config_freeze, events, DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO,
+    TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contractevent, contractimpl, contracttype, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec,
+};
+
+/// Number of most recent changes kept in the log
+pub const MAX_CONFIG_LOG: u32 = 100;
+
+/// One admin configuration change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigLogEntry {
+    pub timestamp: u64,
+    pub actor: Address,           // Owner or Operator that made the change
+    pub change: Symbol,           // Setting that changed, e.g. "mint_paused"
+    pub summary_hash: BytesN<32>, // SHA256 of the new value's XDR
+}
+
+/// Event emitted for every admin configuration change
+#[contractevent]
+pub struct ConfigChanged {
+    pub change: Symbol,
+    pub actor: Address,
+    pub summary_hash: BytesN<32>,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Page through the config log, oldest entry first
+    /// `offset` counts from the oldest retained entry
+    pub fn get_config_log(env: Env, offset: u32, limit: u32) -> Vec<ConfigLogEntry> {
+        let log = read_log(&env);
+        let start = offset.min(log.len());
+        let end = start.saturating_add(limit).min(log.len());
+        log.slice(start..end)
+    }
+}
+
+/// Append a change to the log and emit `ConfigChanged`
+/// `value` is the setting's new value; its XDR hash is what gets recorded
+/// Panics with ConfigurationFinalized if the configuration freeze covers `change`
+pub(crate) fn record<T: ToXdr>(env: &Env, actor: &Address, change: &str, value: T) {
+    config_freeze::require_mutable(env, change);
+    let change = Symbol::new(env, change);
+    let summary_hash: BytesN<32> = env.crypto().sha256(&value.to_xdr(env)).into();
+
+    let mut log = read_log(env);
+    if log.len() >= MAX_CONFIG_LOG {
+        log.pop_front();
+    }
+    log.push_back(ConfigLogEntry {
+        timestamp: env.ledger().timestamp(),
+        actor: actor.clone(),
+        change: change.clone(),
+        summary_hash: summary_hash.clone(),
+    });
+    env.storage().persistent().set(&DataKey::ConfigLog, &log);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::ConfigLog, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+    events::publish(env, |nonce| ConfigChanged {
+        change,
+        actor: actor.clone(),
+        summary_hash,
+        nonce,
+    });
+}
+
+fn read_log(env: &Env) -> Vec<ConfigLogEntry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ConfigLog)
+        .unwrap_or(Vec::new(env))
+}
@@ -0,0 +1,132 @@
+//! Registry of contracts allowed to read verified drand randomness
+//!
+//! Every round this contract verifies costs a BLS pairing, paid by whoever
+//! verified it. Partner contracts may reuse that work, but only once the Owner
+//! has registered them, and optionally for a per-read XLM fee that goes to the
+//! treasury like the mint fee. Reads go through `consume_randomness`, which needs
+//! the consumer's auth (implicit when the consumer contract calls directly) and
+//! emits `RandomnessConsumed` so usage can be accounted for. While a fee is
+//! set, the consumer must also authorize the XLM transfer
+//! (`authorize_as_current_contract`).
+//!
+//! Only rounds verified within the last day are available; the randomness is
+//! derived from the verified signature and expires with the verified-round cache.
+//! Consumers never see the raw randomness: each read names a context tag and
+//! entity ID and gets `derive_from_round` of the round for them, so reads
+//! under different contexts are independent of each other and of the genes.
+
+use crate::{
+    config_log, events, features, fee_rounding, mint_fee, reentrancy, require_configured,
+    roles::{require_role, Role},
+    verification, DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO,
+    TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, panic_with_error, token, Address, BytesN, Env,
+    Symbol,
+};
+
+/// Randomness consumer failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ConsumerError {
+    ConsumerNotRegistered = 53, // consume_randomness by a contract the Owner hasn't registered
+    RandomnessNotVerified = 54, // Round isn't in the verified-round cache
+}
+
+/// Event emitted on every read by a registered consumer
+#[contractevent]
+pub struct RandomnessConsumed {
+    pub consumer: Address,
+    pub round: u64,
+    pub context: Symbol,
+    pub entity_id: u64,
+    pub fee: i128, // Stroops charged for the read (0 while reads are free)
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Allow `contract` to read verified randomness (Owner only)
+    pub fn register_consumer(env: Env, contract: Address) {
+        let actor = require_role(&env, Role::Owner);
+        let key = DataKey::Consumer(contract.clone());
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        config_log::record(&env, &actor, "consumer", (contract, true));
+    }
+
+    /// Revoke a consumer's access (Owner only)
+    pub fn unregister_consumer(env: Env, contract: Address) {
+        let actor = require_role(&env, Role::Owner);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Consumer(contract.clone()));
+        config_log::record(&env, &actor, "consumer", (contract, false));
+    }
+
+    /// Whether `contract` may call consume_randomness
+    pub fn is_consumer_registered(env: Env, contract: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Consumer(contract))
+    }
+
+    /// Set the XLM fee charged per consume_randomness call, in stroops (Owner only, 0 = free)
+    pub fn set_consumer_read_fee(env: Env, fee: i128) {
+        let actor = require_role(&env, Role::Owner);
+        if fee < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ConsumerReadFee, &fee);
+        config_log::record(&env, &actor, "consumer_read_fee", fee);
+    }
+
+    /// Get the XLM fee charged per consume_randomness call (stroops)
+    pub fn get_consumer_read_fee(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ConsumerReadFee)
+            .unwrap_or(0)
+    }
+
+    /// Read `round`'s randomness derived for `context` and `entity_id`, paying the read fee
+    /// Fails with RandomnessNotVerified unless the round was verified within the last day
+    pub fn consume_randomness(
+        env: Env,
+        consumer: Address,
+        round: u64,
+        context: Symbol,
+        entity_id: u64,
+    ) -> BytesN<32> {
+        features::require(&env, "consumers");
+        reentrancy::enter(&env);
+        consumer.require_auth();
+        if !Self::is_consumer_registered(env.clone(), consumer.clone()) {
+            panic_with_error!(&env, ConsumerError::ConsumerNotRegistered);
+        }
+        let randomness = verification::derived_randomness(&env, round, &context, entity_id)
+            .unwrap_or_else(|| panic_with_error!(&env, ConsumerError::RandomnessNotVerified));
+
+        let fee = fee_rounding::apply(&env, Self::get_consumer_read_fee(env.clone()));
+        if fee > 0 {
+            let treasury = mint_fee::treasury(&env);
+            let xlm_token: Address = require_configured(&env, &DataKey::XlmToken);
+            token::Client::new(&env, &xlm_token).transfer(&consumer, &treasury, &fee);
+        }
+
+        events::publish(&env, |nonce| RandomnessConsumed {
+            consumer,
+            round,
+            context,
+            entity_id,
+            fee,
+            nonce,
+        });
+        reentrancy::exit(&env);
+        randomness
+    }
+}
@@ -0,0 +1,142 @@
+//! Shared-round verification cost sharing
+//!
+//! When several cartridges share a drand round, only the first verification of
+//! the round pays for the BLS pairing; later finalizations ride the
+//! verified-round cache. To even that out, every cached finalization pays the
+//! round's verifier a fixed verification share in XLM until the verifier has
+//! been reimbursed the estimated verification cost, so verifying first is never
+//! a loss and never a profit. Both amounts are set by the Owner; a share of zero
+//! (the default) switches the program off.
+
+use crate::{
+    config_log, events, inbox, require_configured,
+    roles::{require_role, Role},
+    verification::VERIFIED_ROUND_TTL,
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{
+    contractevent, contractimpl, contracttype, panic_with_error, token, Address, Env,
+};
+
+/// Who verified a round first, and how much they have been reimbursed so far
+/// Lives in temporary storage alongside the round's verified-cache entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundVerifier {
+    pub verifier: Address,
+    pub reimbursed: i128,
+}
+
+/// Event emitted when a cached finalization reimburses the round's verifier
+#[contractevent]
+pub struct VerificationSharePaid {
+    pub round: u64,
+    pub payer: Address,
+    pub verifier: Address,
+    pub amount: i128,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set the per-finalization verification share and the per-round reimbursement cap,
+    /// both in stroops (Owner only). A share of 0 disables cost sharing
+    pub fn set_verification_share(env: Env, share: i128, cost_cap: i128) {
+        let actor = require_role(&env, Role::Owner);
+        if share < 0 || cost_cap < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::VerificationShare, &share);
+        env.storage()
+            .instance()
+            .set(&DataKey::VerificationCostCap, &cost_cap);
+        config_log::record(&env, &actor, "verification_share", (share, cost_cap));
+    }
+
+    /// Get the amount each cached finalization pays the round's verifier (stroops)
+    pub fn get_verification_share(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerificationShare)
+            .unwrap_or(0)
+    }
+
+    /// Get the most a verifier can be reimbursed for one round (stroops)
+    pub fn get_verification_cost_cap(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::VerificationCostCap)
+            .unwrap_or(0)
+    }
+
+    /// Get the address that first verified `round`, while its cache entry is live
+    pub fn get_round_verifier(env: Env, round: u64) -> Option<Address> {
+        read_round_verifier(&env, round).map(|entry| entry.verifier)
+    }
+
+    /// Get the total reimbursed to `round`'s verifier so far
+    pub fn get_round_reimbursed(env: Env, round: u64) -> i128 {
+        read_round_verifier(&env, round).map_or(0, |entry| entry.reimbursed)
+    }
+}
+
+/// Record `verifier` as the first verifier of `round`; later verifications don't replace it
+pub(crate) fn record_verifier(env: &Env, round: u64, verifier: &Address) {
+    let key = DataKey::RoundVerifier(round);
+    if env.storage().temporary().has(&key) {
+        return;
+    }
+    let entry = RoundVerifier {
+        verifier: verifier.clone(),
+        reimbursed: 0,
+    };
+    env.storage().temporary().set(&key, &entry);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, VERIFIED_ROUND_TTL, VERIFIED_ROUND_TTL);
+}
+
+/// Charge `payer` the verification share for finalizing `cartridge_id` without the pairing
+/// No-op when sharing is off, the verifier is unknown or is the payer, or the cap is reached
+pub(crate) fn pay_share(env: &Env, round: u64, cartridge_id: u32, payer: &Address) {
+    let share = GeneSplicer::get_verification_share(env.clone());
+    if share <= 0 {
+        return;
+    }
+    let Some(mut entry) = read_round_verifier(env, round) else {
+        return;
+    };
+    if entry.verifier == *payer {
+        return;
+    }
+    let cost_cap = GeneSplicer::get_verification_cost_cap(env.clone());
+    let amount = share.min(cost_cap - entry.reimbursed);
+    if amount <= 0 {
+        return;
+    }
+
+    let xlm_token: Address = require_configured(env, &DataKey::XlmToken);
+    token::Client::new(env, &xlm_token).transfer(payer, &entry.verifier, &amount);
+
+    inbox::notify(env, &entry.verifier, "verification_share", cartridge_id);
+    entry.reimbursed += amount;
+    env.storage()
+        .temporary()
+        .set(&DataKey::RoundVerifier(round), &entry);
+
+    events::publish(env, |nonce| VerificationSharePaid {
+        round,
+        payer: payer.clone(),
+        verifier: entry.verifier,
+        amount,
+        nonce,
+    });
+}
+
+fn read_round_verifier(env: &Env, round: u64) -> Option<RoundVerifier> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::RoundVerifier(round))
+}
@@ -0,0 +1,283 @@
+//! Versioned gene derivation
+//!
+//! Every cartridge is stamped with the derivation version that was current when
+//! it was minted, and finalization always dispatches on that stamp. Historical
+//! versions stay callable forever, so shipping a new derivation never changes
+//! the outcome of a cartridge minted under older rules.
+//!
+//! - v1: genes read directly from fixed byte offsets of the drand randomness
+//! - v2: each slot hashes the randomness with a domain tag, the cartridge ID and
+//!   the slot index, so cartridges sharing a round get independent genes
+//! - v3: v2, then no gene ID appears in two slots (the art can't compose them).
+//!   Slots are checked head, body, legs; a slot whose ID is already taken moves
+//!   to the next ID of the same rarity, wrapping within the tier, until it is
+//!   free. Every tier has at least three IDs, so this always terminates, and
+//!   rarities are never changed.
+//! - v4: v3's dedup, but the slot values are the first 12 bytes of
+//!   `derive_from_round(randomness, GENE_CONTEXT, cartridge_id)`, read as three
+//!   big-endian u32s
+//!
+//! Every feature that consumes a round's randomness goes through
+//! `derive_from_round` with its own context tag, so one feature's outcome for
+//! a round says nothing about another's. The cached verified randomness is
+//! private to `verification`, which only hands out derived values. v1-v3 read
+//! the raw randomness and stay as they are so existing cartridges keep their genes.
+//!
+//! The version picks the random value of each slot; the gene table the
+//! cartridge was stamped with turns that value into a rarity and gene ID.
+
+use crate::{
+    config_log, events,
+    gene_table::{self, GeneTable, GENE_TABLE_SCALE},
+    params,
+    roles::{require_role, Role},
+    DataKey, Error, Gene, GeneRarity, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+    GenomeCartridge, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contractevent, contractimpl, panic_with_error, symbol_short, xdr::ToXdr, Bytes, BytesN, Env,
+    Symbol, Vec,
+};
+
+/// Derivation used by cartridges minted before versioning existed
+pub const DERIVATION_V1: u32 = 1;
+
+/// Newest derivation; stamped on new cartridges by default
+pub const LATEST_DERIVATION_VERSION: u32 = 4;
+
+/// Domain separation tag for v2 slot hashing — frozen, changing it changes v2 outcomes
+const DERIVATION_V2_DOMAIN: &[u8] = b"SPLICERS_GENE_V2";
+
+/// Context tag of gene derivation (v4 on) — frozen, changing it changes v4 outcomes
+pub const GENE_CONTEXT: Symbol = symbol_short!("genes");
+
+/// Event emitted when the admin changes the derivation stamped on new cartridges
+#[contractevent]
+pub struct DerivationVersionChanged {
+    pub version: u32,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set the derivation version stamped on cartridges minted from now on (Owner only)
+    /// Already-minted cartridges keep the version they were minted with
+    pub fn set_derivation_version(env: Env, version: u32) {
+        let actor = require_role(&env, Role::Owner);
+
+        if !(DERIVATION_V1..=LATEST_DERIVATION_VERSION).contains(&version) {
+            panic_with_error!(&env, Error::UnsupportedDerivationVersion);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::DerivationVersion, &version);
+        record_history(&env, version);
+        config_log::record(&env, &actor, "derivation_version", version);
+
+        events::publish(&env, |nonce| DerivationVersionChanged { version, nonce });
+    }
+
+    /// Get the derivation version new cartridges are stamped with
+    pub fn get_derivation_version(env: Env) -> u32 {
+        current_version(&env)
+    }
+
+    /// Get the derivation version a cartridge will be finalized with
+    pub fn get_cartridge_derivation_version(env: Env, cartridge_id: u32) -> u32 {
+        let cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Cartridge(cartridge_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CartridgeNotFound));
+        cartridge.derivation_version
+    }
+}
+
+/// Derivation version to stamp on a cartridge being minted now
+/// Contracts deployed before versioning have no stored default and stay on v1
+pub(crate) fn current_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::DerivationVersion)
+        .unwrap_or(DERIVATION_V1)
+}
+
+/// Append (now, version) to the history of versions stamped on new cartridges
+/// Called whenever the stamped version is set, including at construction
+pub(crate) fn record_history(env: &Env, version: u32) {
+    let mut history = history(env);
+    history.push_back((env.ledger().timestamp(), version));
+    env.storage()
+        .persistent()
+        .set(&DataKey::DerivationHistory, &history);
+    env.storage().persistent().extend_ttl(
+        &DataKey::DerivationHistory,
+        TTL_THRESHOLD,
+        TTL_EXTEND_TO,
+    );
+}
+
+/// (since, version) pairs, oldest first: `version` was stamped on cartridges minted from `since`
+pub(crate) fn history(env: &Env) -> Vec<(u64, u32)> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DerivationHistory)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Derive (head, body, legs) genes with the cartridge's version rules and gene table odds
+pub(crate) fn derive(env: &Env, cartridge: &GenomeCartridge, entropy: &Bytes) -> [Gene; 3] {
+    let values = match cartridge.derivation_version {
+        1 => slot_values_v1(entropy),
+        2 | 3 => slot_values_v2(env, entropy, cartridge.id),
+        4 => slot_values_v4(env, entropy, cartridge.id),
+        _ => panic_with_error!(env, Error::UnsupportedDerivationVersion),
+    };
+    let genes = match gene_table::table(env, cartridge.gene_table_epoch) {
+        Some(table) => values.map(|value| gene_from_table(&table, value)),
+        None => values.map(gene_from_value),
+    };
+    if cartridge.derivation_version >= 3 {
+        dedup_slots(genes)
+    } else {
+        genes
+    }
+}
+
+/// v1 genes under the original gene table
+pub fn derive_v1(entropy: &Bytes) -> [Gene; 3] {
+    slot_values_v1(entropy).map(gene_from_value)
+}
+
+/// v2 genes under the original gene table
+pub fn derive_v2(env: &Env, entropy: &Bytes, cartridge_id: u32) -> [Gene; 3] {
+    slot_values_v2(env, entropy, cartridge_id).map(gene_from_value)
+}
+
+/// v3 genes under the original gene table
+pub fn derive_v3(env: &Env, entropy: &Bytes, cartridge_id: u32) -> [Gene; 3] {
+    dedup_slots(derive_v2(env, entropy, cartridge_id))
+}
+
+/// v4 genes under the original gene table
+pub fn derive_v4(env: &Env, entropy: &Bytes, cartridge_id: u32) -> [Gene; 3] {
+    dedup_slots(slot_values_v4(env, entropy, cartridge_id).map(gene_from_value))
+}
+
+/// Derive a value from a round's randomness for one consumer and entity
+/// SHA256(randomness || XDR(context) || entity_id u64 BE); the only way features read a round
+pub fn derive_from_round(
+    env: &Env,
+    round_randomness: &BytesN<32>,
+    context: &Symbol,
+    entity_id: u64,
+) -> BytesN<32> {
+    let mut preimage = Bytes::from(round_randomness.clone());
+    preimage.append(&context.clone().to_xdr(env));
+    preimage.extend_from_array(&entity_id.to_be_bytes());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// v1: slot N reads 4 big-endian bytes at offset N*10 of the randomness
+fn slot_values_v1(entropy: &Bytes) -> [u32; 3] {
+    [0, 1, 2].map(|slot: u32| {
+        // Use different entropy bytes for each gene slot
+        let offset = slot * 10;
+
+        // Extract 4 bytes for this gene and convert to u32
+        let byte1 = entropy.get(offset % 32).unwrap_or(0) as u32;
+        let byte2 = entropy.get((offset + 1) % 32).unwrap_or(0) as u32;
+        let byte3 = entropy.get((offset + 2) % 32).unwrap_or(0) as u32;
+        let byte4 = entropy.get((offset + 3) % 32).unwrap_or(0) as u32;
+
+        (byte1 << 24) | (byte2 << 16) | (byte3 << 8) | byte4
+    })
+}
+
+/// v2: slot N reads the first 4 bytes of
+/// SHA256(DERIVATION_V2_DOMAIN || randomness || cartridge_id BE || N)
+fn slot_values_v2(env: &Env, entropy: &Bytes, cartridge_id: u32) -> [u32; 3] {
+    [0u8, 1, 2].map(|slot| {
+        let mut input = Bytes::from_slice(env, DERIVATION_V2_DOMAIN);
+        input.append(entropy);
+        input.extend_from_array(&cartridge_id.to_be_bytes());
+        input.push_back(slot);
+
+        let digest = env.crypto().sha256(&input).to_array();
+        u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+    })
+}
+
+/// v4: slot N reads 4 big-endian bytes at offset N*4 of the cartridge's gene context value
+fn slot_values_v4(env: &Env, entropy: &Bytes, cartridge_id: u32) -> [u32; 3] {
+    let randomness = params::require(env, params::parse_randomness(entropy.clone()));
+    let value = derive_from_round(env, &randomness, &GENE_CONTEXT, cartridge_id.into()).to_array();
+    [0usize, 1, 2].map(|slot| {
+        let offset = slot * 4;
+        u32::from_be_bytes([
+            value[offset],
+            value[offset + 1],
+            value[offset + 2],
+            value[offset + 3],
+        ])
+    })
+}
+
+/// Map a 32-bit random value to a gene ID with the original table's weighted rarity
+fn gene_from_value(random_value: u32) -> Gene {
+    // Legendary (10%): Golem (IDs 3-5)
+    // Rare (30%): Dark Oracle (IDs 0-2)
+    // Common (60%): Necromancer, Skeleton Crusader, Skeleton Warrior (IDs 6-14)
+    let roll = random_value % 10; // 0-9 for distribution
+    let rarity = if roll == 0 {
+        GeneRarity::Legendary
+    } else if roll <= 3 {
+        GeneRarity::Rare
+    } else {
+        GeneRarity::Normal
+    };
+    gene_of_rarity(rarity, random_value)
+}
+
+/// Map a 32-bit random value to a gene ID with a committed table's per-mille odds
+fn gene_from_table(table: &GeneTable, random_value: u32) -> Gene {
+    let roll = random_value % GENE_TABLE_SCALE;
+    let rarity = if roll < table.legendary_odds {
+        GeneRarity::Legendary
+    } else if roll < table.legendary_odds + table.rare_odds {
+        GeneRarity::Rare
+    } else {
+        GeneRarity::Normal
+    };
+    gene_of_rarity(rarity, random_value)
+}
+
+/// Pick the gene variant within a rarity tier (shared by every table and version)
+fn gene_of_rarity(rarity: GeneRarity, random_value: u32) -> Gene {
+    // Use different bits for variant selection than for the rarity roll
+    let (first, count) = tier(&rarity);
+    Gene {
+        id: first + (random_value >> 8) % count,
+        rarity,
+    }
+}
+
+/// First gene ID and number of variants of a rarity tier
+fn tier(rarity: &GeneRarity) -> (u32, u32) {
+    match rarity {
+        GeneRarity::Legendary => (3, 3), // Golem: IDs 3-5
+        GeneRarity::Rare => (0, 3),      // Dark Oracle: IDs 0-2
+        GeneRarity::Normal => (6, 9),    // Common: IDs 6-14, 9 variants
+    }
+}
+
+/// v3 dedup: move each slot whose ID an earlier slot already uses to the next free ID of its tier
+fn dedup_slots(mut genes: [Gene; 3]) -> [Gene; 3] {
+    for slot in 1..genes.len() {
+        let (first, count) = tier(&genes[slot].rarity);
+        while genes[..slot].iter().any(|gene| gene.id == genes[slot].id) {
+            genes[slot].id = first + (genes[slot].id - first + 1) % count;
+        }
+    }
+    genes
+}
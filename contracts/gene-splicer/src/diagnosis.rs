@@ -0,0 +1,189 @@
+//! Staged drand signature verification and its dry run
+//!
+//! Beacon verification is a chain of stages, each a small function returning
+//! the stage that failed. `verify_drand_signature` and two-phase verification
+//! run them and turn a failure into the stage's contract error (`fail`).
+//! `diagnose_verification` runs the same functions read-only and reports the
+//! first failing stage, so a real beacon that fails on-chain can be debugged
+//! without bisecting off-chain, and the diagnosis can't drift from the
+//! enforcing path.
+//!
+//! A signature that is not on the curve at all still fails inside the host
+//! during the subgroup check; points on the curve but outside G1 are reported
+//! as `SignatureSubgroup`.
+
+use crate::{
+    configured, coordinates_in_range, negate_g1_bytes, params, DataKey, Error, GeneSplicer,
+    GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{
+    contractimpl, contracttype,
+    crypto::bls12_381::{G1Affine, G2Affine},
+    panic_with_error, Bytes, BytesN, Env, Vec,
+};
+
+/// drand quicknet hash-to-curve domain separation tag
+/// quicknet swaps groups: signatures on G1, public keys on G2
+pub const DRAND_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Standard BLS12-381 G2 generator, uncompressed: x_c1 || x_c0 || y_c1 || y_c0 (CAP-0059 byte order)
+/// Reference: IETF draft-irtf-cfrg-pairing-friendly-curves
+const G2_GENERATOR: [u8; 192] = [
+    // x_c1 (48 bytes)
+    0x13, 0xe0, 0x2b, 0x60, 0x52, 0x71, 0x9f, 0x60, 0x7d, 0xac, 0xd3, 0xa0, 0x88, 0x27, 0x4f, 0x65,
+    0x59, 0x6b, 0xd0, 0xd0, 0x99, 0x20, 0xb6, 0x1a, 0xb5, 0xda, 0x61, 0xbb, 0xdc, 0x7f, 0x50, 0x49,
+    0x33, 0x4c, 0xf1, 0x12, 0x13, 0x94, 0x5d, 0x57, 0xe5, 0xac, 0x7d, 0x05, 0x5d, 0x04, 0x2b, 0x7e,
+    // x_c0 (48 bytes)
+    0x02, 0x4a, 0xa2, 0xb2, 0xf0, 0x8f, 0x0a, 0x91, 0x26, 0x08, 0x05, 0x27, 0x2d, 0xc5, 0x10, 0x51,
+    0xc6, 0xe4, 0x7a, 0xd4, 0xfa, 0x40, 0x3b, 0x02, 0xb4, 0x51, 0x0b, 0x64, 0x7a, 0xe3, 0xd1, 0x77,
+    0x0b, 0xac, 0x03, 0x26, 0xa8, 0x05, 0xbb, 0xef, 0xd4, 0x80, 0x56, 0xc8, 0xc1, 0x21, 0xbd, 0xb8,
+    // y_c1 (48 bytes)
+    0x06, 0x06, 0xc4, 0xa0, 0x2e, 0xa7, 0x34, 0xcc, 0x32, 0xac, 0xd2, 0xb0, 0x2b, 0xc2, 0x8b, 0x99,
+    0xcb, 0x3e, 0x28, 0x7e, 0x85, 0xa7, 0x63, 0xaf, 0x26, 0x74, 0x92, 0xab, 0x57, 0x2e, 0x99, 0xab,
+    0x3f, 0x37, 0x0d, 0x27, 0x5c, 0xec, 0x1d, 0xa1, 0xaa, 0xa9, 0x07, 0x5f, 0xf0, 0x5f, 0x79, 0xbe,
+    // y_c0 (48 bytes)
+    0x0c, 0xe5, 0xd5, 0x27, 0x72, 0x7d, 0x6e, 0x11, 0x8c, 0xc9, 0xcd, 0xc6, 0xda, 0x2e, 0x35, 0x1a,
+    0xad, 0xfd, 0x9b, 0xaa, 0x8c, 0xbd, 0xd3, 0xa7, 0x6d, 0x42, 0x9a, 0x69, 0x51, 0x60, 0xd1, 0x2c,
+    0x92, 0x3a, 0xc9, 0xcc, 0x3b, 0xac, 0xa2, 0x89, 0xe1, 0x93, 0x54, 0x86, 0x08, 0xb8, 0x28, 0x01,
+];
+
+/// Verification stages, in the order they run
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum VerificationStage {
+    SignatureLength,      // Signature is not 96 bytes
+    SignatureCoordinates, // A signature coordinate is not below the field modulus
+    SignatureSubgroup,    // Signature point is not in the G1 subgroup
+    HashToCurve,          // Hashed message is not in the G1 subgroup (never expected)
+    PublicKeyLength,      // Public key is not 192 bytes
+    PublicKeyCoordinates, // A public key coordinate is not below the field modulus
+    PublicKey,            // Public key is the zero mock, carries flags, or is not in G2
+    Pairing,              // e(signature, G2 generator) != e(H(message), public key)
+    Passed,               // Every stage passed
+}
+
+/// Outcome of a dry-run verification
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationDiagnosis {
+    pub stage: VerificationStage, // First stage that failed, or Passed
+    pub message: BytesN<32>,      // SHA256(round as 8 big-endian bytes), the message drand signs
+    pub dst: Bytes,               // Hash-to-curve domain separation tag
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Run every verification stage on a beacon without recording anything
+    /// `public_key` defaults to the configured drand key; reports the first stage that failed
+    pub fn diagnose_verification(
+        env: Env,
+        round: u64,
+        signature: Bytes,
+        public_key: Option<Bytes>,
+    ) -> Result<VerificationDiagnosis, Error> {
+        let public_key = match public_key {
+            Some(key) => key,
+            None => configured(&env, &DataKey::DrandPublicKey)?,
+        };
+        let stage = params::parse_signature_g1(signature)
+            .map_err(|_| VerificationStage::SignatureLength)
+            .and_then(|signature| signature_stage(&env, &signature))
+            .and_then(|negated| pairing_stages(&env, round, negated, public_key))
+            .err()
+            .unwrap_or(VerificationStage::Passed);
+
+        Ok(VerificationDiagnosis {
+            stage,
+            message: message(&env, round),
+            dst: Bytes::from_slice(&env, DRAND_DST),
+        })
+    }
+}
+
+/// Range-check the signature, negate it and check the negated point is in G1
+/// Pairing uses e(-sig, G2_gen) * e(H(msg), pubkey) == 1, so the negation happens on the bytes
+pub(crate) fn signature_stage(
+    env: &Env,
+    signature: &BytesN<96>,
+) -> Result<G1Affine, VerificationStage> {
+    if !coordinates_in_range(&signature.to_array()) {
+        return Err(VerificationStage::SignatureCoordinates);
+    }
+    let negated = G1Affine::from_bytes(negate_g1_bytes(env, signature));
+    if !env.crypto().bls12_381().g1_is_in_subgroup(&negated) {
+        return Err(VerificationStage::SignatureSubgroup);
+    }
+    Ok(negated)
+}
+
+/// Hash the round to G1, check `public_key` and run the pairing against `negated_signature`
+pub(crate) fn pairing_stages(
+    env: &Env,
+    round: u64,
+    negated_signature: G1Affine,
+    public_key: Bytes,
+) -> Result<(), VerificationStage> {
+    let bls = env.crypto().bls12_381();
+    let hashed = bls.hash_to_g1(
+        &Bytes::from(message(env, round)),
+        &Bytes::from_slice(env, DRAND_DST),
+    );
+    if !bls.g1_is_in_subgroup(&hashed) {
+        return Err(VerificationStage::HashToCurve);
+    }
+
+    let public_key =
+        params::parse_pubkey_g2(public_key).map_err(|_| VerificationStage::PublicKeyLength)?;
+    match GeneSplicer::check_drand_public_key(env, &public_key) {
+        Ok(()) => {}
+        Err(Error::PubkeyCoordinateOutOfRange) => {
+            return Err(VerificationStage::PublicKeyCoordinates)
+        }
+        Err(_) => return Err(VerificationStage::PublicKey),
+    }
+
+    let g1_points = Vec::from_array(env, [negated_signature, hashed]);
+    let g2_points = Vec::from_array(
+        env,
+        [
+            G2Affine::from_array(env, &G2_GENERATOR),
+            G2Affine::from_bytes(public_key),
+        ],
+    );
+    if !bls.pairing_check(g1_points, g2_points) {
+        return Err(VerificationStage::Pairing);
+    }
+    Ok(())
+}
+
+/// Fail the invocation for `stage` the way the enforcing path reports it
+pub(crate) fn fail(env: &Env, stage: VerificationStage) -> ! {
+    match stage {
+        VerificationStage::SignatureLength => {
+            panic_with_error!(env, Error::InvalidSignatureLength)
+        }
+        VerificationStage::SignatureCoordinates => {
+            panic_with_error!(env, Error::SignatureCoordinateOutOfRange)
+        }
+        VerificationStage::SignatureSubgroup => {
+            panic_with_error!(env, Error::SignatureNotInSubgroup)
+        }
+        VerificationStage::HashToCurve => panic_with_error!(env, Error::HashToCurveFailed),
+        VerificationStage::PublicKeyLength => {
+            panic_with_error!(env, Error::InvalidPublicKeyLength)
+        }
+        VerificationStage::PublicKeyCoordinates => {
+            panic_with_error!(env, Error::PubkeyCoordinateOutOfRange)
+        }
+        VerificationStage::PublicKey => panic_with_error!(env, Error::InvalidDrandPublicKey),
+        VerificationStage::Pairing => panic_with_error!(env, Error::PairingCheckFailed),
+        VerificationStage::Passed => unreachable!("Passed is not a failing stage"),
+    }
+}
+
+/// Message drand quicknet signs for `round`: SHA256 of its 8-byte big-endian encoding
+fn message(env: &Env, round: u64) -> BytesN<32> {
+    env.crypto()
+        .sha256(&Bytes::from_array(env, &round.to_be_bytes()))
+        .into()
+}
@@ -0,0 +1,292 @@
+//! Verifiable rarity-weighted drawings among creatures
+//!
+//! The Operator opens a drawing for `winners` creatures, to be decided by the
+//! drand beacon of `close_round`, which must not have been emitted yet. The
+//! population is fixed at creation as creature IDs 1..=population (the
+//! cartridges minted so far); whether an ID holds an eligible creature is
+//! judged when the drawing is resolved.
+//!
+//! Iterating every creature on-chain isn't feasible, so selection samples:
+//!
+//! 1. `state = sha256(DRAWING_DOMAIN || drawing_id (u32 BE) || randomness)`
+//! 2. Each attempt hashes `state = sha256(state)` and reads two big-endian
+//!    u64s from it. Bytes 0..8 pick an index in 0..population and bytes 8..16 a
+//!    ticket in 0..MAX_RARITY_SCORE, each by rejection: a value at or above the
+//!    largest multiple of the range wastes the attempt, so there is no modulo bias.
+//! 3. Creature `index + 1` wins if it exists, isn't retired or already a
+//!    winner, and `ticket < rarity_score`. Acceptance is proportional to the
+//!    score, so each draw is weighted by rarity without knowing the total weight.
+//!
+//! A drawing stops once it has its winners or after MAX_DRAWING_ATTEMPTS
+//! attempts, keeping whoever won by then. Each `resolve_drawing` call runs at
+//! most MAX_ATTEMPTS_PER_CALL attempts to keep the read footprint bounded; the
+//! hash chain continues across calls, so the result is the same however it is
+//! split. Given the randomness, anyone can recompute the winners off-chain.
+
+use crate::{
+    compress_g1, drand_round_time, events, features, params, retirement,
+    roles::{require_role, Role},
+    verification, Creature, DataKey, Error, GeneRarity, GeneSplicer, GeneSplicerArgs,
+    GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, contracttype, panic_with_error, Address, Bytes,
+    BytesN, Env, Vec,
+};
+
+/// Domain separation tag prefixed to a drawing's seed
+pub const DRAWING_DOMAIN: &[u8] = b"SPLICERS_DRAWING_V1";
+
+/// Most winners a single drawing can have
+pub const MAX_DRAWING_WINNERS: u32 = 20;
+
+/// Attempts after which a drawing resolves with the winners found so far
+pub const MAX_DRAWING_ATTEMPTS: u32 = 2_000;
+
+/// Attempts run by one resolve_drawing call (each reads up to two ledger entries)
+pub const MAX_ATTEMPTS_PER_CALL: u32 = 30;
+
+/// Rarity score of a creature with three Legendary genes
+pub const MAX_RARITY_SCORE: u64 = 30;
+
+/// Drawing failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DrawingError {
+    DrawingNotFound = 73, // No drawing with this ID
+    DrawingResolved = 74, // resolve_drawing on a drawing that already has its result
+    InvalidDrawing = 75,  // Winner count out of range or close round already emitted
+}
+
+/// A drawing and its resolution progress
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Drawing {
+    pub id: u32,
+    pub winners: u32,              // Number of creatures to pick
+    pub close_round: u64,          // drand round whose beacon decides the drawing
+    pub population: u32,           // Highest creature ID taking part
+    pub state: Option<BytesN<32>>, // Current hash chain value; None until the beacon is verified
+    pub attempts: u32,
+    pub winner_ids: Vec<u32>, // In order of selection
+    pub resolved: bool,
+}
+
+/// Event emitted when the Operator opens a drawing
+#[contractevent]
+pub struct DrawingCreated {
+    pub drawing_id: u32,
+    pub winners: u32,
+    pub close_round: u64,
+    pub population: u32,
+    pub nonce: u64,
+}
+
+/// Event emitted for every creature a drawing selects
+#[contractevent]
+pub struct DrawingWinner {
+    pub drawing_id: u32,
+    pub creature_id: u32,
+    pub owner: Address,
+    pub nonce: u64,
+}
+
+/// Event emitted when a drawing has all its winners or runs out of attempts
+#[contractevent]
+pub struct DrawingResolved {
+    pub drawing_id: u32,
+    pub winner_ids: Vec<u32>,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Open a drawing for `winners` creatures decided by the beacon of `close_round` (Operator)
+    /// Refused with InvalidDrawing unless 1 <= winners <= MAX_DRAWING_WINNERS and the round is still in the future
+    pub fn create_drawing(env: Env, winners: u32, close_round: u64) -> u32 {
+        features::require(&env, "drawings");
+        require_role(&env, Role::Operator);
+        if winners == 0
+            || winners > MAX_DRAWING_WINNERS
+            || drand_round_time(close_round) <= env.ledger().timestamp()
+        {
+            panic_with_error!(&env, DrawingError::InvalidDrawing);
+        }
+
+        let drawing_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextDrawingId)
+            .unwrap_or(1);
+        env.storage().instance().set(
+            &DataKey::NextDrawingId,
+            &drawing_id.checked_add(1).expect("Drawing ID overflow"),
+        );
+
+        let population = Self::get_total_cartridges(env.clone());
+        write_drawing(
+            &env,
+            &Drawing {
+                id: drawing_id,
+                winners,
+                close_round,
+                population,
+                state: None,
+                attempts: 0,
+                winner_ids: Vec::new(&env),
+                resolved: false,
+            },
+        );
+
+        events::publish(&env, |nonce| DrawingCreated {
+            drawing_id,
+            winners,
+            close_round,
+            population,
+            nonce,
+        });
+        drawing_id
+    }
+
+    /// Run the next batch of selection attempts for a drawing (permissionless)
+    /// The first call verifies the close_round beacon: `randomness` (32 bytes) must be the
+    /// SHA256 of the compressed `signature` (96 bytes, uncompressed). Returns true once resolved
+    pub fn resolve_drawing(env: Env, drawing_id: u32, randomness: Bytes, signature: Bytes) -> bool {
+        features::require(&env, "drawings");
+        let mut drawing = read_drawing(&env, drawing_id);
+        if drawing.resolved {
+            panic_with_error!(&env, DrawingError::DrawingResolved);
+        }
+
+        let mut state = match drawing.state.clone() {
+            Some(state) => state,
+            None => {
+                let randomness = params::require(&env, params::parse_randomness(randomness));
+                let signature = params::require(&env, params::parse_signature_g1(signature));
+                let expected: BytesN<32> = env
+                    .crypto()
+                    .sha256(&Bytes::from(compress_g1(&env, &signature)))
+                    .into();
+                if randomness != expected {
+                    panic_with_error!(&env, Error::RandomnessMismatch);
+                }
+                if !verification::is_verified(&env, drawing.close_round, &signature) {
+                    Self::verify_drand_signature(&env, drawing.close_round, &signature);
+                }
+                seed(&env, drawing_id, &randomness)
+            }
+        };
+
+        let mut attempts_left = MAX_ATTEMPTS_PER_CALL;
+        while attempts_left > 0
+            && drawing.winner_ids.len() < drawing.winners
+            && drawing.attempts < MAX_DRAWING_ATTEMPTS
+        {
+            attempts_left -= 1;
+            drawing.attempts += 1;
+            state = env.crypto().sha256(&Bytes::from(state)).into();
+            if let Some((creature_id, owner)) = attempt(&env, &drawing, &state) {
+                drawing.winner_ids.push_back(creature_id);
+                events::publish(&env, |nonce| DrawingWinner {
+                    drawing_id,
+                    creature_id,
+                    owner,
+                    nonce,
+                });
+            }
+        }
+        drawing.state = Some(state);
+        drawing.resolved =
+            drawing.winner_ids.len() == drawing.winners || drawing.attempts == MAX_DRAWING_ATTEMPTS;
+        write_drawing(&env, &drawing);
+
+        if drawing.resolved {
+            events::publish(&env, |nonce| DrawingResolved {
+                drawing_id,
+                winner_ids: drawing.winner_ids,
+                nonce,
+            });
+        }
+        drawing.resolved
+    }
+
+    /// Get a drawing and its resolution progress
+    pub fn get_drawing(env: Env, drawing_id: u32) -> Option<Drawing> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Drawing(drawing_id))
+    }
+}
+
+/// A creature's weight in drawings: 1 per Normal, 3 per Rare and 10 per Legendary gene
+pub fn rarity_score(creature: &Creature) -> u64 {
+    [
+        &creature.head_gene,
+        &creature.body_gene,
+        &creature.legs_gene,
+    ]
+    .iter()
+    .map(|gene| match gene.rarity {
+        GeneRarity::Normal => 1,
+        GeneRarity::Rare => 3,
+        GeneRarity::Legendary => 10,
+    })
+    .sum()
+}
+
+/// Starting point of a drawing's hash chain
+fn seed(env: &Env, drawing_id: u32, randomness: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, DRAWING_DOMAIN);
+    preimage.extend_from_array(&drawing_id.to_be_bytes());
+    preimage.append(&Bytes::from(randomness.clone()));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// One selection attempt from a hash chain value; the winning creature and its owner, if any
+fn attempt(env: &Env, drawing: &Drawing, state: &BytesN<32>) -> Option<(u32, Address)> {
+    let bytes = state.to_array();
+    let word = |offset: usize| {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&bytes[offset..offset + 8]);
+        u64::from_be_bytes(word)
+    };
+    let index = uniform(word(0), drawing.population as u64)?;
+    let ticket = uniform(word(8), MAX_RARITY_SCORE)?;
+
+    let creature_id = index as u32 + 1;
+    if drawing.winner_ids.contains(creature_id) {
+        return None;
+    }
+    let creature: Creature = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Creature(creature_id))?;
+    retirement::check_active(env, creature_id).ok()?;
+    if ticket >= rarity_score(&creature) {
+        return None;
+    }
+    Some((creature_id, creature.owner))
+}
+
+/// Map `value` uniformly onto 0..range, or None if it falls in the biased tail
+fn uniform(value: u64, range: u64) -> Option<u64> {
+    if range == 0 {
+        return None;
+    }
+    let zone = u64::MAX - (u64::MAX % range);
+    (value < zone).then(|| value % range)
+}
+
+fn read_drawing(env: &Env, drawing_id: u32) -> Drawing {
+    GeneSplicer::get_drawing(env.clone(), drawing_id)
+        .unwrap_or_else(|| panic_with_error!(env, DrawingError::DrawingNotFound))
+}
+
+fn write_drawing(env: &Env, drawing: &Drawing) {
+    let key = DataKey::Drawing(drawing.id);
+    env.storage().persistent().set(&key, drawing);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
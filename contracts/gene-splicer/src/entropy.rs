@@ -0,0 +1,166 @@
+//! Stored drand entropy, submitted once per round
+//!
+//! `submit_entropy` verifies a round's beacon signature and keeps it for good,
+//! so every cartridge assigned to that round can then be finalized with
+//! `finalize_with_entropy` from its ID alone: no beacon to fetch or pass, and
+//! no pairing check per cartridge. Unlike the verified-round cache (see
+//! `verification`), stored entropy doesn't expire, and a later drand key
+//! rotation doesn't discard it: it was checked against the key live when it
+//! was submitted. Each round can be submitted once.
+//!
+//! Cartridges sharing a round still get distinct genes: from v2 on, derivation
+//! mixes the cartridge ID into the round's randomness. v1 reads fixed bytes of
+//! the randomness, so every v1 cartridge of a round would get the same genes;
+//! `finalize_with_entropy` refuses them with SharedEntropyUnsupported and they
+//! finalize with their own beacon through finalize_splice instead. The stored
+//! randomness is always SHA-256 of the compressed signature; in dev_mode
+//! the pairing and the comparison with the submitted value are skipped.
+//!
+//! `finalize_with_entropy` is not a second finalization pipeline: it runs the
+//! same body as finalize_splice with the stored beacon in place of a submitted
+//! one. finalize_splice stays for v1 cartridges and for rounds nobody has
+//! submitted, and a beacon it is given that matches the stored entropy skips
+//! the pairing too.
+
+use crate::{
+    compress_g1, derivation::DERIVATION_V1, events, params, reentrancy, verification, DataKey,
+    Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge, TTL_EXTEND_TO,
+    TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, contracttype, panic_with_error, Address, Bytes,
+    BytesN, Env,
+};
+
+/// Stored entropy failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EntropyError {
+    EntropyAlreadySubmitted = 103, // submit_entropy for a round whose entropy is already stored
+    EntropyNotAvailable = 104, // finalize_with_entropy before the cartridge's round is submitted
+    SharedEntropyUnsupported = 106, // finalize_with_entropy on a v1 cartridge (genes ignore the ID)
+}
+
+/// A verified drand beacon, stored for the round it was emitted in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DrandEntropy {
+    pub round: u64,
+    pub randomness: BytesN<32>, // SHA-256 of the compressed signature
+    pub signature: BytesN<96>,  // Uncompressed G1 signature
+    pub submitted_at: u64,
+    pub submitter: Address,
+}
+
+/// Event emitted when a round's entropy is stored
+#[contractevent]
+pub struct EntropySubmitted {
+    pub round: u64,
+    pub submitter: Address,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Verify `round`'s beacon and store it for finalize_with_entropy
+    /// `signature` is the 96-byte uncompressed signature and `randomness` its drand randomness
+    /// If `chain_hash` is provided it must match the configured drand network (checked before any crypto)
+    /// Refused with EntropyAlreadySubmitted once the round is stored
+    pub fn submit_entropy(
+        env: Env,
+        submitter: Address,
+        round: u64,
+        randomness: Bytes,
+        signature: Bytes,
+        chain_hash: Option<Bytes>,
+    ) {
+        submitter.require_auth();
+        let randomness = params::require(&env, params::parse_randomness(randomness));
+        let signature = params::require(&env, params::parse_signature_g1(signature));
+        let chain_hash =
+            chain_hash.map(|hash| params::require(&env, params::parse_chain_hash(hash)));
+        Self::require_drand_network(&env, chain_hash);
+        let key = DataKey::Entropy(round);
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(&env, EntropyError::EntropyAlreadySubmitted);
+        }
+
+        let dev_mode: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::DevMode)
+            .unwrap_or(false);
+        if !dev_mode && !verification::is_verified(&env, round, &signature) {
+            Self::verify_drand_signature(&env, round, &signature);
+            verification::record_verified(&env, round, &signature, Some(&submitter));
+        }
+        let computed: BytesN<32> = env
+            .crypto()
+            .sha256(&Bytes::from(compress_g1(&env, &signature)))
+            .into();
+        if !dev_mode && randomness != computed {
+            panic_with_error!(&env, Error::RandomnessMismatch);
+        }
+
+        let entropy = DrandEntropy {
+            round,
+            randomness: computed,
+            signature,
+            submitted_at: env.ledger().timestamp(),
+            submitter: submitter.clone(),
+        };
+        env.storage().persistent().set(&key, &entropy);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        events::publish(&env, |nonce| EntropySubmitted {
+            round,
+            submitter,
+            nonce,
+        });
+    }
+
+    /// Get the stored entropy of a round, if it was submitted
+    pub fn get_entropy(env: Env, round: u64) -> Option<DrandEntropy> {
+        env.storage().persistent().get(&DataKey::Entropy(round))
+    }
+
+    /// Finalize a cartridge from the stored entropy of its splice_round (finalizer auth)
+    /// Refused with EntropyNotAvailable until that round's entropy is submitted,
+    /// and with SharedEntropyUnsupported for v1 cartridges (see the module docs)
+    pub fn finalize_with_entropy(env: Env, cartridge_id: u32) -> u32 {
+        reentrancy::enter(&env);
+        let creature_id = Self::finalize_by_finalizer(&env, cartridge_id, |cartridge| {
+            stored_beacon(&env, cartridge)
+        });
+        reentrancy::exit(&env);
+        creature_id
+    }
+}
+
+/// The cartridge's splice_round and the beacon stored for it, in place of a submitted one
+fn stored_beacon(env: &Env, cartridge: &GenomeCartridge) -> (u64, params::Beacon) {
+    if cartridge.derivation_version == DERIVATION_V1 {
+        panic_with_error!(env, EntropyError::SharedEntropyUnsupported);
+    }
+    let round = cartridge.splice_round;
+    let entropy = GeneSplicer::get_entropy(env.clone(), round)
+        .unwrap_or_else(|| panic_with_error!(env, EntropyError::EntropyNotAvailable));
+    let beacon = params::Beacon {
+        randomness: entropy.randomness,
+        signature_compressed: compress_g1(env, &entropy.signature),
+        signature_uncompressed: entropy.signature,
+        chain_hash: None,
+    };
+    (round, beacon)
+}
+
+/// Whether `signature` is the stored entropy of `round`, so its pairing was already checked
+pub(crate) fn is_stored(env: &Env, round: u64, signature: &BytesN<96>) -> bool {
+    env.storage()
+        .persistent()
+        .get::<_, DrandEntropy>(&DataKey::Entropy(round))
+        .is_some_and(|entropy| entropy.signature == *signature)
+}
@@ -0,0 +1,58 @@
+//! Maximum age of the randomness a cartridge may be finalized with
+//!
+//! A cartridge is bound to a round emitted after it was minted, and round
+//! substitution only moves it further ahead. Remediation that rewrites a
+//! cartridge's round could still pair a fresh cartridge with a beacon that
+//! was public, and sitting in the verified-round cache, long before it
+//! existed. Its outcome would then be known before the mint. Finalization
+//! refuses such a pairing with `EntropyTooOldForCartridge` once the round was
+//! emitted more than the configured age before the cartridge's `created_at`.
+//! The check is part of the shared finalize preconditions, so every
+//! finalization path and `can_finalize` apply it.
+
+use crate::{
+    config_log, drand_round_time,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge,
+};
+use soroban_sdk::{contractimpl, panic_with_error, Env};
+
+/// Default seconds a round may predate the cartridge consuming it (1 hour)
+pub const DEFAULT_MAX_RANDOMNESS_AGE: u64 = 3_600;
+
+/// Bounds on the configurable age: one minute to 30 days
+pub const MIN_RANDOMNESS_AGE: u64 = 60;
+pub const MAX_RANDOMNESS_AGE: u64 = 2_592_000;
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set how long before a cartridge's mint its round may have been emitted, in seconds (Owner only)
+    /// Must lie within [MIN_RANDOMNESS_AGE, MAX_RANDOMNESS_AGE]
+    pub fn set_max_randomness_age(env: Env, seconds: u64) {
+        let actor = require_role(&env, Role::Owner);
+        if !(MIN_RANDOMNESS_AGE..=MAX_RANDOMNESS_AGE).contains(&seconds) {
+            panic_with_error!(&env, Error::InvalidRandomnessAge);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxRandomnessAge, &seconds);
+        config_log::record(&env, &actor, "max_randomness_age", seconds);
+    }
+
+    /// Get how long before a cartridge's mint its round may have been emitted (seconds)
+    pub fn get_max_randomness_age(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxRandomnessAge)
+            .unwrap_or(DEFAULT_MAX_RANDOMNESS_AGE)
+    }
+}
+
+/// Refuse `round` for the cartridge if it was emitted too long before the cartridge was minted
+pub(crate) fn check(env: &Env, cartridge: &GenomeCartridge, round: u64) -> Result<(), Error> {
+    let age = cartridge.created_at.saturating_sub(drand_round_time(round));
+    if age > GeneSplicer::get_max_randomness_age(env.clone()) {
+        return Err(Error::EntropyTooOldForCartridge);
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+//! Paged ownership queries and enumeration of minted cartridges
+//!
+//! `get_user_cartridges` and `get_user_creatures` return an owner's whole ID
+//! list, which outgrows the return-value budget once a collector holds a few
+//! hundred tokens. The page getters return a slice of the same list, in the
+//! same order, so clients can walk it `limit` IDs at a time; the counts tell
+//! them where it ends. `get_cartridges_range` lets an indexer hydrate full
+//! cartridges by ID instead of probing IDs one by one.
+//!
+//! Offsets and IDs past the end return an empty Vec rather than failing, so a
+//! client can page until it sees a short or empty page.
+
+use crate::{
+    DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge, MAX_BATCH_READ,
+    MAX_OWNER_PAGE,
+};
+use soroban_sdk::{contractimpl, Address, Env, Vec};
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Page through the cartridge IDs owned by `user`, in get_user_cartridges order
+    /// Returns at most `limit` IDs (capped at Limits.max_owner_page) starting at position `start`
+    pub fn get_user_cartridges_page(env: Env, user: Address, start: u32, limit: u32) -> Vec<u32> {
+        page(&env, DataKey::UserCartridges(user), start, limit)
+    }
+
+    /// Page through the creature IDs owned by `user`, in get_user_creatures order
+    /// Returns at most `limit` IDs (capped at Limits.max_owner_page) starting at position `start`
+    pub fn get_user_creatures_page(env: Env, user: Address, start: u32, limit: u32) -> Vec<u32> {
+        page(&env, DataKey::UserCreatures(user), start, limit)
+    }
+
+    /// Get the number of cartridges owned by `user`
+    pub fn get_user_cartridge_count(env: Env, user: Address) -> u32 {
+        Self::get_user_cartridges(env, user).len()
+    }
+
+    /// Get the number of creatures owned by `user`
+    pub fn get_user_creature_count(env: Env, user: Address) -> u32 {
+        Self::get_user_creatures(env, user).len()
+    }
+
+    /// Get the cartridges with IDs in `start_id..end_id`, in ID order
+    /// Covers at most Limits.max_batch_read IDs from `start_id`; IDs with no cartridge are skipped
+    pub fn get_cartridges_range(env: Env, start_id: u32, end_id: u32) -> Vec<GenomeCartridge> {
+        let next_id = Self::get_total_cartridges(env.clone()).saturating_add(1);
+        let end_id = end_id
+            .min(start_id.saturating_add(MAX_BATCH_READ))
+            .min(next_id);
+
+        let mut cartridges = Vec::new(&env);
+        for id in start_id..end_id {
+            if let Some(cartridge) = env.storage().persistent().get(&DataKey::Cartridge(id)) {
+                cartridges.push_back(cartridge);
+            }
+        }
+        cartridges
+    }
+}
+
+/// Slice the ID list under `key` from position `start`, at most `limit` (capped) long
+fn page(env: &Env, key: DataKey, start: u32, limit: u32) -> Vec<u32> {
+    let ids: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    let start = start.min(ids.len());
+    let end = start
+        .saturating_add(limit.min(MAX_OWNER_PAGE))
+        .min(ids.len());
+    ids.slice(start..end)
+}
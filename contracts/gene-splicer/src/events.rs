@@ -0,0 +1,28 @@
+//! Sequenced event publishing
+//!
+//! Every event the contract emits carries a `nonce`: one counter shared by all
+//! event types, incremented per event, starting at 1. Indexers that replay a
+//! range after an RPC hiccup can drop events they have already seen, and a gap
+//! between consecutive nonces means they missed one. All events go through
+//! `publish` so none is emitted without taking the next nonce.
+
+use crate::{DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient};
+use soroban_sdk::{contractimpl, Env, Event};
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get the nonce of the most recently emitted event (0 before the first event)
+    pub fn get_event_nonce(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::EventNonce)
+            .unwrap_or(0)
+    }
+}
+
+/// Publish the event `build` makes from the next nonce
+pub(crate) fn publish<E: Event>(env: &Env, build: impl FnOnce(u64) -> E) {
+    let nonce = GeneSplicer::get_event_nonce(env.clone()) + 1;
+    env.storage().instance().set(&DataKey::EventNonce, &nonce);
+    build(nonce).publish(env);
+}
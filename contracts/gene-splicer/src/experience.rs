@@ -0,0 +1,159 @@
+//! Creature experience and levels
+//!
+//! The game server reports match results through a game operator key the
+//! Owner assigns. `grant_xp` adds experience to a creature once per match ID,
+//! so a replayed or duplicated report can't grant twice. Levels follow the
+//! fixed `LEVEL_XP` curve and stop at `MAX_LEVEL`. Experience past the last
+//! threshold isn't kept, and a single grant is capped at `MAX_XP_PER_GRANT`.
+//!
+//! Retired creatures are out of play and can't earn experience.
+
+use crate::{
+    config_log, events, features, retirement,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contracterror, contractevent, contractimpl, panic_with_error, Address, Env};
+
+/// Most experience a single grant may add
+pub const MAX_XP_PER_GRANT: u64 = 500;
+
+/// Experience needed to reach each level, from level 1
+pub const LEVEL_XP: [u64; 10] = [0, 100, 250, 500, 1_000, 2_000, 3_500, 5_500, 8_000, 12_000];
+
+/// Highest level a creature can reach
+pub const MAX_LEVEL: u32 = LEVEL_XP.len() as u32;
+
+/// Experience grant failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ExperienceError {
+    NotGameOperator = 67,     // Caller isn't the configured game operator
+    MatchAlreadyGranted = 68, // Experience for this match ID was already granted
+}
+
+/// Event emitted when the game operator grants experience for a match
+#[contractevent]
+pub struct XpGranted {
+    pub creature_id: u32,
+    pub match_id: u64,
+    pub amount: u64,
+    pub xp: u64, // Creature's experience after the grant
+    pub nonce: u64,
+}
+
+/// Event emitted when a grant takes a creature past one or more level thresholds
+#[contractevent]
+pub struct CreatureLeveledUp {
+    pub creature_id: u32,
+    pub from_level: u32,
+    pub to_level: u32,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Assign (or clear with None) the address allowed to grant experience (Owner only)
+    pub fn set_game_operator(env: Env, operator: Option<Address>) {
+        let actor = require_role(&env, Role::Owner);
+        match &operator {
+            Some(address) => env
+                .storage()
+                .instance()
+                .set(&DataKey::GameOperator, address),
+            None => env.storage().instance().remove(&DataKey::GameOperator),
+        }
+        config_log::record(&env, &actor, "game_operator", operator);
+    }
+
+    /// Get the game operator, if one is assigned
+    pub fn get_game_operator(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::GameOperator)
+    }
+
+    /// Grant a creature experience for a match (game operator only, once per match)
+    /// Refused with InvalidAmount unless 0 < amount <= MAX_XP_PER_GRANT
+    /// Returns the creature's level after the grant
+    pub fn grant_xp(
+        env: Env,
+        operator: Address,
+        creature_id: u32,
+        amount: u64,
+        match_id: u64,
+    ) -> u32 {
+        features::require(&env, "experience");
+        operator.require_auth();
+        if Self::get_game_operator(env.clone()).as_ref() != Some(&operator) {
+            panic_with_error!(&env, ExperienceError::NotGameOperator);
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Creature(creature_id))
+        {
+            panic_with_error!(&env, Error::CreatureNotFound);
+        }
+        retirement::assert_active(&env, creature_id);
+        if amount == 0 || amount > MAX_XP_PER_GRANT {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let match_key = DataKey::XpGranted(match_id);
+        if env.storage().persistent().has(&match_key) {
+            panic_with_error!(&env, ExperienceError::MatchAlreadyGranted);
+        }
+        env.storage().persistent().set(&match_key, &creature_id);
+        env.storage()
+            .persistent()
+            .extend_ttl(&match_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        let previous = Self::get_creature_xp(env.clone(), creature_id);
+        let xp = previous
+            .saturating_add(amount)
+            .min(LEVEL_XP[LEVEL_XP.len() - 1]);
+        let xp_key = DataKey::CreatureXp(creature_id);
+        env.storage().persistent().set(&xp_key, &xp);
+        env.storage()
+            .persistent()
+            .extend_ttl(&xp_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        events::publish(&env, |nonce| XpGranted {
+            creature_id,
+            match_id,
+            amount,
+            xp,
+            nonce,
+        });
+        let (from_level, to_level) = (level_for(previous), level_for(xp));
+        if to_level > from_level {
+            events::publish(&env, |nonce| CreatureLeveledUp {
+                creature_id,
+                from_level,
+                to_level,
+                nonce,
+            });
+        }
+        to_level
+    }
+
+    /// Get a creature's experience (0 if it never earned any)
+    pub fn get_creature_xp(env: Env, creature_id: u32) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CreatureXp(creature_id))
+            .unwrap_or(0)
+    }
+
+    /// Get a creature's level, from 1 to MAX_LEVEL
+    pub fn get_level(env: Env, creature_id: u32) -> u32 {
+        level_for(Self::get_creature_xp(env, creature_id))
+    }
+}
+
+/// Level reached with `xp` experience on the LEVEL_XP curve
+fn level_for(xp: u64) -> u32 {
+    LEVEL_XP
+        .iter()
+        .filter(|threshold| xp >= **threshold)
+        .count() as u32
+}
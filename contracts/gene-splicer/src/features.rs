@@ -0,0 +1,103 @@
+//! Per-subsystem feature switches
+//!
+//! Optional subsystems can be turned off on a deployment, and integrators read
+//! which ones are on with `get_features` instead of try-calling. Each
+//! subsystem's entrypoints start with `require`, so a disabled one fails with
+//! FeatureDisabled. The Operator can flip a switch at any time, which makes it
+//! a kill-switch for that subsystem alone. Entrypoints that let users get their
+//! funds or items back (revoking an offer, settling a storefront's debt) are not
+//! gated.
+//!
+//! Until a switch is first flipped every feature in FEATURES is enabled. After
+//! that the stored set is authoritative: a feature added by a later upgrade
+//! starts disabled and is turned on with `set_feature_enabled`.
+
+use crate::{
+    config_log, events,
+    roles::{require_role, Role},
+    DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{contracterror, contractevent, contractimpl, panic_with_error, Env, Symbol, Vec};
+
+/// Optional subsystems, and the entrypoints each one gates
+pub const FEATURES: &[&str] = &[
+    "marketplace", // sell_finalization_right, splice_and_list, buy_finalization_right
+    "storefront",  // storefront_mint
+    "points",      // splice_genome_with_points
+    "governance",  // create_proposal, snapshot_weights, cast_vote
+    "consumers",   // consume_randomness
+    "experience",  // grant_xp
+    "retirement",  // retire_creature
+    "drawings",    // create_drawing, resolve_drawing
+    "companions",  // mint_companion
+];
+
+/// Feature switch failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FeatureError {
+    FeatureDisabled = 76, // Entrypoint belongs to a subsystem switched off on this deployment
+    UnknownFeature = 77,  // set_feature_enabled with a name not in FEATURES
+}
+
+/// Event emitted when the Operator turns a feature on or off
+#[contractevent]
+pub struct FeatureToggled {
+    pub feature: Symbol,
+    pub enabled: bool,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Turn an optional subsystem on or off (Operator)
+    /// Fails with UnknownFeature for anything not listed in FEATURES
+    pub fn set_feature_enabled(env: Env, feature: Symbol, enabled: bool) {
+        let actor = require_role(&env, Role::Operator);
+        if !FEATURES
+            .iter()
+            .any(|known| Symbol::new(&env, known) == feature)
+        {
+            panic_with_error!(&env, FeatureError::UnknownFeature);
+        }
+
+        let mut features = Self::get_features(env.clone());
+        match (features.first_index_of(&feature), enabled) {
+            (None, true) => features.push_back(feature.clone()),
+            (Some(index), false) => {
+                features.remove(index);
+            }
+            _ => {}
+        }
+        env.storage().instance().set(&DataKey::Features, &features);
+        config_log::record(&env, &actor, "feature", (feature.clone(), enabled));
+
+        events::publish(&env, |nonce| FeatureToggled {
+            feature,
+            enabled,
+            nonce,
+        });
+    }
+
+    /// Get the enabled optional subsystems
+    pub fn get_features(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Features)
+            .unwrap_or_else(|| {
+                let mut features = Vec::new(&env);
+                for feature in FEATURES {
+                    features.push_back(Symbol::new(&env, feature));
+                }
+                features
+            })
+    }
+}
+
+/// FeatureDisabled unless `feature` is enabled
+pub(crate) fn require(env: &Env, feature: &str) {
+    if !GeneSplicer::get_features(env.clone()).contains(Symbol::new(env, feature)) {
+        panic_with_error!(env, FeatureError::FeatureDisabled);
+    }
+}
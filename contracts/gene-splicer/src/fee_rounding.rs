@@ -0,0 +1,71 @@
+//! Dust-free fee rounding
+//!
+//! Discounts, bulk tiers and points payments can leave a fee at an awkward
+//! stroop amount. The Owner-set `FeeRounding` policy is the last step of every
+//! fee computation: a non-zero fee is raised to at least `minimum` and then
+//! rounded up to a multiple of `round_to`, so a charged fee is always both. A
+//! fee that is exactly zero (fully covered by a discount, or a free setting)
+//! stays zero.
+//!
+//! Rounding is idempotent, so a fee rounded where it is quoted and again in
+//! `charge_mint_fee` is charged exactly as quoted. The default policy
+//! (`round_to` 1, `minimum` 0) leaves every fee unchanged.
+
+use crate::{
+    config_log,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Env};
+
+/// Rounding applied to every computed fee, in stroops
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeRounding {
+    pub round_to: i128, // Non-zero fees round up to a multiple of this (1 = no rounding)
+    pub minimum: i128,  // Floor for non-zero fees, applied before rounding
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set the fee rounding policy (Owner only)
+    /// Refused with InvalidAmount unless round_to >= 1 and minimum >= 0
+    pub fn set_fee_rounding(env: Env, rounding: FeeRounding) {
+        let actor = require_role(&env, Role::Owner);
+        if rounding.round_to < 1 || rounding.minimum < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeRounding, &rounding);
+        config_log::record(&env, &actor, "fee_rounding", rounding);
+    }
+
+    /// Get the fee rounding policy
+    pub fn get_fee_rounding(env: Env) -> FeeRounding {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeRounding)
+            .unwrap_or(FeeRounding {
+                round_to: 1,
+                minimum: 0,
+            })
+    }
+}
+
+/// Apply the configured rounding policy to a computed fee
+pub(crate) fn apply(env: &Env, fee: i128) -> i128 {
+    round_fee(&GeneSplicer::get_fee_rounding(env.clone()), fee)
+}
+
+/// Raise a non-zero `fee` to `minimum`, then round it up to a multiple of `round_to`
+pub fn round_fee(rounding: &FeeRounding, fee: i128) -> i128 {
+    if fee == 0 {
+        return 0;
+    }
+    let floored = fee.max(rounding.minimum);
+    match floored % rounding.round_to {
+        0 => floored,
+        remainder => floored + rounding.round_to - remainder,
+    }
+}
@@ -0,0 +1,194 @@
+//! Sale of a cartridge's finalization right
+//!
+//! The cartridge owner can sell the right to finalize: the buyer becomes the
+//! cartridge's `finalize_beneficiary`, is the one who authorizes finalization,
+//! and receives the resulting Creature. The cartridge itself (and therefore
+//! its mint provenance) stays with the original owner. Only one beneficiary
+//! can exist; the offer can be revoked until someone buys it, and the right
+//! is cleared once finalization uses it.
+
+use crate::{
+    account_auth, events, features, inbox, reentrancy, require_configured, DataKey, Error,
+    GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, panic_with_error, token, Address, Env,
+};
+
+/// Finalization right sale failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FinalizationRightError {
+    InvalidPrice = 14,             // Price must be positive
+    FinalizationRightSold = 15,    // Cartridge already has a finalization beneficiary
+    NoFinalizationRightOffer = 16, // Cartridge's finalization right isn't for sale
+}
+
+/// Event emitted when an owner offers a cartridge's finalization right for sale
+#[contractevent]
+pub struct FinalizationRightListed {
+    pub cartridge_id: u32,
+    pub owner: Address,
+    pub price: i128,
+    pub nonce: u64,
+}
+
+/// Event emitted when an owner withdraws an unsold offer
+#[contractevent]
+pub struct FinalizationRightRevoked {
+    pub cartridge_id: u32,
+    pub nonce: u64,
+}
+
+/// Event emitted when a buyer becomes the finalization beneficiary
+#[contractevent]
+pub struct FinalizationRightSold {
+    pub cartridge_id: u32,
+    pub seller: Address,
+    pub buyer: Address,
+    pub price: i128,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Offer the right to finalize a cartridge (and own the resulting Creature) for `price` stroops
+    /// Listing again replaces the asking price
+    pub fn sell_finalization_right(env: Env, owner: Address, cartridge_id: u32, price: i128) {
+        features::require(&env, "marketplace");
+        account_auth::require_list(&env, &owner, cartridge_id, price);
+        list_finalization_right(&env, owner, cartridge_id, price);
+    }
+
+    /// Splice a new cartridge and list its finalization right for `list_price` stroops in one call
+    /// Pays the usual minting fee; the buyer of the right finalizes and receives the Creature
+    pub fn splice_and_list(env: Env, user: Address, list_price: i128) -> u32 {
+        features::require(&env, "marketplace");
+        reentrancy::enter(&env);
+        user.require_auth();
+
+        if list_price <= 0 {
+            panic_with_error!(&env, FinalizationRightError::InvalidPrice);
+        }
+        let cartridge_id =
+            Self::splice_authorized(&env, user.clone(), Self::get_mint_fee(env.clone()));
+        list_finalization_right(&env, user, cartridge_id, list_price);
+        reentrancy::exit(&env);
+        cartridge_id
+    }
+
+    /// Withdraw an unsold finalization right offer
+    pub fn revoke_finalization_right(env: Env, owner: Address, cartridge_id: u32) {
+        account_auth::require_list(&env, &owner, cartridge_id, 0);
+
+        read_sellable_cartridge(&env, &owner, cartridge_id);
+        let key = DataKey::FinalizationRightPrice(cartridge_id);
+        if !env.storage().persistent().has(&key) {
+            panic_with_error!(&env, FinalizationRightError::NoFinalizationRightOffer);
+        }
+        env.storage().persistent().remove(&key);
+
+        events::publish(&env, |nonce| FinalizationRightRevoked {
+            cartridge_id,
+            nonce,
+        });
+    }
+
+    /// Buy a listed finalization right, paying the cartridge owner in XLM
+    /// The buyer is recorded as the cartridge's finalize_beneficiary
+    pub fn buy_finalization_right(env: Env, buyer: Address, cartridge_id: u32) {
+        features::require(&env, "marketplace");
+        reentrancy::enter(&env);
+        buyer.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        let key = DataKey::FinalizationRightPrice(cartridge_id);
+        let price: i128 = env.storage().persistent().get(&key).unwrap_or_else(|| {
+            panic_with_error!(&env, FinalizationRightError::NoFinalizationRightOffer)
+        });
+
+        let mut cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Cartridge(cartridge_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CartridgeNotFound));
+        if cartridge.finalized {
+            panic_with_error!(&env, Error::AlreadyFinalized);
+        }
+
+        let xlm_token: Address = require_configured(&env, &DataKey::XlmToken);
+        token::Client::new(&env, &xlm_token).transfer(&buyer, &cartridge.owner, &price);
+
+        cartridge.finalize_beneficiary = Some(buyer.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Cartridge(cartridge_id), &cartridge);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Cartridge(cartridge_id),
+            TTL_THRESHOLD,
+            TTL_EXTEND_TO,
+        );
+        env.storage().persistent().remove(&key);
+        inbox::notify(&env, &cartridge.owner, "right_sold", cartridge_id);
+
+        events::publish(&env, |nonce| FinalizationRightSold {
+            cartridge_id,
+            seller: cartridge.owner,
+            buyer,
+            price,
+            nonce,
+        });
+        reentrancy::exit(&env);
+    }
+
+    /// Get the asking price of a listed finalization right
+    pub fn get_finalization_right_price(env: Env, cartridge_id: u32) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FinalizationRightPrice(cartridge_id))
+    }
+}
+
+/// List a cartridge's finalization right once `owner` has authorized the call
+fn list_finalization_right(env: &Env, owner: Address, cartridge_id: u32, price: i128) {
+    if price <= 0 {
+        panic_with_error!(env, FinalizationRightError::InvalidPrice);
+    }
+    let cartridge = read_sellable_cartridge(env, &owner, cartridge_id);
+    if cartridge.finalize_beneficiary.is_some() {
+        panic_with_error!(env, FinalizationRightError::FinalizationRightSold);
+    }
+
+    let key = DataKey::FinalizationRightPrice(cartridge_id);
+    env.storage().persistent().set(&key, &price);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+    events::publish(env, |nonce| FinalizationRightListed {
+        cartridge_id,
+        owner,
+        price,
+        nonce,
+    });
+}
+
+/// Load a cartridge the caller owns and that hasn't been finalized yet
+fn read_sellable_cartridge(env: &Env, owner: &Address, cartridge_id: u32) -> GenomeCartridge {
+    let cartridge: GenomeCartridge = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Cartridge(cartridge_id))
+        .unwrap_or_else(|| panic_with_error!(env, Error::CartridgeNotFound));
+    if cartridge.owner != *owner {
+        panic_with_error!(env, Error::NotCartridgeOwner);
+    }
+    if cartridge.finalized {
+        panic_with_error!(env, Error::AlreadyFinalized);
+    }
+    cartridge
+}
@@ -0,0 +1,257 @@
+//! Asynchronous finalization through a public crank
+//!
+//! For large drops, finalizing every cartridge with its own beacon submission
+//! repeats the same pairing check per transaction. Owners can instead call
+//! `request_finalization`, which only checks the request and appends the
+//! cartridge to its round's queue. Keepers then call `process_queue` with the
+//! round's beacon. It verifies the beacon once (or reuses the verified-round
+//! cache) and finalizes up to `MAX_QUEUE_BATCH` queued cartridges in order.
+//!
+//! Each round has its own queue, stored in chunks of `FINALIZE_QUEUE_CHUNK`
+//! IDs between a head (next to process) and a tail (next free position), so
+//! appending and processing touch only the chunks at either end. A queued
+//! cartridge that fails its own checks is skipped with a `QueueItemSkipped`
+//! event. A cartridge whose round isn't emitted by the ledger clock yet goes to
+//! the back of the queue; any other failure, such as a cartridge finalized
+//! directly in the meantime, a missing record or an inconsistent derivation
+//! stamp, drops it, so one bad item never aborts the crank for the rest.
+//!
+//! Queued finalizations don't pay the verification share: the crank carries no
+//! one's auth to charge, and a round verified by the crank has no verifier to
+//! reimburse.
+
+use crate::{
+    account_auth, compress_g1, derivation, events, invariants, params, preflight, verification,
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge,
+    MAX_QUEUE_BATCH, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contractevent, contractimpl, panic_with_error, Address, Bytes, BytesN, Env, Vec,
+};
+
+/// Cartridge IDs per queue chunk entry
+pub const FINALIZE_QUEUE_CHUNK: u32 = 100;
+
+/// Event emitted when an owner queues a cartridge for finalization
+#[contractevent]
+pub struct FinalizationQueued {
+    pub cartridge_id: u32,
+    pub round: u64,
+    pub position: u32, // Cartridges ahead of it in the round's queue
+    pub nonce: u64,
+}
+
+/// Event emitted when the crank can't finalize a queued cartridge
+#[contractevent]
+pub struct QueueItemSkipped {
+    pub cartridge_id: u32,
+    pub round: u64,
+    pub error: u32,     // Code of the Error finalization would fail with
+    pub requeued: bool, // Moved to the back of the queue rather than dropped
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Queue a cartridge for finalization by the `process_queue` crank (finalizer auth)
+    /// Returns the number of cartridges ahead of it in its round's queue
+    pub fn request_finalization(env: Env, owner: Address, cartridge_id: u32) -> u32 {
+        account_auth::require_finalize(&env, &owner, cartridge_id);
+        let cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Cartridge(cartridge_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CartridgeNotFound));
+        if cartridge.finalizer() != owner {
+            panic_with_error!(&env, Error::NotCartridgeOwner);
+        }
+        if cartridge.finalized {
+            panic_with_error!(&env, Error::AlreadyFinalized);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::QueuedCartridge(cartridge_id))
+        {
+            panic_with_error!(&env, Error::AlreadyQueued);
+        }
+
+        let round = cartridge.splice_round;
+        let position = enqueue(&env, round, cartridge_id);
+        events::publish(&env, |nonce| FinalizationQueued {
+            cartridge_id,
+            round,
+            position,
+            nonce,
+        });
+        position
+    }
+
+    /// Finalize up to `max_items` cartridges queued for `round` (permissionless)
+    /// `randomness` (32 bytes) must be the SHA256 of the compressed `signature` (96 bytes,
+    /// uncompressed); the beacon is verified once per call unless the round is already cached
+    /// Returns the number of creatures minted; skipped cartridges emit QueueItemSkipped
+    pub fn process_queue(
+        env: Env,
+        round: u64,
+        randomness: Bytes,
+        signature: Bytes,
+        max_items: u32,
+    ) -> u32 {
+        if let Err(error) = preflight::check_open(&env) {
+            panic_with_error!(&env, error);
+        }
+        let randomness = params::require(&env, params::parse_randomness(randomness));
+        let signature = params::require(&env, params::parse_signature_g1(signature));
+
+        let dev_mode: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::DevMode)
+            .unwrap_or(false);
+        let computed: BytesN<32> = env
+            .crypto()
+            .sha256(&Bytes::from(compress_g1(&env, &signature)))
+            .into();
+        if !dev_mode {
+            if randomness != computed {
+                panic_with_error!(&env, Error::RandomnessMismatch);
+            }
+            if !verification::is_verified(&env, round, &signature) {
+                Self::verify_drand_signature(&env, round, &signature);
+                verification::record_verified(&env, round, &signature, None);
+            }
+        }
+        let verified_randomness = Bytes::from(computed);
+
+        let mut minted = 0;
+        for _ in 0..max_items.min(MAX_QUEUE_BATCH) {
+            let Some(cartridge_id) = dequeue(&env, round) else {
+                break;
+            };
+            let checked = env
+                .storage()
+                .persistent()
+                .get::<_, GenomeCartridge>(&DataKey::Cartridge(cartridge_id))
+                .ok_or(Error::CartridgeNotFound)
+                .and_then(|cartridge| {
+                    preflight::check_preconditions(&env, &cartridge, round)?;
+                    invariants::check_derivation_consistency(&env, &cartridge)?;
+                    Ok(cartridge)
+                });
+            match checked {
+                Ok(cartridge) => {
+                    let genes = derivation::derive(&env, &cartridge, &verified_randomness);
+                    Self::mint_creature(&env, cartridge, genes, false);
+                    minted += 1;
+                }
+                Err(error) => {
+                    let requeued = error == Error::RoundNotReady;
+                    if requeued {
+                        enqueue(&env, round, cartridge_id);
+                    }
+                    events::publish(&env, |nonce| QueueItemSkipped {
+                        cartridge_id,
+                        round,
+                        error: error as u32,
+                        requeued,
+                        nonce,
+                    });
+                }
+            }
+        }
+        minted
+    }
+
+    /// Get how many cartridges are queued ahead of this one, or None if it isn't queued
+    pub fn get_queue_position(env: Env, cartridge_id: u32) -> Option<u32> {
+        let (round, position): (u64, u32) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::QueuedCartridge(cartridge_id))?;
+        let (head, _) = read_bounds(&env, round);
+        Some(position - head)
+    }
+
+    /// Get how many cartridges are waiting in `round`'s queue
+    pub fn get_queue_length(env: Env, round: u64) -> u32 {
+        let (head, tail) = read_bounds(&env, round);
+        tail - head
+    }
+}
+
+/// Append `cartridge_id` to `round`'s queue, returning how many cartridges are ahead of it
+fn enqueue(env: &Env, round: u64, cartridge_id: u32) -> u32 {
+    let (head, tail) = read_bounds(env, round);
+    let chunk_index = tail / FINALIZE_QUEUE_CHUNK;
+    let mut chunk = read_chunk(env, round, chunk_index);
+    chunk.push_back(cartridge_id);
+    persist(env, &DataKey::FinalizeQueue(round, chunk_index), &chunk);
+    persist(env, &DataKey::FinalizeQueueBounds(round), &(head, tail + 1));
+    persist(env, &DataKey::QueuedCartridge(cartridge_id), &(round, tail));
+    tail - head
+}
+
+/// Take the next cartridge off `round`'s queue, freeing chunks and bounds once consumed
+fn dequeue(env: &Env, round: u64) -> Option<u32> {
+    let (head, tail) = read_bounds(env, round);
+    if head == tail {
+        return None;
+    }
+    let chunk_index = head / FINALIZE_QUEUE_CHUNK;
+    let cartridge_id = read_chunk(env, round, chunk_index)
+        .get(head % FINALIZE_QUEUE_CHUNK)
+        .expect("Queue chunk covers its positions");
+    let storage = env.storage().persistent();
+    storage.remove(&DataKey::QueuedCartridge(cartridge_id));
+
+    let head = head + 1;
+    if head % FINALIZE_QUEUE_CHUNK == 0 || head == tail {
+        storage.remove(&DataKey::FinalizeQueue(round, chunk_index));
+    }
+    if head == tail {
+        storage.remove(&DataKey::FinalizeQueueBounds(round));
+    } else {
+        persist(env, &DataKey::FinalizeQueueBounds(round), &(head, tail));
+    }
+    Some(cartridge_id)
+}
+
+/// Whether a cartridge's queue record, if any, points at a live queue position holding it
+pub(crate) fn entry_consistent(env: &Env, cartridge_id: u32) -> bool {
+    let Some((round, position)) = env
+        .storage()
+        .persistent()
+        .get::<_, (u64, u32)>(&DataKey::QueuedCartridge(cartridge_id))
+    else {
+        return true;
+    };
+    let (head, tail) = read_bounds(env, round);
+    head <= position
+        && position < tail
+        && read_chunk(env, round, position / FINALIZE_QUEUE_CHUNK)
+            .get(position % FINALIZE_QUEUE_CHUNK)
+            == Some(cartridge_id)
+}
+
+/// (head, tail) positions of `round`'s queue; both restart at 0 once it empties
+fn read_bounds(env: &Env, round: u64) -> (u32, u32) {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FinalizeQueueBounds(round))
+        .unwrap_or((0, 0))
+}
+
+fn read_chunk(env: &Env, round: u64, chunk_index: u32) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FinalizeQueue(round, chunk_index))
+        .unwrap_or(Vec::new(env))
+}
+
+fn persist<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
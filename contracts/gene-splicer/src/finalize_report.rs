@@ -0,0 +1,121 @@
+//! Finalization with a remembered failure reason
+//!
+//! A trapping `finalize_splice` leaves no trace, and users retry the same wrong
+//! inputs. `finalize_splice_or_report` runs the checks that depend on what the
+//! user submitted before attempting anything. If one fails, it stores the error
+//! code and time in temporary storage and returns None instead of trapping,
+//! so the record survives. A support UI reads it with
+//! `get_last_finalize_error`, and any successful finalization clears it.
+//!
+//! The BLS pairing itself still traps on a bad signature. Those inputs come
+//! straight from drand, while the recorded errors cover the ones users get
+//! wrong: wrong round, wrong network, malformed or mismatched beacon fields.
+
+use crate::{
+    account_auth, gc, params, preflight, reentrancy, DataKey, Error, GeneSplicer, GeneSplicerArgs,
+    GeneSplicerClient, GenomeCartridge,
+};
+use soroban_sdk::{contractimpl, panic_with_error, Bytes, BytesN, Env};
+
+/// Ledgers a failure record is kept (~1 day)
+const LAST_FINALIZE_ERROR_TTL: u32 = 17_280;
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Finalize like `finalize_splice`, but record input errors instead of trapping
+    /// Returns the creature ID, or None after recording the error (see get_last_finalize_error)
+    pub fn finalize_splice_or_report(
+        env: Env,
+        cartridge_id: u32,
+        round: u64,
+        randomness: Bytes,
+        signature_compressed: Bytes,
+        signature_uncompressed: Bytes,
+        chain_hash: Option<Bytes>,
+    ) -> Option<u32> {
+        reentrancy::enter(&env);
+        let cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Cartridge(cartridge_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CartridgeNotFound));
+        let finalizer = cartridge.finalizer();
+        account_auth::require_finalize(&env, &finalizer, cartridge_id);
+
+        let checked = params::parse_beacon(
+            randomness,
+            signature_compressed,
+            signature_uncompressed,
+            chain_hash,
+        )
+        .and_then(|beacon| check_inputs(&env, &cartridge, round, &beacon).map(|_| beacon));
+        let creature_id = match checked {
+            Ok(beacon) => Some(Self::finalize_cartridge(
+                &env, cartridge, round, beacon, &finalizer,
+            )),
+            Err(error) => {
+                let key = DataKey::LastFinalizeError(cartridge_id);
+                env.storage()
+                    .temporary()
+                    .set(&key, &(error as u32, env.ledger().timestamp()));
+                env.storage().temporary().extend_ttl(
+                    &key,
+                    LAST_FINALIZE_ERROR_TTL,
+                    LAST_FINALIZE_ERROR_TTL,
+                );
+                let expiry = env.ledger().sequence() + LAST_FINALIZE_ERROR_TTL;
+                gc::track(&env, "finalize_error", key, expiry.into());
+                None
+            }
+        };
+        reentrancy::exit(&env);
+        creature_id
+    }
+
+    /// Get the error code and timestamp of the cartridge's last failed finalize_splice_or_report
+    pub fn get_last_finalize_error(env: Env, cartridge_id: u32) -> Option<(u32, u64)> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::LastFinalizeError(cartridge_id))
+    }
+}
+
+/// Forget a cartridge's failure record once it is finalized
+pub(crate) fn clear(env: &Env, cartridge_id: u32) {
+    env.storage()
+        .temporary()
+        .remove(&DataKey::LastFinalizeError(cartridge_id));
+}
+
+/// The finalize checks that depend only on state and the submitted inputs, without the pairing
+fn check_inputs(
+    env: &Env,
+    cartridge: &GenomeCartridge,
+    round: u64,
+    beacon: &params::Beacon,
+) -> Result<(), Error> {
+    preflight::check_preconditions(env, cartridge, round)?;
+    let instance = env.storage().instance();
+    if let Some(chain_hash) = &beacon.chain_hash {
+        let expected: Option<BytesN<32>> = instance.get(&DataKey::DrandChainHash);
+        if expected.as_ref() != Some(chain_hash) {
+            return Err(Error::WrongDrandNetwork);
+        }
+    }
+    if !GeneSplicer::signature_compression_matches(
+        env,
+        &beacon.signature_compressed,
+        &beacon.signature_uncompressed,
+    ) {
+        return Err(Error::SignatureCompressionMismatch);
+    }
+    let dev_mode: bool = instance.get(&DataKey::DevMode).unwrap_or(false);
+    let computed: BytesN<32> = env
+        .crypto()
+        .sha256(&Bytes::from(beacon.signature_compressed.clone()))
+        .into();
+    if !dev_mode && beacon.randomness != computed {
+        return Err(Error::RandomnessMismatch);
+    }
+    Ok(())
+}
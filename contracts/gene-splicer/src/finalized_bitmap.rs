@@ -0,0 +1,94 @@
+//! Finalized-cartridge bitmap
+//!
+//! Answers "which of these IDs are finalized" with one 32-byte read per 256
+//! cartridges instead of one probe per ID. Chunk `c` covers cartridge IDs
+//! `c * 256 ..= c * 256 + 255`; ID `id` is bit `id % 8` (least significant
+//! first) of byte `(id % 256) / 8`. The `finalized` flag on each cartridge
+//! stays authoritative: the bitmap is an index, and `repair_finalized_bitmap`
+//! rewrites a range of bits from the cartridges themselves.
+
+use crate::{
+    DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge, ReadLimitError,
+    MAX_BATCH_READ, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contractimpl, panic_with_error, BytesN, Env};
+
+/// Cartridge IDs covered by one bitmap chunk (one bit each in 32 bytes)
+pub const FINALIZED_BITMAP_CHUNK: u32 = 256;
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get the finalized bits for cartridge IDs `chunk * 256 ..= chunk * 256 + 255`
+    pub fn get_finalized_bitmap(env: Env, chunk: u32) -> BytesN<32> {
+        BytesN::from_array(&env, &read_chunk(&env, chunk))
+    }
+
+    /// Check a cartridge's finalized bit without loading the cartridge
+    pub fn is_finalized_fast(env: Env, cartridge_id: u32) -> bool {
+        let bits = read_chunk(&env, cartridge_id / FINALIZED_BITMAP_CHUNK);
+        let (byte, mask) = bit_position(cartridge_id);
+        bits[byte] & mask != 0
+    }
+
+    /// Rewrite the bits of `count` cartridges starting at `first_id` from their
+    /// authoritative `finalized` flags (at most Limits.max_batch_read). Permissionless
+    pub fn repair_finalized_bitmap(env: Env, first_id: u32, count: u32) {
+        if count > MAX_BATCH_READ {
+            panic_with_error!(&env, ReadLimitError::MaxBatchReadExceeded);
+        }
+        let end = first_id.saturating_add(count);
+        let mut id = first_id;
+        while id < end {
+            let chunk = id / FINALIZED_BITMAP_CHUNK;
+            let chunk_end = end.min((chunk + 1).saturating_mul(FINALIZED_BITMAP_CHUNK));
+            let mut bits = read_chunk(&env, chunk);
+            for cartridge_id in id..chunk_end {
+                let finalized = env
+                    .storage()
+                    .persistent()
+                    .get::<_, GenomeCartridge>(&DataKey::Cartridge(cartridge_id))
+                    .is_some_and(|cartridge| cartridge.finalized);
+                let (byte, mask) = bit_position(cartridge_id);
+                if finalized {
+                    bits[byte] |= mask;
+                } else {
+                    bits[byte] &= !mask;
+                }
+            }
+            write_chunk(&env, chunk, &bits);
+            id = chunk_end;
+        }
+    }
+}
+
+/// Set a newly finalized cartridge's bit
+pub(crate) fn mark_finalized(env: &Env, cartridge_id: u32) {
+    let chunk = cartridge_id / FINALIZED_BITMAP_CHUNK;
+    let mut bits = read_chunk(env, chunk);
+    let (byte, mask) = bit_position(cartridge_id);
+    bits[byte] |= mask;
+    write_chunk(env, chunk, &bits);
+}
+
+/// Byte index and bit mask of a cartridge ID within its chunk
+fn bit_position(cartridge_id: u32) -> (usize, u8) {
+    let bit = cartridge_id % FINALIZED_BITMAP_CHUNK;
+    ((bit / 8) as usize, 1 << (bit % 8))
+}
+
+fn read_chunk(env: &Env, chunk: u32) -> [u8; 32] {
+    env.storage()
+        .persistent()
+        .get::<_, BytesN<32>>(&DataKey::FinalizedBitmapChunk(chunk))
+        .map_or([0; 32], |bits| bits.to_array())
+}
+
+fn write_chunk(env: &Env, chunk: u32, bits: &[u8; 32]) {
+    let key = DataKey::FinalizedBitmapChunk(chunk);
+    env.storage()
+        .persistent()
+        .set(&key, &BytesN::from_array(env, bits));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
@@ -0,0 +1,190 @@
+//! First-splice-free program
+//!
+//! An account that has never minted can claim one free cartridge while the
+//! program is enabled. Minting by any route records the account in `FirstSeen`,
+//! so the free mint can only ever be the account's first. When an attestor key
+//! is configured, claims must also carry an ed25519 attestation from our backend
+//! that the account is at least `min_account_age` seconds old, which makes
+//! farming with fresh wallets impractical.
+
+use crate::{
+    config_log, events, maintenance,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, ORIGINAL_COLLECTION,
+    TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, panic_with_error, xdr::ToXdr, Address, Bytes,
+    BytesN, Env,
+};
+
+const FREE_MINT_DOMAIN: &[u8] = b"SPLICERS_FREE_MINT";
+
+/// Free first mint failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FreeMintError {
+    FreeMintDisabled = 22,    // Free first mint program is switched off
+    FreeMintAlreadyUsed = 23, // Account has already minted, so its first mint can't be free
+    AttestationRequired = 24, // Free mint claimed without the configured account-age attestation
+}
+
+/// Event emitted when the free mint program is switched on or off
+#[contractevent]
+pub struct FreeMintProgramChanged {
+    pub enabled: bool,
+    pub nonce: u64,
+}
+
+/// Event emitted when an account claims its free first mint
+#[contractevent]
+pub struct FreeMintClaimed {
+    pub user: Address,
+    pub cartridge_id: u32,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Enable or disable the free first mint program (Operator)
+    pub fn set_free_mint_enabled(env: Env, enabled: bool) {
+        let actor = require_role(&env, Role::Operator);
+        env.storage()
+            .instance()
+            .set(&DataKey::FreeMintEnabled, &enabled);
+        config_log::record(&env, &actor, "free_mint_enabled", enabled);
+        events::publish(&env, |nonce| FreeMintProgramChanged { enabled, nonce });
+    }
+
+    /// Require (or stop requiring, with None) a backend attestation of account age (Owner only)
+    /// `min_account_age` is part of the signed message, so changing it invalidates old attestations
+    pub fn set_free_mint_attestor(env: Env, attestor: Option<BytesN<32>>, min_account_age: u64) {
+        let actor = require_role(&env, Role::Owner);
+        match &attestor {
+            Some(key) => env
+                .storage()
+                .instance()
+                .set(&DataKey::FreeMintAttestor, key),
+            None => env.storage().instance().remove(&DataKey::FreeMintAttestor),
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::FreeMintMinAccountAge, &min_account_age);
+        config_log::record(
+            &env,
+            &actor,
+            "free_mint_attestor",
+            (attestor, min_account_age),
+        );
+    }
+
+    /// Mint the caller's first cartridge without the XLM fee
+    /// - Program must be enabled and the account must never have minted before
+    /// - `attestation` is required when an attestor is configured: an ed25519 signature
+    ///   over `free_mint_message(user)`
+    pub fn claim_free_mint(env: Env, user: Address, attestation: Option<BytesN<64>>) -> u32 {
+        user.require_auth();
+
+        if !Self::is_free_mint_enabled(env.clone()) {
+            panic_with_error!(&env, FreeMintError::FreeMintDisabled);
+        }
+        if env
+            .storage()
+            .instance()
+            .get(&DataKey::MintPaused)
+            .unwrap_or(false)
+        {
+            panic_with_error!(&env, Error::MintPaused);
+        }
+        if let Err(error) = maintenance::check(&env) {
+            panic_with_error!(&env, error);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::FirstSeen(user.clone()))
+        {
+            panic_with_error!(&env, FreeMintError::FreeMintAlreadyUsed);
+        }
+
+        let attestor: Option<BytesN<32>> = env.storage().instance().get(&DataKey::FreeMintAttestor);
+        if let Some(attestor) = attestor {
+            let attestation = attestation
+                .unwrap_or_else(|| panic_with_error!(&env, FreeMintError::AttestationRequired));
+            let message = Self::free_mint_message(env.clone(), user.clone());
+            env.crypto()
+                .ed25519_verify(&attestor, &message, &attestation);
+        }
+
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        // Marks the account as seen, consuming the free mint
+        let cartridge_id = Self::mint_cartridge(&env, user.clone(), ORIGINAL_COLLECTION);
+
+        let claimed = Self::get_free_mint_count(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::FreeMintCount, &(claimed + 1));
+
+        events::publish(&env, |nonce| FreeMintClaimed {
+            user,
+            cartridge_id,
+            nonce,
+        });
+        cartridge_id
+    }
+
+    /// Bytes the backend signs to attest `user`'s account age:
+    /// domain tag || contract address (XDR) || user address (XDR) || min_account_age (u64 BE)
+    pub fn free_mint_message(env: Env, user: Address) -> Bytes {
+        let min_account_age: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::FreeMintMinAccountAge)
+            .unwrap_or(0);
+        let mut message = Bytes::from_slice(&env, FREE_MINT_DOMAIN);
+        message.append(&env.current_contract_address().to_xdr(&env));
+        message.append(&user.to_xdr(&env));
+        message.extend_from_array(&min_account_age.to_be_bytes());
+        message
+    }
+
+    /// Get whether the free first mint program is enabled
+    pub fn is_free_mint_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::FreeMintEnabled)
+            .unwrap_or(false)
+    }
+
+    /// Get whether `user` could still claim a free mint (ignoring attestation)
+    pub fn is_free_mint_eligible(env: Env, user: Address) -> bool {
+        Self::is_free_mint_enabled(env.clone())
+            && !env.storage().persistent().has(&DataKey::FirstSeen(user))
+    }
+
+    /// Get the number of free mints claimed so far
+    pub fn get_free_mint_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::FreeMintCount)
+            .unwrap_or(0)
+    }
+}
+
+/// Record the ledger timestamp of `user`'s first mint, if not already recorded
+pub(crate) fn mark_seen(env: &Env, user: &Address) {
+    let key = DataKey::FirstSeen(user.clone());
+    if env.storage().persistent().has(&key) {
+        return;
+    }
+    env.storage()
+        .persistent()
+        .set(&key, &env.ledger().timestamp());
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
@@ -0,0 +1,109 @@
+//! Bounded cleanup of short-lived entries
+//!
+//! Client references, consumed permits, begun verifications and finalize
+//! failure records are only meaningful for a while. Each subsystem records
+//! what it creates with `track`, in a small per-kind ring of the most recent
+//! GC_RING_CAPACITY keys and their logical expiry, and anyone can call `gc`
+//! to delete the ones whose expiry has passed.
+//!
+//! Temporary entries expire on their own, but their TTL can be extended by
+//! anyone, so `gc` removes them at their logical expiry regardless. Consumed
+//! permits are persistent: once the permit's `expires_at` has passed it is
+//! rejected as expired before the single-use check, so the marker can go.
+//! A key that falls out of a full ring is left to its TTL.
+
+use crate::{
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contractimpl, panic_with_error, Env, Symbol, Vec};
+
+/// Kinds of entries gc can clean up
+/// "permit" expiries are ledger timestamps; the rest are temporary and expire by ledger sequence
+pub const GC_KINDS: &[&str] = &[
+    "client_ref",     // splice_genome_ref references
+    "permit",         // consumed finalization permits
+    "verification",   // signatures held between the two verification phases
+    "finalize_error", // finalize_splice_or_report failure records
+];
+
+/// Most recent keys remembered per kind
+pub const GC_RING_CAPACITY: u32 = 64;
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Delete tracked entries of `kinds` whose logical expiry has passed (permissionless)
+    /// Examines at most `limit` tracked keys, oldest first; returns how many entries were deleted
+    pub fn gc(env: Env, kinds: Vec<Symbol>, limit: u32) -> u32 {
+        for kind in kinds.iter() {
+            if !is_kind(&env, &kind) {
+                panic_with_error!(&env, Error::UnknownGcKind);
+            }
+        }
+
+        let mut examined = 0;
+        let mut removed = 0;
+        for kind in kinds.iter() {
+            let timestamped = kind == Symbol::new(&env, "permit");
+            let ring = ring(&env, &kind);
+            let mut kept = Vec::new(&env);
+            for (key, expiry) in ring.iter() {
+                if examined == limit {
+                    kept.push_back((key, expiry));
+                    continue;
+                }
+                examined += 1;
+                let now = if timestamped {
+                    env.ledger().timestamp()
+                } else {
+                    env.ledger().sequence().into()
+                };
+                if now <= expiry {
+                    kept.push_back((key, expiry));
+                } else if timestamped && env.storage().persistent().has(&key) {
+                    env.storage().persistent().remove(&key);
+                    removed += 1;
+                } else if !timestamped && env.storage().temporary().has(&key) {
+                    env.storage().temporary().remove(&key);
+                    removed += 1;
+                }
+            }
+            if kept.len() != ring.len() {
+                write_ring(&env, kind, &kept);
+            }
+        }
+        removed
+    }
+}
+
+/// Remember `key` under `kind`, live through `expiry` (ledger sequence, or timestamp for "permit")
+/// A full ring forgets its oldest key
+pub(crate) fn track(env: &Env, kind: &str, key: DataKey, expiry: u64) {
+    let kind = Symbol::new(env, kind);
+    let mut ring = ring(env, &kind);
+    if ring.len() >= GC_RING_CAPACITY {
+        ring.pop_front();
+    }
+    ring.push_back((key, expiry));
+    write_ring(env, kind, &ring);
+}
+
+fn ring(env: &Env, kind: &Symbol) -> Vec<(DataKey, u64)> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GcRing(kind.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn write_ring(env: &Env, kind: Symbol, ring: &Vec<(DataKey, u64)>) {
+    let key = DataKey::GcRing(kind);
+    env.storage().persistent().set(&key, ring);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+fn is_kind(env: &Env, kind: &Symbol) -> bool {
+    GC_KINDS
+        .iter()
+        .any(|known| Symbol::new(env, known) == *kind)
+}
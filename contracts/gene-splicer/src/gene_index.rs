@@ -0,0 +1,130 @@
+//! Creature index by gene, for trait-based discovery
+//!
+//! Every finalized creature is appended to one list per gene slot (0 head,
+//! 1 body, 2 legs) keyed by the gene ID in that slot. Popular genes collect
+//! many creatures, so each list is stored as fixed-size chunks of
+//! `GENE_INDEX_CHUNK` IDs plus a count entry; appending touches only the last
+//! chunk and a page read touches at most two.
+
+use crate::{
+    reveal, Creature, DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+    MAX_GENE_INDEX_PAGE, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contractimpl, panic_with_error, Env, Vec};
+
+/// Creature IDs per index chunk entry
+pub const GENE_INDEX_CHUNK: u32 = 100;
+
+/// Gene slots in creature order: head, body, legs
+const GENE_SLOTS: u32 = 3;
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Page through creatures carrying `gene_id` in `slot` (0 head, 1 body, 2 legs), in finalization order
+    /// Returns at most `limit` IDs (capped at Limits.max_gene_index_page) starting at position `offset`
+    /// Both index getters are refused with NotRevealed until the collection reveal
+    pub fn get_creatures_by_gene(
+        env: Env,
+        slot: u32,
+        gene_id: u32,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u32> {
+        let count = Self::get_creatures_by_gene_count(env.clone(), slot, gene_id);
+        let end = offset
+            .saturating_add(limit.min(MAX_GENE_INDEX_PAGE))
+            .min(count);
+
+        let mut page = Vec::new(&env);
+        let mut position = offset;
+        while position < end {
+            let chunk_index = position / GENE_INDEX_CHUNK;
+            let chunk = read_chunk(&env, slot, gene_id, chunk_index);
+            let chunk_end = end.min((chunk_index + 1) * GENE_INDEX_CHUNK);
+            for i in position..chunk_end {
+                page.push_back(chunk.get(i % GENE_INDEX_CHUNK).unwrap());
+            }
+            position = chunk_end;
+        }
+        page
+    }
+
+    /// Get the number of creatures carrying `gene_id` in `slot`
+    pub fn get_creatures_by_gene_count(env: Env, slot: u32, gene_id: u32) -> u32 {
+        reveal::require_revealed(&env);
+        if slot >= GENE_SLOTS {
+            panic_with_error!(&env, Error::InvalidGeneSlot);
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::CreaturesByGeneCount(slot, gene_id))
+            .unwrap_or(0)
+    }
+}
+
+/// Append a newly finalized creature to the index of each of its three genes
+pub(crate) fn index_creature(env: &Env, creature: &Creature) {
+    for (slot, gene_id) in (0..GENE_SLOTS).zip(slot_genes(creature)) {
+        append(env, slot, gene_id, creature.id);
+    }
+}
+
+/// Whether the creature appears in the index of each of its three genes
+pub(crate) fn is_indexed(env: &Env, creature: &Creature) -> bool {
+    (0..GENE_SLOTS)
+        .zip(slot_genes(creature))
+        .all(|(slot, gene_id)| contains(env, slot, gene_id, creature.id))
+}
+
+/// Append the creature to whichever of its gene indexes is missing it
+pub(crate) fn reindex_creature(env: &Env, creature: &Creature) {
+    for (slot, gene_id) in (0..GENE_SLOTS).zip(slot_genes(creature)) {
+        if !contains(env, slot, gene_id, creature.id) {
+            append(env, slot, gene_id, creature.id);
+        }
+    }
+}
+
+fn slot_genes(creature: &Creature) -> [u32; GENE_SLOTS as usize] {
+    [
+        creature.head_gene.id,
+        creature.body_gene.id,
+        creature.legs_gene.id,
+    ]
+}
+
+fn contains(env: &Env, slot: u32, gene_id: u32, creature_id: u32) -> bool {
+    let count: u32 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::CreaturesByGeneCount(slot, gene_id))
+        .unwrap_or(0);
+    (0..count.div_ceil(GENE_INDEX_CHUNK))
+        .any(|chunk_index| read_chunk(env, slot, gene_id, chunk_index).contains(creature_id))
+}
+
+fn append(env: &Env, slot: u32, gene_id: u32, creature_id: u32) {
+    let count_key = DataKey::CreaturesByGeneCount(slot, gene_id);
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+    let chunk_index = count / GENE_INDEX_CHUNK;
+    let chunk_key = DataKey::CreaturesByGeneChunk(slot, gene_id, chunk_index);
+    let mut chunk = read_chunk(env, slot, gene_id, chunk_index);
+    chunk.push_back(creature_id);
+
+    env.storage().persistent().set(&chunk_key, &chunk);
+    env.storage()
+        .persistent()
+        .extend_ttl(&chunk_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    env.storage().persistent().set(&count_key, &(count + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&count_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+fn read_chunk(env: &Env, slot: u32, gene_id: u32, chunk_index: u32) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CreaturesByGeneChunk(slot, gene_id, chunk_index))
+        .unwrap_or(Vec::new(env))
+}
@@ -0,0 +1,189 @@
+//! Pre-committed gene table changes
+//!
+//! The gene table sets the odds of each rarity. To show that the odds are
+//! never retuned in reaction to recent drops, a new table goes live in two
+//! steps. The Owner first publishes `commit_gene_table` with the SHA256 of the
+//! table's XDR encoding. At least `GENE_TABLE_DELAY` later, `apply_gene_table`
+//! reveals the table, checks it against the commitment and activates it as a
+//! new epoch. Both steps emit the hash, so anyone can check the table was fixed
+//! before the drops it affects.
+//!
+//! Each cartridge is stamped with the epoch that was live when it was minted
+//! and is finalized with that epoch's table, like the derivation version.
+//! Epoch 0 is the original 60/30/10 table built into the derivation. Like the
+//! derivation version, every epoch change is appended to a history that
+//! `invariants` checks stamps against.
+
+use crate::{
+    config_log, events,
+    roles::{require_role, Role},
+    DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, contracttype, panic_with_error, xdr::ToXdr, BytesN,
+    Env, Vec,
+};
+
+/// Seconds a gene table commitment must wait before it can be applied (3 days)
+pub const GENE_TABLE_DELAY: u64 = 259_200;
+
+/// Denominator of gene table odds: odds are rolls out of 1000
+pub const GENE_TABLE_SCALE: u32 = 1_000;
+
+/// Gene table commit and apply failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GeneTableError {
+    GeneTableNotCommitted = 63, // apply_gene_table called with no pending commitment
+    GeneTableDelayActive = 64,  // Gene table commitment hasn't waited out the mandatory delay
+    GeneTableHashMismatch = 65, // Applied gene table doesn't hash to the commitment
+    InvalidGeneTable = 66,      // Gene table odds add up to more than GENE_TABLE_SCALE
+}
+
+/// Rarity odds, in rolls out of GENE_TABLE_SCALE; the remainder rolls Normal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GeneTable {
+    pub legendary_odds: u32,
+    pub rare_odds: u32,
+}
+
+/// A gene table hash waiting out the delay
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GeneTableCommitment {
+    pub table_hash: BytesN<32>, // SHA256 of the table's XDR encoding
+    pub apply_after: u64,       // Earliest ledger timestamp apply_gene_table accepts
+}
+
+/// Event emitted when a gene table hash is committed
+#[contractevent]
+pub struct GeneTableCommitted {
+    pub table_hash: BytesN<32>,
+    pub apply_after: u64,
+    pub nonce: u64,
+}
+
+/// Event emitted when a committed gene table goes live
+#[contractevent]
+pub struct GeneTableApplied {
+    pub epoch: u32,
+    pub table_hash: BytesN<32>,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Commit to the hash of the next gene table (Owner only)
+    /// Replacing a pending commitment restarts the delay
+    pub fn commit_gene_table(env: Env, table_hash: BytesN<32>) {
+        let actor = require_role(&env, Role::Owner);
+        let commitment = GeneTableCommitment {
+            table_hash: table_hash.clone(),
+            apply_after: env.ledger().timestamp() + GENE_TABLE_DELAY,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::GeneTableCommitment, &commitment);
+        config_log::record(&env, &actor, "gene_table_commitment", table_hash.clone());
+
+        events::publish(&env, |nonce| GeneTableCommitted {
+            table_hash,
+            apply_after: commitment.apply_after,
+            nonce,
+        });
+    }
+
+    /// Activate the committed gene table for cartridges minted from now on (Owner only)
+    /// Returns the new epoch
+    pub fn apply_gene_table(env: Env, table: GeneTable) -> u32 {
+        let actor = require_role(&env, Role::Owner);
+        let commitment = Self::get_gene_table_commitment(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, GeneTableError::GeneTableNotCommitted));
+        if env.ledger().timestamp() < commitment.apply_after {
+            panic_with_error!(&env, GeneTableError::GeneTableDelayActive);
+        }
+        let table_hash: BytesN<32> = env.crypto().sha256(&table.clone().to_xdr(&env)).into();
+        if table_hash != commitment.table_hash {
+            panic_with_error!(&env, GeneTableError::GeneTableHashMismatch);
+        }
+        if table.legendary_odds.saturating_add(table.rare_odds) > GENE_TABLE_SCALE {
+            panic_with_error!(&env, GeneTableError::InvalidGeneTable);
+        }
+
+        let epoch = current_epoch(&env) + 1;
+        let key = DataKey::GeneTable(epoch);
+        env.storage().persistent().set(&key, &table);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.storage()
+            .instance()
+            .set(&DataKey::GeneTableEpoch, &epoch);
+        record_history(&env, epoch);
+        env.storage()
+            .instance()
+            .remove(&DataKey::GeneTableCommitment);
+        config_log::record(&env, &actor, "gene_table", table);
+
+        events::publish(&env, |nonce| GeneTableApplied {
+            epoch,
+            table_hash,
+            nonce,
+        });
+        epoch
+    }
+
+    /// Get the pending gene table commitment, if any
+    pub fn get_gene_table_commitment(env: Env) -> Option<GeneTableCommitment> {
+        env.storage().instance().get(&DataKey::GeneTableCommitment)
+    }
+
+    /// Get the gene table epoch stamped on new cartridges (0 = the original table)
+    pub fn get_gene_table_epoch(env: Env) -> u32 {
+        current_epoch(&env)
+    }
+
+    /// Get the gene table applied at `epoch` (None for the built-in epoch 0 and unknown epochs)
+    pub fn get_gene_table(env: Env, epoch: u32) -> Option<GeneTable> {
+        table(&env, epoch)
+    }
+}
+
+/// Gene table epoch to stamp on a cartridge being minted now
+pub(crate) fn current_epoch(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::GeneTableEpoch)
+        .unwrap_or(0)
+}
+
+/// Append (now, epoch) to the history of epochs stamped on new cartridges
+/// Called whenever an epoch goes live, including epoch 0 at construction
+pub(crate) fn record_history(env: &Env, epoch: u32) {
+    let mut history = history(env);
+    history.push_back((env.ledger().timestamp(), epoch));
+    env.storage()
+        .persistent()
+        .set(&DataKey::GeneTableHistory, &history);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::GeneTableHistory, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// (since, epoch) pairs, oldest first: `epoch` was stamped on cartridges minted from `since`
+pub(crate) fn history(env: &Env) -> Vec<(u64, u32)> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GeneTableHistory)
+        .unwrap_or(Vec::new(env))
+}
+
+/// The table applied at `epoch`; None means the original built-in odds
+pub(crate) fn table(env: &Env, epoch: u32) -> Option<GeneTable> {
+    if epoch == 0 {
+        return None;
+    }
+    env.storage().persistent().get(&DataKey::GeneTable(epoch))
+}
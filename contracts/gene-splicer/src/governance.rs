@@ -0,0 +1,228 @@
+//! Lightweight on-chain governance signals weighted by creature holdings
+//!
+//! Voting weight comes from the creatures a user holds: a creature with any
+//! Legendary gene counts 3, every other creature counts 1. Because holdings
+//! change (new finalizations, transfers), the admin freezes weights per
+//! proposal with `snapshot_weights`; votes always use the frozen value.
+
+use crate::{
+    events, features, migration, retirement,
+    roles::{require_role, Role},
+    Creature, DataKey, GeneRarity, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+    MAX_SNAPSHOT_BATCH, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, contracttype, panic_with_error, Address, Env, Vec,
+};
+
+// Weight of a creature carrying at least one Legendary gene
+const LEGENDARY_WEIGHT: u64 = 3;
+// Weight of every other creature
+const BASE_WEIGHT: u64 = 1;
+
+/// Governance failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernanceError {
+    ProposalNotFound = 7,          // No governance proposal with this ID
+    NotInSnapshot = 8,             // Voter has no snapshotted weight for the proposal
+    AlreadyVoted = 9,              // Voter already voted on the proposal
+    MaxSnapshotBatchExceeded = 10, // Over Limits.max_snapshot_batch voters in one snapshot_weights
+}
+
+/// Governance proposal with running vote tallies
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u32,
+    pub created_at: u64, // Ledger timestamp
+    pub yes_weight: u64, // Sum of snapshotted weights voting in support
+    pub no_weight: u64,  // Sum of snapshotted weights voting against
+}
+
+/// Event emitted when the admin creates a proposal
+#[contractevent]
+pub struct ProposalCreated {
+    pub proposal_id: u32,
+    pub nonce: u64,
+}
+
+/// Event emitted when a snapshotted voter casts a vote
+#[contractevent]
+pub struct VoteCast {
+    pub proposal_id: u32,
+    pub voter: Address,
+    pub support: bool,
+    pub weight: u64,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get a user's current voting weight from their creature holdings
+    pub fn get_voting_weight(env: Env, user: Address) -> u64 {
+        let creature_ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserCreatures(user))
+            .unwrap_or(Vec::new(&env));
+        let mut weight = 0u64;
+        for id in creature_ids.iter() {
+            if retirement::is_retired(&env, id) || migration::is_exported(&env, id) {
+                continue;
+            }
+            if let Some(creature) = env
+                .storage()
+                .persistent()
+                .get::<_, Creature>(&DataKey::Creature(id))
+            {
+                weight += creature_weight(&creature);
+            }
+        }
+        weight
+    }
+
+    /// Create a new proposal (Operator)
+    /// Returns the proposal ID
+    pub fn create_proposal(env: Env) -> u32 {
+        features::require(&env, "governance");
+        require_role(&env, Role::Operator);
+
+        let proposal_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .unwrap_or(1);
+        env.storage().instance().set(
+            &DataKey::NextProposalId,
+            &proposal_id.checked_add(1).expect("Proposal ID overflow"),
+        );
+
+        let proposal = Proposal {
+            id: proposal_id,
+            created_at: env.ledger().timestamp(),
+            yes_weight: 0,
+            no_weight: 0,
+        };
+        write_proposal(&env, &proposal);
+
+        events::publish(&env, |nonce| ProposalCreated { proposal_id, nonce });
+        proposal_id
+    }
+
+    /// Freeze current voting weights for the listed voters (Operator)
+    /// A voter's snapshot is written once; later calls never overwrite it, so
+    /// holdings changes after the snapshot don't affect the proposal
+    pub fn snapshot_weights(env: Env, proposal_id: u32, voters: Vec<Address>) {
+        features::require(&env, "governance");
+        require_role(&env, Role::Operator);
+
+        if voters.len() > MAX_SNAPSHOT_BATCH {
+            panic_with_error!(&env, GovernanceError::MaxSnapshotBatchExceeded);
+        }
+        read_proposal(&env, proposal_id);
+
+        for voter in voters.iter() {
+            let key = DataKey::SnapshotWeight(proposal_id, voter.clone());
+            if env.storage().persistent().has(&key) {
+                continue;
+            }
+            let weight = Self::get_voting_weight(env.clone(), voter);
+            env.storage().persistent().set(&key, &weight);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+    }
+
+    /// Get a voter's frozen weight for a proposal (None if not snapshotted)
+    pub fn get_snapshot_weight(env: Env, proposal_id: u32, voter: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SnapshotWeight(proposal_id, voter))
+    }
+
+    /// Cast a vote using the voter's snapshotted weight (one vote per proposal)
+    pub fn cast_vote(env: Env, voter: Address, proposal_id: u32, support: bool) {
+        features::require(&env, "governance");
+        voter.require_auth();
+
+        let mut proposal = read_proposal(&env, proposal_id);
+
+        let voted_key = DataKey::Voted(proposal_id, voter.clone());
+        if env.storage().persistent().has(&voted_key) {
+            panic_with_error!(&env, GovernanceError::AlreadyVoted);
+        }
+
+        let weight: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SnapshotWeight(proposal_id, voter.clone()))
+            .unwrap_or_else(|| panic_with_error!(&env, GovernanceError::NotInSnapshot));
+
+        if support {
+            proposal.yes_weight += weight;
+        } else {
+            proposal.no_weight += weight;
+        }
+        write_proposal(&env, &proposal);
+
+        env.storage().persistent().set(&voted_key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&voted_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        events::publish(&env, |nonce| VoteCast {
+            proposal_id,
+            voter,
+            support,
+            weight,
+            nonce,
+        });
+    }
+
+    /// Get (yes_weight, no_weight) for a proposal
+    pub fn tally(env: Env, proposal_id: u32) -> (u64, u64) {
+        let proposal = read_proposal(&env, proposal_id);
+        (proposal.yes_weight, proposal.no_weight)
+    }
+
+    /// Get proposal data by ID
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Option<Proposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+    }
+}
+
+/// Voting weight of a single creature
+pub(crate) fn creature_weight(creature: &Creature) -> u64 {
+    let legendary = [
+        &creature.head_gene,
+        &creature.body_gene,
+        &creature.legs_gene,
+    ]
+    .iter()
+    .any(|gene| gene.rarity == GeneRarity::Legendary);
+    if legendary {
+        LEGENDARY_WEIGHT
+    } else {
+        BASE_WEIGHT
+    }
+}
+
+fn read_proposal(env: &Env, proposal_id: u32) -> Proposal {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .unwrap_or_else(|| panic_with_error!(env, GovernanceError::ProposalNotFound))
+}
+
+fn write_proposal(env: &Env, proposal: &Proposal) {
+    let key = DataKey::Proposal(proposal.id);
+    env.storage().persistent().set(&key, proposal);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
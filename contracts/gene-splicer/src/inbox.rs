@@ -0,0 +1,60 @@
+//! Per-user notification inbox
+//!
+//! Wallets without an indexer can poll a single key to learn what happened to
+//! an address since they last looked. Features call `notify` for events the
+//! user didn't initiate themselves (a creature landing in their wallet, a sale
+//! of something they listed, a payout); the inbox keeps the last `MAX_INBOX`
+//! entries, evicting the oldest, until its owner clears it.
+
+use crate::{
+    DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+/// Number of most recent entries kept per user
+pub const MAX_INBOX: u32 = 20;
+
+/// One notification: what happened, to which cartridge or creature, and when
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InboxEntry {
+    pub kind: Symbol, // e.g. "finalized", "right_sold", "creature_received"
+    pub id: u32,      // Cartridge / creature ID the event concerns
+    pub timestamp: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get a user's notifications, oldest first
+    pub fn read_inbox(env: Env, user: Address) -> Vec<InboxEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Inbox(user))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Drop all of a user's notifications (user auth)
+    pub fn clear_inbox(env: Env, user: Address) {
+        user.require_auth();
+        env.storage().persistent().remove(&DataKey::Inbox(user));
+    }
+}
+
+/// Append a notification to `user`'s inbox, evicting the oldest beyond MAX_INBOX
+pub(crate) fn notify(env: &Env, user: &Address, kind: &str, id: u32) {
+    let mut inbox = GeneSplicer::read_inbox(env.clone(), user.clone());
+    if inbox.len() >= MAX_INBOX {
+        inbox.pop_front();
+    }
+    inbox.push_back(InboxEntry {
+        kind: Symbol::new(env, kind),
+        id,
+        timestamp: env.ledger().timestamp(),
+    });
+
+    let key = DataKey::Inbox(user.clone());
+    env.storage().persistent().set(&key, &inbox);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
@@ -0,0 +1,227 @@
+//! Per-asset consistency checks across the parallel indexes
+//!
+//! Ownership lists, the pending index, the finalized bitmap, the gene index,
+//! finalization queues and companion links are all maintained alongside the
+//! cartridge and creature records they describe. `check_integrity` takes one
+//! ID and evaluates every invariant in `INVARIANTS` against it, returning the
+//! names of those that fail. Anyone can call it.
+//!
+//! Invariants whose index is derived from the authoritative records come with
+//! a repair, which `repair` (Operator only) runs for each failing one. The
+//! others would need a judgement call (which side of a broken link is right?)
+//! and are only reported. Any new index must add its invariant to the table.
+//!
+//! Checks only look at what can be found from the ID itself: a stale entry in
+//! a previous owner's list, or in the index of a gene the creature doesn't
+//! carry, isn't detected.
+
+use crate::{
+    events, finalize_queue, gene_index, pending,
+    roles::{require_role, Role},
+    Creature, DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge,
+    IMPORTED_ID_BASE, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contractevent, contractimpl, Env, Symbol, Vec};
+
+/// One cross-index invariant of an asset ID
+struct Invariant {
+    name: &'static str,
+    holds: fn(&Env, u32) -> bool,
+    repair: Option<fn(&Env, u32)>,
+}
+
+/// Every invariant check_integrity evaluates, in reporting order
+const INVARIANTS: &[Invariant] = &[
+    // A cartridge is listed exactly once under its owner
+    Invariant {
+        name: "cartridge_listed",
+        holds: cartridge_listed,
+        repair: Some(relist_cartridge),
+    },
+    // A creature is listed exactly once under its owner
+    Invariant {
+        name: "creature_listed",
+        holds: creature_listed,
+        repair: Some(relist_creature),
+    },
+    // The bitmap bit is set exactly when the cartridge is finalized
+    Invariant {
+        name: "finalized_bit",
+        holds: finalized_bit,
+        repair: Some(rewrite_finalized_bit),
+    },
+    // The pending index holds exactly the unfinalized cartridges
+    Invariant {
+        name: "pending_index",
+        holds: pending_index,
+        repair: Some(rewrite_pending),
+    },
+    // A creature appears in the index of each of its genes
+    Invariant {
+        name: "gene_indexed",
+        holds: gene_indexed,
+        repair: Some(reindex),
+    },
+    // A minted creature has a finalized cartridge, and a finalized cartridge has its creature
+    Invariant {
+        name: "creature_matches_cartridge",
+        holds: creature_matches_cartridge,
+        repair: None,
+    },
+    // A queued cartridge's recorded position holds it
+    Invariant {
+        name: "queue_entry",
+        holds: finalize_queue::entry_consistent,
+        repair: None,
+    },
+    // Companion links point back at each other
+    Invariant {
+        name: "companion_link",
+        holds: companion_link,
+        repair: None,
+    },
+];
+
+/// Event emitted when repair fixes an asset's indexes
+#[contractevent]
+pub struct IntegrityRepaired {
+    pub asset_id: u32,
+    pub repaired: Vec<Symbol>, // Invariants that failed and were repaired
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Check a cartridge or creature ID against every index invariant (permissionless)
+    /// Returns the names of the violated invariants; empty means healthy
+    pub fn check_integrity(env: Env, creature_or_cartridge_id: u32) -> Vec<Symbol> {
+        let mut violated = Vec::new(&env);
+        for invariant in INVARIANTS {
+            if !(invariant.holds)(&env, creature_or_cartridge_id) {
+                violated.push_back(Symbol::new(&env, invariant.name));
+            }
+        }
+        violated
+    }
+
+    /// Repair the violated invariants of an asset that can be rebuilt from its records (Operator only)
+    /// Returns the names of the invariants repaired; check_integrity reports any left
+    pub fn repair(env: Env, id: u32) -> Vec<Symbol> {
+        require_role(&env, Role::Operator);
+        let mut repaired = Vec::new(&env);
+        for invariant in INVARIANTS {
+            let Some(repair) = invariant.repair else {
+                continue;
+            };
+            if !(invariant.holds)(&env, id) {
+                repair(&env, id);
+                repaired.push_back(Symbol::new(&env, invariant.name));
+            }
+        }
+        if !repaired.is_empty() {
+            events::publish(&env, |nonce| IntegrityRepaired {
+                asset_id: id,
+                repaired: repaired.clone(),
+                nonce,
+            });
+        }
+        repaired
+    }
+}
+
+fn cartridge(env: &Env, id: u32) -> Option<GenomeCartridge> {
+    env.storage().persistent().get(&DataKey::Cartridge(id))
+}
+
+fn creature(env: &Env, id: u32) -> Option<Creature> {
+    env.storage().persistent().get(&DataKey::Creature(id))
+}
+
+fn listed_once(env: &Env, key: &DataKey, id: u32) -> bool {
+    let ids: Vec<u32> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    ids.iter().filter(|listed| *listed == id).count() == 1
+}
+
+/// Rewrite the list under `key` with `id` exactly once, at the end
+fn relist(env: &Env, key: DataKey, id: u32) {
+    let ids: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    let mut fixed = Vec::new(env);
+    for listed in ids.iter().filter(|listed| *listed != id) {
+        fixed.push_back(listed);
+    }
+    fixed.push_back(id);
+    env.storage().persistent().set(&key, &fixed);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+fn cartridge_listed(env: &Env, id: u32) -> bool {
+    cartridge(env, id)
+        .is_none_or(|cartridge| listed_once(env, &DataKey::UserCartridges(cartridge.owner), id))
+}
+
+fn relist_cartridge(env: &Env, id: u32) {
+    if let Some(cartridge) = cartridge(env, id) {
+        relist(env, DataKey::UserCartridges(cartridge.owner), id);
+    }
+}
+
+fn creature_listed(env: &Env, id: u32) -> bool {
+    creature(env, id)
+        .is_none_or(|creature| listed_once(env, &DataKey::UserCreatures(creature.owner), id))
+}
+
+fn relist_creature(env: &Env, id: u32) {
+    if let Some(creature) = creature(env, id) {
+        relist(env, DataKey::UserCreatures(creature.owner), id);
+    }
+}
+
+fn finalized_bit(env: &Env, id: u32) -> bool {
+    let finalized = cartridge(env, id).is_some_and(|cartridge| cartridge.finalized);
+    GeneSplicer::is_finalized_fast(env.clone(), id) == finalized
+}
+
+fn rewrite_finalized_bit(env: &Env, id: u32) {
+    GeneSplicer::repair_finalized_bitmap(env.clone(), id, 1);
+}
+
+fn pending_index(env: &Env, id: u32) -> bool {
+    let unfinalized = cartridge(env, id).is_some_and(|cartridge| !cartridge.finalized);
+    pending::is_pending(env, id) == unfinalized
+}
+
+fn rewrite_pending(env: &Env, id: u32) {
+    let unfinalized = cartridge(env, id).is_some_and(|cartridge| !cartridge.finalized);
+    pending::set_pending(env, id, unfinalized);
+}
+
+fn gene_indexed(env: &Env, id: u32) -> bool {
+    creature(env, id).is_none_or(|creature| gene_index::is_indexed(env, &creature))
+}
+
+fn reindex(env: &Env, id: u32) {
+    if let Some(creature) = creature(env, id) {
+        gene_index::reindex_creature(env, &creature);
+    }
+}
+
+fn creature_matches_cartridge(env: &Env, id: u32) -> bool {
+    let finalized = cartridge(env, id).is_some_and(|cartridge| cartridge.finalized);
+    let has_creature = env.storage().persistent().has(&DataKey::Creature(id));
+    // Imported creatures have no cartridge here
+    finalized == has_creature || (has_creature && id >= IMPORTED_ID_BASE)
+}
+
+fn companion_link(env: &Env, id: u32) -> bool {
+    let companion = GeneSplicer::get_companion(env.clone(), id);
+    let parent = GeneSplicer::get_companion_parent(env.clone(), id);
+    companion.is_none_or(|companion| {
+        GeneSplicer::get_companion_parent(env.clone(), companion) == Some(id)
+    }) && parent.is_none_or(|parent| GeneSplicer::get_companion(env.clone(), parent) == Some(id))
+}
@@ -0,0 +1,27 @@
+//! Interface fingerprint for client bindings
+//!
+//! Bindings generated from an older build fail in confusing ways after an
+//! upgrade changes a signature or a type. `build.rs` hashes the entrypoint
+//! signatures and contract type definitions into `INTERFACE_HASH`; frontends
+//! compare it against the hash recorded with their bindings at startup, and
+//! integrating contracts call `assert_interface` before relying on us.
+
+use crate::{Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient};
+use soroban_sdk::{contractimpl, panic_with_error, BytesN, Env};
+
+include!(concat!(env!("OUT_DIR"), "/interface_hash.rs"));
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get the SHA-256 of this build's entrypoint signatures and contract types
+    pub fn interface_hash(env: Env) -> BytesN<32> {
+        BytesN::from_array(&env, &INTERFACE_HASH)
+    }
+
+    /// Panic with InterfaceMismatch unless the deployed interface hash is `expected`
+    pub fn assert_interface(env: Env, expected: BytesN<32>) {
+        if expected.to_array() != INTERFACE_HASH {
+            panic_with_error!(&env, Error::InterfaceMismatch);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Defensive invariants checked before entropy is consumed
+//!
+//! Finalization dispatches on the derivation version stamped on the cartridge,
+//! so a cartridge whose stamp doesn't match the version live when it was minted
+//! could get a derivation it was never sold under. That happens through a
+//! storage migration bug or a forged write. Every path that turns entropy into
+//! genes first calls `assert_derivation_consistency`, or, in the queue crank,
+//! `check_derivation_consistency` so a bad item is skipped instead of aborting
+//! the batch.
+//!
+//! The gene table epoch stamp picks the odds table the same way, so it is
+//! checked alongside the version.
+//!
+//! The config log only keeps hashes of the last `MAX_CONFIG_LOG` changes, so it
+//! can't say which version was live at an arbitrary past time. The check reads
+//! the histories `derivation` and `gene_table` keep instead: one (since, value)
+//! entry per change.
+
+use crate::{derivation, gene_table, Error, GenomeCartridge};
+use soroban_sdk::{panic_with_error, Env, Vec};
+
+/// Fail with InconsistentDerivation unless the cartridge's derivation version and
+/// gene table epoch were the ones stamped on new cartridges at its `created_at`
+/// A change in the same ledger as the mint makes both the old and new value valid.
+/// Cartridges minted before a history was first recorded can't be checked against it
+pub(crate) fn check_derivation_consistency(
+    env: &Env,
+    cartridge: &GenomeCartridge,
+) -> Result<(), Error> {
+    let created_at = cartridge.created_at;
+    if was_live(
+        &derivation::history(env),
+        created_at,
+        cartridge.derivation_version,
+    ) && was_live(
+        &gene_table::history(env),
+        created_at,
+        cartridge.gene_table_epoch,
+    ) {
+        Ok(())
+    } else {
+        Err(Error::InconsistentDerivation)
+    }
+}
+
+/// Like `check_derivation_consistency`, but panics with the error
+pub(crate) fn assert_derivation_consistency(env: &Env, cartridge: &GenomeCartridge) {
+    if let Err(error) = check_derivation_consistency(env, cartridge) {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Whether `stamped` was the live value of a (since, value) history at `created_at`
+fn was_live(history: &Vec<(u64, u32)>, created_at: u64, stamped: u32) -> bool {
+    let Some((first_since, first_value)) = history.first() else {
+        return true;
+    };
+    if created_at < first_since {
+        return true;
+    }
+
+    let mut live = first_value;
+    let mut changed_at_mint = false;
+    for (since, value) in history.iter() {
+        if since < created_at {
+            live = value;
+        } else if since == created_at {
+            changed_at_mint |= value == stamped;
+        } else {
+            break;
+        }
+    }
+    live == stamped || changed_at_mint
+}
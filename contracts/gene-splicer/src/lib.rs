@@ -9,13 +9,149 @@
 //! - BLS12-381 pairing verification is performed on-chain (cannot be forged)
 //! - Entropy round is assigned at mint time (prevents frontrunning)
 //! - All persistent storage entries have TTL management (prevents expiration)
+//!
+//! Events:
+//! - Single mints/finalizations emit one `CartridgeMinted` / `CreatureFinalized` each
+//! - Batch operations with more than `BATCH_EVENT_THRESHOLD` items emit a single
+//!   aggregated `BatchCartridgeMinted` / `BatchCreatureFinalized` instead; smaller
+//!   batches keep the per-item events. Indexers must handle both shapes.
 
 #![no_std]
 
+mod account_auth;
+mod accounting;
+mod bootstrap;
+mod bulk_pricing;
+mod burn_rewards;
+mod capabilities;
+mod cartridge_status;
+mod client_refs;
+mod collections;
+mod companions;
+mod config_bootstrap;
+mod config_freeze;
+mod config_log;
+mod consumers;
+mod cost_sharing;
+mod derivation;
+mod diagnosis;
+mod drawings;
+mod entropy;
+mod entropy_age;
+mod enumeration;
+mod events;
+mod experience;
+mod features;
+mod fee_rounding;
+mod finalization_right;
+mod finalize_queue;
+mod finalize_report;
+mod finalized_bitmap;
+mod free_mint;
+mod gc;
+mod gene_index;
+mod gene_table;
+mod governance;
+mod inbox;
+mod integrity;
+mod interface;
+mod invariants;
+mod locks;
+mod maintenance;
+mod metadata;
+mod migration;
+mod mint_fee;
+mod params;
+mod points;
+mod pending;
+mod preflight;
+mod privacy;
+mod provenance;
+mod receipts;
+mod reentrancy;
+mod render;
+mod retirement;
+mod reveal;
+mod roles;
+mod round_assignment;
+mod round_substitution;
+mod storefront;
+mod token_metadata;
+mod transfers;
+mod verification;
+
+pub use accounting::{AccountingError, Pool, PoolLiability, SolvencyReport};
+pub use bootstrap::{Bootstrap, PendingAction};
+pub use bulk_pricing::{BatchQuote, BulkPricingError, MAX_BULK_TIERS};
+pub use burn_rewards::{
+    BurnRewardSchedule, BURN_REWARD_EPOCH, DEFAULT_BURN_REWARD_EPOCH_BUDGET,
+    DEFAULT_BURN_REWARD_LEGENDARY, DEFAULT_BURN_REWARD_NORMAL, DEFAULT_BURN_REWARD_RARE,
+};
+pub use capabilities::FunctionCapability;
+pub use cartridge_status::{CartridgeState, CartridgeStatus, DEFAULT_MAX_FINALIZATION_LAG};
+pub use client_refs::CLIENT_REF_TTL;
+pub use collections::{
+    Collection, CollectionError, CollectionParams, COLLECTION_INDEX_CHUNK, ORIGINAL_COLLECTION,
+};
+pub use companions::{CompanionError, CompanionMinted, DEFAULT_COMPANION_FEE};
+pub use config_bootstrap::FullBootstrap;
+pub use config_log::{ConfigLogEntry, MAX_CONFIG_LOG};
+pub use consumers::{ConsumerError, RandomnessConsumed};
+pub use cost_sharing::RoundVerifier;
+pub use derivation::{
+    derive_from_round, derive_v1, derive_v2, derive_v3, derive_v4, GENE_CONTEXT,
+    LATEST_DERIVATION_VERSION,
+};
+pub use diagnosis::{VerificationDiagnosis, VerificationStage, DRAND_DST};
+pub use drawings::{
+    rarity_score, Drawing, DrawingError, DRAWING_DOMAIN, MAX_ATTEMPTS_PER_CALL,
+    MAX_DRAWING_ATTEMPTS, MAX_DRAWING_WINNERS, MAX_RARITY_SCORE,
+};
+pub use entropy::{DrandEntropy, EntropyError};
+pub use entropy_age::{DEFAULT_MAX_RANDOMNESS_AGE, MAX_RANDOMNESS_AGE, MIN_RANDOMNESS_AGE};
+pub use experience::{ExperienceError, LEVEL_XP, MAX_LEVEL, MAX_XP_PER_GRANT};
+pub use features::{FeatureError, FEATURES};
+pub use fee_rounding::{round_fee, FeeRounding};
+pub use finalization_right::FinalizationRightError;
+pub use finalize_queue::FINALIZE_QUEUE_CHUNK;
+pub use finalized_bitmap::FINALIZED_BITMAP_CHUNK;
+pub use free_mint::FreeMintError;
+pub use gc::{GC_KINDS, GC_RING_CAPACITY};
+pub use gene_index::GENE_INDEX_CHUNK;
+pub use gene_table::{
+    GeneTable, GeneTableCommitment, GeneTableError, GENE_TABLE_DELAY, GENE_TABLE_SCALE,
+};
+pub use governance::GovernanceError;
+pub use inbox::{InboxEntry, MAX_INBOX};
+pub use interface::INTERFACE_HASH;
+pub use locks::{Lock, CONFLICTS, LOCK_KINDS};
+pub use maintenance::MaintenanceWindow;
+pub use metadata::{MetadataError, MetadataRevision};
+pub use migration::{ExportVoucher, ImportOrigin, MigrationError, IMPORTED_ID_BASE};
+pub use mint_fee::MintFeeUpdated;
+pub use pending::{PendingCartridge, PENDING_INDEX_CHUNK};
+pub use points::{PointsCheckout, PointsError};
+pub use receipts::{FinalizationReceipt, RECEIPT_DOMAIN};
+pub use render::{LayerRef, LayerRule, LayerSource, MAX_LAYER_RULES};
+pub use retirement::{
+    RETIREMENT_POINTS_LEGENDARY, RETIREMENT_POINTS_NORMAL, RETIREMENT_POINTS_RARE,
+};
+pub use reveal::{RevealError, HIDDEN_GENE_ID};
+pub use roles::Role;
+pub use round_assignment::MAX_ROUND_SEARCH;
+pub use round_substitution::{SubstitutionError, DEFAULT_SUBSTITUTION_DELAY};
+pub use storefront::{Storefront, StorefrontError};
+pub use token_metadata::MAX_METADATA_LEN;
+pub use transfers::{TokenApproval, TransferError};
+
+use roles::{require_role, OwnerChanged};
+
 use soroban_sdk::{
-    contract, contractevent, contractimpl, contracttype,
+    contract, contracterror, contractevent, contractimpl, contracttype,
     crypto::bls12_381::{G1Affine, G2Affine},
-    token, Address, Bytes, BytesN, Env, Vec,
+    panic_with_error, token,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Map, Symbol, TryFromVal, Val, Vec,
 };
 
 // TTL constants (in ledger sequences, ~6 seconds each)
@@ -24,9 +160,85 @@ const TTL_THRESHOLD: u32 = 100_800;
 // Extend to: set TTL to this value when extending (~30 days)
 const TTL_EXTEND_TO: u32 = 432_000;
 
+// Drand quicknet emitted round 1 at this Unix timestamp (Aug 23, 2023), then one round every DRAND_PERIOD seconds
+const DRAND_GENESIS: u64 = 1692803367;
+const DRAND_PERIOD: u64 = 3;
+
+/// Unix timestamp at which drand quicknet emits `round`
+pub(crate) fn drand_round_time(round: u64) -> u64 {
+    DRAND_GENESIS + round.saturating_sub(1) * DRAND_PERIOD
+}
+
+/// Read a value the constructor stores, failing with NotInitialized if it's absent
+/// Getters for such values return this Result; values with a default or an unset
+/// state use `unwrap_or` or return an Option instead
+pub(crate) fn configured<T: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Result<T, Error> {
+    env.storage().instance().get(key).ok_or(Error::NotInitialized)
+}
+
+/// `configured`, failing the invocation with NotInitialized rather than returning it
+pub(crate) fn require_configured<T: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> T {
+    configured(env, key).unwrap_or_else(|error| panic_with_error!(env, error))
+}
+
+// Mint fee of deployments that predate set_mint_fee, in stroops (1 XLM = 10^7 stroops)
+pub const MINT_FEE: i128 = 10_000_000;
+
+// Batches larger than this emit one aggregated event instead of one event per item
+pub const BATCH_EVENT_THRESHOLD: u32 = 3;
+
+// Chain hash of drand quicknet, the only network whose beacons this contract accepts by default
+const QUICKNET_CHAIN_HASH: [u8; 32] = [
+    0x52, 0xdb, 0x9b, 0xa7, 0x0e, 0x0c, 0xc0, 0xf6, 0xea, 0xf7, 0x80, 0x3d, 0xd0, 0x74, 0x47, 0xa1,
+    0xf5, 0x47, 0x77, 0x35, 0xfd, 0x3f, 0x66, 0x17, 0x92, 0xba, 0x94, 0x60, 0x0c, 0x84, 0xe9, 0x71,
+];
+
+// Maximum IDs accepted by get_cartridges_batch / get_creatures_batch (see `Limits`)
+// Also the most IDs a get_cartridges_range call covers
+pub const MAX_BATCH_READ: u32 = 50;
+
+// Maximum IDs accepted by get_creature_thumbs (see `Limits`)
+// Thumbnails are a fraction of a full Creature, so more fit in the same return-size budget;
+// the cap stays below the 100-entry per-transaction read footprint (instance and code count too)
+pub const MAX_THUMB_READ: u32 = 90;
+
+// Maximum cartridges returned by a single get_pending_details page (see `Limits`)
+// Each entry reads its cartridge plus, once per distinct owner, the owner's privacy flag
+pub const MAX_PENDING_PAGE: u32 = 40;
+
+// Maximum voters accepted by a single snapshot_weights call (see `Limits`)
+// Each voter costs several ledger reads plus one write, so this stays well under per-tx footprint limits
+pub const MAX_SNAPSHOT_BATCH: u32 = 20;
+
+// Maximum creature IDs returned by a single get_creatures_by_gene page (see `Limits`)
+// A page spans at most two index chunks, so the read footprint stays small
+pub const MAX_GENE_INDEX_PAGE: u32 = 100;
+
+// Maximum cartridge IDs returned by a single get_collection_cartridges page (see `Limits`)
+pub const MAX_COLLECTION_PAGE: u32 = 100;
+
+// Maximum IDs returned by a get_user_cartridges_page / get_user_creatures_page call (see `Limits`)
+pub const MAX_OWNER_PAGE: u32 = 100;
+
+// Maximum cartridge and creature records a single bootstrap call loads (see `Limits`)
+// Pending cartridges may use up to half; owned creatures get whatever is left
+pub const MAX_BOOTSTRAP_ENTRIES: u32 = 60;
+
+// Maximum queued cartridges a single process_queue call finalizes (see `Limits`)
+// Each finalization writes the creature plus its owner, bitmap and gene index entries
+pub const MAX_QUEUE_BATCH: u32 = 10;
+
+// Maximum cartridges a single splice_genome_batch call mints (see `Limits`)
+// Each cartridge writes its own entry plus the pending index; the owner list is written once
+pub const MAX_SPLICE_BATCH: u32 = 10;
+
+// Domain separation tag prefixed to every finalization permit message
+const PERMIT_DOMAIN: &[u8] = b"SPLICERS_PERMIT";
+
 /// Gene rarity levels (affects visual appearance and value)
+/// Variants are declared in ascending rarity, so `Ord` ranks them
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub enum GeneRarity {
     Normal,    // 60% chance - Necromancer, Skeleton Crusader, Skeleton Warrior (IDs 6-14)
     Rare,      // 30% chance - Dark Oracle (IDs 0-2)
@@ -51,6 +263,33 @@ pub struct GenomeCartridge {
     pub splice_round: u64, // Drand round for later entropy use
     pub created_at: u64,   // Ledger timestamp
     pub finalized: bool,   // Whether cartridge has been transformed into a Creature
+    pub finalize_beneficiary: Option<Address>, // Buyer of the finalization right; receives the Creature
+    pub derivation_version: u32, // Gene derivation rules fixed at mint time
+    pub dev_entropy: bool, // Minted in dev_mode with the ledger clock before drand genesis; its round was already public
+    pub substituted_from: Option<u64>, // Originally assigned round, if substitute_round rebound it
+    pub collection_id: u32, // Collection minted in (0 = the original collection)
+    pub gene_table_epoch: u32, // Gene table odds fixed at mint time (0 = the original table)
+}
+
+impl GenomeCartridge {
+    /// Address entitled to finalize this cartridge and own the resulting Creature
+    pub fn finalizer(&self) -> Address {
+        self.finalize_beneficiary
+            .clone()
+            .unwrap_or_else(|| self.owner.clone())
+    }
+}
+
+/// Trimmed creature view for gallery grids
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatureThumb {
+    pub id: u32,
+    pub skin_id: u32,
+    pub head_gene_id: u32,
+    pub body_gene_id: u32,
+    pub legs_gene_id: u32,
+    pub top_rarity: GeneRarity, // Highest rarity among the three genes
 }
 
 /// Creature NFT - final form after finalization with entropy
@@ -65,10 +304,13 @@ pub struct Creature {
     pub legs_gene: Gene,    // Legs gene (1 of 10)
     pub finalized_at: u64,  // Ledger timestamp of finalization
     pub entropy_round: u64, // Drand round used for gene selection
+    pub dev_minted: bool,   // Genes came from dev_finalize's seed or a dev_entropy cartridge, not a trustworthy beacon
+    pub collection_id: u32, // Inherited from cartridge
 }
 
 /// Storage keys for the contract
-#[contracttype]
+/// Kept out of the contract spec: clients never build keys, and the spec caps a union at 50 cases
+#[contracttype(export = false)]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
@@ -81,6 +323,215 @@ pub enum DataKey {
     UserCreatures(Address),  // User -> Vec<u32> of creature IDs
     DevMode,                 // Boolean flag to bypass entropy verification in development
     DrandPublicKey,          // BLS12-381 G2 public key from drand quicknet (96 bytes compressed)
+    SessionKey(Address),     // Owner -> ed25519 public key used to sign finalization permits
+    PermitConsumed(BytesN<32>), // SHA256(permit message) -> true once a permit has been used
+    DrandChainHash,          // Expected drand chain hash (32 bytes), defaults to quicknet
+    MintPaused,              // Boolean flag blocking splice_genome
+    FinalizePaused,          // Boolean flag blocking finalization of existing cartridges
+    SkinMinted(u32),         // Skin ID -> number of cartridges minted with that skin
+    SkinCaps,                // Map<u32, u32> of skin ID -> max supply (absent = uncapped)
+    NextProposalId,          // Counter for governance proposals
+    Proposal(u32),           // Proposal ID -> Proposal
+    SnapshotWeight(u32, Address), // (Proposal ID, voter) -> frozen voting weight
+    Voted(u32, Address),     // (Proposal ID, voter) -> true once the voter has voted
+    FinalizationRightPrice(u32), // Cartridge ID -> asking price (stroops) for its finalization right
+    DerivationVersion,       // Gene derivation version stamped on new cartridges
+    Operator,                // Optional Address holding the Operator role (Admin is the Owner)
+    PendingVerification(u64, BytesN<32>), // (Round, signature SHA-256) -> held signature (temp)
+    VerifiedRound(u64),      // Round -> SHA256(signature || drand key) that passed verification (temporary)
+    FirstSeen(Address),      // User -> ledger timestamp of their first mint
+    FreeMintEnabled,         // Boolean flag for the free first mint program
+    FreeMintAttestor,        // ed25519 key whose account-age attestation free mints require (absent = none)
+    FreeMintMinAccountAge,   // Minimum account age (seconds) the attestation vouches for
+    FreeMintCount,           // Number of free mints claimed
+    ConfigLog,               // Vec<ConfigLogEntry> of the last MAX_CONFIG_LOG admin changes
+    VerificationShare,       // i128 stroops each cached finalization pays the round's verifier
+    VerificationCostCap,     // i128 stroops a verifier can be reimbursed per round
+    RoundVerifier(u64),      // RoundVerifier for the round, in temporary storage
+    CreaturesByGeneCount(u32, u32),      // (slot, gene_id) -> u32 creatures indexed
+    CreaturesByGeneChunk(u32, u32, u32), // (slot, gene_id, chunk) -> Vec<u32> of up to GENE_INDEX_CHUNK IDs
+    MetadataOperator,        // Address allowed to anchor creature metadata hashes
+    MetadataHash(u32),       // Creature ID -> anchored SHA-256 of its metadata JSON
+    MetadataCorrection(u32), // Creature ID -> replacement hash awaiting Owner approval
+    MetadataProvenance(u32), // Creature ID -> Vec<MetadataRevision> of replaced hashes
+    FinalizedBitmapChunk(u32), // Chunk -> BytesN<32> finalized bits for 256 cartridge IDs
+    MaxAssignmentsPerRound,  // u32 cap on cartridges assigned to one round (0 = uncapped)
+    RoundAssignments(u64),   // Round -> u32 cartridges assigned, in temporary storage
+    LayerTable,              // Vec<LayerRule> mapping creature fields to art layers
+    RevealTime,              // u64 timestamp before which creature genes are hidden
+    RevealTriggered,         // bool, set once the Owner announces the reveal
+    PendingChunk(u32),       // Chunk -> ascending Vec<u32> of unfinalized IDs in its ID range
+    PendingBounds,           // (head, tail) chunk indices of the pending index
+    Liability(Pool, Address), // (Pool, token) -> amount the pool owes users in that token
+    Inbox(Address),          // User -> Vec<InboxEntry> of their last MAX_INBOX notifications
+    Points(Address),         // User -> u64 points balance spendable on the mint fee
+    PointsPerStroop,         // u64 points that buy one stroop of fee discount (0 = points not accepted)
+    RoundSubstitutionDelay,  // u64 seconds a round must be overdue before substitute_round
+    MaxRoundSubstitution,    // u64 furthest round past the original a cartridge may move to (0 = disabled)
+    ConfigurationFinalizedAt, // u64 timestamp of finalize_configuration; present = configuration frozen
+    OperationsLocked,        // bool, whether the freeze also covers pausing and the free mint switch
+    LastFinalizeError(u32),  // Cartridge ID -> (error code, timestamp) of its last reported finalize failure (temporary)
+    BulkDiscounts,           // Vec<(min_count, discount_bps)> batch mint discount tiers
+    DerivationHistory,       // Vec<(since, version)> of derivation versions stamped on new cartridges
+    NextCollectionId,        // Counter for created collections (collection 0 is the original)
+    Collection(u32),         // Collection ID -> Collection
+    CollectionMinted(u32),   // Collection ID -> u32 cartridges minted in it
+    CollectionCartridgeChunk(u32, u32), // (collection, chunk) -> Vec<u32> of up to COLLECTION_INDEX_CHUNK IDs
+    CreatedCollectionsMinted, // u32 cartridges minted across all created collections
+    VerifiedRandomness(u64), // Round -> BytesN<32> randomness of its verified signature (temporary)
+    Consumer(Address),       // Contract -> true while registered to consume verified randomness
+    ConsumerReadFee,         // i128 stroops charged per consume_randomness call
+    MaxFinalizationLag,      // u64 seconds a cartridge may sit ready before it counts as overdue
+    Storefront(Address),     // Storefront -> Storefront terms (credit limit, suspension)
+    StorefrontDebt(Address), // Storefront -> i128 stroops of mint fees owed
+    Retired(u32),            // Creature ID -> true once retired (irreversible)
+    GeneTableCommitment,     // Pending GeneTableCommitment awaiting apply_gene_table
+    GeneTableEpoch,          // u32 epoch of the gene table stamped on new cartridges
+    GeneTable(u32),          // Epoch -> GeneTable applied at that epoch (epoch 0 is built in)
+    EventNonce,              // u64 nonce of the most recently emitted event
+    GameOperator,            // Address allowed to grant creatures experience
+    CreatureXp(u32),         // Creature ID -> u64 experience
+    XpGranted(u64),          // Match ID -> creature ID it granted experience to (replay guard)
+    Reentrancy,              // Temporary flag set while a guarded entrypoint runs
+    Maintenance,             // Scheduled MaintenanceWindow
+    NextDrawingId,           // Counter for drawing IDs
+    Drawing(u32),            // Drawing ID -> Drawing
+    Features,                // Vec<Symbol> of enabled optional subsystems (absent = all)
+    Private(Address),        // Present when the user opted out of aggregate views
+    OwnerAuthorized,         // Set only while bootstrap_config runs its setters
+    FinalizeQueue(u64, u32), // (round, chunk index) -> Vec<u32> of queued cartridge IDs
+    FinalizeQueueBounds(u64), // Round -> (head, tail) positions of its finalization queue
+    QueuedCartridge(u32),    // Cartridge ID -> (round, position) while in a finalization queue
+    MaxRandomnessAge,        // u64 seconds a round may predate the cartridge it finalizes
+    ProvenanceHash,          // BytesN<32> Merkle root over every art asset, set once by the constructor
+    Locks(u32),              // Creature ID -> Vec<Lock> held by listing, staking, swap, export, delegation
+    Exported(u32),           // Creature ID -> commitment of the voucher it was exported with
+    TrustedSource(Address),  // Contract -> true while its export vouchers are accepted
+    ImportedAsset(Address, u32), // (Source contract, source creature ID) -> imported creature ID
+    ImportOrigin(u32),       // Imported creature ID -> ImportOrigin
+    NextImportedId,          // Counter for imported creatures, from IMPORTED_ID_BASE
+    BurnRewardSchedule,      // BurnRewardSchedule of per-gene burn rewards and the epoch budget
+    BurnRewardSpent(u64),    // Epoch -> i128 stroops of burn rewards paid in it
+    ClientRef(BytesN<32>),   // Client mint reference -> cartridge ID minted with it (temporary)
+    GcRing(Symbol),          // gc kind -> Vec<(DataKey, expiry)> of its most recently tracked keys
+    Companion(u32),          // Parent creature ID -> its companion cartridge ID
+    CompanionParent(u32),    // Companion cartridge ID -> parent creature ID
+    CompanionFee,            // i128 stroops charged by mint_companion
+    FeeRounding,             // FeeRounding applied to every computed fee
+    Approval(u32),           // Token ID -> TokenApproval of the spender its owner approved
+    Entropy(u64),            // Drand round -> DrandEntropy submitted for it
+    MintFee,                 // i128 stroops charged by splice_genome (see `mint_fee`)
+    GeneTableHistory,        // Vec<(since, epoch)> of gene table epochs stamped on new cartridges
+    Treasury,                // Address receiving mint fees and other proceeds
+}
+
+/// Structured contract errors, surfaced to clients as distinguishable error codes
+///
+/// The contract spec caps an error enum at 50 cases, so this enum holds the codes shared
+/// across modules or returned by internal checks, and a subsystem with codes of its own
+/// declares them in its module (`GovernanceError`, `EntropyError`, ...). Every enum draws
+/// from this one numbering, so a code still names a single error; gaps here are codes
+/// owned by a subsystem enum. New codes continue the sequence after the highest in use.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    WrongDrandNetwork = 1, // Submitted beacon's chain hash doesn't match the configured network
+    InvalidDrandPublicKey = 2, // Key is the zero mock, flagged, or not in G2 while dev_mode is off
+    MintPaused = 3,            // New mints are paused by the admin
+    FinalizePaused = 4,        // Finalization is paused by the admin
+    AllSkinsExhausted = 5,     // Every skin has reached its supply cap
+    CartridgeNotFound = 11,    // No cartridge with this ID
+    NotCartridgeOwner = 12,    // Caller doesn't own the cartridge
+    AlreadyFinalized = 13,     // Cartridge was already transformed into a Creature
+    InvalidAmount = 17,        // Amount must be positive
+    UnsupportedDerivationVersion = 19, // No gene derivation with this version number
+    DevModeDisabled = 20,      // Dev-only entrypoint called while dev_mode is off
+    NoPendingVerification = 21, // complete_verification without a live begin_verification entry
+    InvalidRandomnessLength = 25, // Randomness is not 32 bytes
+    InvalidSignatureLength = 26,  // Signature is not 48 bytes compressed / 96 bytes uncompressed
+    InvalidPublicKeyLength = 27,  // Drand public key is not 192 bytes (uncompressed G2)
+    InvalidGeneSlot = 28,         // Gene slot is not 0 (head), 1 (body) or 2 (legs)
+    CreatureNotFound = 29,        // No creature with this ID
+    RoundCapacityExhausted = 34,  // Every round within MAX_ROUND_SEARCH of the next is at its assignment cap
+    InvalidLayerTable = 35,       // Layer table too long or not in strictly ascending z_index order
+    ClockBeforeDrandGenesis = 38, // Mint with the ledger clock before drand genesis while dev_mode is off
+    InterfaceMismatch = 39,       // assert_interface called with a hash other than this build's
+    ConfigurationFinalized = 44,  // Configuration change after finalize_configuration froze it
+    RoundMismatch = 45,           // Submitted round isn't the cartridge's splice_round
+    SignatureCompressionMismatch = 46, // Compressed signature isn't the uncompressed point's encoding
+    RandomnessMismatch = 47,      // Randomness isn't SHA256 of the compressed signature
+    InconsistentDerivation = 49,  // Cartridge's derivation version or gene table epoch wasn't live
+    SignatureCoordinateOutOfRange = 55, // Beacon signature coordinate is not below the field modulus
+    PubkeyCoordinateOutOfRange = 56,    // Drand public key coordinate is not below the field modulus
+    RoundNotReady = 57,           // Finalization before the cartridge's drand round is emitted
+    NotCreatureOwner = 61,        // Caller doesn't own the creature
+    CreatureRetired = 62,         // Creature was retired and is out of active use
+    ReentrancyDetected = 69,      // Guarded entrypoint called while another one is running
+    MaintenanceInProgress = 70,   // Mint or finalize during a maintenance window (see get_maintenance)
+    InvalidMaintenanceWindow = 71, // Maintenance window is empty or already over
+    NoMaintenanceScheduled = 72,  // cancel_maintenance with no upcoming or running window
+    BootstrapClosed = 78,         // bootstrap_config after the first cartridge was minted
+    AlreadyQueued = 79,           // Cartridge is already waiting in a finalization queue
+    EntropyTooOldForCartridge = 80, // Round was emitted longer than the max randomness age before the mint
+    InvalidRandomnessAge = 81,    // set_max_randomness_age outside [MIN_RANDOMNESS_AGE, MAX_RANDOMNESS_AGE]
+    LockConflict = 82,            // Creature already holds a lock of the same or a conflicting kind
+    CreatureLocked = 83,          // Creature holds a live lock, so it can't be burned or transferred
+    NotInitialized = 87,          // A value the constructor stores is missing (partial bootstrap or wrong contract)
+    ClientRefInUse = 88,          // splice_genome_ref with a client reference that is still recorded
+    UnknownGcKind = 89,           // gc with a kind not in GC_KINDS
+    InsufficientBalance = 94,     // User's XLM balance doesn't cover the mint fee
+    RoundNotInFuture = 95,        // Assigned drand round would already be emitted at mint time
+    SignatureNotInSubgroup = 96,  // Beacon signature is not in the G1 subgroup
+    HashToCurveFailed = 97,       // Round message hashed to a point outside the G1 subgroup
+    PairingCheckFailed = 98,      // BLS12-381 pairing rejects the signature for the round and key
+}
+
+/// Relayer permit failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PermitError {
+    PermitCartridgeMismatch = 99, // Permit was signed for a different cartridge
+    PermitExpired = 100,          // Permit's expires_at is in the past
+    NoSessionKey = 101,           // Finalizer has no session key to check a permit against
+    PermitAlreadyUsed = 102,      // Permit was already consumed
+}
+
+/// Batch read failures, one code per `Limits` cap
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ReadLimitError {
+    MaxBatchReadExceeded = 6,   // More IDs than Limits.max_batch_read in a batch getter or repair
+    MaxThumbReadExceeded = 108, // More IDs than Limits.max_thumb_read in get_creature_thumbs
+}
+
+/// Per-call limits enforced by batched and paged entrypoints
+/// Each limit is a compile-time constant; changing one is a deliberate code change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Limits {
+    pub max_batch_read: u32,     // IDs per get_*_batch call and per get_cartridges_range call
+    pub max_thumb_read: u32,     // IDs per get_creature_thumbs call
+    pub max_pending_page: u32,   // Cartridges per get_pending_details page
+    pub max_snapshot_batch: u32, // Voters per snapshot_weights call
+    pub max_gene_index_page: u32, // Creature IDs per get_creatures_by_gene page
+    pub max_collection_page: u32, // Cartridge IDs per get_collection_cartridges page
+    pub max_bootstrap_entries: u32, // Cartridge + creature records per bootstrap call
+    pub max_queue_batch: u32,    // Queued cartridges finalized per process_queue call
+    pub max_splice_batch: u32,   // Cartridges minted per splice_genome_batch call
+    pub max_owner_page: u32,     // IDs per get_user_cartridges_page / get_user_creatures_page call
+}
+
+/// Off-chain signed authorization for a relayer to finalize a specific cartridge
+/// `signature` is an ed25519 signature by the owner's session key over `permit_message`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Permit {
+    pub cartridge_id: u32,
+    pub expires_at: u64, // Ledger timestamp after which the permit is rejected
+    pub signature: BytesN<64>,
 }
 
 /// Event emitted when a cartridge is minted
@@ -89,6 +540,7 @@ pub struct CartridgeMinted {
     pub cartridge_id: u32,
     pub owner: Address,
     pub skin_id: u32,
+    pub nonce: u64,
 }
 
 /// Event emitted when a creature is finalized
@@ -98,6 +550,75 @@ pub struct CreatureFinalized {
     pub head_gene_id: u32,
     pub body_gene_id: u32,
     pub legs_gene_id: u32,
+    pub nonce: u64,
+}
+
+/// Event emitted at deployment when dev_mode is on, so monitoring catches a
+/// production contract that skips entropy verification
+#[contractevent]
+pub struct DevModeActive {
+    pub admin: Address,
+    pub nonce: u64,
+}
+
+/// Event emitted when the admin pauses or resumes minting
+#[contractevent]
+pub struct MintPauseChanged {
+    pub paused: bool,
+    pub nonce: u64,
+}
+
+/// Event emitted when the admin pauses or resumes finalization
+#[contractevent]
+pub struct FinalizePauseChanged {
+    pub paused: bool,
+    pub nonce: u64,
+}
+
+/// Event emitted when the admin sets or removes a skin's supply cap
+#[contractevent]
+pub struct SkinCapUpdated {
+    pub skin_id: u32,
+    pub cap: Option<u32>,
+    pub nonce: u64,
+}
+
+/// Event emitted when an owner registers or rotates their permit session key
+#[contractevent]
+pub struct SessionKeyRegistered {
+    pub owner: Address,
+    pub public_key: BytesN<32>,
+    pub nonce: u64,
+}
+
+/// Event emitted when a relayer consumes a finalization permit
+#[contractevent]
+pub struct PermitUsed {
+    pub cartridge_id: u32,
+    pub relayer: Address,
+    pub nonce: u64,
+}
+
+/// Aggregated event emitted instead of per-item `CartridgeMinted` events when a
+/// batch mint exceeds `BATCH_EVENT_THRESHOLD` cartridges.
+/// Cartridge IDs are contiguous: first_id..first_id + count, skin_ids in the same order.
+#[contractevent]
+pub struct BatchCartridgeMinted {
+    pub owner: Address,
+    pub first_id: u32,
+    pub count: u32,
+    pub skin_ids: Vec<u32>,
+    pub unit_price: i128, // Effective per-cartridge fee from the batch quote, in stroops
+    pub nonce: u64,
+}
+
+/// Aggregated event emitted instead of per-item `CreatureFinalized` events when a
+/// batch finalization exceeds `BATCH_EVENT_THRESHOLD` creatures sharing one round.
+#[contractevent]
+pub struct BatchCreatureFinalized {
+    pub round: u64,
+    pub creature_ids: Vec<u32>,
+    pub nonce: u64,
 }
 
 #[contract]
@@ -115,13 +636,19 @@ impl GeneSplicer {
         cartridge_skin_count: u64,
         dev_mode: bool,
         drand_public_key: Bytes,
+        provenance_hash: BytesN<32>, // Merkle root over every art asset, see `provenance`
+        mint_fee: i128,              // Stroops per splice_genome, 0 = free
+        treasury: Address,           // Receives mint fees and other proceeds
     ) {
         // No require_auth needed - constructor only runs once at deployment time
 
-        // Validate drand public key is 192 bytes (BLS12-381 G2 point, uncompressed affine coordinates)
+        // BLS12-381 G2 point, uncompressed affine coordinates
         // Format: x_c1 || x_c0 || y_c1 || y_c0 (each component 48 bytes, CAP-0059)
-        if drand_public_key.len() != 192 {
-            panic!("Drand public key must be 192 bytes (uncompressed G2 affine coordinates)");
+        let drand_public_key = params::require(&env, params::parse_pubkey_g2(drand_public_key));
+
+        // Without dev_mode every finalization runs real verification, so the key must be usable
+        if !dev_mode {
+            params::require(&env, Self::check_drand_public_key(&env, &drand_public_key));
         }
 
         // Store configuration
@@ -136,81 +663,173 @@ impl GeneSplicer {
         env.storage().instance().set(&DataKey::DevMode, &dev_mode);
         env.storage()
             .instance()
-            .set(&DataKey::DrandPublicKey, &drand_public_key);
+            .set(&DataKey::DrandPublicKey, &Bytes::from(drand_public_key));
+        env.storage().instance().set(
+            &DataKey::DrandChainHash,
+            &BytesN::from_array(&env, &QUICKNET_CHAIN_HASH),
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::DerivationVersion, &LATEST_DERIVATION_VERSION);
+        derivation::record_history(&env, LATEST_DERIVATION_VERSION);
+        gene_table::record_history(&env, 0);
+        // Written here only: the art commitment must not move once mints can reveal rarity
+        env.storage()
+            .instance()
+            .set(&DataKey::ProvenanceHash, &provenance_hash);
+        mint_fee::init(&env, mint_fee, &treasury);
 
         // Extend instance TTL on deployment
         env.storage()
             .instance()
             .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        if dev_mode {
+            events::publish(&env, |nonce| DevModeActive { admin, nonce });
+        }
     }
 
     /// Mint a new Genome Cartridge NFT
-    /// - Transfers 1 XLM fee from user to admin
+    /// - Transfers the mint fee (get_mint_fee) from user to the treasury
     /// - Uses PRNG to select random cartridge skin
     /// - Mints cartridge NFT with assigned splice_round
     /// Returns the cartridge ID
     pub fn splice_genome(env: Env, user: Address) -> u32 {
+        reentrancy::enter(&env);
         user.require_auth();
+        let cartridge_id = Self::splice_authorized(&env, user, Self::get_mint_fee(env.clone()));
+        reentrancy::exit(&env);
+        cartridge_id
+    }
+
+    /// Helper: the splice_genome flow once `user` has authorized the call
+    /// `fee_amount` is the XLM part of the fee left after any discount
+    fn splice_authorized(env: &Env, user: Address, fee_amount: i128) -> u32 {
+        Self::charge_mint_fee(env, &user, fee_amount);
+        Self::mint_cartridge(env, user, ORIGINAL_COLLECTION)
+    }
+
+    /// Helper: Refuse paused mints and transfer `fee_amount` from `user` to the treasury
+    fn charge_mint_fee(env: &Env, user: &Address, fee_amount: i128) {
+        // Callers usually round already; rounding is idempotent
+        let fee_amount = fee_rounding::apply(env, fee_amount);
+        if env
+            .storage()
+            .instance()
+            .get(&DataKey::MintPaused)
+            .unwrap_or(false)
+        {
+            panic_with_error!(env, Error::MintPaused);
+        }
+        if let Err(error) = maintenance::check(env) {
+            panic_with_error!(env, error);
+        }
 
         // Extend instance TTL on every interaction
         env.storage()
             .instance()
             .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
 
-        // Get contract configuration
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Admin not configured");
-        let xlm_token: Address = env.storage().instance().get(&DataKey::XlmToken).expect("XLM token not configured");
-        let skin_count: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::CartridgeSkinCount)
-            .expect("Skin count not configured");
-
-        // Transfer 1 XLM (10_000_000 stroops) from user to admin
-        let xlm_client = token::Client::new(&env, &xlm_token);
-        let fee_amount: i128 = 10_000_000; // 1 XLM = 10^7 stroops
+        // A fee fully covered by a discount needs no transfer
+        if fee_amount > 0 {
+            // Get contract configuration
+            let treasury = mint_fee::treasury(env);
+            let xlm_token: Address = require_configured(env, &DataKey::XlmToken);
+            // Transfer the fee from user to the treasury
+            let xlm_client = token::Client::new(env, &xlm_token);
+
+            // Verify user has sufficient balance before attempting transfer
+            let user_balance = xlm_client.balance(user);
+            if user_balance < fee_amount {
+                panic_with_error!(env, Error::InsufficientBalance);
+            }
 
-        // Verify user has sufficient balance before attempting transfer
-        let user_balance = xlm_client.balance(&user);
-        if user_balance < fee_amount {
-            panic!("Insufficient XLM balance for minting fee");
+            // Execute transfer — panics on failure (Soroban token contract guarantee)
+            // No before/after balance comparison: the treasury minting for itself is a
+            // self-transfer with no net change, which such a check would reject
+            xlm_client.transfer(user, &treasury, &fee_amount);
         }
+    }
+
+    /// Helper: Mint a cartridge in `collection_id` for `user` once any fee has been settled
+    /// Picks the skin and drand round, stores the cartridge and emits events
+    fn mint_cartridge(env: &Env, user: Address, collection_id: u32) -> u32 {
+        let skin_count = collections::skin_count(env, collection_id);
 
-        // Execute transfer — panics on failure (Soroban token contract guarantee)
-        xlm_client.transfer(&user, &admin, &fee_amount);
+        // Generate random skin ID using PRNG among skins that haven't hit their cap
+        let skin_id = Self::select_skin(env, skin_count);
 
-        // Generate random skin ID using PRNG (u64 for GenRange compatibility)
-        let skin_id: u64 = env.prng().gen_range(0..skin_count);
-        let skin_id = skin_id as u32;
+        let (splice_round, dev_entropy) = Self::assign_splice_round(env, 1);
+        let cartridge =
+            Self::store_cartridge(env, &user, collection_id, skin_id, splice_round, dev_entropy);
+        Self::list_cartridges(env, &user, &Vec::from_array(env, [cartridge.id]));
+
+        // Emit event
+        events::publish(env, |nonce| CartridgeMinted {
+            cartridge_id: cartridge.id,
+            owner: cartridge.owner,
+            skin_id: cartridge.skin_id,
+            nonce,
+        });
+
+        cartridge.id
+    }
 
+    /// Helper: Assign `count` new cartridges a future drand round, returning it and
+    /// whether they use dev entropy (ledger clock before drand genesis, dev_mode only)
+    fn assign_splice_round(env: &Env, count: u32) -> (u64, bool) {
         // Assign a future drand round to prevent frontrunning
-        // Drand quicknet round 1 started at Unix timestamp 1692803367 (Aug 23, 2023)
-        // Quicknet emits a round every 3 seconds
-        // We assign current_round + 2 to ensure the round hasn't happened yet
+        // We assign current_round + 2 (or later, if that round is full) to ensure the round hasn't happened yet
         let ledger_time = env.ledger().timestamp();
-        let drand_genesis = 1692803367u64;
-        let drand_period = 3u64;
-        let current_round = if ledger_time > drand_genesis {
-            ((ledger_time - drand_genesis) / drand_period) + 1
-        } else {
+
+        // A clock before genesis can't tell which rounds are public; round math would
+        // fall back to early rounds whose signatures everyone already knows
+        let dev_entropy = ledger_time < DRAND_GENESIS;
+        if dev_entropy {
+            let dev_mode: bool = env.storage().instance().get(&DataKey::DevMode).unwrap_or(false);
+            if !dev_mode {
+                panic_with_error!(env, Error::ClockBeforeDrandGenesis);
+            }
+        }
+        let current_round = if dev_entropy {
             1
+        } else {
+            ((ledger_time - DRAND_GENESIS) / DRAND_PERIOD) + 1
         };
-        let splice_round = current_round + 2; // Assign future round
+        let splice_round = round_assignment::assign(env, current_round + 2, count);
 
-        // Mint the cartridge
-        let cartridge_id: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::NextCartridgeId)
-            .expect("NextCartridgeId not configured");
+        // The assigned round must be emitted strictly after this ledger
+        if !dev_entropy && drand_round_time(splice_round) <= ledger_time {
+            panic_with_error!(env, Error::RoundNotInFuture);
+        }
+        (splice_round, dev_entropy)
+    }
+
+    /// Helper: Store a new cartridge under the next ID and record it in the pending and
+    /// collection indexes; the caller adds it to the owner's list and emits its event
+    fn store_cartridge(
+        env: &Env,
+        user: &Address,
+        collection_id: u32,
+        skin_id: u32,
+        splice_round: u64,
+        dev_entropy: bool,
+    ) -> GenomeCartridge {
+        let cartridge_id: u32 = require_configured(env, &DataKey::NextCartridgeId);
 
         let cartridge = GenomeCartridge {
             id: cartridge_id,
             owner: user.clone(),
             skin_id,
             splice_round,
-            created_at: ledger_time,
+            created_at: env.ledger().timestamp(),
             finalized: false,
+            finalize_beneficiary: None,
+            derivation_version: derivation::current_version(env),
+            dev_entropy,
+            substituted_from: None,
+            collection_id,
+            gene_table_epoch: gene_table::current_epoch(env),
         };
 
         // Store cartridge data
@@ -223,36 +842,34 @@ impl GeneSplicer {
             TTL_EXTEND_TO,
         );
 
-        // Add to user's cartridge list
+        pending::set_pending(env, cartridge_id, true);
+        free_mint::mark_seen(env, &cartridge.owner);
+        collections::record_mint(env, collection_id, cartridge_id);
+
+        // Increment cartridge counter
+        env.storage()
+            .instance()
+            .set(&DataKey::NextCartridgeId, &cartridge_id.checked_add(1).expect("Cartridge ID overflow"));
+
+        cartridge
+    }
+
+    /// Helper: Append new cartridge IDs to `user`'s list with a single read and write
+    fn list_cartridges(env: &Env, user: &Address, cartridge_ids: &Vec<u32>) {
         let mut user_cartridges: Vec<u32> = env
             .storage()
             .persistent()
             .get(&DataKey::UserCartridges(user.clone()))
-            .unwrap_or(Vec::new(&env));
-        user_cartridges.push_back(cartridge_id);
+            .unwrap_or(Vec::new(env));
+        user_cartridges.append(cartridge_ids);
         env.storage()
             .persistent()
             .set(&DataKey::UserCartridges(user.clone()), &user_cartridges);
         env.storage().persistent().extend_ttl(
-            &DataKey::UserCartridges(user),
+            &DataKey::UserCartridges(user.clone()),
             TTL_THRESHOLD,
             TTL_EXTEND_TO,
         );
-
-        // Increment cartridge counter
-        env.storage()
-            .instance()
-            .set(&DataKey::NextCartridgeId, &cartridge_id.checked_add(1).expect("Cartridge ID overflow"));
-
-        // Emit event
-        CartridgeMinted {
-            cartridge_id,
-            owner: cartridge.owner.clone(),
-            skin_id: cartridge.skin_id,
-        }
-        .publish(&env);
-
-        cartridge_id
     }
 
     /// Get cartridge data by ID
@@ -262,8 +879,26 @@ impl GeneSplicer {
             .get(&DataKey::Cartridge(cartridge_id))
     }
 
-    /// Get multiple cartridges by IDs in a single call
+    /// Check whether a cartridge exists without decoding it
+    pub fn has_cartridge(env: Env, cartridge_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Cartridge(cartridge_id))
+    }
+
+    /// Check whether a cartridge has been finalized without decoding it
+    /// A Creature entry is written in the same call that sets `finalized`, so its presence is the flag
+    pub fn is_finalized(env: Env, cartridge_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Creature(cartridge_id))
+    }
+
+    /// Get multiple cartridges by IDs in a single call (at most Limits.max_batch_read)
     pub fn get_cartridges_batch(env: Env, ids: Vec<u32>) -> Vec<Option<GenomeCartridge>> {
+        if ids.len() > MAX_BATCH_READ {
+            panic_with_error!(&env, ReadLimitError::MaxBatchReadExceeded);
+        }
         let mut results = Vec::new(&env);
         for id in ids.iter() {
             results.push_back(env.storage().persistent().get(&DataKey::Cartridge(id)));
@@ -271,7 +906,7 @@ impl GeneSplicer {
         results
     }
 
-    /// Get all cartridge IDs owned by a user
+    /// Get all cartridge IDs owned by a user (get_user_cartridges_page for large holdings)
     pub fn get_user_cartridges(env: Env, user: Address) -> Vec<u32> {
         env.storage()
             .persistent()
@@ -289,53 +924,167 @@ impl GeneSplicer {
         next_id - 1
     }
 
-    /// Get the admin address
-    pub fn admin(env: Env) -> Address {
-        env.storage().instance().get(&DataKey::Admin).expect("Admin not configured")
+    /// Get the admin address (the Owner role)
+    pub fn admin(env: Env) -> Result<Address, Error> {
+        configured(&env, &DataKey::Admin)
     }
 
-    /// Update admin (only callable by current admin)
+    /// Hand the Owner role to a new admin (Owner only)
     pub fn set_admin(env: Env, new_admin: Address) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Admin not configured");
-        admin.require_auth();
+        let previous = require_role(&env, Role::Owner);
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        config_log::record(&env, &previous, "admin", new_admin.clone());
+        events::publish(&env, |nonce| OwnerChanged {
+            previous,
+            owner: new_admin,
+            nonce,
+        });
     }
 
-    /// Update cartridge skin count (admin-only)
+    /// Update cartridge skin count (Owner only)
     pub fn set_skin_count(env: Env, new_count: u64) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Admin not configured");
-        admin.require_auth();
+        let actor = require_role(&env, Role::Owner);
         env.storage().instance().set(&DataKey::CartridgeSkinCount, &new_count);
+        config_log::record(&env, &actor, "skin_count", new_count);
     }
 
-    /// Update drand public key (admin-only, 192 bytes uncompressed G2)
+    /// Update drand public key (Owner only, 192 bytes uncompressed G2)
     pub fn set_drand_public_key(env: Env, new_key: Bytes) {
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("Admin not configured");
-        admin.require_auth();
-        if new_key.len() != 192 {
-            panic!("Drand public key must be 192 bytes (uncompressed G2 affine coordinates)");
+        let actor = require_role(&env, Role::Owner);
+        let new_key = params::require(&env, params::parse_pubkey_g2(new_key));
+        let dev_mode: bool = env.storage().instance().get(&DataKey::DevMode).unwrap_or(false);
+        if !dev_mode {
+            params::require(&env, Self::check_drand_public_key(&env, &new_key));
         }
-        env.storage().instance().set(&DataKey::DrandPublicKey, &new_key);
+        env.storage()
+            .instance()
+            .set(&DataKey::DrandPublicKey, &Bytes::from(new_key.clone()));
+        config_log::record(&env, &actor, "drand_key", new_key);
     }
 
-    /// Get number of available cartridge skins
-    pub fn get_skin_count(env: Env) -> u64 {
+    /// Pause or resume new mints (Operator)
+    /// Existing cartridges can still be finalized unless finalization is paused separately
+    pub fn set_mint_paused(env: Env, paused: bool) {
+        let actor = require_role(&env, Role::Operator);
+        Self::write_mint_paused(&env, &actor, paused);
+    }
+
+    /// Pause or resume finalization of existing cartridges (Operator)
+    pub fn set_finalize_paused(env: Env, paused: bool) {
+        let actor = require_role(&env, Role::Operator);
+        Self::write_finalize_paused(&env, &actor, paused);
+    }
+
+    /// Full pause: sets both the mint and finalize flags (Operator)
+    pub fn set_paused(env: Env, paused: bool) {
+        let actor = require_role(&env, Role::Operator);
+        Self::write_mint_paused(&env, &actor, paused);
+        Self::write_finalize_paused(&env, &actor, paused);
+    }
+
+    fn write_mint_paused(env: &Env, actor: &Address, paused: bool) {
+        env.storage().instance().set(&DataKey::MintPaused, &paused);
+        config_log::record(env, actor, "mint_paused", paused);
+        events::publish(env, |nonce| MintPauseChanged { paused, nonce });
+    }
+
+    fn write_finalize_paused(env: &Env, actor: &Address, paused: bool) {
+        env.storage().instance().set(&DataKey::FinalizePaused, &paused);
+        config_log::record(env, actor, "finalize_paused", paused);
+        events::publish(env, |nonce| FinalizePauseChanged { paused, nonce });
+    }
+
+    /// Get whether new mints are paused
+    pub fn is_mint_paused(env: Env) -> bool {
         env.storage()
             .instance()
-            .get(&DataKey::CartridgeSkinCount)
-            .expect("Skin count not configured")
+            .get(&DataKey::MintPaused)
+            .unwrap_or(false)
     }
 
-    /// Get stored drand public key
-    pub fn get_drand_public_key(env: Env) -> Bytes {
+    /// Get whether finalization is paused
+    pub fn is_finalize_paused(env: Env) -> bool {
         env.storage()
             .instance()
-            .get(&DataKey::DrandPublicKey)
-            .expect("Drand public key not configured")
+            .get(&DataKey::FinalizePaused)
+            .unwrap_or(false)
+    }
+
+    /// Set or remove a skin's supply cap (Owner only)
+    /// Once `cap` cartridges carry this skin it is removed from the selection pool
+    pub fn set_skin_cap(env: Env, skin_id: u32, cap: Option<u32>) {
+        let actor = require_role(&env, Role::Owner);
+        let mut caps: Map<u32, u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::SkinCaps)
+            .unwrap_or(Map::new(&env));
+        match cap {
+            Some(cap) => caps.set(skin_id, cap),
+            None => {
+                caps.remove(skin_id);
+            }
+        }
+        env.storage().instance().set(&DataKey::SkinCaps, &caps);
+        config_log::record(&env, &actor, "skin_cap", (skin_id, cap));
+        events::publish(&env, |nonce| SkinCapUpdated { skin_id, cap, nonce });
+    }
+
+    /// Get (minted, cap) for a skin; cap is None when the skin is uncapped
+    pub fn get_skin_supply(env: Env, skin_id: u32) -> (u32, Option<u32>) {
+        let minted: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SkinMinted(skin_id))
+            .unwrap_or(0);
+        let caps: Map<u32, u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::SkinCaps)
+            .unwrap_or(Map::new(&env));
+        (minted, caps.get(skin_id))
+    }
+
+    /// Get skin IDs that can still be assigned to new cartridges
+    pub fn get_available_skins(env: Env) -> Result<Vec<u32>, Error> {
+        let skin_count: u64 = configured(&env, &DataKey::CartridgeSkinCount)?;
+        let exhausted = Self::exhausted_skins(&env, skin_count);
+        let mut available = Vec::new(&env);
+        for skin_id in 0..skin_count as u32 {
+            if !exhausted.contains(skin_id) {
+                available.push_back(skin_id);
+            }
+        }
+        Ok(available)
+    }
+
+    /// Get number of available cartridge skins
+    pub fn get_skin_count(env: Env) -> Result<u64, Error> {
+        configured(&env, &DataKey::CartridgeSkinCount)
+    }
+
+    /// Get stored drand public key
+    pub fn get_drand_public_key(env: Env) -> Result<Bytes, Error> {
+        configured(&env, &DataKey::DrandPublicKey)
+    }
+
+    /// Update expected drand chain hash (Owner only, rotate together with the public key)
+    pub fn set_drand_chain_hash(env: Env, new_hash: BytesN<32>) {
+        let actor = require_role(&env, Role::Owner);
+        env.storage().instance().set(&DataKey::DrandChainHash, &new_hash);
+        config_log::record(&env, &actor, "drand_chain_hash", new_hash);
+    }
+
+    /// Get expected drand chain hash
+    pub fn get_drand_chain_hash(env: Env) -> Result<BytesN<32>, Error> {
+        configured(&env, &DataKey::DrandChainHash)
     }
 
     /// Finalize a cartridge into a Creature NFT using drand entropy
     /// User submits entropy (round, randomness, signature) which is verified inline
+    /// `randomness` must be SHA-256 of `signature_compressed` (RandomnessMismatch otherwise); genes
+    /// always come from that hash, so in dev_mode, where the comparison is skipped, it is ignored
+    /// If `chain_hash` is provided it must match the configured drand network (checked before any crypto)
     pub fn finalize_splice(
         env: Env,
         cartridge_id: u32,
@@ -343,50 +1092,287 @@ impl GeneSplicer {
         randomness: Bytes,             // 32 bytes - user claims this is the randomness (verified)
         signature_compressed: Bytes,   // 48 bytes - for randomness (matches drand)
         signature_uncompressed: Bytes, // 96 bytes - for BLS verification
+        chain_hash: Option<Bytes>,     // 32 bytes - drand chain hash the beacon was fetched from
     ) -> u32 {
+        reentrancy::enter(&env);
+        let beacon = params::require_beacon(
+            &env,
+            randomness,
+            signature_compressed,
+            signature_uncompressed,
+            chain_hash,
+        );
+        let creature_id = Self::finalize_by_finalizer(&env, cartridge_id, |_| (round, beacon));
+        reentrancy::exit(&env);
+        creature_id
+    }
+
+    /// Dev-only: finalize immediately with genes derived from SHA256(`seed`)
+    /// - Refuses to run unless dev_mode is on; no round, beacon or signature checks
+    /// - The resulting creature is marked `dev_minted`
+    pub fn dev_finalize(env: Env, cartridge_id: u32, seed: Bytes) -> u32 {
+        Self::require_dev_mode(&env);
+
+        let cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Cartridge(cartridge_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CartridgeNotFound));
+        account_auth::require_finalize(&env, &cartridge.finalizer(), cartridge_id);
+
+        Self::dev_finalize_authorized(&env, cartridge, &seed)
+    }
+
+    /// Dev-only: mint a cartridge (charging the usual fee) and finalize it from `seed` in one call
+    /// - Refuses to run unless dev_mode is on; emits both the mint and finalization events
+    /// - Returns the creature ID, which is also the cartridge ID
+    pub fn dev_splice_and_finalize(env: Env, user: Address, seed: Bytes) -> u32 {
+        reentrancy::enter(&env);
+        Self::require_dev_mode(&env);
+        user.require_auth();
+
+        let cartridge_id = Self::splice_authorized(&env, user, Self::get_mint_fee(env.clone()));
+        let cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Cartridge(cartridge_id))
+            .expect("Cartridge just minted");
+        let creature_id = Self::dev_finalize_authorized(&env, cartridge, &seed);
+        reentrancy::exit(&env);
+        creature_id
+    }
+
+    /// Helper: panic with DevModeDisabled unless dev_mode is on
+    fn require_dev_mode(env: &Env) {
+        let dev_mode: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::DevMode)
+            .unwrap_or(false);
+        if !dev_mode {
+            panic_with_error!(env, Error::DevModeDisabled);
+        }
+    }
+
+    /// Helper: the dev_finalize flow once the finalizer has authorized the call
+    fn dev_finalize_authorized(env: &Env, cartridge: GenomeCartridge, seed: &Bytes) -> u32 {
+        if env
+            .storage()
+            .instance()
+            .get(&DataKey::FinalizePaused)
+            .unwrap_or(false)
+        {
+            panic_with_error!(env, Error::FinalizePaused);
+        }
+        // The seed stands in for the round, so only the finalized state matters here
+        if cartridge_status::state(env, &cartridge) == CartridgeState::Finalized {
+            panic_with_error!(env, Error::AlreadyFinalized);
+        }
+        invariants::assert_derivation_consistency(env, &cartridge);
+
+        let randomness: Bytes = env.crypto().sha256(seed).into();
+        let genes = derivation::derive(env, &cartridge, &randomness);
+
+        Self::mint_creature(env, cartridge, genes, true)
+    }
+
+    /// Register (or rotate) the ed25519 session key used to sign finalization permits
+    /// The owner signs permits off-chain with this key so relayers can finalize on their behalf
+    pub fn register_session_key(env: Env, owner: Address, public_key: BytesN<32>) {
+        owner.require_auth();
+
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::SessionKey(owner.clone()), &public_key);
+        env.storage().persistent().extend_ttl(
+            &DataKey::SessionKey(owner.clone()),
+            TTL_THRESHOLD,
+            TTL_EXTEND_TO,
+        );
+
+        events::publish(&env, |nonce| SessionKeyRegistered { owner, public_key, nonce });
+    }
+
+    /// Get the registered session key for an owner
+    pub fn get_session_key(env: Env, owner: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::SessionKey(owner))
+    }
+
+    /// Finalize a cartridge on behalf of its owner using an off-chain signed permit
+    /// - Relayer authorizes the call; the owner's auth is replaced by the permit signature
+    /// - Permit must name this cartridge, be unexpired, and verify against the owner's session key
+    /// - Permits are single-use; the creature is still assigned to the cartridge's finalizer
+    ///   (the owner, or the buyer of its finalization right, whose session key signs the permit)
+    /// - Without a permit the finalizer's account authorizes the `finalize` action itself,
+    ///   so smart wallets can apply their own session policy instead of an ed25519 key
+    pub fn finalize_with_permit(
+        env: Env,
+        relayer: Address,
+        cartridge_id: u32,
+        round: u64,
+        randomness: Bytes,
+        signature_compressed: Bytes,
+        signature_uncompressed: Bytes,
+        chain_hash: Option<Bytes>,
+        permit: Option<Permit>,
+    ) -> u32 {
+        reentrancy::enter(&env);
+        let beacon = params::require_beacon(
+            &env,
+            randomness,
+            signature_compressed,
+            signature_uncompressed,
+            chain_hash,
+        );
+        relayer.require_auth();
+
         // Extend instance TTL on every interaction
         env.storage()
             .instance()
             .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
 
-        // Get cartridge
-        let mut cartridge: GenomeCartridge = env
+        let cartridge: GenomeCartridge = env
             .storage()
             .persistent()
             .get(&DataKey::Cartridge(cartridge_id))
-            .unwrap_or_else(|| panic!("Cartridge not found"));
-
-        // Require auth from cartridge owner
-        cartridge.owner.require_auth();
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CartridgeNotFound));
 
-        // Check if already finalized
-        if cartridge.finalized {
-            panic!("Cartridge already finalized");
+        // Without a permit the finalizer authorizes directly: typically a smart wallet
+        // enforcing its own session policy on the `finalize` action (see `account_auth`)
+        match permit {
+            Some(permit) => Self::consume_permit(&env, &cartridge, &relayer, permit),
+            None => account_auth::require_finalize(&env, &cartridge.finalizer(), cartridge_id),
         }
 
-        // Verify round matches cartridge's assigned round
-        if round != cartridge.splice_round {
-            panic!("Round mismatch");
-        }
+        // The relayer, not the owner, authorized this call, so it pays any verification share
+        let creature_id = Self::finalize_cartridge(&env, cartridge, round, beacon, &relayer);
+        reentrancy::exit(&env);
+        creature_id
+    }
 
-        // Validate randomness is 32 bytes (SHA-256 output)
-        if randomness.len() != 32 {
-            panic!("Randomness must be 32 bytes");
+    /// Helper: check a finalization permit against the finalizer's session key and mark it used
+    fn consume_permit(env: &Env, cartridge: &GenomeCartridge, relayer: &Address, permit: Permit) {
+        if permit.cartridge_id != cartridge.id {
+            panic_with_error!(env, PermitError::PermitCartridgeMismatch);
         }
-
-        // Validate compressed signature is 48 bytes (BLS12-381 G1 point, compressed)
-        if signature_compressed.len() != 48 {
-            panic!("Compressed signature must be 48 bytes");
+        if env.ledger().timestamp() > permit.expires_at {
+            panic_with_error!(env, PermitError::PermitExpired);
         }
 
-        // Validate uncompressed signature is 96 bytes (BLS12-381 G1 point, uncompressed affine coordinates)
-        if signature_uncompressed.len() != 96 {
-            panic!("Uncompressed signature must be 96 bytes");
+        let session_key: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SessionKey(cartridge.finalizer()))
+            .unwrap_or_else(|| panic_with_error!(env, PermitError::NoSessionKey));
+
+        // Single-use: permits are identified by the hash of the signed message
+        let message = Self::permit_message(env.clone(), cartridge.id, permit.expires_at);
+        let permit_hash: BytesN<32> = env.crypto().sha256(&message).into();
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PermitConsumed(permit_hash.clone()))
+        {
+            panic_with_error!(env, PermitError::PermitAlreadyUsed);
         }
 
-        // Verify compressed and uncompressed signatures represent the same point
-        // by checking x-coordinates match (compressed bytes 0-47 == uncompressed bytes 0-47, ignoring flag bits)
-        Self::verify_signature_compression(&signature_compressed, &signature_uncompressed);
+        // Traps if the signature does not verify
+        env.crypto()
+            .ed25519_verify(&session_key, &message, &permit.signature);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PermitConsumed(permit_hash.clone()), &true);
+        env.storage().persistent().extend_ttl(
+            &DataKey::PermitConsumed(permit_hash.clone()),
+            TTL_THRESHOLD,
+            TTL_EXTEND_TO,
+        );
+        // Once expired the permit is refused before this check, so the marker can be collected
+        gc::track(
+            env,
+            "permit",
+            DataKey::PermitConsumed(permit_hash),
+            permit.expires_at,
+        );
+
+        events::publish(env, |nonce| PermitUsed {
+            cartridge_id: cartridge.id,
+            relayer: relayer.clone(),
+            nonce,
+        });
+    }
+
+    /// Build the message a session key signs to authorize finalization:
+    /// "SPLICERS_PERMIT" || contract address (XDR) || cartridge_id (4 bytes BE) || expires_at (8 bytes BE)
+    /// Binding the contract address prevents replaying a permit against another deployment
+    pub fn permit_message(env: Env, cartridge_id: u32, expires_at: u64) -> Bytes {
+        let mut message = Bytes::from_slice(&env, PERMIT_DOMAIN);
+        message.append(&env.current_contract_address().to_xdr(&env));
+        message.extend_from_array(&cartridge_id.to_be_bytes());
+        message.extend_from_array(&expires_at.to_be_bytes());
+        message
+    }
+
+    /// Shared body of the entrypoints the finalizer authorizes itself (finalize_splice and
+    /// finalize_with_entropy), which differ only in where the beacon comes from
+    /// `beacon_for` supplies the round and beacon once the cartridge is loaded and authorized
+    fn finalize_by_finalizer(
+        env: &Env,
+        cartridge_id: u32,
+        beacon_for: impl FnOnce(&GenomeCartridge) -> (u64, params::Beacon),
+    ) -> u32 {
+        // Extend instance TTL on every interaction
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        // Get cartridge
+        let cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Cartridge(cartridge_id))
+            .unwrap_or_else(|| panic_with_error!(env, Error::CartridgeNotFound));
+
+        // Require auth from cartridge owner (or the buyer of its finalization right)
+        let finalizer = cartridge.finalizer();
+        account_auth::require_finalize(env, &finalizer, cartridge_id);
+
+        let (round, beacon) = beacon_for(&cartridge);
+        Self::finalize_cartridge(env, cartridge, round, beacon, &finalizer)
+    }
+
+    /// Shared finalization path once the caller has been authorized
+    /// `payer` authorized the call: it is recorded as the round's verifier if it runs the
+    /// pairing, and pays the verification share if the round was already verified
+    fn finalize_cartridge(
+        env: &Env,
+        cartridge: GenomeCartridge,
+        round: u64,
+        beacon: params::Beacon,
+        payer: &Address,
+    ) -> u32 {
+        invariants::assert_derivation_consistency(env, &cartridge);
+        let cartridge_id = cartridge.id;
+        let params::Beacon {
+            randomness,
+            signature_compressed,
+            signature_uncompressed,
+            chain_hash,
+        } = beacon;
+
+        // State and round checks shared with the can_finalize pre-flight
+        preflight::require_preconditions(env, &cartridge, round);
+
+        // Fail fast on beacons from the wrong drand network (e.g. default chain instead of quicknet)
+        Self::require_drand_network(env, chain_hash);
+
+        // Verify the compressed signature is the canonical encoding of the uncompressed point
+        Self::verify_signature_compression(env, &signature_compressed, &signature_uncompressed);
 
         // Check if dev_mode is enabled
         let dev_mode: bool = env
@@ -396,44 +1382,63 @@ impl GeneSplicer {
             .unwrap_or(false);
 
         // Verify BLS signature using uncompressed signature (unless in dev mode)
+        // A round already verified or stored for this exact signature skips the pairing,
+        // sharing its cost
         if !dev_mode {
-            Self::verify_drand_signature(&env, round, &signature_uncompressed);
+            if verification::is_verified(env, round, &signature_uncompressed)
+                || entropy::is_stored(env, round, &signature_uncompressed)
+            {
+                cost_sharing::pay_share(env, round, cartridge_id, payer);
+            } else {
+                Self::verify_drand_signature(env, round, &signature_uncompressed);
+                verification::record_verified(env, round, &signature_uncompressed, Some(payer));
+            }
         }
 
-        // Derive randomness from compressed signature (matches drand's published randomness!)
-        // Drand spec: randomness = SHA256(compressed_signature)
-        let computed_randomness: BytesN<32> = env.crypto().sha256(&signature_compressed).into();
-        let computed_randomness_bytes: Bytes = computed_randomness.into();
+        // Derive randomness from the verified point's canonical compression, as every other
+        // finalize path does (drand spec: randomness = SHA256(compressed_signature))
+        let computed_randomness: BytesN<32> = env
+            .crypto()
+            .sha256(&Bytes::from(compress_g1(env, &signature_uncompressed)))
+            .into();
 
         // Verify user-provided randomness matches our computed value (defense-in-depth)
-        if !dev_mode {
-            if randomness != computed_randomness_bytes {
-                panic!("Randomness does not match SHA256(signature_compressed) - falsification attempt detected");
-            }
+        if !dev_mode && randomness != computed_randomness {
+            panic_with_error!(env, Error::RandomnessMismatch);
         }
 
         // Use computed randomness (not user-provided) for extra safety
-        let verified_randomness = computed_randomness_bytes;
+        let verified_randomness = Bytes::from(computed_randomness);
 
-        // Select genes using verified entropy
-        let head_gene = Self::select_gene(&env, &verified_randomness, 0);
-        let body_gene = Self::select_gene(&env, &verified_randomness, 1);
-        let legs_gene = Self::select_gene(&env, &verified_randomness, 2);
+        // Select genes using verified entropy, with the rules the cartridge was minted under
+        let genes = derivation::derive(env, &cartridge, &verified_randomness);
+
+        Self::mint_creature(env, cartridge, genes, false)
+    }
 
-        // Create creature
+    /// Helper: Turn a cartridge into a Creature with the given genes, store it and emit events
+    fn mint_creature(env: &Env, mut cartridge: GenomeCartridge, genes: [Gene; 3], dev_minted: bool) -> u32 {
+        let cartridge_id = cartridge.id;
+        let [head_gene, body_gene, legs_gene] = genes;
+
+        // Create creature, owned by the finalization right holder (cartridge owner by default)
+        let owner = cartridge.finalizer();
         let creature = Creature {
             id: cartridge_id,
-            owner: cartridge.owner.clone(),
+            owner: owner.clone(),
             skin_id: cartridge.skin_id,
             head_gene,
             body_gene,
             legs_gene,
             finalized_at: env.ledger().timestamp(),
             entropy_round: cartridge.splice_round,
+            dev_minted: dev_minted || cartridge.dev_entropy,
+            collection_id: cartridge.collection_id,
         };
 
-        // Mark cartridge as finalized
+        // Mark cartridge as finalized; the finalization right is consumed
         cartridge.finalized = true;
+        cartridge.finalize_beneficiary = None;
         env.storage()
             .persistent()
             .set(&DataKey::Cartridge(cartridge_id), &cartridge);
@@ -443,100 +1448,136 @@ impl GeneSplicer {
             TTL_EXTEND_TO,
         );
 
-        // Store creature
-        env.storage()
-            .persistent()
-            .set(&DataKey::Creature(cartridge_id), &creature);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Creature(cartridge_id),
-            TTL_THRESHOLD,
-            TTL_EXTEND_TO,
-        );
-
-        // Add to user's creature list
-        let mut user_creatures: Vec<u32> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::UserCreatures(cartridge.owner.clone()))
-            .unwrap_or(Vec::new(&env));
-        user_creatures.push_back(cartridge_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::UserCreatures(cartridge.owner.clone()), &user_creatures);
-        env.storage().persistent().extend_ttl(
-            &DataKey::UserCreatures(cartridge.owner),
-            TTL_THRESHOLD,
-            TTL_EXTEND_TO,
-        );
+        pending::set_pending(env, cartridge_id, false);
+        finalized_bitmap::mark_finalized(env, cartridge_id);
+        finalize_report::clear(env, cartridge_id);
+        store_creature(env, &creature);
+        inbox::notify(env, &owner, "finalized", cartridge_id);
 
         // Emit event
-        CreatureFinalized {
-            cartridge_id,
-            head_gene_id: creature.head_gene.id,
-            body_gene_id: creature.body_gene.id,
-            legs_gene_id: creature.legs_gene.id,
-        }
-        .publish(&env);
+        Self::publish_creatures_finalized(
+            env,
+            creature.entropy_round,
+            &Vec::from_array(env, [creature]),
+        );
 
         cartridge_id
     }
 
-    /// Helper: Select a gene using entropy bytes and gene slot (0=head, 1=body, 2=legs)
-    fn select_gene(_env: &Env, entropy: &Bytes, slot: u32) -> Gene {
-        // Use different entropy bytes for each gene slot
-        let offset = (slot * 10) as u32;
-
-        // Extract 4 bytes for this gene and convert to u32
-        let byte1 = entropy.get(offset % 32).unwrap_or(0) as u32;
-        let byte2 = entropy.get((offset + 1) % 32).unwrap_or(0) as u32;
-        let byte3 = entropy.get((offset + 2) % 32).unwrap_or(0) as u32;
-        let byte4 = entropy.get((offset + 3) % 32).unwrap_or(0) as u32;
-
-        let random_value = (byte1 << 24) | (byte2 << 16) | (byte3 << 8) | byte4;
-
-        // Map to 0-14 gene ID with weighted distribution
-        // Legendary (10%): Golem (IDs 3-5)
-        // Rare (30%): Dark Oracle (IDs 0-2)
-        // Common (60%): Necromancer, Skeleton Crusader, Skeleton Warrior (IDs 6-14)
-
-        let roll = (random_value % 10) as u32; // 0-9 for distribution
-        let (gene_id, rarity) = if roll == 0 {
-            // 10% chance - Legendary (Golem: IDs 3-5)
-            let golem_variant = (random_value >> 8) % 3; // Use different bits for variant selection
-            (3 + golem_variant as u32, GeneRarity::Legendary)
-        } else if roll <= 3 {
-            // 30% chance - Rare (Dark Oracle: IDs 0-2)
-            let oracle_variant = (random_value >> 8) % 3;
-            (oracle_variant as u32, GeneRarity::Rare)
-        } else {
-            // 60% chance - Common (IDs 6-14, 9 variants)
-            let common_variant = (random_value >> 8) % 9;
-            (6 + common_variant as u32, GeneRarity::Normal)
-        };
+    /// Helper: Skin IDs below `skin_count` whose cap has been reached, in ascending order
+    fn exhausted_skins(env: &Env, skin_count: u64) -> Vec<u32> {
+        let caps: Map<u32, u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::SkinCaps)
+            .unwrap_or(Map::new(env));
+        let mut exhausted = Vec::new(env);
+        // Map iterates keys in ascending order
+        for (skin_id, cap) in caps.iter() {
+            if (skin_id as u64) >= skin_count {
+                break;
+            }
+            let minted: u32 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::SkinMinted(skin_id))
+                .unwrap_or(0);
+            if minted >= cap {
+                exhausted.push_back(skin_id);
+            }
+        }
+        exhausted
+    }
 
-        Gene {
-            id: gene_id,
-            rarity,
+    /// Helper: Pick a skin uniformly among non-exhausted skins and record the assignment
+    /// Draws an index over the available skins and maps it past exhausted IDs, so a single
+    /// PRNG draw suffices and the choice stays unbiased (no rejection loop)
+    fn select_skin(env: &Env, skin_count: u64) -> u32 {
+        let exhausted = Self::exhausted_skins(env, skin_count);
+        let available = skin_count - exhausted.len() as u64;
+        if available == 0 {
+            panic_with_error!(env, Error::AllSkinsExhausted);
+        }
+
+        // u64 for GenRange compatibility
+        let index: u64 = env.prng().gen_range(0..available);
+        let mut skin_id = index as u32;
+        for exhausted_id in exhausted.iter() {
+            if exhausted_id <= skin_id {
+                skin_id += 1;
+            }
         }
+
+        let key = DataKey::SkinMinted(skin_id);
+        let minted: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(minted + 1));
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        skin_id
     }
 
     /// Get creature data by ID
     pub fn get_creature(env: Env, creature_id: u32) -> Option<Creature> {
+        let revealed = Self::is_revealed(env.clone());
         env.storage()
             .persistent()
             .get(&DataKey::Creature(creature_id))
+            .map(|creature| reveal::visible(creature, revealed))
+    }
+
+    /// Check whether a creature exists without decoding it
+    pub fn has_creature(env: Env, creature_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Creature(creature_id))
     }
 
-    /// Get multiple creatures by IDs in a single call
+    /// Get multiple creatures by IDs in a single call (at most Limits.max_batch_read)
     pub fn get_creatures_batch(env: Env, ids: Vec<u32>) -> Vec<Option<Creature>> {
+        if ids.len() > MAX_BATCH_READ {
+            panic_with_error!(&env, ReadLimitError::MaxBatchReadExceeded);
+        }
+        let revealed = Self::is_revealed(env.clone());
         let mut results = Vec::new(&env);
         for id in ids.iter() {
-            results.push_back(env.storage().persistent().get(&DataKey::Creature(id)));
+            let creature: Option<Creature> = env.storage().persistent().get(&DataKey::Creature(id));
+            results.push_back(creature.map(|creature| reveal::visible(creature, revealed)));
         }
         results
     }
 
-    /// Get all creature IDs owned by a user
+    /// Get gallery thumbnails for creatures by IDs (at most Limits.max_thumb_read)
+    /// IDs without a creature are skipped; each thumb carries its ID
+    pub fn get_creature_thumbs(env: Env, ids: Vec<u32>) -> Vec<CreatureThumb> {
+        if ids.len() > MAX_THUMB_READ {
+            panic_with_error!(&env, ReadLimitError::MaxThumbReadExceeded);
+        }
+        let revealed = Self::is_revealed(env.clone());
+        let mut thumbs = Vec::new(&env);
+        for id in ids.iter() {
+            let creature: Option<Creature> = env.storage().persistent().get(&DataKey::Creature(id));
+            if let Some(creature) = creature.map(|creature| reveal::visible(creature, revealed)) {
+                let top_rarity = creature
+                    .head_gene
+                    .rarity
+                    .max(creature.body_gene.rarity)
+                    .max(creature.legs_gene.rarity);
+                thumbs.push_back(CreatureThumb {
+                    id,
+                    skin_id: creature.skin_id,
+                    head_gene_id: creature.head_gene.id,
+                    body_gene_id: creature.body_gene.id,
+                    legs_gene_id: creature.legs_gene.id,
+                    top_rarity,
+                });
+            }
+        }
+        thumbs
+    }
+
+    /// Get all creature IDs owned by a user (get_user_creatures_page for large holdings)
     pub fn get_user_creatures(env: Env, user: Address) -> Vec<u32> {
         env.storage()
             .persistent()
@@ -555,6 +1596,22 @@ impl GeneSplicer {
             .extend_ttl_for_code(env.current_contract_address(), TTL_THRESHOLD, TTL_EXTEND_TO);
     }
 
+    /// Get the per-call limits enforced by batched and paged entrypoints
+    pub fn get_limits(_env: Env) -> Limits {
+        Limits {
+            max_batch_read: MAX_BATCH_READ,
+            max_thumb_read: MAX_THUMB_READ,
+            max_pending_page: MAX_PENDING_PAGE,
+            max_snapshot_batch: MAX_SNAPSHOT_BATCH,
+            max_gene_index_page: MAX_GENE_INDEX_PAGE,
+            max_collection_page: MAX_COLLECTION_PAGE,
+            max_bootstrap_entries: MAX_BOOTSTRAP_ENTRIES,
+            max_queue_batch: MAX_QUEUE_BATCH,
+            max_splice_batch: MAX_SPLICE_BATCH,
+            max_owner_page: MAX_OWNER_PAGE,
+        }
+    }
+
     /// Get current dev mode status
     pub fn get_dev_mode(env: Env) -> bool {
         env.storage()
@@ -563,62 +1620,226 @@ impl GeneSplicer {
             .unwrap_or(false)
     }
 
+    /// Aggregate deployment checks for pipelines to assert on:
+    /// dev_mode is off and the stored drand public key is a usable G2 point
+    pub fn is_production_ready(env: Env) -> bool {
+        let dev_mode: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::DevMode)
+            .unwrap_or(false);
+        if dev_mode {
+            return false;
+        }
+        match env
+            .storage()
+            .instance()
+            .get::<_, Bytes>(&DataKey::DrandPublicKey)
+        {
+            Some(key) => params::parse_pubkey_g2(key)
+                .is_ok_and(|key| Self::is_valid_drand_public_key(&env, &key)),
+            None => false,
+        }
+    }
+
+}
+
+/// Store a new creature, index its genes and add it to its owner's creature list
+pub(crate) fn store_creature(env: &Env, creature: &Creature) {
+    gene_index::index_creature(env, creature);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Creature(creature.id), creature);
+    env.storage().persistent().extend_ttl(
+        &DataKey::Creature(creature.id),
+        TTL_THRESHOLD,
+        TTL_EXTEND_TO,
+    );
+
+    let owner = creature.owner.clone();
+    let mut user_creatures: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::UserCreatures(owner.clone()))
+        .unwrap_or(Vec::new(env));
+    user_creatures.push_back(creature.id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::UserCreatures(owner.clone()), &user_creatures);
+    env.storage().persistent().extend_ttl(
+        &DataKey::UserCreatures(owner),
+        TTL_THRESHOLD,
+        TTL_EXTEND_TO,
+    );
 }
 
 /// Negate a G1 point by negating its y-coordinate
-/// Input: Bytes containing uncompressed G1 point (96 bytes: x || y)
-/// Output: Bytes containing negated point (96 bytes: x || -y) where -y = p - y
+/// Input: uncompressed G1 point (96 bytes: x || y)
+/// Output: negated point (96 bytes: x || -y) where -y = p - y
 /// p is the BLS12-381 base field modulus
-fn negate_g1_bytes(env: &Env, point_bytes: &Bytes) -> Bytes {
-    // Extract x-coordinate (bytes 0-47)
-    let mut x_bytes = [0u8; 48];
-    for i in 0..48_u32 {
-        x_bytes[i as usize] = point_bytes.get(i).unwrap();
+fn negate_g1_bytes(env: &Env, point_bytes: &BytesN<96>) -> BytesN<96> {
+    // x-coordinate is bytes 0-47, y-coordinate bytes 48-95
+    let point = point_bytes.to_array();
+    // Negating a coordinate >= p would produce bytes the host traps on
+    if !coordinates_in_range(&point) {
+        panic_with_error!(env, Error::SignatureCoordinateOutOfRange);
     }
+    let y_bytes = &point[48..];
 
-    // Extract y-coordinate (bytes 48-95)
-    let mut y_bytes = [0u8; 48];
-    for i in 0..48_u32 {
-        y_bytes[i as usize] = point_bytes.get(48 + i).unwrap();
-    }
+    let neg_y = negate_fp(y_bytes);
 
-    // BLS12-381 base field modulus p (48 bytes, big-endian)
-    let p: [u8; 48] = [
-        0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac,
-        0xd7, 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0,
-        0xf6, 0x24, 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff,
-        0xff, 0xaa, 0xab,
-    ];
+    // Construct negated point bytes: x || (-y)
+    let mut negated = point;
+    negated[48..].copy_from_slice(&neg_y);
+
+    BytesN::from_array(env, &negated)
+}
 
-    // Compute -y = p - y (big-endian subtraction)
+// BLS12-381 base field modulus p (48 bytes, big-endian)
+pub(crate) const BLS12_381_P: [u8; 48] = [
+    0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac,
+    0xd7, 0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0,
+    0xf6, 0x24, 0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff,
+    0xff, 0xaa, 0xab,
+];
+
+/// Whether every 48-byte coordinate of an uncompressed point is a field element (< p)
+/// The flag bits in the top 3 bits of byte 0 are not part of the coordinate
+fn coordinates_in_range(point: &[u8]) -> bool {
+    point.chunks(48).enumerate().all(|(i, limb)| {
+        let mut coordinate = [0u8; 48];
+        coordinate.copy_from_slice(limb);
+        if i == 0 {
+            coordinate[0] &= 0x1F;
+        }
+        coordinate < BLS12_381_P
+    })
+}
+
+/// Compute -y = p - y (big-endian subtraction)
+fn negate_fp(y_bytes: &[u8]) -> [u8; 48] {
     let mut neg_y = [0u8; 48];
     let mut borrow: u16 = 0;
 
     for i in (0..48).rev() {
-        let p_byte = p[i] as u16;
+        let p_byte = BLS12_381_P[i] as u16;
         let y_byte = y_bytes[i] as u16;
         let diff = p_byte.wrapping_sub(y_byte).wrapping_sub(borrow);
         neg_y[i] = (diff & 0xFF) as u8;
         borrow = if diff > 0xFF { 1 } else { 0 };
     }
+    neg_y
+}
 
-    // Construct negated point bytes: x || (-y)
-    let mut negated = Bytes::new(env);
+/// Compress an uncompressed G1 point (96 bytes: x || y) to the 48-byte ZCash encoding
+/// drand publishes: x with the compression flag, plus the sign flag when y > p - y
+/// drand's randomness is SHA256 of exactly these bytes
+fn compress_g1(env: &Env, point_bytes: &BytesN<96>) -> BytesN<48> {
+    let point = point_bytes.to_array();
+    let y_bytes = &point[48..];
+
+    let mut compressed = [0u8; 48];
+    compressed.copy_from_slice(&point[..48]);
+    compressed[0] |= 0x80;
+    // Equal-length big-endian byte strings compare like the integers they encode
+    if *y_bytes > negate_fp(y_bytes)[..] {
+        compressed[0] |= 0x20;
+    }
+    BytesN::from_array(env, &compressed)
+}
 
-    // Copy x-coordinate
-    for i in 0..48 {
-        negated.push_back(x_bytes[i]);
+impl GeneSplicer {
+    /// Check a drand public key can be used for real verification:
+    /// not the all-zero mock, no encoding flags (compression/infinity), and in the G2 subgroup
+    /// Cheap structural checks run first so common deployment mistakes never reach the host crypto
+    pub(crate) fn is_valid_drand_public_key(env: &Env, key: &BytesN<192>) -> bool {
+        Self::check_drand_public_key(env, key).is_ok()
     }
 
-    // Copy negated y-coordinate
-    for i in 0..48 {
-        negated.push_back(neg_y[i]);
+    /// The checks behind is_valid_drand_public_key, naming the one that failed
+    /// Coordinates are range-checked before deserializing, which would trap on them
+    pub(crate) fn check_drand_public_key(env: &Env, key: &BytesN<192>) -> Result<(), Error> {
+        let key_bytes = key.to_array();
+        if key_bytes.iter().all(|b| *b == 0) {
+            return Err(Error::InvalidDrandPublicKey);
+        }
+        // Top 3 bits of byte 0 are the compression, infinity, and sort flags
+        if key_bytes[0] & 0xE0 != 0 {
+            return Err(Error::InvalidDrandPublicKey);
+        }
+        if !coordinates_in_range(&key_bytes) {
+            return Err(Error::PubkeyCoordinateOutOfRange);
+        }
+        if !env
+            .crypto()
+            .bls12_381()
+            .g2_is_in_subgroup(&G2Affine::from_bytes(key.clone()))
+        {
+            return Err(Error::InvalidDrandPublicKey);
+        }
+        Ok(())
     }
 
-    negated
-}
+    /// Emit mint events for a batch of contiguous cartridges owned by `owner`, each at `unit_price`
+    /// Per-item `CartridgeMinted` up to BATCH_EVENT_THRESHOLD, one `BatchCartridgeMinted` above it
+    pub(crate) fn publish_cartridges_minted(
+        env: &Env,
+        owner: &Address,
+        first_id: u32,
+        skin_ids: &Vec<u32>,
+        unit_price: i128,
+    ) {
+        if skin_ids.len() > BATCH_EVENT_THRESHOLD {
+            events::publish(env, |nonce| BatchCartridgeMinted {
+                owner: owner.clone(),
+                first_id,
+                count: skin_ids.len(),
+                skin_ids: skin_ids.clone(),
+                unit_price,
+                nonce,
+            });
+            return;
+        }
+
+        for (offset, skin_id) in skin_ids.iter().enumerate() {
+            events::publish(env, |nonce| CartridgeMinted {
+                cartridge_id: first_id + offset as u32,
+                owner: owner.clone(),
+                skin_id,
+                nonce,
+            });
+        }
+    }
+
+    /// Emit finalization events for creatures finalized from the same drand round
+    /// Per-item `CreatureFinalized` up to BATCH_EVENT_THRESHOLD, one `BatchCreatureFinalized` above it
+    pub(crate) fn publish_creatures_finalized(
+        env: &Env,
+        round: u64,
+        creatures: &Vec<Creature>,
+    ) {
+        if creatures.len() > BATCH_EVENT_THRESHOLD {
+            let mut creature_ids = Vec::new(env);
+            for creature in creatures.iter() {
+                creature_ids.push_back(creature.id);
+            }
+            events::publish(env, |nonce| BatchCreatureFinalized { round, creature_ids, nonce });
+            return;
+        }
+
+        let revealed = Self::is_revealed(env.clone());
+        for creature in creatures.iter().map(|creature| reveal::visible(creature, revealed)) {
+            events::publish(env, |nonce| CreatureFinalized {
+                cartridge_id: creature.id,
+                head_gene_id: creature.head_gene.id,
+                body_gene_id: creature.body_gene.id,
+                legs_gene_id: creature.legs_gene.id,
+                nonce,
+            });
+        }
+    }
 
-impl GeneSplicer {
     /// Verify drand quicknet (unchained) BLS12-381 signature using CAP-0059
     ///
     /// QUICKNET UNCHAINED MODE:
@@ -634,6 +1855,17 @@ impl GeneSplicer {
     ///   * G2 pubkey: 96 bytes compressed -> 192 bytes uncompressed (x_c1 || x_c0 || y_c1 || y_c0)
     /// - Pass uncompressed affine coordinates to finalize_splice
     ///
+    /// Fail with WrongDrandNetwork if a beacon's declared chain hash isn't the configured network
+    /// Beacons submitted without a chain hash pass; the pairing still binds them to the key
+    pub(crate) fn require_drand_network(env: &Env, chain_hash: Option<BytesN<32>>) {
+        if let Some(chain_hash) = chain_hash {
+            let expected: BytesN<32> = require_configured(env, &DataKey::DrandChainHash);
+            if chain_hash != expected {
+                panic_with_error!(env, Error::WrongDrandNetwork);
+            }
+        }
+    }
+
     /// Verify that compressed and uncompressed signatures represent the same G1 point
     ///
     /// Compressed format (48 bytes): flag_byte || x_coordinate[1..47]
@@ -641,30 +1873,26 @@ impl GeneSplicer {
     ///
     /// Uncompressed format (96 bytes): x_coordinate[0..47] || y_coordinate[48..95]
     ///
-    /// Verification: Extract x from compressed (strip flags) and compare with uncompressed x
+    /// Verification: the compressed bytes must be exactly the canonical encoding of the
+    /// uncompressed point, flags included. Comparing only x would let one beacon pass with
+    /// any of 8 flag combinations, each hashing to different randomness
     fn verify_signature_compression(
-        compressed: &Bytes,
-        uncompressed: &Bytes,
+        env: &Env,
+        compressed: &BytesN<48>,
+        uncompressed: &BytesN<96>,
     ) {
-        // Extract x-coordinate from compressed (bytes 0-47, but byte 0 has flag bits in top 3 bits)
-        // We need to mask off the top 3 bits from byte 0
-        let compressed_byte0 = compressed.get(0).unwrap();
-        let compressed_byte0_no_flags = compressed_byte0 & 0x1F; // Mask: 0001_1111
-
-        // Extract x-coordinate from uncompressed (bytes 0-47)
-        let uncompressed_byte0 = uncompressed.get(0).unwrap();
-
-        // Check if byte 0 matches (after removing flags from compressed)
-        if compressed_byte0_no_flags != uncompressed_byte0 {
-            panic!("Signature compression mismatch: byte 0 doesn't match");
+        if !Self::signature_compression_matches(env, compressed, uncompressed) {
+            panic_with_error!(env, Error::SignatureCompressionMismatch);
         }
+    }
 
-        // Check remaining x-coordinate bytes (1-47)
-        for i in 1..48 {
-            if compressed.get(i).unwrap() != uncompressed.get(i).unwrap() {
-                panic!("Signature compression mismatch: x-coordinate mismatch");
-            }
-        }
+    /// Non-panicking check behind verify_signature_compression
+    pub(crate) fn signature_compression_matches(
+        env: &Env,
+        compressed: &BytesN<48>,
+        uncompressed: &BytesN<96>,
+    ) -> bool {
+        *compressed == compress_g1(env, uncompressed)
     }
 
     /// CONTRACT RESPONSIBILITIES (this function):
@@ -674,128 +1902,30 @@ impl GeneSplicer {
     /// 4. Hash message to G1 using hash_to_g1() with DST "BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_"
     /// 5. Perform subgroup check on hashed point
     /// 6. Construct G2Affine from drand public key bytes (192 bytes uncompressed)
-    /// 7. Perform subgroup check on public key
+    /// 7. Check the public key: not the zero mock, no flags, coordinates in range, in the G2 subgroup
     /// 8. Construct G2 generator
     /// 9. Verify pairing: e(signature, G2_gen) == e(H(msg), drand_pubkey)
-    pub fn verify_drand_signature(env: &Env, round: u64, signature: &Bytes) {
-        // Signature must be 96 bytes: x (48 bytes) || y (48 bytes)
-        if signature.len() != 96 {
-            panic!("Signature must be 96 bytes (uncompressed G1 affine)");
-        }
-
-        // Negate signature BEFORE deserializing to avoid needing to negate G1Affine
-        // Verification: e(sig, G2_gen) == e(H(msg), pubkey)
-        // Rearranges to: e(-sig, G2_gen) * e(H(msg), pubkey) == 1
-        let negated_sig = crate::negate_g1_bytes(env, signature);
-
-        // Construct G1Affine from negated signature bytes
-        let sig_bytes: BytesN<96> = negated_sig
-            .try_into()
-            .unwrap_or_else(|_| panic!("Signature must be exactly 96 bytes"));
-        let neg_sig_point = G1Affine::from_bytes(sig_bytes);
-
-        // Subgroup check on negated signature
-        if !env.crypto().bls12_381().g1_is_in_subgroup(&neg_sig_point) {
-            panic!("Signature not in G1 subgroup");
-        }
-
-        // Construct message for unchained quicknet: SHA256(round_number)
-        // Per official drand implementation: sha256(abi.encodePacked(roundNumber))
-        let round_bytes: [u8; 8] = round.to_be_bytes();
-        let mut round_bytes_soroban = Bytes::new(env);
-        for byte in round_bytes.iter() {
-            round_bytes_soroban.push_back(*byte);
-        }
-
-        // SHA256 hash the round number to get the message (32 bytes)
-        let message_hash = env.crypto().sha256(&round_bytes_soroban);
-
-        // Convert BytesN<32> to Bytes for hash_to_g1
-        let message_bytes_n = message_hash.to_bytes();
-        let mut message = Bytes::new(env);
-        for i in 0..32 {
-            message.push_back(message_bytes_n.get(i).unwrap());
-        }
-
-        // Hash message to G1 using drand quicknet DST
-        // DST: "BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_"
-        // Note: Uses G1 because quicknet uses G1-G2 swap (signatures on G1, public keys on G2)
-        let dst = Bytes::from_slice(env, b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_");
-
-        let hashed_point = env.crypto().bls12_381().hash_to_g1(&message, &dst);
-
-        // Subgroup check on hashed point (should always pass for hash_to_g1, but verify)
-        if !env.crypto().bls12_381().g1_is_in_subgroup(&hashed_point) {
-            panic!("Hashed point not in G1 subgroup");
-        }
-
-        // Fetch drand public key (192 bytes uncompressed G2 affine)
-        let drand_pubkey_bytes: Bytes = env
-            .storage()
-            .instance()
-            .get(&DataKey::DrandPublicKey)
-            .expect("Drand public key not configured");
-
-        if drand_pubkey_bytes.len() != 192 {
-            panic!("Drand public key must be 192 bytes (uncompressed G2 affine)");
-        }
-
-        // Construct G2Affine from public key bytes
-        // Convert Bytes to BytesN<192>
-        let pubkey_bytes: BytesN<192> = drand_pubkey_bytes
-            .try_into()
-            .unwrap_or_else(|_| panic!("Public key must be exactly 192 bytes"));
-        let drand_pubkey = G2Affine::from_bytes(pubkey_bytes);
-
-        // Subgroup check on public key
-        if !env.crypto().bls12_381().g2_is_in_subgroup(&drand_pubkey) {
-            panic!("Public key not in G2 subgroup");
-        }
-
-        // G2 generator (standard BLS12-381 G2 generator, uncompressed 192 bytes)
-        // Format: x_c1 || x_c0 || y_c1 || y_c0 (CAP-0059 byte order)
-        // Reference: IETF draft-irtf-cfrg-pairing-friendly-curves
-        let g2_gen_bytes: BytesN<192> = BytesN::from_array(
-            env,
-            &[
-                // x_c1 (48 bytes)
-                0x13, 0xe0, 0x2b, 0x60, 0x52, 0x71, 0x9f, 0x60, 0x7d, 0xac, 0xd3, 0xa0, 0x88, 0x27,
-                0x4f, 0x65, 0x59, 0x6b, 0xd0, 0xd0, 0x99, 0x20, 0xb6, 0x1a, 0xb5, 0xda, 0x61, 0xbb,
-                0xdc, 0x7f, 0x50, 0x49, 0x33, 0x4c, 0xf1, 0x12, 0x13, 0x94, 0x5d, 0x57, 0xe5, 0xac,
-                0x7d, 0x05, 0x5d, 0x04, 0x2b, 0x7e, // x_c0 (48 bytes)
-                0x02, 0x4a, 0xa2, 0xb2, 0xf0, 0x8f, 0x0a, 0x91, 0x26, 0x08, 0x05, 0x27, 0x2d, 0xc5,
-                0x10, 0x51, 0xc6, 0xe4, 0x7a, 0xd4, 0xfa, 0x40, 0x3b, 0x02, 0xb4, 0x51, 0x0b, 0x64,
-                0x7a, 0xe3, 0xd1, 0x77, 0x0b, 0xac, 0x03, 0x26, 0xa8, 0x05, 0xbb, 0xef, 0xd4, 0x80,
-                0x56, 0xc8, 0xc1, 0x21, 0xbd, 0xb8, // y_c1 (48 bytes)
-                0x06, 0x06, 0xc4, 0xa0, 0x2e, 0xa7, 0x34, 0xcc, 0x32, 0xac, 0xd2, 0xb0, 0x2b, 0xc2,
-                0x8b, 0x99, 0xcb, 0x3e, 0x28, 0x7e, 0x85, 0xa7, 0x63, 0xaf, 0x26, 0x74, 0x92, 0xab,
-                0x57, 0x2e, 0x99, 0xab, 0x3f, 0x37, 0x0d, 0x27, 0x5c, 0xec, 0x1d, 0xa1, 0xaa, 0xa9,
-                0x07, 0x5f, 0xf0, 0x5f, 0x79, 0xbe, // y_c0 (48 bytes)
-                0x0c, 0xe5, 0xd5, 0x27, 0x72, 0x7d, 0x6e, 0x11, 0x8c, 0xc9, 0xcd, 0xc6, 0xda, 0x2e,
-                0x35, 0x1a, 0xad, 0xfd, 0x9b, 0xaa, 0x8c, 0xbd, 0xd3, 0xa7, 0x6d, 0x42, 0x9a, 0x69,
-                0x51, 0x60, 0xd1, 0x2c, 0x92, 0x3a, 0xc9, 0xcc, 0x3b, 0xac, 0xa2, 0x89, 0xe1, 0x93,
-                0x54, 0x86, 0x08, 0xb8, 0x28, 0x01,
-            ],
-        );
-
-        let g2_gen = G2Affine::from_bytes(g2_gen_bytes);
-
-        // Construct vectors for pairing check
-        // Verify: e(signature, G2_gen) == e(H(msg), pubkey)
-        // Using pairing_check: e(-sig, G2_gen) * e(H(msg), pubkey) == 1
-        let mut g1_points = Vec::new(env);
-        g1_points.push_back(neg_sig_point); // -signature (negated earlier)
-        g1_points.push_back(hashed_point); // H(msg)
-
-        let mut g2_points = Vec::new(env);
-        g2_points.push_back(g2_gen); // G2 generator
-        g2_points.push_back(drand_pubkey); // drand public key
+    ///
+    /// Steps 1-2 and 3-9 are also exposed separately for two-phase verification
+    /// (see the `verification` module); `diagnosis` holds the stages themselves
+    pub fn verify_drand_signature(env: &Env, round: u64, signature: &BytesN<96>) {
+        let neg_sig_point = Self::negated_signature_point(env, signature);
+        Self::verify_beacon_pairing(env, round, neg_sig_point);
+    }
 
-        // Perform pairing check: e(-sig, G2_gen) * e(H(msg), pubkey) == 1
-        let valid = env.crypto().bls12_381().pairing_check(g1_points, g2_points);
+    /// Steps 1-2: deserialize the negated signature and check it is in the G1 subgroup
+    pub(crate) fn negated_signature_point(env: &Env, signature: &BytesN<96>) -> G1Affine {
+        diagnosis::signature_stage(env, signature)
+            .unwrap_or_else(|stage| diagnosis::fail(env, stage))
+    }
 
-        if !valid {
-            panic!("BLS12-381 pairing verification failed");
+    /// Steps 3-9: hash the round to G1 and run the pairing check against the drand public key
+    /// `neg_sig_point` must come from `negated_signature_point`
+    pub(crate) fn verify_beacon_pairing(env: &Env, round: u64, neg_sig_point: G1Affine) {
+        let drand_pubkey_bytes: Bytes = require_configured(env, &DataKey::DrandPublicKey);
+        let verified = diagnosis::pairing_stages(env, round, neg_sig_point, drand_pubkey_bytes);
+        if let Err(stage) = verified {
+            diagnosis::fail(env, stage);
         }
     }
 }
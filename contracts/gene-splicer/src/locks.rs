@@ -0,0 +1,136 @@
+//! Creature lock registry
+//!
+//! Listings, staking, swaps, exports and delegation each need to restrict
+//! what can happen to a creature, and separate flags per subsystem would let
+//! incompatible states pile up (listed and staked at once). Every such
+//! restriction is instead a `Lock` in one per-creature list. A subsystem
+//! acquires a lock of its kind through `acquire_lock`, which refuses it with
+//! `LockConflict` if a live lock of a conflicting kind exists (see
+//! `CONFLICTS`). Two locks of the same kind always conflict.
+//!
+//! A lock lapses on its own at `expires` (0 = held until released), so an
+//! abandoned listing or swap never strands a creature. Expired entries are
+//! pruned whenever the list is written. Burns and transfers consult the whole
+//! registry once through `require_unlocked` rather than each subsystem's state.
+
+use crate::{
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Symbol, Vec};
+
+/// Lock kinds subsystems acquire
+pub const LOCK_KINDS: [&str; 5] = ["listed", "staked", "swap", "export", "delegated"];
+
+/// Pairs of kinds that can't be held on the same creature at once
+/// Delegation only lends use of a creature, so it coexists with a listing or stake
+pub const CONFLICTS: [(&str, &str); 8] = [
+    ("listed", "staked"),
+    ("listed", "swap"),
+    ("listed", "export"),
+    ("staked", "swap"),
+    ("staked", "export"),
+    ("swap", "export"),
+    ("swap", "delegated"),
+    ("export", "delegated"),
+];
+
+/// A restriction one subsystem holds on a creature
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lock {
+    pub kind: Symbol,
+    pub holder: Address, // Who may release it: the buyer, staking pool, delegate, ...
+    pub expires: u64,    // Ledger timestamp the lock lapses at; 0 = held until released
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get the live locks on a creature
+    pub fn get_locks(env: Env, creature_id: u32) -> Vec<Lock> {
+        live_locks(&env, creature_id)
+    }
+}
+
+/// Take a `kind` lock on the creature for `holder`
+/// Fails with LockConflict if a live lock of the same or a conflicting kind is held
+pub(crate) fn acquire_lock(
+    env: &Env,
+    creature_id: u32,
+    kind: &str,
+    holder: &Address,
+    expires: u64,
+) -> Result<(), Error> {
+    let mut locks = live_locks(env, creature_id);
+    let kind = Symbol::new(env, kind);
+    if locks.iter().any(|lock| conflicts(env, &lock.kind, &kind)) {
+        return Err(Error::LockConflict);
+    }
+    locks.push_back(Lock {
+        kind,
+        holder: holder.clone(),
+        expires,
+    });
+    write_locks(env, creature_id, &locks);
+    Ok(())
+}
+
+/// Drop `holder`'s `kind` lock on the creature; returns whether a live one was held
+/// Test-only until a subsystem takes a lock it can give back (export locks are permanent)
+#[cfg(test)]
+pub(crate) fn release_lock(env: &Env, creature_id: u32, kind: &str, holder: &Address) -> bool {
+    let mut locks = live_locks(env, creature_id);
+    let kind = Symbol::new(env, kind);
+    let position = locks
+        .iter()
+        .position(|lock| lock.kind == kind && lock.holder == *holder);
+    if let Some(position) = position {
+        locks.remove(position as u32);
+    }
+    write_locks(env, creature_id, &locks);
+    position.is_some()
+}
+
+/// Fail with CreatureLocked while any live lock is held on the creature
+pub(crate) fn require_unlocked(env: &Env, creature_id: u32) -> Result<(), Error> {
+    if live_locks(env, creature_id).is_empty() {
+        Ok(())
+    } else {
+        Err(Error::CreatureLocked)
+    }
+}
+
+fn conflicts(env: &Env, held: &Symbol, wanted: &Symbol) -> bool {
+    held == wanted
+        || CONFLICTS.iter().any(|(a, b)| {
+            let (a, b) = (Symbol::new(env, a), Symbol::new(env, b));
+            (*held == a && *wanted == b) || (*held == b && *wanted == a)
+        })
+}
+
+fn live_locks(env: &Env, creature_id: u32) -> Vec<Lock> {
+    let now = env.ledger().timestamp();
+    let locks: Vec<Lock> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Locks(creature_id))
+        .unwrap_or(Vec::new(env));
+    let mut live = Vec::new(env);
+    for lock in locks.iter() {
+        if lock.expires == 0 || now < lock.expires {
+            live.push_back(lock);
+        }
+    }
+    live
+}
+
+fn write_locks(env: &Env, creature_id: u32, locks: &Vec<Lock>) {
+    let key = DataKey::Locks(creature_id);
+    if locks.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, locks);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+}
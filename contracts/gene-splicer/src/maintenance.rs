@@ -0,0 +1,102 @@
+//! Announced maintenance windows
+//!
+//! Before an upgrade the Owner schedules a window instead of pausing without
+//! warning. Clients read it with `get_maintenance` and can show "maintenance
+//! at T" ahead of time. While the window is open, every mint path and
+//! finalization fails with MaintenanceInProgress; `get_maintenance` then tells
+//! them when it ends. A window that has ended is ignored and reads as None.
+//!
+//! Scheduling replaces any earlier window. Like pausing, it keeps working after
+//! the configuration freeze unless operations were locked.
+
+use crate::{
+    config_log, events,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{contractevent, contractimpl, contracttype, panic_with_error, Env, Symbol};
+
+/// A scheduled maintenance window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaintenanceWindow {
+    pub start: u64, // Mints and finalization are refused from this timestamp...
+    pub end: u64,   // ...until this one (exclusive)
+    pub note: Symbol,
+}
+
+/// Event emitted when the Owner schedules a maintenance window
+#[contractevent]
+pub struct MaintenanceScheduled {
+    pub start: u64,
+    pub end: u64,
+    pub note: Symbol,
+    pub nonce: u64,
+}
+
+/// Event emitted when the Owner cancels a scheduled maintenance window
+#[contractevent]
+pub struct MaintenanceCancelled {
+    pub start: u64,
+    pub end: u64,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Schedule a maintenance window, replacing any earlier one (Owner only)
+    /// Refused with InvalidMaintenanceWindow unless start < end and the window hasn't ended
+    pub fn schedule_maintenance(env: Env, start: u64, end: u64, note: Symbol) {
+        let actor = require_role(&env, Role::Owner);
+        if start >= end || end <= env.ledger().timestamp() {
+            panic_with_error!(&env, Error::InvalidMaintenanceWindow);
+        }
+        let window = MaintenanceWindow {
+            start,
+            end,
+            note: note.clone(),
+        };
+        env.storage().instance().set(&DataKey::Maintenance, &window);
+        config_log::record(&env, &actor, "maintenance", Some(window));
+
+        events::publish(&env, |nonce| MaintenanceScheduled {
+            start,
+            end,
+            note,
+            nonce,
+        });
+    }
+
+    /// Cancel the scheduled or running maintenance window (Owner only)
+    pub fn cancel_maintenance(env: Env) {
+        let actor = require_role(&env, Role::Owner);
+        let window = Self::get_maintenance(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NoMaintenanceScheduled));
+        env.storage().instance().remove(&DataKey::Maintenance);
+        config_log::record(&env, &actor, "maintenance", None::<MaintenanceWindow>);
+
+        events::publish(&env, |nonce| MaintenanceCancelled {
+            start: window.start,
+            end: window.end,
+            nonce,
+        });
+    }
+
+    /// Get the upcoming or running maintenance window (None once it has ended)
+    pub fn get_maintenance(env: Env) -> Option<MaintenanceWindow> {
+        env.storage()
+            .instance()
+            .get::<_, MaintenanceWindow>(&DataKey::Maintenance)
+            .filter(|window| window.end > env.ledger().timestamp())
+    }
+}
+
+/// MaintenanceInProgress while a window is open
+pub(crate) fn check(env: &Env) -> Result<(), Error> {
+    match GeneSplicer::get_maintenance(env.clone()) {
+        Some(window) if window.start <= env.ledger().timestamp() => {
+            Err(Error::MaintenanceInProgress)
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,218 @@
+//! On-chain commitments to off-chain creature metadata
+//!
+//! The renderer produces a creature's artwork and metadata JSON off-chain. A
+//! configured metadata operator anchors the SHA-256 of that JSON once per
+//! creature, so clients can check fetched metadata against the chain. An
+//! anchored hash never changes silently: a correction is requested by the
+//! operator, takes effect only when the Owner approves it, and the replaced
+//! hash is kept in the creature's metadata provenance.
+
+use crate::{
+    config_log, events,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, contracttype, panic_with_error, Address, BytesN,
+    Env, Vec,
+};
+
+/// Metadata anchoring failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MetadataError {
+    NotMetadataOperator = 30, // Caller isn't the configured metadata operator
+    MetadataAlreadyAnchored = 31, // Metadata hash is set; changes go through a correction
+    MetadataNotAnchored = 32, // Creature has no anchored metadata hash to correct
+    NoMetadataCorrection = 33, // No correction awaits approval for the creature
+}
+
+/// A metadata hash that was anchored for a creature and later corrected
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetadataRevision {
+    pub metadata_hash: BytesN<32>, // The replaced hash
+    pub replaced_at: u64,          // Ledger timestamp of the approved correction
+}
+
+/// Event emitted when a creature's metadata hash is first anchored
+#[contractevent]
+pub struct MetadataAnchored {
+    pub creature_id: u32,
+    pub metadata_hash: BytesN<32>,
+    pub nonce: u64,
+}
+
+/// Event emitted when the metadata operator asks to replace an anchored hash
+#[contractevent]
+pub struct MetadataCorrectionRequested {
+    pub creature_id: u32,
+    pub metadata_hash: BytesN<32>,
+    pub nonce: u64,
+}
+
+/// Event emitted when the Owner approves a correction
+#[contractevent]
+pub struct MetadataCorrected {
+    pub creature_id: u32,
+    pub previous_hash: BytesN<32>,
+    pub metadata_hash: BytesN<32>,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Assign (or clear with None) the address allowed to anchor metadata (Owner only)
+    pub fn set_metadata_operator(env: Env, operator: Option<Address>) {
+        let actor = require_role(&env, Role::Owner);
+        match &operator {
+            Some(address) => env
+                .storage()
+                .instance()
+                .set(&DataKey::MetadataOperator, address),
+            None => env.storage().instance().remove(&DataKey::MetadataOperator),
+        }
+        config_log::record(&env, &actor, "metadata_operator", operator);
+    }
+
+    /// Get the metadata operator, if one is assigned
+    pub fn get_metadata_operator(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::MetadataOperator)
+    }
+
+    /// Anchor the SHA-256 of a creature's metadata JSON (metadata operator only, once per creature)
+    pub fn anchor_metadata(
+        env: Env,
+        operator: Address,
+        creature_id: u32,
+        metadata_hash: BytesN<32>,
+    ) {
+        require_metadata_operator(&env, &operator);
+        require_creature(&env, creature_id);
+
+        let key = DataKey::MetadataHash(creature_id);
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(&env, MetadataError::MetadataAlreadyAnchored);
+        }
+        env.storage().persistent().set(&key, &metadata_hash);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        events::publish(&env, |nonce| MetadataAnchored {
+            creature_id,
+            metadata_hash,
+            nonce,
+        });
+    }
+
+    /// Ask to replace a creature's anchored hash (metadata operator only)
+    /// Takes effect once the Owner calls `approve_metadata_correction`; asking again replaces the request
+    pub fn request_metadata_correction(
+        env: Env,
+        operator: Address,
+        creature_id: u32,
+        metadata_hash: BytesN<32>,
+    ) {
+        require_metadata_operator(&env, &operator);
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::MetadataHash(creature_id))
+        {
+            panic_with_error!(&env, MetadataError::MetadataNotAnchored);
+        }
+        let key = DataKey::MetadataCorrection(creature_id);
+        env.storage().persistent().set(&key, &metadata_hash);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        events::publish(&env, |nonce| MetadataCorrectionRequested {
+            creature_id,
+            metadata_hash,
+            nonce,
+        });
+    }
+
+    /// Apply a requested correction, moving the old hash into provenance (Owner only)
+    pub fn approve_metadata_correction(env: Env, creature_id: u32) {
+        require_role(&env, Role::Owner);
+
+        let correction_key = DataKey::MetadataCorrection(creature_id);
+        let metadata_hash: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&correction_key)
+            .unwrap_or_else(|| panic_with_error!(&env, MetadataError::NoMetadataCorrection));
+        let hash_key = DataKey::MetadataHash(creature_id);
+        let previous_hash: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&hash_key)
+            .unwrap_or_else(|| panic_with_error!(&env, MetadataError::MetadataNotAnchored));
+
+        let mut provenance = Self::get_metadata_provenance(env.clone(), creature_id);
+        provenance.push_back(MetadataRevision {
+            metadata_hash: previous_hash.clone(),
+            replaced_at: env.ledger().timestamp(),
+        });
+        let provenance_key = DataKey::MetadataProvenance(creature_id);
+        env.storage().persistent().set(&provenance_key, &provenance);
+        env.storage()
+            .persistent()
+            .extend_ttl(&provenance_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.storage().persistent().set(&hash_key, &metadata_hash);
+        env.storage()
+            .persistent()
+            .extend_ttl(&hash_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.storage().persistent().remove(&correction_key);
+
+        events::publish(&env, |nonce| MetadataCorrected {
+            creature_id,
+            previous_hash,
+            metadata_hash,
+            nonce,
+        });
+    }
+
+    /// Get a creature's anchored metadata hash
+    pub fn get_metadata_hash(env: Env, creature_id: u32) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MetadataHash(creature_id))
+    }
+
+    /// Get a creature's pending metadata correction, if one awaits approval
+    pub fn get_metadata_correction(env: Env, creature_id: u32) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MetadataCorrection(creature_id))
+    }
+
+    /// Get the hashes a creature's metadata anchor replaced, oldest first
+    pub fn get_metadata_provenance(env: Env, creature_id: u32) -> Vec<MetadataRevision> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MetadataProvenance(creature_id))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+fn require_metadata_operator(env: &Env, operator: &Address) {
+    operator.require_auth();
+    if GeneSplicer::get_metadata_operator(env.clone()).as_ref() != Some(operator) {
+        panic_with_error!(env, MetadataError::NotMetadataOperator);
+    }
+}
+
+fn require_creature(env: &Env, creature_id: u32) {
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::Creature(creature_id))
+    {
+        panic_with_error!(env, Error::CreatureNotFound);
+    }
+}
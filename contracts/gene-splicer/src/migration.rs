@@ -0,0 +1,263 @@
+//! Creature migration between contract deployments
+//!
+//! A clean-slate redeploy (new ID scheme, new storage layout) moves creatures
+//! through a voucher instead of a manual re-mint. `export_asset` takes a
+//! permanent `export` lock on the creature here, so it can no longer be
+//! listed, staked, retired or transferred and drops out of voting weight. It
+//! returns an `ExportVoucher` carrying the creature's data and a commitment:
+//!
+//! ```text
+//! commitment = SHA256(XDR((source, creature, exported_at)))
+//! ```
+//!
+//! The owner presents the voucher to the new deployment's `import_asset`. The
+//! new deployment only accepts vouchers from sources its Owner marked trusted.
+//! It recomputes the commitment and asks the source's `confirm_export` whether
+//! it really issued it, since the voucher itself is just data anyone could
+//! forge. It then mints an equivalent creature with the same genes, skin,
+//! entropy round and timestamps. Each exported creature imports once.
+//!
+//! Imported creatures have no cartridge, so they take IDs from their own
+//! counter starting at `IMPORTED_ID_BASE`, far above any cartridge ID.
+//! `get_import_origin` links one back to the source contract and ID.
+
+use crate::{
+    account_auth, config_log, events, locks, retirement, reveal,
+    roles::{require_role, Role},
+    store_creature, Creature, DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+    TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, contracttype, panic_with_error, xdr::ToXdr,
+    Address, BytesN, Env,
+};
+
+/// First ID given to an imported creature
+pub const IMPORTED_ID_BASE: u32 = 1 << 31;
+
+/// Export and import failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MigrationError {
+    UntrustedSource = 84, // import_asset from a contract the Owner hasn't marked trusted
+    InvalidExportVoucher = 85, // Voucher doesn't match its commitment or the source didn't issue it
+    AlreadyImported = 86, // The voucher's creature was already imported here
+}
+
+/// A creature exported from `source`, redeemable once on a deployment that trusts it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportVoucher {
+    pub source: Address,        // Contract the creature was exported from
+    pub creature: Creature,     // The creature as stored there, genes included
+    pub exported_at: u64,       // Ledger timestamp of the export
+    pub commitment: BytesN<32>, // SHA256 over (source, creature, exported_at)
+}
+
+/// Where an imported creature came from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportOrigin {
+    pub source: Address,
+    pub source_creature_id: u32,
+    pub commitment: BytesN<32>,
+}
+
+/// Event emitted when an owner exports a creature for migration
+#[contractevent]
+pub struct AssetExported {
+    pub creature_id: u32,
+    pub owner: Address,
+    pub commitment: BytesN<32>,
+    pub nonce: u64,
+}
+
+/// Event emitted when an exported creature is imported from a trusted source
+#[contractevent]
+pub struct AssetImported {
+    pub creature_id: u32,
+    pub source: Address,
+    pub source_creature_id: u32,
+    pub owner: Address,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Lock a creature here for good and get the voucher to import it elsewhere
+    /// Waits for the collection reveal, since the voucher carries the genes
+    pub fn export_asset(env: Env, owner: Address, creature_id: u32) -> ExportVoucher {
+        account_auth::require_export(&env, &owner, creature_id);
+        reveal::require_revealed(&env);
+
+        let creature: Creature = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Creature(creature_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CreatureNotFound));
+        if creature.owner != owner {
+            panic_with_error!(&env, Error::NotCreatureOwner);
+        }
+        retirement::assert_active(&env, creature_id);
+        // Conflicts with every other lock kind, including an earlier export
+        let source = env.current_contract_address();
+        if let Err(error) = locks::acquire_lock(&env, creature_id, "export", &source, 0) {
+            panic_with_error!(&env, error);
+        }
+
+        let exported_at = env.ledger().timestamp();
+        let commitment = commitment(&env, &source, &creature, exported_at);
+        let key = DataKey::Exported(creature_id);
+        env.storage().persistent().set(&key, &commitment);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        events::publish(&env, |nonce| AssetExported {
+            creature_id,
+            owner,
+            commitment: commitment.clone(),
+            nonce,
+        });
+        ExportVoucher {
+            source,
+            creature,
+            exported_at,
+            commitment,
+        }
+    }
+
+    /// Whether this contract exported `creature_id` with `commitment`
+    /// Called by importing deployments before they honour a voucher
+    pub fn confirm_export(env: Env, creature_id: u32, commitment: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, BytesN<32>>(&DataKey::Exported(creature_id))
+            .is_some_and(|exported| exported == commitment)
+    }
+
+    /// Mint the creature a voucher from `source_contract` describes to its owner
+    /// Returns the new creature ID
+    pub fn import_asset(
+        env: Env,
+        owner: Address,
+        voucher: ExportVoucher,
+        source_contract: Address,
+    ) -> u32 {
+        owner.require_auth();
+
+        if !Self::is_trusted_source(env.clone(), source_contract.clone()) {
+            panic_with_error!(&env, MigrationError::UntrustedSource);
+        }
+        let source_creature_id = voucher.creature.id;
+        if voucher.source != source_contract
+            || voucher.commitment
+                != commitment(
+                    &env,
+                    &source_contract,
+                    &voucher.creature,
+                    voucher.exported_at,
+                )
+        {
+            panic_with_error!(&env, MigrationError::InvalidExportVoucher);
+        }
+        if voucher.creature.owner != owner {
+            panic_with_error!(&env, Error::NotCreatureOwner);
+        }
+        let imported_key = DataKey::ImportedAsset(source_contract.clone(), source_creature_id);
+        if env.storage().persistent().has(&imported_key) {
+            panic_with_error!(&env, MigrationError::AlreadyImported);
+        }
+        if !GeneSplicerClient::new(&env, &source_contract)
+            .confirm_export(&source_creature_id, &voucher.commitment)
+        {
+            panic_with_error!(&env, MigrationError::InvalidExportVoucher);
+        }
+
+        let creature_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextImportedId)
+            .unwrap_or(IMPORTED_ID_BASE);
+        env.storage().instance().set(
+            &DataKey::NextImportedId,
+            &creature_id.checked_add(1).expect("Imported ID overflow"),
+        );
+
+        env.storage().persistent().set(&imported_key, &creature_id);
+        env.storage()
+            .persistent()
+            .extend_ttl(&imported_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        let origin_key = DataKey::ImportOrigin(creature_id);
+        env.storage().persistent().set(
+            &origin_key,
+            &ImportOrigin {
+                source: source_contract.clone(),
+                source_creature_id,
+                commitment: voucher.commitment,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&origin_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        store_creature(
+            &env,
+            &Creature {
+                id: creature_id,
+                ..voucher.creature
+            },
+        );
+
+        events::publish(&env, |nonce| AssetImported {
+            creature_id,
+            source: source_contract,
+            source_creature_id,
+            owner,
+            nonce,
+        });
+        creature_id
+    }
+
+    /// Trust or distrust a contract as a source of imported creatures (Owner only)
+    pub fn set_trusted_source(env: Env, source: Address, trusted: bool) {
+        let actor = require_role(&env, Role::Owner);
+        let key = DataKey::TrustedSource(source.clone());
+        if trusted {
+            env.storage().persistent().set(&key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        config_log::record(&env, &actor, "trusted_source", (source, trusted));
+    }
+
+    /// Whether vouchers exported by `source` are accepted here
+    pub fn is_trusted_source(env: Env, source: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::TrustedSource(source))
+    }
+
+    /// Get the source contract and ID an imported creature was migrated from
+    pub fn get_import_origin(env: Env, creature_id: u32) -> Option<ImportOrigin> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ImportOrigin(creature_id))
+    }
+}
+
+/// Whether the creature was exported and is locked here for good
+pub(crate) fn is_exported(env: &Env, creature_id: u32) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Exported(creature_id))
+}
+
+fn commitment(env: &Env, source: &Address, creature: &Creature, exported_at: u64) -> BytesN<32> {
+    let preimage = (source.clone(), creature.clone(), exported_at).to_xdr(env);
+    env.crypto().sha256(&preimage).into()
+}
@@ -0,0 +1,85 @@
+//! Configurable mint fee and treasury
+//!
+//! The constructor sets the original collection's mint fee and the treasury
+//! that receives contract revenue; the Owner can change either afterwards. The
+//! fee is the base every original-collection price is derived from: splice
+//! routes, bulk quotes, points discounts and storefront debt all read it at
+//! mint time, so a change applies from the next mint on. A fee of zero makes
+//! minting free and skips the token transfer entirely.
+//!
+//! Mint fees (every collection), storefront settlements and consumer read fees
+//! are paid to the treasury, which keeps proceeds apart from the operational
+//! admin key. Deployments from before the treasury existed pay the admin, and
+//! charge `MINT_FEE`, until the Owner sets their own.
+
+use crate::{
+    config_log, events, require_configured,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, MINT_FEE,
+};
+use soroban_sdk::{contractevent, contractimpl, panic_with_error, Address, Env};
+
+/// Event emitted when the mint fee changes, for tracking pricing history
+#[contractevent]
+pub struct MintFeeUpdated {
+    pub old_fee: i128,
+    pub new_fee: i128,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set the original collection's mint fee in stroops (Owner only, 0 = free)
+    /// Refused with InvalidAmount when negative
+    pub fn set_mint_fee(env: Env, fee: i128) {
+        let actor = require_role(&env, Role::Owner);
+        if fee < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let old_fee = Self::get_mint_fee(env.clone());
+        env.storage().instance().set(&DataKey::MintFee, &fee);
+        config_log::record(&env, &actor, "mint_fee", fee);
+        events::publish(&env, |nonce| MintFeeUpdated {
+            old_fee,
+            new_fee: fee,
+            nonce,
+        });
+    }
+
+    /// Get the original collection's mint fee in stroops
+    pub fn get_mint_fee(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MintFee)
+            .unwrap_or(MINT_FEE)
+    }
+
+    /// Set the address that receives mint fees and other proceeds (Owner only)
+    pub fn set_treasury(env: Env, treasury: Address) {
+        let actor = require_role(&env, Role::Owner);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        config_log::record(&env, &actor, "treasury", treasury);
+    }
+
+    /// Get the address that receives mint fees and other proceeds
+    pub fn get_treasury(env: Env) -> Address {
+        treasury(&env)
+    }
+}
+
+/// Recipient of contract revenue: the treasury, or the admin if none was ever set
+pub(crate) fn treasury(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::Treasury)
+        .unwrap_or_else(|| require_configured(env, &DataKey::Admin))
+}
+
+/// Validate and store the constructor's fee settings
+pub(crate) fn init(env: &Env, fee: i128, treasury: &Address) {
+    if fee < 0 {
+        panic_with_error!(env, Error::InvalidAmount);
+    }
+    env.storage().instance().set(&DataKey::MintFee, &fee);
+    env.storage().instance().set(&DataKey::Treasury, treasury);
+}
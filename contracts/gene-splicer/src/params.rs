@@ -0,0 +1,81 @@
+//! Canonical parsing of raw byte parameters
+//!
+//! Entrypoints that take drand material as raw `Bytes` convert it here, once,
+//! before doing anything else. A wrong length is rejected with the same error
+//! code whichever entrypoint received it, and everything downstream works with
+//! fixed-size `BytesN` values that cannot be malformed.
+
+use crate::Error;
+use soroban_sdk::{panic_with_error, Bytes, BytesN, Env};
+
+/// drand randomness: SHA256 of the compressed signature (32 bytes)
+pub fn parse_randomness(bytes: Bytes) -> Result<BytesN<32>, Error> {
+    bytes.try_into().map_err(|_| Error::InvalidRandomnessLength)
+}
+
+/// Uncompressed BLS12-381 G1 signature: x || y (96 bytes)
+pub fn parse_signature_g1(bytes: Bytes) -> Result<BytesN<96>, Error> {
+    bytes.try_into().map_err(|_| Error::InvalidSignatureLength)
+}
+
+/// Compressed BLS12-381 G1 signature, as published by drand (48 bytes)
+pub fn parse_signature_g1_compressed(bytes: Bytes) -> Result<BytesN<48>, Error> {
+    bytes.try_into().map_err(|_| Error::InvalidSignatureLength)
+}
+
+/// Uncompressed BLS12-381 G2 public key (192 bytes)
+pub fn parse_pubkey_g2(bytes: Bytes) -> Result<BytesN<192>, Error> {
+    bytes.try_into().map_err(|_| Error::InvalidPublicKeyLength)
+}
+
+/// drand chain hash (32 bytes); any other length cannot name the configured network
+pub fn parse_chain_hash(bytes: Bytes) -> Result<BytesN<32>, Error> {
+    bytes.try_into().map_err(|_| Error::WrongDrandNetwork)
+}
+
+/// Unwrap a parsed parameter, failing the invocation with its error code
+pub(crate) fn require<T>(env: &Env, parsed: Result<T, Error>) -> T {
+    parsed.unwrap_or_else(|error| panic_with_error!(env, error))
+}
+
+/// A drand beacon as submitted to the finalize entrypoints, already length-checked
+pub(crate) struct Beacon {
+    pub randomness: BytesN<32>,
+    pub signature_compressed: BytesN<48>,
+    pub signature_uncompressed: BytesN<96>,
+    pub chain_hash: Option<BytesN<32>>,
+}
+
+/// Parse the raw beacon parameters shared by the finalize entrypoints
+pub(crate) fn parse_beacon(
+    randomness: Bytes,
+    signature_compressed: Bytes,
+    signature_uncompressed: Bytes,
+    chain_hash: Option<Bytes>,
+) -> Result<Beacon, Error> {
+    Ok(Beacon {
+        randomness: parse_randomness(randomness)?,
+        signature_compressed: parse_signature_g1_compressed(signature_compressed)?,
+        signature_uncompressed: parse_signature_g1(signature_uncompressed)?,
+        chain_hash: chain_hash.map(parse_chain_hash).transpose()?,
+    })
+}
+
+/// `parse_beacon`, failing the invocation on a malformed parameter
+pub(crate) fn require_beacon(
+    env: &Env,
+    randomness: Bytes,
+    signature_compressed: Bytes,
+    signature_uncompressed: Bytes,
+    chain_hash: Option<Bytes>,
+) -> Beacon {
+    require(
+        env,
+        parse_beacon(
+            randomness,
+            signature_compressed,
+            signature_uncompressed,
+            chain_hash,
+        ),
+    )
+}
@@ -0,0 +1,165 @@
+//! Global index of unfinalized cartridges
+//!
+//! The relayer polls this instead of listing every user's cartridges and
+//! fetching each one. Every path that starts or ends a cartridge's pending
+//! life (mint, finalize, and any future cancel or expiry) goes through
+//! `set_pending`.
+//!
+//! Cartridges that are never finalized stay pending for good, so the index
+//! can't be one list rewritten on every mint. It is split by ID range into
+//! chunks of `PENDING_INDEX_CHUNK`, each an ascending list of the pending IDs
+//! in its range, between a head and a tail chunk index, so a mint or finalize
+//! only touches the chunk its ID falls in. Emptied chunks are removed, and the
+//! bounds only move when the head or tail chunk empties, so chunks between
+//! them may be empty; a page read skips those, up to `MAX_PENDING_SCAN`
+//! chunks per call.
+
+use crate::{
+    privacy, DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge,
+    MAX_PENDING_PAGE, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+/// Cartridge ID range covered by one pending index chunk
+pub const PENDING_INDEX_CHUNK: u32 = 100;
+
+// Chunks a get_pending_details page reads at most, empty or not
+// Keeps a page's reads (cartridges, privacy flags, chunks) under the per-transaction footprint
+const MAX_PENDING_SCAN: u32 = 8;
+
+/// Compact view of an unfinalized cartridge for relayer polling
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingCartridge {
+    pub id: u32,
+    pub splice_round: u64, // Drand round the cartridge waits for
+    pub owner: Address,
+    pub created_at: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Page through unfinalized cartridges in ascending ID order
+    /// - Returns pending cartridges with ID >= `cursor`, reading at most `limit` index entries
+    ///   (capped at Limits.max_pending_page)
+    /// - The second value is the cursor for the next page, or 0 once the index is exhausted
+    /// - Indexed IDs with no cartridge record are skipped, and a page stops after reading
+    ///   MAX_PENDING_SCAN index chunks, so a page can come back short before the end
+    /// - Owners who opted out with set_privacy show as the contract address; get_cartridge has the real one
+    pub fn get_pending_details(env: Env, cursor: u32, limit: u32) -> (Vec<PendingCartridge>, u32) {
+        let (head, tail) = read_bounds(&env);
+        let limit = limit.min(MAX_PENDING_PAGE);
+
+        let mut redactor = privacy::Redactor::new(&env);
+        let mut page = Vec::new(&env);
+        let mut read = 0;
+        let mut chunk_index = (cursor / PENDING_INDEX_CHUNK).max(head);
+        let scan_end = chunk_index.saturating_add(MAX_PENDING_SCAN).min(tail);
+        while chunk_index < scan_end && read < limit {
+            let chunk = read_chunk(&env, chunk_index);
+            // Position of the first pending ID >= cursor
+            let start = match chunk.binary_search(cursor) {
+                Ok(pos) => pos,
+                Err(pos) => pos,
+            };
+            for pos in start..chunk.len() {
+                let id = chunk.get_unchecked(pos);
+                if read == limit {
+                    return (page, id);
+                }
+                read += 1;
+                // A stale index entry is skipped so one bad ID can't break relayer polling
+                let Some(cartridge) = env
+                    .storage()
+                    .persistent()
+                    .get::<_, GenomeCartridge>(&DataKey::Cartridge(id))
+                else {
+                    continue;
+                };
+                page.push_back(PendingCartridge {
+                    id,
+                    splice_round: cartridge.splice_round,
+                    owner: redactor.owner(&env, cartridge.owner),
+                    created_at: cartridge.created_at,
+                });
+            }
+            chunk_index += 1;
+        }
+
+        let next_cursor = if chunk_index < tail {
+            chunk_index * PENDING_INDEX_CHUNK
+        } else {
+            0
+        };
+        (page, next_cursor)
+    }
+}
+
+/// Add (`pending = true`) or remove a cartridge from the pending index
+/// Idempotent; touches only the chunk covering the ID, plus the bounds when they move
+pub(crate) fn set_pending(env: &Env, cartridge_id: u32, pending: bool) {
+    let chunk_index = cartridge_id / PENDING_INDEX_CHUNK;
+    let mut chunk = read_chunk(env, chunk_index);
+    match (chunk.binary_search(cartridge_id), pending) {
+        (Err(pos), true) => chunk.insert(pos, cartridge_id),
+        (Ok(pos), false) => chunk.remove_unchecked(pos),
+        _ => return,
+    }
+
+    let key = DataKey::PendingChunk(chunk_index);
+    let (head, tail) = read_bounds(env);
+    let bounds = if pending {
+        persist(env, &key, &chunk);
+        if head == tail {
+            (chunk_index, chunk_index + 1)
+        } else {
+            (head.min(chunk_index), tail.max(chunk_index + 1))
+        }
+    } else if chunk.is_empty() {
+        env.storage().persistent().remove(&key);
+        match (chunk_index == head, chunk_index + 1 == tail) {
+            (true, true) => (0, 0),
+            (true, false) => (head + 1, tail),
+            (false, true) => (head, tail - 1),
+            (false, false) => (head, tail),
+        }
+    } else {
+        persist(env, &key, &chunk);
+        (head, tail)
+    };
+
+    if bounds == (0, 0) {
+        env.storage().persistent().remove(&DataKey::PendingBounds);
+    } else if bounds != (head, tail) {
+        persist(env, &DataKey::PendingBounds, &bounds);
+    }
+}
+
+/// Whether a cartridge is in the pending index
+pub(crate) fn is_pending(env: &Env, cartridge_id: u32) -> bool {
+    read_chunk(env, cartridge_id / PENDING_INDEX_CHUNK)
+        .binary_search(cartridge_id)
+        .is_ok()
+}
+
+/// (head, tail) chunk indices; every non-empty chunk lies in head..tail
+fn read_bounds(env: &Env) -> (u32, u32) {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingBounds)
+        .unwrap_or((0, 0))
+}
+
+fn read_chunk(env: &Env, chunk_index: u32) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingChunk(chunk_index))
+        .unwrap_or(Vec::new(env))
+}
+
+fn persist<V: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey, value: &V) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
@@ -0,0 +1,116 @@
+//! Points-discounted checkout
+//!
+//! Users holding points can apply them to the mint fee: every `PointsPerStroop`
+//! points knock one stroop off, up to the whole fee, and only the remainder is
+//! charged in XLM. Points are debited in the same invocation as the XLM
+//! transfer and the mint, so a failure anywhere leaves both balances untouched.
+//! Only whole stroops are bought: points that don't add up to one stay with the
+//! user.
+
+use crate::{
+    config_log, events, features, fee_rounding, reentrancy,
+    roles::{require_role, Role},
+    DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contracterror, contractevent, contractimpl, panic_with_error, Address, Env};
+
+/// Points checkout failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PointsError {
+    PointsDisabled = 40,     // Points checkout while no points rate is configured
+    InsufficientPoints = 41, // User's points balance is below points_to_apply
+}
+
+/// Event emitted when a mint is paid partly or fully with points
+#[contractevent]
+pub struct PointsCheckout {
+    pub cartridge_id: u32,
+    pub user: Address,
+    pub points_spent: u64,
+    pub xlm_paid: i128,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set how many points buy one stroop of fee discount, or 0 to stop accepting points (Owner only)
+    pub fn set_points_rate(env: Env, points_per_stroop: u64) {
+        let actor = require_role(&env, Role::Owner);
+        env.storage()
+            .instance()
+            .set(&DataKey::PointsPerStroop, &points_per_stroop);
+        config_log::record(&env, &actor, "points_rate", points_per_stroop);
+    }
+
+    /// Get the points-per-stroop rate (0 = points not accepted)
+    pub fn get_points_rate(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PointsPerStroop)
+            .unwrap_or(0)
+    }
+
+    /// Get a user's points balance
+    pub fn get_points(env: Env, user: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Points(user))
+            .unwrap_or(0)
+    }
+
+    /// Mint a cartridge, paying up to `points_to_apply` points towards the fee and the rest in XLM
+    /// Never spends more points than cover the full fee. Returns the cartridge ID
+    pub fn splice_genome_with_points(env: Env, user: Address, points_to_apply: u64) -> u32 {
+        features::require(&env, "points");
+        reentrancy::enter(&env);
+        user.require_auth();
+
+        let rate = Self::get_points_rate(env.clone());
+        if rate == 0 {
+            panic_with_error!(&env, PointsError::PointsDisabled);
+        }
+        let balance = Self::get_points(env.clone(), user.clone());
+        if balance < points_to_apply {
+            panic_with_error!(&env, PointsError::InsufficientPoints);
+        }
+
+        let mint_fee = Self::get_mint_fee(env.clone());
+        let discount = ((points_to_apply / rate) as i128).min(mint_fee);
+        let points_spent = discount as u64 * rate;
+        let xlm_paid = fee_rounding::apply(&env, mint_fee - discount);
+
+        let key = DataKey::Points(user.clone());
+        env.storage()
+            .persistent()
+            .set(&key, &(balance - points_spent));
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        let cartridge_id = Self::splice_authorized(&env, user.clone(), xlm_paid);
+
+        events::publish(&env, |nonce| PointsCheckout {
+            cartridge_id,
+            user,
+            points_spent,
+            xlm_paid,
+            nonce,
+        });
+        reentrancy::exit(&env);
+        cartridge_id
+    }
+}
+
+/// Add `amount` points to a user's balance
+pub(crate) fn credit(env: &Env, user: &Address, amount: u64) {
+    let key = DataKey::Points(user.clone());
+    let balance = GeneSplicer::get_points(env.clone(), user.clone());
+    env.storage()
+        .persistent()
+        .set(&key, &balance.saturating_add(amount));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
@@ -0,0 +1,69 @@
+//! Finalization pre-flight for wallets
+//!
+//! Simulating `finalize_splice` only tells a wallet that it trapped. Instead,
+//! `can_finalize` answers with the code of the first failing precondition from
+//! the same `Error` enum finalize panics with. `finalize_cartridge` runs the
+//! same `check_preconditions` before touching the beacon, so the two can't
+//! drift apart.
+//!
+//! Only checks that don't depend on the beacon are covered: the BLS pairing and
+//! the beacon's own consistency are left to finalization.
+
+use crate::{
+    cartridge_status, entropy_age, maintenance, DataKey, Error, GeneSplicer, GeneSplicerArgs,
+    GeneSplicerClient, GenomeCartridge,
+};
+use soroban_sdk::{contractimpl, panic_with_error, Env};
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Check whether the cartridge could be finalized with `round` right now
+    /// Returns (true, 0), or (false, code of the Error finalization would fail with)
+    pub fn can_finalize(env: Env, cartridge_id: u32, round: u64) -> (bool, u32) {
+        let checked = env
+            .storage()
+            .persistent()
+            .get::<_, GenomeCartridge>(&DataKey::Cartridge(cartridge_id))
+            .ok_or(Error::CartridgeNotFound)
+            .and_then(|cartridge| check_preconditions(&env, &cartridge, round));
+        match checked {
+            Ok(()) => (true, 0),
+            Err(error) => (false, error as u32),
+        }
+    }
+}
+
+/// The first finalize precondition the cartridge fails for `round`, short of the beacon checks
+pub(crate) fn check_preconditions(
+    env: &Env,
+    cartridge: &GenomeCartridge,
+    round: u64,
+) -> Result<(), Error> {
+    check_open(env)?;
+    // Only a cartridge whose round has been emitted and that isn't finalized yet
+    cartridge_status::check_ready(env, cartridge)?;
+    if round != cartridge.splice_round {
+        return Err(Error::RoundMismatch);
+    }
+    entropy_age::check(env, cartridge, round)
+}
+
+/// Whether finalization is open at all: not paused and outside any maintenance window
+pub(crate) fn check_open(env: &Env) -> Result<(), Error> {
+    let paused: bool = env
+        .storage()
+        .instance()
+        .get(&DataKey::FinalizePaused)
+        .unwrap_or(false);
+    if paused {
+        return Err(Error::FinalizePaused);
+    }
+    maintenance::check(env)
+}
+
+/// Panic with the first finalize precondition the cartridge fails for `round`
+pub(crate) fn require_preconditions(env: &Env, cartridge: &GenomeCartridge, round: u64) {
+    if let Err(error) = check_preconditions(env, cartridge, round) {
+        panic_with_error!(env, error);
+    }
+}
@@ -0,0 +1,68 @@
+//! Opt-in owner privacy for aggregate views
+//!
+//! A holder can ask not to be listed on the contract's aggregate surfaces,
+//! the views that list many assets at once for relayers and dashboards. For a
+//! flagged holder those views show the contract's own address as the owner.
+//! Per-asset getters (`get_cartridge`, `get_creature`, `get_user_creatures`)
+//! stay authoritative. Ledger data is public anyway, so this only keeps
+//! addresses off our own listings; it hides nothing from anyone who reads
+//! storage or the event stream.
+
+use crate::{
+    DataKey, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contractimpl, Address, Env, Map};
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Hide (or show again) `user`'s address in aggregate views (user auth)
+    /// Only our listings are redacted: per-asset getters, storage and events still show the owner
+    pub fn set_privacy(env: Env, user: Address, hide: bool) {
+        user.require_auth();
+        let key = DataKey::Private(user);
+        if hide {
+            env.storage().persistent().set(&key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    /// Whether `user` asked to be hidden from aggregate views
+    pub fn is_private(env: Env, user: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Private(user))
+    }
+}
+
+/// Owner addresses as shown by one aggregate view call
+/// Remembers each owner's flag, so a listing reads it once per distinct owner
+pub(crate) struct Redactor {
+    flags: Map<Address, bool>,
+}
+
+impl Redactor {
+    pub(crate) fn new(env: &Env) -> Self {
+        Redactor {
+            flags: Map::new(env),
+        }
+    }
+
+    /// `owner`, or the contract's address if they opted out of aggregate views
+    pub(crate) fn owner(&mut self, env: &Env, owner: Address) -> Address {
+        let private = match self.flags.get(owner.clone()) {
+            Some(private) => private,
+            None => {
+                let private = GeneSplicer::is_private(env.clone(), owner.clone());
+                self.flags.set(owner.clone(), private);
+                private
+            }
+        };
+        if private {
+            env.current_contract_address()
+        } else {
+            owner
+        }
+    }
+}
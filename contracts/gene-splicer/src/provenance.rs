@@ -0,0 +1,70 @@
+//! Collection provenance commitment
+//!
+//! The constructor stores a Merkle root over the content hash (SHA256 of the
+//! file) of every art asset. Holders can then check that no art was reassigned
+//! after the mints showed which IDs turned out rare. Leaves are ordered
+//! by ID, genes first and skins after them, so asset `index` is the gene with
+//! that ID or, past the last gene, skin `index - gene_count`. The root is
+//! written once and nothing can change it: no setter exists, and neither
+//! `bootstrap_config` nor any config log change touches it.
+//!
+//! The tree follows RFC 6962's domain separation, so a leaf can never pass
+//! for an inner node:
+//!
+//! ```text
+//! leaf = SHA256(0x00 || content_hash)
+//! node = SHA256(0x01 || left || right)
+//! ```
+//!
+//! The leaf count is padded to a power of two with leaves of an all-zero
+//! content hash. A proof lists the sibling hashes from the leaf upwards, and
+//! the bits of `index` (lowest first) say whether each sibling sits on the
+//! right (0) or the left (1).
+
+use crate::{configured, DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient};
+use soroban_sdk::{contractimpl, Bytes, BytesN, Env, Vec};
+
+/// Deepest proof accepted: 2^32 leaves covers every u32 index
+const MAX_PROOF_DEPTH: u32 = 32;
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get the Merkle root over every gene and skin asset's content hash, fixed at deployment
+    pub fn get_provenance_hash(env: Env) -> Result<BytesN<32>, Error> {
+        configured(&env, &DataKey::ProvenanceHash)
+    }
+
+    /// Check one asset's content hash against the committed provenance root
+    /// `proof` holds the sibling hashes from the leaf up; `index` orders genes first, then skins
+    pub fn verify_provenance_segment(
+        env: Env,
+        index: u32,
+        content_hash: BytesN<32>,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<bool, Error> {
+        let root = Self::get_provenance_hash(env.clone())?;
+        let depth = proof.len();
+        if depth > MAX_PROOF_DEPTH || (depth < MAX_PROOF_DEPTH && index >> depth != 0) {
+            return Ok(false);
+        }
+
+        let mut node = hash_with_prefix(&env, 0x00, &[&content_hash]);
+        for (level, sibling) in proof.iter().enumerate() {
+            node = if (index >> level) & 1 == 0 {
+                hash_with_prefix(&env, 0x01, &[&node, &sibling])
+            } else {
+                hash_with_prefix(&env, 0x01, &[&sibling, &node])
+            };
+        }
+        Ok(node == root)
+    }
+}
+
+/// SHA256(prefix || parts...)
+fn hash_with_prefix(env: &Env, prefix: u8, parts: &[&BytesN<32>]) -> BytesN<32> {
+    let mut preimage = Bytes::from_array(env, &[prefix]);
+    for part in parts {
+        preimage.append(&Bytes::from((*part).clone()));
+    }
+    env.crypto().sha256(&preimage).into()
+}
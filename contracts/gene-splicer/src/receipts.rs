@@ -0,0 +1,95 @@
+//! Shareable finalization receipts
+//!
+//! A receipt carries what a third party needs to re-run a creature's gene
+//! derivation: the drand round, the derivation version and gene table epoch
+//! the cartridge was stamped with, and the resulting gene IDs. A verifier
+//! fetches the round from drand's API, computes the randomness as
+//! SHA256(compressed signature) exactly as finalization does, and re-derives
+//! the genes. While the round's verified randomness is still cached on-chain,
+//! the receipt includes the creature's gene seed, `derive_from_round` of it
+//! under `GENE_CONTEXT` and the creature ID, which v4 derivation reads the
+//! slots from.
+//!
+//! `digest` is SHA256 over a fixed encoding of the receipt, so a receipt
+//! shared off-chain can be checked against the contract's copy byte for byte.
+//! It leaves out the gene seed so it doesn't change when the cache
+//! expires:
+//!
+//! ```text
+//! RECEIPT_DOMAIN || creature_id u32 || entropy_round u64 || derivation_version u32
+//!   || gene_table_epoch u32 || head u32 || body u32 || legs u32 || dev_minted u8
+//! ```
+//!
+//! Integers are big-endian. Genes stay hidden until the reveal, so receipts do too.
+
+use crate::{
+    reveal, verification, Creature, DataKey, Error, GeneSplicer, GeneSplicerArgs,
+    GeneSplicerClient, GenomeCartridge, GENE_CONTEXT,
+};
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Bytes, BytesN, Env};
+
+/// Domain tag opening every receipt digest preimage — frozen, changing it changes every digest
+pub const RECEIPT_DOMAIN: &[u8] = b"SPLICERS_RECEIPT_V1";
+
+/// Everything needed to check a creature's genes against public drand data
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FinalizationReceipt {
+    pub creature_id: u32,
+    pub entropy_round: u64,
+    pub gene_seed: Option<BytesN<32>>, // Round's gene context value, while cached on-chain
+    pub derivation_version: u32,
+    pub gene_table_epoch: u32,
+    pub head_gene_id: u32,
+    pub body_gene_id: u32,
+    pub legs_gene_id: u32,
+    pub dev_minted: bool, // Genes came from a dev seed, not the drand round
+    pub digest: BytesN<32>,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get the receipt proving how a creature's genes were derived
+    /// Fails with NotRevealed before the collection reveal
+    pub fn get_finalization_receipt(env: Env, creature_id: u32) -> FinalizationReceipt {
+        reveal::require_revealed(&env);
+        let creature: Creature = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Creature(creature_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CreatureNotFound));
+        let cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Cartridge(creature_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CartridgeNotFound));
+
+        let mut preimage = Bytes::from_slice(&env, RECEIPT_DOMAIN);
+        preimage.extend_from_array(&creature.id.to_be_bytes());
+        preimage.extend_from_array(&creature.entropy_round.to_be_bytes());
+        preimage.extend_from_array(&cartridge.derivation_version.to_be_bytes());
+        preimage.extend_from_array(&cartridge.gene_table_epoch.to_be_bytes());
+        preimage.extend_from_array(&creature.head_gene.id.to_be_bytes());
+        preimage.extend_from_array(&creature.body_gene.id.to_be_bytes());
+        preimage.extend_from_array(&creature.legs_gene.id.to_be_bytes());
+        preimage.push_back(creature.dev_minted as u8);
+
+        FinalizationReceipt {
+            creature_id: creature.id,
+            entropy_round: creature.entropy_round,
+            gene_seed: verification::derived_randomness(
+                &env,
+                creature.entropy_round,
+                &GENE_CONTEXT,
+                creature.id.into(),
+            ),
+            derivation_version: cartridge.derivation_version,
+            gene_table_epoch: cartridge.gene_table_epoch,
+            head_gene_id: creature.head_gene.id,
+            body_gene_id: creature.body_gene.id,
+            legs_gene_id: creature.legs_gene.id,
+            dev_minted: creature.dev_minted,
+            digest: env.crypto().sha256(&preimage).into(),
+        }
+    }
+}
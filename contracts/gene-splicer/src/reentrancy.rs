@@ -0,0 +1,27 @@
+//! Reentrancy guard for token-moving entrypoints
+//!
+//! Every entrypoint that calls a token contract and then writes ownership or
+//! counters calls `enter` first and `exit` before returning. A nested call into
+//! any guarded entrypoint, for example from a token's `transfer` calling back
+//! into `splice_genome`, finds the flag set and fails with ReentrancyDetected.
+//!
+//! The flag lives in temporary storage and only for the duration of the call.
+//! A panic reverts the whole invocation, including the flag, so there is no
+//! unwinding to handle. Soroban's host also refuses contract re-entry; this
+//! guard keeps the contract safe without relying on that.
+
+use crate::{DataKey, Error};
+use soroban_sdk::{panic_with_error, Env};
+
+/// Mark a guarded entrypoint as running; panics if one already is
+pub(crate) fn enter(env: &Env) {
+    if env.storage().temporary().has(&DataKey::Reentrancy) {
+        panic_with_error!(env, Error::ReentrancyDetected);
+    }
+    env.storage().temporary().set(&DataKey::Reentrancy, &true);
+}
+
+/// Clear the mark set by `enter`; call on every return path of a guarded entrypoint
+pub(crate) fn exit(env: &Env) {
+    env.storage().temporary().remove(&DataKey::Reentrancy);
+}
@@ -0,0 +1,132 @@
+//! Deterministic render layer lists
+//!
+//! The contract doesn't store art, only which assets compose a creature. A
+//! layer table maps creature fields to asset IDs: each rule names a layer
+//! category, the field it reads, an asset ID offset and a z-index, and the
+//! creature's layers are the rules applied in order. Renderers fetch the assets
+//! by ID (e.g. from IPFS) and stack them bottom to top. The Owner can replace
+//! the table when the art is reorganized, without an upgrade.
+
+use crate::{
+    config_log, reveal,
+    roles::{require_role, Role},
+    Creature, DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, symbol_short, Env, Symbol, Vec};
+
+/// Most rules a layer table can hold
+pub const MAX_LAYER_RULES: u32 = 16;
+
+/// Creature field a layer's asset is chosen by
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LayerSource {
+    Skin,      // skin_id
+    HeadGene,  // head_gene.id
+    BodyGene,  // body_gene.id
+    LegsGene,  // legs_gene.id
+    TopRarity, // Highest gene rarity: 0 Normal, 1 Rare, 2 Legendary
+}
+
+/// One row of the layer table: asset_id = asset_offset + value of `source`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LayerRule {
+    pub category: Symbol,
+    pub source: LayerSource,
+    pub asset_offset: u32,
+    pub z_index: u32,
+}
+
+/// One art layer of a creature, bottom-most first in `get_render_layers`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LayerRef {
+    pub category: Symbol,
+    pub asset_id: u32,
+    pub z_index: u32,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Replace the layer table (Owner only)
+    /// Rules must be in strictly ascending z_index order, at most MAX_LAYER_RULES of them
+    pub fn set_layer_table(env: Env, rules: Vec<LayerRule>) {
+        let actor = require_role(&env, Role::Owner);
+        if rules.len() > MAX_LAYER_RULES {
+            panic_with_error!(&env, Error::InvalidLayerTable);
+        }
+        let mut previous_z: Option<u32> = None;
+        for rule in rules.iter() {
+            if previous_z.is_some_and(|z| rule.z_index <= z) {
+                panic_with_error!(&env, Error::InvalidLayerTable);
+            }
+            previous_z = Some(rule.z_index);
+        }
+        env.storage().instance().set(&DataKey::LayerTable, &rules);
+        config_log::record(&env, &actor, "layer_table", rules);
+    }
+
+    /// Get the layer table, or the default one if the Owner never set it
+    pub fn get_layer_table(env: Env) -> Vec<LayerRule> {
+        env.storage()
+            .instance()
+            .get(&DataKey::LayerTable)
+            .unwrap_or_else(|| default_table(&env))
+    }
+
+    /// Get the ordered art layers composing a creature, bottom-most first
+    /// Refused with NotRevealed until the collection reveal
+    pub fn get_render_layers(env: Env, creature_id: u32) -> Vec<LayerRef> {
+        reveal::require_revealed(&env);
+        let creature: Creature = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Creature(creature_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CreatureNotFound));
+
+        let mut layers = Vec::new(&env);
+        for rule in Self::get_layer_table(env.clone()).iter() {
+            layers.push_back(LayerRef {
+                category: rule.category,
+                asset_id: rule.asset_offset + source_value(&creature, rule.source),
+                z_index: rule.z_index,
+            });
+        }
+        layers
+    }
+}
+
+/// Background from the skin, then legs, torso and head from their genes
+fn default_table(env: &Env) -> Vec<LayerRule> {
+    let rule = |category, source, asset_offset, z_index| LayerRule {
+        category,
+        source,
+        asset_offset,
+        z_index,
+    };
+    Vec::from_array(
+        env,
+        [
+            rule(symbol_short!("bg"), LayerSource::Skin, 0, 0),
+            rule(symbol_short!("legs"), LayerSource::LegsGene, 1_000, 10),
+            rule(symbol_short!("torso"), LayerSource::BodyGene, 2_000, 20),
+            rule(symbol_short!("head"), LayerSource::HeadGene, 3_000, 30),
+        ],
+    )
+}
+
+fn source_value(creature: &Creature, source: LayerSource) -> u32 {
+    match source {
+        LayerSource::Skin => creature.skin_id,
+        LayerSource::HeadGene => creature.head_gene.id,
+        LayerSource::BodyGene => creature.body_gene.id,
+        LayerSource::LegsGene => creature.legs_gene.id,
+        LayerSource::TopRarity => creature
+            .head_gene
+            .rarity
+            .clone()
+            .max(creature.body_gene.rarity.clone())
+            .max(creature.legs_gene.rarity.clone()) as u32,
+    }
+}
@@ -0,0 +1,121 @@
+//! End-of-season creature retirement
+//!
+//! A retired creature keeps its identity, genes and artwork, but no longer
+//! takes part in anything active. Today that means governance: retired
+//! creatures carry no voting weight. Retirement is irreversible and pays the
+//! owner points by rarity, summed over the three genes, spendable like any
+//! other points, plus an XLM reward from the burn reward pool while its epoch
+//! budget lasts (see `burn_rewards`).
+//!
+//! The points reveal how rare a creature is, so retirement waits for the
+//! collection reveal.
+
+use crate::{
+    account_auth, burn_rewards, events, features, locks, points, reentrancy, reveal, Creature,
+    DataKey, Error, GeneRarity, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, TTL_EXTEND_TO,
+    TTL_THRESHOLD,
+};
+use soroban_sdk::{contractevent, contractimpl, panic_with_error, Address, Env};
+
+/// Points for retiring a creature, per gene of each rarity
+pub const RETIREMENT_POINTS_NORMAL: u64 = 10;
+pub const RETIREMENT_POINTS_RARE: u64 = 30;
+pub const RETIREMENT_POINTS_LEGENDARY: u64 = 100;
+
+/// Event emitted when an owner retires a creature
+#[contractevent]
+pub struct CreatureRetired {
+    pub creature_id: u32,
+    pub owner: Address,
+    pub points: u64,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Permanently retire a creature, crediting its owner points by rarity
+    /// Also pays the burn reward if the epoch budget and pool allow; returns the points credited
+    pub fn retire_creature(env: Env, owner: Address, creature_id: u32) -> u64 {
+        reentrancy::enter(&env);
+        features::require(&env, "retirement");
+        account_auth::require_burn(&env, &owner, creature_id);
+        reveal::require_revealed(&env);
+
+        let creature: Creature = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Creature(creature_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CreatureNotFound));
+        if creature.owner != owner {
+            panic_with_error!(&env, Error::NotCreatureOwner);
+        }
+        assert_active(&env, creature_id);
+        // A listed, staked or exported creature has to be released first
+        if let Err(error) = locks::require_unlocked(&env, creature_id) {
+            panic_with_error!(&env, error);
+        }
+
+        let key = DataKey::Retired(creature_id);
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        let points = retirement_points(&creature);
+        points::credit(&env, &owner, points);
+        burn_rewards::pay(&env, &creature, &owner);
+
+        events::publish(&env, |nonce| CreatureRetired {
+            creature_id,
+            owner,
+            points,
+            nonce,
+        });
+        reentrancy::exit(&env);
+        points
+    }
+
+    /// Whether a creature has been retired
+    pub fn is_creature_retired(env: Env, creature_id: u32) -> bool {
+        is_retired(&env, creature_id)
+    }
+}
+
+/// Whether the creature is retired and out of every active use
+pub(crate) fn is_retired(env: &Env, creature_id: u32) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Retired(creature_id))
+}
+
+/// Fail with CreatureRetired if the creature is retired
+pub(crate) fn check_active(env: &Env, creature_id: u32) -> Result<(), Error> {
+    if is_retired(env, creature_id) {
+        Err(Error::CreatureRetired)
+    } else {
+        Ok(())
+    }
+}
+
+/// Panic with CreatureRetired if the creature is retired; entrypoints that act on a
+/// creature call this instead of checking `is_retired` themselves
+pub(crate) fn assert_active(env: &Env, creature_id: u32) {
+    if let Err(error) = check_active(env, creature_id) {
+        panic_with_error!(env, error);
+    }
+}
+
+fn retirement_points(creature: &Creature) -> u64 {
+    [
+        &creature.head_gene,
+        &creature.body_gene,
+        &creature.legs_gene,
+    ]
+    .iter()
+    .map(|gene| match gene.rarity {
+        GeneRarity::Normal => RETIREMENT_POINTS_NORMAL,
+        GeneRarity::Rare => RETIREMENT_POINTS_RARE,
+        GeneRarity::Legendary => RETIREMENT_POINTS_LEGENDARY,
+    })
+    .sum()
+}
@@ -0,0 +1,104 @@
+//! Time-locked gene reveal
+//!
+//! A drop can keep genes sealed until a reveal time. Finalization is
+//! unchanged: genes are derived from the beacon and stored as usual, so the
+//! outcome is fixed the moment entropy lands. Until the collection is revealed,
+//! getters and `CreatureFinalized` events report `HIDDEN_GENE_ID` (with Normal
+//! rarity) in place of each gene, and gene-derived views refuse with
+//! `NotRevealed`. Reads switch to the real genes at the reveal time without any
+//! further transaction; `trigger_reveal` announces it (or reveals early).
+
+use crate::{
+    config_log, events,
+    roles::{require_role, Role},
+    Creature, DataKey, Gene, GeneRarity, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{contracterror, contractevent, contractimpl, panic_with_error, Env};
+
+/// Gene ID reported in place of every gene before the reveal
+pub const HIDDEN_GENE_ID: u32 = u32::MAX;
+
+/// Collection reveal failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RevealError {
+    NotRevealed = 36,     // Gene-derived view called before the collection reveal
+    AlreadyRevealed = 37, // trigger_reveal was already called
+}
+
+/// Event emitted once when the Owner announces the collection reveal
+#[contractevent]
+pub struct CollectionRevealed {
+    pub revealed_at: u64,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set (or clear with None) the timestamp at which genes become visible (Owner only)
+    pub fn set_reveal_time(env: Env, reveal_time: Option<u64>) {
+        let actor = require_role(&env, Role::Owner);
+        match reveal_time {
+            Some(time) => env.storage().instance().set(&DataKey::RevealTime, &time),
+            None => env.storage().instance().remove(&DataKey::RevealTime),
+        }
+        config_log::record(&env, &actor, "reveal_time", reveal_time);
+    }
+
+    /// Get the configured reveal time, if any
+    pub fn get_reveal_time(env: Env) -> Option<u64> {
+        env.storage().instance().get(&DataKey::RevealTime)
+    }
+
+    /// Whether creature genes are visible: no reveal time, reveal time reached, or reveal triggered
+    pub fn is_revealed(env: Env) -> bool {
+        let triggered = env
+            .storage()
+            .instance()
+            .get(&DataKey::RevealTriggered)
+            .unwrap_or(false);
+        triggered
+            || Self::get_reveal_time(env.clone())
+                .is_none_or(|time| env.ledger().timestamp() >= time)
+    }
+
+    /// Announce the reveal with a collection-level event, revealing early if the time hasn't come (Owner only)
+    pub fn trigger_reveal(env: Env) {
+        require_role(&env, Role::Owner);
+        if env.storage().instance().has(&DataKey::RevealTriggered) {
+            panic_with_error!(&env, RevealError::AlreadyRevealed);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RevealTriggered, &true);
+        events::publish(&env, |nonce| CollectionRevealed {
+            revealed_at: env.ledger().timestamp(),
+            nonce,
+        });
+    }
+}
+
+/// `creature` as readers may see it: genes replaced by the hidden sentinel unless `revealed`
+pub(crate) fn visible(creature: Creature, revealed: bool) -> Creature {
+    if revealed {
+        return creature;
+    }
+    let hidden = Gene {
+        id: HIDDEN_GENE_ID,
+        rarity: GeneRarity::Normal,
+    };
+    Creature {
+        head_gene: hidden.clone(),
+        body_gene: hidden.clone(),
+        legs_gene: hidden,
+        ..creature
+    }
+}
+
+/// Refuse gene-derived views before the reveal
+pub(crate) fn require_revealed(env: &Env) {
+    if !GeneSplicer::is_revealed(env.clone()) {
+        panic_with_error!(env, RevealError::NotRevealed);
+    }
+}
@@ -0,0 +1,107 @@
+//! Owner and Operator roles
+//!
+//! - Owner: the original admin key (`DataKey::Admin`). Can call every privileged
+//!   entrypoint, including key rotation, upgrades and assigning the Operator.
+//! - Operator: optional day-to-day key, limited to operational entrypoints
+//!   (pausing, governance proposals and snapshots).
+//!
+//! Soroban auth is per address, so an operational entrypoint requires the
+//! Operator's auth while one is assigned and the Owner's otherwise; the Owner
+//! can always take operations back by clearing or reassigning the Operator.
+
+use crate::{
+    config_log, events, require_configured, DataKey, GeneSplicer, GeneSplicerArgs,
+    GeneSplicerClient,
+};
+use soroban_sdk::{contractevent, contractimpl, Address, BytesN, Env};
+
+/// Privilege level an entrypoint requires
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    Owner,
+    Operator,
+}
+
+/// Event emitted when the Owner hands the role to a new address
+#[contractevent]
+pub struct OwnerChanged {
+    pub previous: Address,
+    pub owner: Address,
+    pub nonce: u64,
+}
+
+/// Event emitted when the Owner assigns or clears the Operator
+#[contractevent]
+pub struct OperatorChanged {
+    pub operator: Option<Address>,
+    pub nonce: u64,
+}
+
+/// Event emitted when the Owner replaces the contract's WASM
+#[contractevent]
+pub struct ContractUpgraded {
+    pub wasm_hash: BytesN<32>,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get the Operator address, if one is assigned
+    pub fn operator(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Operator)
+    }
+
+    /// Assign (or clear with None) the Operator (Owner only)
+    pub fn set_operator(env: Env, operator: Option<Address>) {
+        let actor = require_role(&env, Role::Owner);
+        match &operator {
+            Some(address) => env.storage().instance().set(&DataKey::Operator, address),
+            None => env.storage().instance().remove(&DataKey::Operator),
+        }
+        config_log::record(&env, &actor, "operator", operator.clone());
+        events::publish(&env, |nonce| OperatorChanged { operator, nonce });
+    }
+
+    /// Replace the contract's WASM with an already-uploaded one (Owner only)
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let actor = require_role(&env, Role::Owner);
+        // Logged first so a configuration freeze refuses the upgrade before it is attempted
+        config_log::record(&env, &actor, "wasm", new_wasm_hash.clone());
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        events::publish(&env, |nonce| ContractUpgraded {
+            wasm_hash: new_wasm_hash,
+            nonce,
+        });
+    }
+}
+
+/// Require auth from the address holding `role`
+/// Operator entrypoints fall back to the Owner while no Operator is assigned
+pub(crate) fn require_role(env: &Env, role: Role) -> Address {
+    let operator: Option<Address> = match role {
+        Role::Owner => None,
+        Role::Operator => env.storage().instance().get(&DataKey::Operator),
+    };
+    let address = operator.unwrap_or_else(|| require_configured(env, &DataKey::Admin));
+    // bootstrap_config already holds the Owner's auth; Soroban refuses a second
+    // require_auth for the same address within one invocation
+    let authorized: Option<Address> = env.storage().instance().get(&DataKey::OwnerAuthorized);
+    if authorized.as_ref() != Some(&address) {
+        address.require_auth();
+    }
+    address
+}
+
+/// Run `f` with the Owner's auth taken once up front
+/// Role checks inside `f` accept the Owner without asking for auth again; the
+/// marker is removed before returning, and a panic reverts it with everything else
+pub(crate) fn as_owner<T>(env: &Env, f: impl FnOnce() -> T) -> T {
+    let owner = require_role(env, Role::Owner);
+    env.storage()
+        .instance()
+        .set(&DataKey::OwnerAuthorized, &owner);
+    let result = f();
+    env.storage().instance().remove(&DataKey::OwnerAuthorized);
+    result
+}
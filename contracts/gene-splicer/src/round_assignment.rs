@@ -0,0 +1,67 @@
+//! Per-round assignment cap
+//!
+//! Every cartridge waits for one drand round, and per-round structures (the
+//! verified-round cache and its cost sharing, relayer batches) grow with the
+//! number of cartridges sharing it. With a cap configured, a mint whose round
+//! is full is assigned the next round with capacity instead, searching at most
+//! `MAX_ROUND_SEARCH` rounds ahead. Counters live in temporary storage: they
+//! only matter until the round has been emitted.
+
+use crate::{
+    config_log,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{contractimpl, panic_with_error, Env};
+
+/// Rounds tried, starting at the earliest eligible one, before a mint fails
+pub const MAX_ROUND_SEARCH: u64 = 10;
+
+/// Ledgers an assignment counter is kept (~1 day, far beyond the ~6s until its round)
+const ROUND_ASSIGNMENTS_TTL: u32 = 17_280;
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Set the most cartridges assigned to one drand round, or 0 for no cap (Owner only)
+    pub fn set_max_assignments_per_round(env: Env, max: u32) {
+        let actor = require_role(&env, Role::Owner);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxAssignmentsPerRound, &max);
+        config_log::record(&env, &actor, "max_assignments_per_round", max);
+    }
+
+    /// Get the per-round assignment cap (0 = uncapped)
+    pub fn get_max_assignments_per_round(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxAssignmentsPerRound)
+            .unwrap_or(0)
+    }
+
+    /// Get how many cartridges have been assigned to `round`, while its counter is live
+    pub fn get_round_assignments(env: Env, round: u64) -> u32 {
+        env.storage()
+            .temporary()
+            .get(&DataKey::RoundAssignments(round))
+            .unwrap_or(0)
+    }
+}
+
+/// Assign `count` cartridges together to `earliest`, or the first later round with room for all
+pub(crate) fn assign(env: &Env, earliest: u64, count: u32) -> u64 {
+    let max = GeneSplicer::get_max_assignments_per_round(env.clone());
+    let round = (earliest..earliest + MAX_ROUND_SEARCH)
+        .find(|round| {
+            max == 0 || GeneSplicer::get_round_assignments(env.clone(), *round) + count <= max
+        })
+        .unwrap_or_else(|| panic_with_error!(env, Error::RoundCapacityExhausted));
+
+    let key = DataKey::RoundAssignments(round);
+    let assigned = GeneSplicer::get_round_assignments(env.clone(), round);
+    env.storage().temporary().set(&key, &(assigned + count));
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, ROUND_ASSIGNMENTS_TTL, ROUND_ASSIGNMENTS_TTL);
+    round
+}
@@ -0,0 +1,122 @@
+//! Recovery of cartridges whose drand round never arrives
+//!
+//! drand has had outages where specific rounds were late or never served, and
+//! a cartridge bound to such a round can't be finalized. Once the round is
+//! overdue by the configured waiting period, the holder of the finalization
+//! right may rebind the cartridge to one of the next `max_offset` rounds after
+//! its original one. The chain can't prove a round is missing, so the waiting
+//! period stands in for that proof.
+//!
+//! Substitution has a fairness cost: those nearby rounds are usually already
+//! public, so the holder can pick among several known outcomes. It is therefore
+//! off until the Owner sets a non-zero `max_offset`, kept to a small window,
+//! and every use is visible. The cartridge keeps its original round in
+//! `substituted_from`, and `RoundSubstituted` is emitted.
+
+use crate::{
+    config_log, drand_round_time, events,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, GenomeCartridge,
+    TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{contracterror, contractevent, contractimpl, panic_with_error, Env};
+
+/// Default seconds a round must be overdue before its cartridge can be rebound (1 day)
+pub const DEFAULT_SUBSTITUTION_DELAY: u64 = 86_400;
+
+/// Round substitution failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SubstitutionError {
+    SubstitutionNotAllowed = 42, // Substitution disabled, or new round outside the allowed window
+    SubstitutionTooEarly = 43,   // Cartridge's round isn't overdue by the substitution delay yet
+}
+
+/// Event emitted when a cartridge is rebound to a different drand round
+#[contractevent]
+pub struct RoundSubstituted {
+    pub cartridge_id: u32,
+    pub original_round: u64,
+    pub previous_round: u64,
+    pub new_round: u64,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Configure round substitution (Owner only)
+    /// - `delay`: seconds past a round's expected time before its cartridges can be rebound
+    /// - `max_offset`: furthest round after the original one a cartridge can move to (0 disables)
+    pub fn set_round_substitution(env: Env, delay: u64, max_offset: u64) {
+        let actor = require_role(&env, Role::Owner);
+        env.storage()
+            .instance()
+            .set(&DataKey::RoundSubstitutionDelay, &delay);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxRoundSubstitution, &max_offset);
+        config_log::record(&env, &actor, "round_substitution", (delay, max_offset));
+    }
+
+    /// Get the seconds a round must be overdue before substitution
+    pub fn get_round_substitution_delay(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoundSubstitutionDelay)
+            .unwrap_or(DEFAULT_SUBSTITUTION_DELAY)
+    }
+
+    /// Get the furthest a cartridge may move past its original round (0 = disabled)
+    pub fn get_max_round_substitution(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxRoundSubstitution)
+            .unwrap_or(0)
+    }
+
+    /// Rebind an unfinalized cartridge whose round is overdue to `new_round`
+    /// `new_round` must be within (original, original + max_offset]; authorized by the finalizer
+    pub fn substitute_round(env: Env, cartridge_id: u32, new_round: u64) {
+        let key = DataKey::Cartridge(cartridge_id);
+        let mut cartridge: GenomeCartridge = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CartridgeNotFound));
+        cartridge.finalizer().require_auth();
+        if cartridge.finalized {
+            panic_with_error!(&env, Error::AlreadyFinalized);
+        }
+
+        let original_round = cartridge.substituted_from.unwrap_or(cartridge.splice_round);
+        let max_offset = Self::get_max_round_substitution(env.clone());
+        if new_round <= original_round
+            || new_round - original_round > max_offset
+            || new_round == cartridge.splice_round
+        {
+            panic_with_error!(&env, SubstitutionError::SubstitutionNotAllowed);
+        }
+        let overdue_at = drand_round_time(cartridge.splice_round)
+            .saturating_add(Self::get_round_substitution_delay(env.clone()));
+        if env.ledger().timestamp() < overdue_at {
+            panic_with_error!(&env, SubstitutionError::SubstitutionTooEarly);
+        }
+
+        let previous_round = cartridge.splice_round;
+        cartridge.splice_round = new_round;
+        cartridge.substituted_from = Some(original_round);
+        env.storage().persistent().set(&key, &cartridge);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        events::publish(&env, |nonce| RoundSubstituted {
+            cartridge_id,
+            original_round,
+            previous_round,
+            new_round,
+            nonce,
+        });
+    }
+}
@@ -0,0 +1,164 @@
+//! Deferred settlement for partner storefronts
+//!
+//! A partner storefront sells mints in its own app and settles with us in
+//! batches. `storefront_mint` mints to the storefront's customer without
+//! payment and adds the mint fee to the storefront's debt. `settle_storefront`
+//! pays the debt down in XLM, to the treasury like any other mint fee.
+//!
+//! The Owner registers each storefront with a credit limit. A mint that would
+//! take the debt past the limit is refused. The Owner can also suspend a
+//! storefront, for example one whose debt exceeds a limit that was lowered,
+//! which blocks further mints but not settlement. Suspension is an operational
+//! switch, so it keeps working after the configuration freeze unless operations
+//! were locked too.
+
+use crate::{
+    config_log, events, features, fee_rounding, mint_fee, reentrancy, require_configured,
+    roles::{require_role, Role},
+    DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient, ORIGINAL_COLLECTION,
+    TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, contracttype, panic_with_error, token, Address, Env,
+};
+
+/// Storefront failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StorefrontError {
+    StorefrontNotRegistered = 58, // Storefront call from an address the Owner hasn't registered
+    StorefrontSuspended = 59,     // Suspended storefront tried to mint on credit
+    CreditLimitExceeded = 60,     // Storefront mint would take its debt past the credit limit
+}
+
+/// Terms of a registered storefront
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Storefront {
+    pub credit_limit: i128, // Most debt (stroops) the storefront may run up
+    pub suspended: bool,    // Suspended storefronts can settle but not mint
+}
+
+/// Event emitted when a storefront pays down its debt
+#[contractevent]
+pub struct StorefrontSettled {
+    pub storefront: Address,
+    pub amount: i128,
+    pub remaining_debt: i128,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Register a storefront, or change its credit limit, in stroops (Owner only)
+    pub fn register_storefront(env: Env, storefront: Address, credit_limit: i128) {
+        let actor = require_role(&env, Role::Owner);
+        if credit_limit < 0 {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+        let terms = Storefront {
+            credit_limit,
+            suspended: Self::get_storefront(env.clone(), storefront.clone())
+                .is_some_and(|terms| terms.suspended),
+        };
+        write_storefront(&env, &storefront, &terms);
+        config_log::record(&env, &actor, "storefront", (storefront, credit_limit));
+    }
+
+    /// Suspend or reinstate a storefront's minting (Owner only)
+    pub fn suspend_storefront(env: Env, storefront: Address, suspended: bool) {
+        let actor = require_role(&env, Role::Owner);
+        let mut terms = read_storefront(&env, &storefront);
+        terms.suspended = suspended;
+        write_storefront(&env, &storefront, &terms);
+        config_log::record(
+            &env,
+            &actor,
+            "storefront_suspended",
+            (storefront, suspended),
+        );
+    }
+
+    /// Mint a cartridge to `recipient` on the storefront's credit
+    /// The mint fee is added to the storefront's debt instead of being paid now
+    pub fn storefront_mint(env: Env, storefront: Address, recipient: Address) -> u32 {
+        features::require(&env, "storefront");
+        storefront.require_auth();
+        let terms = read_storefront(&env, &storefront);
+        if terms.suspended {
+            panic_with_error!(&env, StorefrontError::StorefrontSuspended);
+        }
+        let debt = Self::get_storefront_debt(env.clone(), storefront.clone())
+            + fee_rounding::apply(&env, Self::get_mint_fee(env.clone()));
+        if debt > terms.credit_limit {
+            panic_with_error!(&env, StorefrontError::CreditLimitExceeded);
+        }
+
+        write_debt(&env, &storefront, debt);
+        // Nothing is collected now, but the mint pause still applies
+        Self::charge_mint_fee(&env, &storefront, 0);
+        Self::mint_cartridge(&env, recipient, ORIGINAL_COLLECTION)
+    }
+
+    /// Pay `amount` stroops of the storefront's debt
+    /// Refused with InvalidAmount unless 0 < amount <= debt
+    pub fn settle_storefront(env: Env, storefront: Address, amount: i128) {
+        reentrancy::enter(&env);
+        storefront.require_auth();
+        let debt = Self::get_storefront_debt(env.clone(), storefront.clone());
+        if amount <= 0 || amount > debt {
+            panic_with_error!(&env, Error::InvalidAmount);
+        }
+
+        let treasury = mint_fee::treasury(&env);
+        let xlm_token: Address = require_configured(&env, &DataKey::XlmToken);
+        token::Client::new(&env, &xlm_token).transfer(&storefront, &treasury, &amount);
+
+        let remaining_debt = debt - amount;
+        write_debt(&env, &storefront, remaining_debt);
+        events::publish(&env, |nonce| StorefrontSettled {
+            storefront,
+            amount,
+            remaining_debt,
+            nonce,
+        });
+        reentrancy::exit(&env);
+    }
+
+    /// Get a storefront's credit limit and suspension flag (None if not registered)
+    pub fn get_storefront(env: Env, storefront: Address) -> Option<Storefront> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Storefront(storefront))
+    }
+
+    /// Get the mint fees a storefront owes (stroops)
+    pub fn get_storefront_debt(env: Env, storefront: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StorefrontDebt(storefront))
+            .unwrap_or(0)
+    }
+}
+
+fn read_storefront(env: &Env, storefront: &Address) -> Storefront {
+    GeneSplicer::get_storefront(env.clone(), storefront.clone())
+        .unwrap_or_else(|| panic_with_error!(env, StorefrontError::StorefrontNotRegistered))
+}
+
+fn write_storefront(env: &Env, storefront: &Address, terms: &Storefront) {
+    let key = DataKey::Storefront(storefront.clone());
+    env.storage().persistent().set(&key, terms);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+fn write_debt(env: &Env, storefront: &Address, debt: i128) {
+    let key = DataKey::StorefrontDebt(storefront.clone());
+    env.storage().persistent().set(&key, &debt);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
@@ -1,7 +1,18 @@
 #![cfg(test)]
 
-use crate::{GeneSplicer, GeneSplicerClient};
-use soroban_sdk::{testutils::Address as _, token, Address, Bytes, Env};
+extern crate std;
+
+use crate::{
+    AccountingError, BulkPricingError, CollectionError, CompanionError, ConsumerError,
+    DrawingError, EntropyError, Error, ExperienceError, FeatureError, FinalizationRightError,
+    FreeMintError, GeneSplicer, GeneSplicerClient, GeneTableError, GovernanceError, MetadataError,
+    MigrationError, PermitError, PointsError, ReadLimitError, RevealError, StorefrontError,
+    SubstitutionError, TransferError, DRAND_GENESIS, MINT_FEE,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _},
+    token, Address, Bytes, Env,
+};
 
 fn create_xlm_token<'a>(env: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
     let asset_contract = env.register_stellar_asset_contract_v2(admin.clone());
@@ -25,8 +36,9 @@ fn create_mock_entropy(env: &Env) -> (Bytes, Bytes, Bytes) {
     compressed[0] = 0x80 | 0x0a; // Compression flag + x-coord first byte = 0x8a
 
     // Uncompressed G1: 96 bytes — first 48 = x-coordinate, last 48 = y-coordinate
-    // x-coordinate must match compressed (after stripping flags)
-    let mut uncompressed = [0xbb_u8; 96];
+    // x-coordinate must match compressed (after stripping flags); y is below p/2, so the
+    // canonical compressed encoding carries no sign flag
+    let mut uncompressed = [0x01_u8; 96];
     uncompressed[0] = 0x0a; // Same x-coord byte 0 as compressed (without flags)
     for i in 1..48 {
         uncompressed[i] = 0xaa; // Same x-coord as compressed
@@ -37,7 +49,17 @@ fn create_mock_entropy(env: &Env) -> (Bytes, Bytes, Bytes) {
     (randomness, sig_compressed, sig_uncompressed)
 }
 
+/// Helper: convert a contract error into the host error surfaced by `try_` client calls
+fn contract_error(error: impl Into<soroban_sdk::Error>) -> soroban_sdk::Error {
+    error.into()
+}
+
 /// Helper: register contract with constructor args (replaces old initialize pattern)
+fn mock_provenance_hash(env: &Env) -> soroban_sdk::BytesN<32> {
+    // Arbitrary root; provenance tests deploy with a tree of their own
+    soroban_sdk::BytesN::from_array(env, &[0x50; 32])
+}
+
 fn setup_contract<'a>(
     env: &Env,
     admin: &Address,
@@ -47,7 +69,7 @@ fn setup_contract<'a>(
     let mock_pubkey = create_mock_drand_pubkey(env);
     let contract_id = env.register(
         GeneSplicer,
-        (admin, xlm_token_address, 10u64, dev_mode, mock_pubkey),
+        (admin, xlm_token_address, 10u64, dev_mode, mock_pubkey, mock_provenance_hash(env), MINT_FEE, admin),
     );
     GeneSplicerClient::new(env, &contract_id)
 }
@@ -91,6 +113,25 @@ fn test_splice_genome() {
     assert_eq!(xlm_token.balance(&user), 90_000_000);
 }
 
+#[test]
+fn test_admin_self_mint() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&admin, &100_000_000);
+
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // Payer and fee recipient are the same address
+    let cartridge_id = client.splice_genome(&admin);
+    assert_eq!(client.get_cartridge(&cartridge_id).unwrap().owner, admin);
+
+    // Fee is a self-transfer: no net balance change
+    assert_eq!(xlm_token.balance(&admin), 100_000_000);
+}
+
 #[test]
 fn test_multiple_splices() {
     let env = Env::default();
@@ -126,7 +167,7 @@ fn test_multiple_splices() {
 }
 
 #[test]
-#[should_panic(expected = "Insufficient XLM balance for minting fee")]
+#[should_panic(expected = "Error(Contract, #94)")]
 fn test_splice_insufficient_balance() {
     let env = Env::default();
     env.mock_all_auths();
@@ -209,6 +250,7 @@ fn test_finalize_splice() {
         &randomness,
         &sig_compressed,
         &sig_uncompressed,
+        &None,
     );
     assert_eq!(creature_id, cartridge_id);
 
@@ -235,7 +277,7 @@ fn test_finalize_splice() {
 }
 
 #[test]
-#[should_panic(expected = "Cartridge already finalized")]
+#[should_panic(expected = "Error(Contract, #13)")]
 fn test_double_finalization() {
     let env = Env::default();
     env.mock_all_auths();
@@ -259,6 +301,7 @@ fn test_double_finalization() {
         &randomness,
         &sig_compressed,
         &sig_uncompressed,
+        &None,
     );
 
     // Try to finalize again — should panic
@@ -269,11 +312,12 @@ fn test_double_finalization() {
         &randomness2,
         &sig_compressed2,
         &sig_uncompressed2,
+        &None,
     );
 }
 
 #[test]
-#[should_panic(expected = "Round mismatch")]
+#[should_panic(expected = "Error(Contract, #45)")]
 fn test_finalize_wrong_round() {
     let env = Env::default();
     env.mock_all_auths();
@@ -290,11 +334,11 @@ fn test_finalize_wrong_round() {
     let (randomness, sig_compressed, sig_uncompressed) = create_mock_entropy(&env);
 
     // Use wrong round number — should panic
-    client.finalize_splice(&cartridge_id, &99999u64, &randomness, &sig_compressed, &sig_uncompressed);
+    client.finalize_splice(&cartridge_id, &99999u64, &randomness, &sig_compressed, &sig_uncompressed, &None);
 }
 
 #[test]
-#[should_panic(expected = "Cartridge not found")]
+#[should_panic(expected = "Error(Contract, #11)")]
 fn test_finalize_nonexistent_cartridge() {
     let env = Env::default();
     env.mock_all_auths();
@@ -307,13 +351,13 @@ fn test_finalize_nonexistent_cartridge() {
     let (randomness, sig_compressed, sig_uncompressed) = create_mock_entropy(&env);
 
     // Cartridge 999 doesn't exist — should panic
-    client.finalize_splice(&999u32, &1u64, &randomness, &sig_compressed, &sig_uncompressed);
+    client.finalize_splice(&999u32, &1u64, &randomness, &sig_compressed, &sig_uncompressed, &None);
 }
 
 // ===== Input validation tests =====
 
 #[test]
-#[should_panic(expected = "Randomness must be 32 bytes")]
+#[should_panic(expected = "Error(Contract, #25)")]
 fn test_finalize_wrong_randomness_length() {
     let env = Env::default();
     env.mock_all_auths();
@@ -340,11 +384,12 @@ fn test_finalize_wrong_randomness_length() {
         &bad_randomness,
         &sig_compressed,
         &sig_uncompressed,
+        &None,
     );
 }
 
 #[test]
-#[should_panic(expected = "Compressed signature must be 48 bytes")]
+#[should_panic(expected = "Error(Contract, #26)")]
 fn test_finalize_wrong_compressed_sig_length() {
     let env = Env::default();
     env.mock_all_auths();
@@ -370,11 +415,12 @@ fn test_finalize_wrong_compressed_sig_length() {
         &randomness,
         &bad_sig_compressed,
         &sig_uncompressed,
+        &None,
     );
 }
 
 #[test]
-#[should_panic(expected = "Uncompressed signature must be 96 bytes")]
+#[should_panic(expected = "Error(Contract, #26)")]
 fn test_finalize_wrong_uncompressed_sig_length() {
     let env = Env::default();
     env.mock_all_auths();
@@ -400,6 +446,7 @@ fn test_finalize_wrong_uncompressed_sig_length() {
         &randomness,
         &sig_compressed,
         &bad_sig_uncompressed,
+        &None,
     );
 }
 
@@ -422,7 +469,7 @@ fn test_extend_ttl() {
 // ===== Constructor validation =====
 
 #[test]
-#[should_panic(expected = "Drand public key must be 192 bytes")]
+#[should_panic(expected = "Error(Contract, #27)")]
 fn test_constructor_rejects_wrong_pubkey_length() {
     let env = Env::default();
     let admin = Address::generate(&env);
@@ -432,7 +479,7 @@ fn test_constructor_rejects_wrong_pubkey_length() {
     let bad_pubkey = Bytes::from_array(&env, &[0x00; 96]);
     env.register(
         GeneSplicer,
-        (&admin, &xlm_token.address, 10u64, true, bad_pubkey),
+        (&admin, &xlm_token.address, 10u64, true, bad_pubkey, mock_provenance_hash(&env), MINT_FEE, &admin),
     );
 }
 
@@ -501,8 +548,8 @@ fn test_get_creatures_batch() {
     let (r, sc, su) = create_mock_entropy(&env);
     let (r2, sc2, su2) = create_mock_entropy(&env);
 
-    client.finalize_splice(&id1, &c1.splice_round, &r, &sc, &su);
-    client.finalize_splice(&id2, &c2.splice_round, &r2, &sc2, &su2);
+    client.finalize_splice(&id1, &c1.splice_round, &r, &sc, &su, &None);
+    client.finalize_splice(&id2, &c2.splice_round, &r2, &sc2, &su2, &None);
 
     // Batch fetch
     let mut ids = soroban_sdk::Vec::new(&env);
@@ -554,7 +601,7 @@ fn test_set_drand_public_key() {
 }
 
 #[test]
-#[should_panic(expected = "Drand public key must be 192 bytes")]
+#[should_panic(expected = "Error(Contract, #27)")]
 fn test_set_drand_public_key_wrong_length() {
     let env = Env::default();
     env.mock_all_auths();
@@ -572,12 +619,21 @@ fn test_set_drand_public_key_wrong_length() {
 
 /// Real drand quicknet public key (192 bytes uncompressed G2, CAP-0059 byte order)
 fn real_drand_pubkey(env: &Env) -> Bytes {
-    Bytes::from_slice(
-        env,
-        &hex::decode(
-            "03cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a01a714f2edb74119a2f2b0d5a7c75ba902d163700a61bc224ededd8e63aef7be1aaf8e93d7a9718b047ccddb3eb5d68b0e5db2b6bfbb01c867749cadffca88b36c24f3012ba09fc4d3022c5c37dce0f977d3adb5d183c7477c442b1f04515273"
-        ).unwrap(),
-    )
+    use splicers_drand_client::{decompress_g2, QUICKNET_PUBLIC_KEY};
+    Bytes::from_array(env, &decompress_g2(&QUICKNET_PUBLIC_KEY).unwrap())
+}
+
+/// Move the ledger clock past drand genesis, where production-mode mints are allowed
+fn past_drand_genesis(env: &Env) {
+    use soroban_sdk::testutils::Ledger;
+    env.ledger().with_mut(|li| li.timestamp = DRAND_GENESIS + 1_000_000);
+}
+
+/// Advance the ledger clock to when `round` is emitted, so its cartridges are ready to finalize
+fn wait_for_round(env: &Env, round: u64) {
+    use soroban_sdk::testutils::Ledger;
+    let round_time = crate::drand_round_time(round);
+    env.ledger().with_mut(|li| li.timestamp = li.timestamp.max(round_time));
 }
 
 #[test]
@@ -597,9 +653,10 @@ fn test_real_bls_verification() {
     let pubkey = real_drand_pubkey(&env);
     let contract_id = env.register(
         GeneSplicer,
-        (&admin, &xlm_token.address, 10u64, false, pubkey),
+        (&admin, &xlm_token.address, 10u64, false, pubkey, mock_provenance_hash(&env), MINT_FEE, &admin),
     );
     let client = GeneSplicerClient::new(&env, &contract_id);
+    past_drand_genesis(&env);
 
     // Mint a cartridge
     let cartridge_id = client.splice_genome(&user);
@@ -615,20 +672,9 @@ fn test_real_bls_verification() {
             &cartridge,
         );
     });
+    wait_for_round(&env, 27448023);
 
-    // Real drand round 27448023 data:
-    let randomness = Bytes::from_slice(
-        &env,
-        &hex::decode("f22d19a3d8cd3a181fe8155d051fe006a726b1fe0b18043bda3a2fe4c6c1e5d8").unwrap(),
-    );
-    let sig_compressed = Bytes::from_slice(
-        &env,
-        &hex::decode("967e8a7aa839aa8f672800bb50b1ee29dfa4757d120112c7b858b1f625193a41fb156ad7c69fefc644b9719f88d60313").unwrap(),
-    );
-    let sig_uncompressed = Bytes::from_slice(
-        &env,
-        &hex::decode("167e8a7aa839aa8f672800bb50b1ee29dfa4757d120112c7b858b1f625193a41fb156ad7c69fefc644b9719f88d603130165791da033fb75626a46b01aeb3e1207d87423db1b5de2dabeb60ee4cc227f750d10de8ec1f77dedd4f311586e5c3e").unwrap(),
-    );
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
 
     // This performs REAL BLS12-381 pairing verification on-chain
     let creature_id = client.finalize_splice(
@@ -637,6 +683,7 @@ fn test_real_bls_verification() {
         &randomness,
         &sig_compressed,
         &sig_uncompressed,
+        &None,
     );
 
     assert_eq!(creature_id, cartridge_id);
@@ -664,7 +711,7 @@ fn test_real_bls_rejects_invalid_signature() {
     let pubkey = real_drand_pubkey(&env);
     let contract_id = env.register(
         GeneSplicer,
-        (&admin, &xlm_token.address, 10u64, false, pubkey),
+        (&admin, &xlm_token.address, 10u64, false, pubkey, mock_provenance_hash(&env), MINT_FEE, &admin),
     );
     let client = GeneSplicerClient::new(&env, &contract_id);
 
@@ -677,6 +724,7 @@ fn test_real_bls_rejects_invalid_signature() {
             &cartridge,
         );
     });
+    wait_for_round(&env, 27448023);
 
     // Valid format but WRONG signature data (all 0x11)
     let randomness = Bytes::from_array(&env, &[0x42; 32]);
@@ -698,5 +746,7053 @@ fn test_real_bls_rejects_invalid_signature() {
         &randomness,
         &sig_compressed,
         &sig_uncompressed,
+        &None,
+    );
+}
+
+// ===== Batch event aggregation tests =====
+
+/// Count events emitted by `contract_id` whose first topic is the given event name
+fn count_events(env: &Env, contract_id: &Address, name: &str) -> u32 {
+    use soroban_sdk::xdr::{ContractEventBody, ScSymbol, ScVal};
+
+    let expected = ScVal::Symbol(ScSymbol(name.try_into().unwrap()));
+    let mut count = 0;
+    for event in env.events().all().filter_by_contract(contract_id).events() {
+        let ContractEventBody::V0(body) = &event.body;
+        if body.topics.first() == Some(&expected) {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn mock_creature(id: u32, owner: &Address) -> crate::Creature {
+    let gene = |id| crate::Gene {
+        id,
+        rarity: crate::GeneRarity::Normal,
+    };
+    crate::Creature {
+        id,
+        owner: owner.clone(),
+        skin_id: 0,
+        head_gene: gene(6),
+        body_gene: gene(7),
+        legs_gene: gene(8),
+        finalized_at: 0,
+        entropy_round: 42,
+        dev_minted: false,
+        collection_id: 0,
+    }
+}
+
+#[test]
+fn test_batch_minted_event_shape_by_size() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    for (size, per_item, aggregated) in [(1u32, 1u32, 0u32), (3, 3, 0), (10, 0, 1)] {
+        let mut skin_ids = soroban_sdk::Vec::new(&env);
+        for i in 0..size {
+            skin_ids.push_back(i % 10);
+        }
+        env.as_contract(&client.address, || {
+            GeneSplicer::publish_cartridges_minted(&env, &owner, 1, &skin_ids, crate::MINT_FEE);
+            assert_eq!(count_events(&env, &client.address, "cartridge_minted"), per_item);
+            assert_eq!(
+                count_events(&env, &client.address, "batch_cartridge_minted"),
+                aggregated
+            );
+        });
+    }
+}
+
+#[test]
+fn test_batch_finalized_event_shape_by_size() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    for (size, per_item, aggregated) in [(1u32, 1u32, 0u32), (3, 3, 0), (10, 0, 1)] {
+        let mut creatures = soroban_sdk::Vec::new(&env);
+        for id in 1..=size {
+            creatures.push_back(mock_creature(id, &owner));
+        }
+        env.as_contract(&client.address, || {
+            GeneSplicer::publish_creatures_finalized(&env, 42, &creatures);
+            assert_eq!(count_events(&env, &client.address, "creature_finalized"), per_item);
+            assert_eq!(
+                count_events(&env, &client.address, "batch_creature_finalized"),
+                aggregated
+            );
+        });
+    }
+}
+
+// ===== Finalization permit tests =====
+
+fn sign_permit(
+    env: &Env,
+    client: &GeneSplicerClient,
+    key: &ed25519_dalek::SigningKey,
+    cartridge_id: u32,
+    expires_at: u64,
+) -> crate::Permit {
+    use ed25519_dalek::Signer;
+
+    let message = client.permit_message(&cartridge_id, &expires_at);
+    let mut message_bytes = std::vec![0u8; message.len() as usize];
+    message.copy_into_slice(&mut message_bytes);
+    let signature = key.sign(&message_bytes).to_bytes();
+    crate::Permit {
+        cartridge_id,
+        expires_at,
+        signature: soroban_sdk::BytesN::from_array(env, &signature),
+    }
+}
+
+/// Mint a cartridge for `user`, register a session key for them, and return the key
+fn setup_permit<'a>(
+    env: &Env,
+) -> (GeneSplicerClient<'a>, Address, u32, ed25519_dalek::SigningKey) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let user = Address::generate(env);
+    let xlm_token = create_xlm_token(env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(env, &admin, &xlm_token.address, true);
+
+    let cartridge_id = client.splice_genome(&user);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = soroban_sdk::BytesN::from_array(env, &key.verifying_key().to_bytes());
+    client.register_session_key(&user, &public_key);
+    assert_eq!(client.get_session_key(&user), Some(public_key));
+
+    (client, user, cartridge_id, key)
+}
+
+#[test]
+fn test_finalize_with_permit() {
+    let env = Env::default();
+    let (client, user, cartridge_id, key) = setup_permit(&env);
+    let relayer = Address::generate(&env);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+
+    let permit = sign_permit(&env, &client, &key, cartridge_id, 1_000);
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_with_permit(&relayer, &cartridge_id, &round, &r, &sc, &su, &None, &Some(permit));
+
+    // Creature belongs to the owner, not the relayer
+    assert_eq!(client.get_creature(&cartridge_id).unwrap().owner, user);
+    assert_eq!(client.get_user_creatures(&relayer).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #100)")]
+fn test_finalize_with_expired_permit() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let (client, _user, cartridge_id, key) = setup_permit(&env);
+    let relayer = Address::generate(&env);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+
+    let permit = sign_permit(&env, &client, &key, cartridge_id, 1_000);
+    env.ledger().set_timestamp(1_001);
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_with_permit(&relayer, &cartridge_id, &round, &r, &sc, &su, &None, &Some(permit));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #99)")]
+fn test_finalize_with_permit_wrong_cartridge() {
+    let env = Env::default();
+    let (client, user, cartridge_id, key) = setup_permit(&env);
+    let relayer = Address::generate(&env);
+    let other_id = client.splice_genome(&user);
+    let round = client.get_cartridge(&other_id).unwrap().splice_round;
+
+    // Permit signed for the first cartridge, presented for the second
+    let permit = sign_permit(&env, &client, &key, cartridge_id, 1_000);
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_with_permit(&relayer, &other_id, &round, &r, &sc, &su, &None, &Some(permit));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #102)")]
+fn test_finalize_with_permit_replay() {
+    let env = Env::default();
+    let (client, _user, cartridge_id, key) = setup_permit(&env);
+    let relayer = Address::generate(&env);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+
+    let permit = sign_permit(&env, &client, &key, cartridge_id, 1_000);
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_with_permit(&relayer, &cartridge_id, &round, &r, &sc, &su, &None, &Some(permit.clone()));
+    client.finalize_with_permit(&relayer, &cartridge_id, &round, &r, &sc, &su, &None, &Some(permit));
+}
+
+#[test]
+fn test_finalize_with_permit_without_session_key() {
+    let env = Env::default();
+    let (client, user, cartridge_id, key) = setup_permit(&env);
+    let relayer = Address::generate(&env);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+
+    let permit = sign_permit(&env, &client, &key, cartridge_id, 1_000);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().remove(&crate::DataKey::SessionKey(user.clone()));
+    });
+    let (r, sc, su) = create_mock_entropy(&env);
+    assert_eq!(
+        client.try_finalize_with_permit(&relayer, &cartridge_id, &round, &r, &sc, &su, &None, &Some(permit)),
+        Err(Ok(contract_error(PermitError::NoSessionKey)))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_finalize_with_permit_wrong_key() {
+    let env = Env::default();
+    let (client, _user, cartridge_id, _key) = setup_permit(&env);
+    let relayer = Address::generate(&env);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+
+    // Signed by a key that was never registered for the owner
+    let attacker_key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    let permit = sign_permit(&env, &client, &attacker_key, cartridge_id, 1_000);
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_with_permit(&relayer, &cartridge_id, &round, &r, &sc, &su, &None, &Some(permit));
+}
+
+#[test]
+fn test_finalize_with_permit_falls_back_to_finalizer_auth() {
+    use soroban_sdk::IntoVal;
+    use soroban_sdk::testutils::{AuthorizedFunction, AuthorizedInvocation};
+
+    let env = Env::default();
+    let (client, user, cartridge_id, _key) = setup_permit(&env);
+    let relayer = Address::generate(&env);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let (r, sc, su) = create_mock_entropy(&env);
+
+    client.finalize_with_permit(&relayer, &cartridge_id, &round, &r, &sc, &su, &None, &None);
+
+    // The finalizer signed the canonical `finalize` tuple, not the entrypoint's arguments
+    let finalize_args: soroban_sdk::Vec<soroban_sdk::Val> =
+        (soroban_sdk::symbol_short!("finalize"), cartridge_id).into_val(&env);
+    assert!(env.auths().contains(&(
+        user.clone(),
+        AuthorizedInvocation {
+            function: AuthorizedFunction::Contract((
+                client.address.clone(),
+                soroban_sdk::Symbol::new(&env, "finalize_with_permit"),
+                finalize_args,
+            )),
+            sub_invocations: std::vec![],
+        }
+    )));
+    assert_eq!(client.get_creature(&cartridge_id).unwrap().owner, user);
+}
+
+// ===== Smart wallet auth tests =====
+
+/// Custom account contract whose policy allows the `finalize` action and nothing else
+mod finalize_only_wallet {
+    use soroban_sdk::{
+        auth::{Context, CustomAccountInterface},
+        contract, contracterror, contractimpl,
+        crypto::Hash,
+        symbol_short, Env, Symbol, TryFromVal, Vec,
+    };
+
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    pub enum WalletError {
+        ActionNotAllowed = 1,
+    }
+
+    #[contract]
+    pub struct FinalizeOnlyWallet;
+
+    #[contractimpl]
+    impl CustomAccountInterface for FinalizeOnlyWallet {
+        type Signature = ();
+        type Error = WalletError;
+
+        fn __check_auth(
+            env: Env,
+            _payload: Hash<32>,
+            _signature: (),
+            contexts: Vec<Context>,
+        ) -> Result<(), WalletError> {
+            for context in contexts.iter() {
+                let Context::Contract(call) = context else {
+                    return Err(WalletError::ActionNotAllowed);
+                };
+                let action = call
+                    .args
+                    .get(0)
+                    .and_then(|arg| Symbol::try_from_val(&env, &arg).ok());
+                if action != Some(symbol_short!("finalize")) {
+                    return Err(WalletError::ActionNotAllowed);
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Authorization entry for `wallet` covering one call with the given auth args
+fn wallet_auth(
+    env: &Env,
+    wallet: &Address,
+    contract: &Address,
+    fn_name: &str,
+    args: soroban_sdk::Vec<soroban_sdk::Val>,
+    nonce: i64,
+) -> soroban_sdk::xdr::SorobanAuthorizationEntry {
+    use soroban_sdk::xdr::{
+        InvokeContractArgs, ScSymbol, ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry,
+        SorobanAuthorizedFunction, SorobanAuthorizedInvocation, SorobanCredentials,
+    };
+    use soroban_sdk::TryFromVal;
+
+    let args: std::vec::Vec<ScVal> = args
+        .iter()
+        .map(|arg| ScVal::try_from_val(env, &arg).unwrap())
+        .collect();
+    SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: wallet.into(),
+            nonce,
+            signature_expiration_ledger: 1_000,
+            signature: ScVal::Void,
+        }),
+        root_invocation: SorobanAuthorizedInvocation {
+            function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+                contract_address: contract.into(),
+                function_name: ScSymbol(fn_name.try_into().unwrap()),
+                args: args.try_into().unwrap(),
+            }),
+            sub_invocations: Default::default(),
+        },
+    }
+}
+
+#[test]
+fn test_smart_wallet_policy_sees_owner_action() {
+    use finalize_only_wallet::FinalizeOnlyWallet;
+    use soroban_sdk::{symbol_short, IntoVal};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    let wallet = env.register(FinalizeOnlyWallet, ());
+    xlm_token.mint(&wallet, &100_000_000);
+    let listed_id = client.splice_genome(&wallet);
+    let cartridge_id = client.splice_genome(&wallet);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let (r, sc, su) = create_mock_entropy(&env);
+
+    // Listing is signed as ("list", cartridge_id, price), which the wallet refuses
+    let list_args = (symbol_short!("list"), listed_id, 5_000_000i128).into_val(&env);
+    env.set_auths(&[wallet_auth(&env, &wallet, &client.address, "sell_finalization_right", list_args, 1)]);
+    assert!(client.try_sell_finalization_right(&wallet, &listed_id, &5_000_000).is_err());
+    assert_eq!(client.get_finalization_right_price(&listed_id), None);
+
+    // Finalization is signed as ("finalize", cartridge_id), which it allows
+    let finalize_args = (symbol_short!("finalize"), cartridge_id).into_val(&env);
+    env.set_auths(&[wallet_auth(&env, &wallet, &client.address, "finalize_splice", finalize_args, 2)]);
+    client.finalize_splice(&cartridge_id, &round, &r, &sc, &su, &None);
+    assert_eq!(client.get_creature(&cartridge_id).unwrap().owner, wallet);
+
+    // Retirement is signed as ("burn", creature_id), which it refuses
+    let burn_args = (symbol_short!("burn"), cartridge_id).into_val(&env);
+    env.set_auths(&[wallet_auth(&env, &wallet, &client.address, "retire_creature", burn_args, 3)]);
+    assert!(client.try_retire_creature(&wallet, &cartridge_id).is_err());
+    assert!(!client.is_creature_retired(&cartridge_id));
+}
+
+// ===== Drand chain hash tests =====
+
+const DEFAULT_CHAIN_HASH_HEX: &str =
+    "8990e7a9aaed2ffed73dbd7092123d6f289930540d7651336225dc172e51b2ce";
+
+#[test]
+fn test_drand_chain_hash_defaults_to_quicknet() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let expected = Bytes::from_array(&env, &splicers_drand_client::QUICKNET_CHAIN_HASH);
+    assert_eq!(Bytes::from(client.get_drand_chain_hash()), expected);
+
+    let new_hash = soroban_sdk::BytesN::from_array(&env, &[0x11; 32]);
+    client.set_drand_chain_hash(&new_hash);
+    assert_eq!(client.get_drand_chain_hash(), new_hash);
+}
+
+#[test]
+fn test_finalize_with_matching_chain_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let cartridge_id = client.splice_genome(&user);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let (r, sc, su) = create_mock_entropy(&env);
+    let chain_hash = Bytes::from_array(&env, &splicers_drand_client::QUICKNET_CHAIN_HASH);
+
+    client.finalize_splice(&cartridge_id, &round, &r, &sc, &su, &Some(chain_hash));
+    assert!(client.get_cartridge(&cartridge_id).unwrap().finalized);
+}
+
+#[test]
+fn test_finalize_with_wrong_chain_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let cartridge_id = client.splice_genome(&user);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let (r, sc, su) = create_mock_entropy(&env);
+
+    // Beacon fetched from the drand default chain instead of quicknet
+    let chain_hash = Bytes::from_slice(&env, &hex::decode(DEFAULT_CHAIN_HASH_HEX).unwrap());
+    let result = client.try_finalize_splice(&cartridge_id, &round, &r, &sc, &su, &Some(chain_hash));
+    assert_eq!(result, Err(Ok(contract_error(Error::WrongDrandNetwork))));
+    assert!(!client.get_cartridge(&cartridge_id).unwrap().finalized);
+}
+
+// ===== Dev mode / public key consistency tests =====
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_constructor_rejects_zero_key_without_dev_mode() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+
+    // dev_mode=false with the all-zero mock key would fail every real verification
+    setup_contract(&env, &admin, &xlm_token.address, false);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_constructor_rejects_flagged_key_without_dev_mode() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+
+    // Compression/infinity flag bits set — not an uncompressed affine encoding
+    let bad_pubkey = Bytes::from_array(&env, &[0xff; 192]);
+    env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, bad_pubkey, mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+}
+
+#[test]
+fn test_dev_mode_deployment_is_flagged() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    assert_eq!(count_events(&env, &client.address, "dev_mode_active"), 1);
+    assert!(!client.is_production_ready());
+}
+
+#[test]
+fn test_production_deployment_is_ready() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+    assert_eq!(count_events(&env, &contract_id, "dev_mode_active"), 0);
+    assert!(client.is_production_ready());
+}
+
+#[test]
+fn test_rotation_rejects_zero_key_without_dev_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+
+    let result = client.try_set_drand_public_key(&create_mock_drand_pubkey(&env));
+    assert_eq!(result, Err(Ok(contract_error(Error::InvalidDrandPublicKey))));
+    assert_eq!(client.get_drand_public_key(), real_drand_pubkey(&env));
+    assert!(client.is_production_ready());
+}
+
+// ===== Pause flag tests =====
+
+#[test]
+fn test_pause_flag_combinations() {
+    for (mint_paused, finalize_paused) in [(false, false), (true, false), (false, true), (true, true)] {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let xlm_token = create_xlm_token(&env, &admin);
+        xlm_token.mint(&user, &100_000_000);
+        let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+        // Mint one cartridge before pausing so there is something to finalize
+        let cartridge_id = client.splice_genome(&user);
+        let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+
+        client.set_mint_paused(&mint_paused);
+        client.set_finalize_paused(&finalize_paused);
+        assert_eq!(client.is_mint_paused(), mint_paused);
+        assert_eq!(client.is_finalize_paused(), finalize_paused);
+
+        let mint = client.try_splice_genome(&user);
+        if mint_paused {
+            assert_eq!(mint, Err(Ok(contract_error(Error::MintPaused))));
+        } else {
+            assert!(mint.is_ok());
+        }
+
+        let (r, sc, su) = create_mock_entropy(&env);
+        let finalize = client.try_finalize_splice(&cartridge_id, &round, &r, &sc, &su, &None);
+        if finalize_paused {
+            assert_eq!(finalize, Err(Ok(contract_error(Error::FinalizePaused))));
+        } else {
+            assert!(finalize.is_ok());
+        }
+    }
+}
+
+#[test]
+fn test_full_pause_sets_both_flags() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.set_paused(&true);
+    assert_eq!(count_events(&env, &client.address, "mint_pause_changed"), 1);
+    assert_eq!(count_events(&env, &client.address, "finalize_pause_changed"), 1);
+    assert!(client.is_mint_paused());
+    assert!(client.is_finalize_paused());
+
+    client.set_paused(&false);
+    assert!(!client.is_mint_paused());
+    assert!(!client.is_finalize_paused());
+}
+
+#[test]
+fn test_maintenance_window_sweep() {
+    use soroban_sdk::testutils::Ledger;
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &1_000_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let now = env.ledger().timestamp();
+    let (start, end) = (now + 100, now + 200);
+    assert_eq!(
+        client.try_schedule_maintenance(&end, &start, &soroban_sdk::symbol_short!("upgrade")),
+        Err(Ok(contract_error(Error::InvalidMaintenanceWindow)))
     );
+    client.schedule_maintenance(&start, &end, &soroban_sdk::symbol_short!("upgrade"));
+    assert_eq!(count_events(&env, &client.address, "maintenance_scheduled"), 1);
+    assert_eq!(client.get_maintenance().unwrap().end, end);
+
+    // One cartridge per step, minted beforehand so finalize can be tried at each
+    let cartridge_ids: std::vec::Vec<u32> = (0..5).map(|_| client.splice_genome(&user)).collect();
+    for (step, (at, open)) in [(start - 1, false), (start, true), (end - 1, true), (end, false), (end + 1, false)]
+        .into_iter()
+        .enumerate()
+    {
+        env.ledger().with_mut(|li| li.timestamp = at);
+        let mint = client.try_splice_genome(&user);
+        let cartridge_id = cartridge_ids[step];
+        let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+        let (r, sc, su) = create_mock_entropy(&env);
+        let finalize = client.try_finalize_splice(&cartridge_id, &round, &r, &sc, &su, &None);
+        if open {
+            assert_eq!(mint, Err(Ok(contract_error(Error::MaintenanceInProgress))));
+            assert_eq!(finalize, Err(Ok(contract_error(Error::MaintenanceInProgress))));
+            assert_eq!(client.can_finalize(&cartridge_id, &round), (false, Error::MaintenanceInProgress as u32));
+        } else {
+            assert!(mint.is_ok());
+            assert!(finalize.is_ok());
+        }
+    }
+
+    // Past windows read as None and can't be cancelled or scheduled
+    assert_eq!(client.get_maintenance(), None);
+    assert_eq!(client.try_cancel_maintenance(), Err(Ok(contract_error(Error::NoMaintenanceScheduled))));
+    let now = env.ledger().timestamp();
+    assert_eq!(
+        client.try_schedule_maintenance(&(now - 10), &now, &soroban_sdk::symbol_short!("late")),
+        Err(Ok(contract_error(Error::InvalidMaintenanceWindow)))
+    );
+
+    // Cancelling a running window reopens minting
+    client.schedule_maintenance(&now, &(now + 50), &soroban_sdk::symbol_short!("hotfix"));
+    assert!(client.try_splice_genome(&user).is_err());
+    client.cancel_maintenance();
+    assert_eq!(count_events(&env, &client.address, "maintenance_cancelled"), 1);
+    assert_eq!(client.get_maintenance(), None);
+    client.splice_genome(&user);
+
+    env.set_auths(&[]);
+    assert!(client.try_schedule_maintenance(&now, &(now + 50), &soroban_sdk::symbol_short!("x")).is_err());
+}
+
+// ===== Skin supply tests =====
+
+#[test]
+fn test_skin_caps_drive_to_exhaustion() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &1_000_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_skin_count(&3u64);
+
+    // Skin 0: one left, skin 1: disabled, skin 2: two left
+    client.set_skin_cap(&0, &Some(1));
+    client.set_skin_cap(&1, &Some(0));
+    client.set_skin_cap(&2, &Some(2));
+    assert_eq!(client.get_available_skins(), soroban_sdk::vec![&env, 0, 2]);
+
+    for _ in 0..3 {
+        let id = client.splice_genome(&user);
+        assert_ne!(client.get_cartridge(&id).unwrap().skin_id, 1);
+    }
+
+    assert_eq!(client.get_skin_supply(&0), (1, Some(1)));
+    assert_eq!(client.get_skin_supply(&1), (0, Some(0)));
+    assert_eq!(client.get_skin_supply(&2), (2, Some(2)));
+    assert_eq!(client.get_available_skins().len(), 0);
+
+    let result = client.try_splice_genome(&user);
+    assert_eq!(result, Err(Ok(contract_error(Error::AllSkinsExhausted))));
+
+    // Lifting a cap puts the skin back into the pool
+    client.set_skin_cap(&1, &None);
+    assert_eq!(client.get_available_skins(), soroban_sdk::vec![&env, 1]);
+    let id = client.splice_genome(&user);
+    assert_eq!(client.get_cartridge(&id).unwrap().skin_id, 1);
+    assert_eq!(client.get_skin_supply(&1), (1, None));
+}
+
+#[test]
+fn test_skin_supply_tracks_uncapped_mints() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let mut total = 0;
+    for _ in 0..5 {
+        client.splice_genome(&user);
+    }
+    for skin_id in 0..10u32 {
+        let (minted, cap) = client.get_skin_supply(&skin_id);
+        assert_eq!(cap, None);
+        total += minted;
+    }
+    assert_eq!(total, 5);
+    assert_eq!(client.get_available_skins().len(), 10);
+}
+
+// ===== Existence probe tests =====
+
+#[test]
+fn test_existence_probes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // Never existed
+    assert!(!client.has_cartridge(&1));
+    assert!(!client.has_creature(&1));
+    assert!(!client.is_finalized(&1));
+
+    // Minted but not finalized
+    let cartridge_id = client.splice_genome(&user);
+    assert!(client.has_cartridge(&cartridge_id));
+    assert!(!client.has_creature(&cartridge_id));
+    assert!(!client.is_finalized(&cartridge_id));
+
+    // Finalized — probes agree with the full structs
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_splice(&cartridge_id, &round, &r, &sc, &su, &None);
+    assert!(client.has_creature(&cartridge_id));
+    assert!(client.is_finalized(&cartridge_id));
+    assert!(client.get_cartridge(&cartridge_id).unwrap().finalized);
+    assert!(!client.has_cartridge(&999));
+}
+
+// ===== Limit tests =====
+
+#[test]
+fn test_batch_getters_enforce_read_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let limit = client.get_limits().max_batch_read;
+    assert_eq!(limit, crate::MAX_BATCH_READ);
+
+    let mut ids = soroban_sdk::Vec::new(&env);
+    for id in 1..=limit {
+        ids.push_back(id);
+    }
+    // Exactly at the limit is accepted
+    assert_eq!(client.get_cartridges_batch(&ids).len(), limit);
+    assert_eq!(client.get_creatures_batch(&ids).len(), limit);
+
+    // One over is rejected
+    ids.push_back(limit + 1);
+    assert_eq!(
+        client.try_get_cartridges_batch(&ids),
+        Err(Ok(contract_error(ReadLimitError::MaxBatchReadExceeded)))
+    );
+    assert_eq!(
+        client.try_get_creatures_batch(&ids),
+        Err(Ok(contract_error(ReadLimitError::MaxBatchReadExceeded)))
+    );
+}
+
+// ===== Governance tests =====
+
+/// Write a creature with the given gene rarities directly into storage and index it for its owner
+fn store_creature(
+    env: &Env,
+    contract_id: &Address,
+    id: u32,
+    owner: &Address,
+    rarities: [crate::GeneRarity; 3],
+) {
+    let [head, body, legs] = rarities;
+    let mut creature = mock_creature(id, owner);
+    creature.head_gene.rarity = head;
+    creature.body_gene.rarity = body;
+    creature.legs_gene.rarity = legs;
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::Creature(id), &creature);
+        let key = crate::DataKey::UserCreatures(owner.clone());
+        let mut ids: soroban_sdk::Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(soroban_sdk::Vec::new(env));
+        ids.push_back(id);
+        env.storage().persistent().set(&key, &ids);
+    });
+}
+
+#[test]
+fn test_voting_weight_math() {
+    use crate::GeneRarity::{Legendary, Normal, Rare};
+
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    assert_eq!(client.get_voting_weight(&user), 0);
+    store_creature(&env, &client.address, 1, &user, [Normal, Normal, Normal]);
+    assert_eq!(client.get_voting_weight(&user), 1);
+    store_creature(&env, &client.address, 2, &user, [Rare, Rare, Rare]);
+    assert_eq!(client.get_voting_weight(&user), 2);
+    // A single Legendary gene makes the whole creature count 3x
+    store_creature(&env, &client.address, 3, &user, [Normal, Legendary, Rare]);
+    assert_eq!(client.get_voting_weight(&user), 5);
+}
+
+#[test]
+fn test_snapshot_immutable_after_holdings_change() {
+    use crate::GeneRarity::{Legendary, Normal};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    store_creature(&env, &client.address, 1, &user, [Normal, Normal, Normal]);
+    let proposal_id = client.create_proposal();
+    client.snapshot_weights(&proposal_id, &soroban_sdk::vec![&env, user.clone()]);
+    assert_eq!(client.get_snapshot_weight(&proposal_id, &user), Some(1));
+
+    // Holdings grow after the snapshot; re-snapshotting doesn't overwrite
+    store_creature(&env, &client.address, 2, &user, [Legendary, Normal, Normal]);
+    assert_eq!(client.get_voting_weight(&user), 4);
+    client.snapshot_weights(&proposal_id, &soroban_sdk::vec![&env, user.clone()]);
+    assert_eq!(client.get_snapshot_weight(&proposal_id, &user), Some(1));
+
+    client.cast_vote(&user, &proposal_id, &true);
+    assert_eq!(client.tally(&proposal_id), (1, 0));
+}
+
+#[test]
+fn test_cast_vote_and_tally() {
+    use crate::GeneRarity::{Legendary, Normal};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    store_creature(&env, &client.address, 1, &alice, [Legendary, Normal, Normal]);
+    store_creature(&env, &client.address, 2, &bob, [Normal, Normal, Normal]);
+    store_creature(&env, &client.address, 3, &bob, [Normal, Normal, Normal]);
+
+    let proposal_id = client.create_proposal();
+    client.snapshot_weights(
+        &proposal_id,
+        &soroban_sdk::vec![&env, alice.clone(), bob.clone()],
+    );
+
+    client.cast_vote(&alice, &proposal_id, &true);
+    client.cast_vote(&bob, &proposal_id, &false);
+    assert_eq!(client.tally(&proposal_id), (3, 2));
+
+    // Double vote and unsnapshotted voters are rejected
+    assert_eq!(
+        client.try_cast_vote(&alice, &proposal_id, &false),
+        Err(Ok(contract_error(GovernanceError::AlreadyVoted)))
+    );
+    assert_eq!(
+        client.try_cast_vote(&carol, &proposal_id, &true),
+        Err(Ok(contract_error(GovernanceError::NotInSnapshot)))
+    );
+    assert_eq!(
+        client.try_tally(&99),
+        Err(Ok(contract_error(GovernanceError::ProposalNotFound)))
+    );
+    assert_eq!(client.tally(&proposal_id), (3, 2));
+}
+
+#[test]
+fn test_snapshot_batch_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let proposal_id = client.create_proposal();
+    let limit = client.get_limits().max_snapshot_batch;
+    let mut voters = soroban_sdk::Vec::new(&env);
+    for _ in 0..limit {
+        voters.push_back(Address::generate(&env));
+    }
+    client.snapshot_weights(&proposal_id, &voters);
+
+    voters.push_back(Address::generate(&env));
+    assert_eq!(
+        client.try_snapshot_weights(&proposal_id, &voters),
+        Err(Ok(contract_error(GovernanceError::MaxSnapshotBatchExceeded)))
+    );
+}
+
+// ===== Finalization right sale tests =====
+
+#[test]
+fn test_finalization_right_sale_diverges_ownership() {
+    use soroban_sdk::testutils::AuthorizedFunction;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&seller, &100_000_000);
+    xlm_token.mint(&buyer, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let cartridge_id = client.splice_genome(&seller);
+    client.sell_finalization_right(&seller, &cartridge_id, &25_000_000);
+    assert_eq!(client.get_finalization_right_price(&cartridge_id), Some(25_000_000));
+
+    client.buy_finalization_right(&buyer, &cartridge_id);
+    assert_eq!(xlm_token.balance(&buyer), 75_000_000);
+    assert_eq!(xlm_token.balance(&seller), 90_000_000 + 25_000_000);
+    assert_eq!(client.get_finalization_right_price(&cartridge_id), None);
+
+    let cartridge = client.get_cartridge(&cartridge_id).unwrap();
+    assert_eq!(cartridge.owner, seller);
+    assert_eq!(cartridge.finalize_beneficiary, Some(buyer.clone()));
+
+    // The buyer authorizes finalization and receives the creature
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_splice(&cartridge_id, &cartridge.splice_round, &r, &sc, &su, &None);
+    let (authorizer, invocation) = env.auths().first().unwrap().clone();
+    assert_eq!(authorizer, buyer);
+    assert!(matches!(invocation.function, AuthorizedFunction::Contract(_)));
+
+    assert_eq!(client.get_creature(&cartridge_id).unwrap().owner, buyer);
+    assert_eq!(client.get_user_creatures(&buyer), soroban_sdk::vec![&env, cartridge_id]);
+    assert_eq!(client.get_user_creatures(&seller).len(), 0);
+
+    // Cartridge provenance still points at the minter; the right is cleared on use
+    let cartridge = client.get_cartridge(&cartridge_id).unwrap();
+    assert_eq!(cartridge.owner, seller);
+    assert_eq!(cartridge.finalize_beneficiary, None);
+    assert_eq!(client.get_user_cartridges(&seller), soroban_sdk::vec![&env, cartridge_id]);
+}
+
+#[test]
+fn test_finalization_right_revoke_and_guards() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&seller, &100_000_000);
+    xlm_token.mint(&buyer, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let cartridge_id = client.splice_genome(&seller);
+
+    // Only the owner can list, and only for a positive price
+    assert_eq!(
+        client.try_sell_finalization_right(&buyer, &cartridge_id, &1),
+        Err(Ok(contract_error(Error::NotCartridgeOwner)))
+    );
+    assert_eq!(
+        client.try_sell_finalization_right(&seller, &cartridge_id, &0),
+        Err(Ok(contract_error(FinalizationRightError::InvalidPrice)))
+    );
+
+    // Revoked before purchase — nothing to buy
+    client.sell_finalization_right(&seller, &cartridge_id, &5);
+    client.revoke_finalization_right(&seller, &cartridge_id);
+    assert_eq!(
+        client.try_buy_finalization_right(&buyer, &cartridge_id),
+        Err(Ok(contract_error(FinalizationRightError::NoFinalizationRightOffer)))
+    );
+
+    // Once sold, it can't be listed or revoked again
+    client.sell_finalization_right(&seller, &cartridge_id, &5);
+    client.buy_finalization_right(&buyer, &cartridge_id);
+    assert_eq!(
+        client.try_sell_finalization_right(&seller, &cartridge_id, &5),
+        Err(Ok(contract_error(FinalizationRightError::FinalizationRightSold)))
+    );
+    assert_eq!(
+        client.try_revoke_finalization_right(&seller, &cartridge_id),
+        Err(Ok(contract_error(FinalizationRightError::NoFinalizationRightOffer)))
+    );
+}
+
+#[test]
+fn test_splice_and_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let flipper = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&flipper, &100_000_000);
+    xlm_token.mint(&buyer, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    assert_eq!(
+        client.try_splice_and_list(&flipper, &0),
+        Err(Ok(contract_error(FinalizationRightError::InvalidPrice)))
+    );
+
+    // One invocation mints and lists
+    let cartridge_id = client.splice_and_list(&flipper, &30_000_000);
+    assert_eq!(count_events(&env, &client.address, "cartridge_minted"), 1);
+    assert_eq!(count_events(&env, &client.address, "finalization_right_listed"), 1);
+    assert_eq!(client.get_cartridge(&cartridge_id).unwrap().owner, flipper);
+    assert_eq!(client.get_finalization_right_price(&cartridge_id), Some(30_000_000));
+    assert_eq!(xlm_token.balance(&flipper), 90_000_000);
+
+    client.buy_finalization_right(&buyer, &cartridge_id);
+    assert_eq!(xlm_token.balance(&flipper), 120_000_000);
+
+    let cartridge = client.get_cartridge(&cartridge_id).unwrap();
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_splice(&cartridge_id, &cartridge.splice_round, &r, &sc, &su, &None);
+    assert_eq!(client.get_creature(&cartridge_id).unwrap().owner, buyer);
+}
+
+// ===== Stranded token withdrawal tests =====
+
+#[test]
+fn test_admin_withdraw_token_respects_liabilities() {
+    use crate::Pool;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // Pools hold 300 at the contract; 50 more were sent there by mistake
+    xlm_token.mint(&client.address, &350);
+    env.as_contract(&client.address, || {
+        crate::accounting::add_liability(&env, Pool::Insurance, &xlm_token.address, 100);
+        crate::accounting::add_liability(&env, Pool::Bounty, &xlm_token.address, 120);
+        crate::accounting::add_liability(&env, Pool::Escrow, &xlm_token.address, 80);
+    });
+    assert_eq!(client.get_liability(&Pool::Bounty, &xlm_token.address), 120);
+    assert_eq!(client.get_total_liabilities(&xlm_token.address), 300);
+    assert_eq!(client.get_free_balance(&xlm_token.address), 50);
+
+    // Anything beyond the free balance is refused
+    assert_eq!(
+        client.try_admin_withdraw_token(&xlm_token.address, &recipient, &51),
+        Err(Ok(contract_error(AccountingError::InsufficientFreeBalance)))
+    );
+    assert_eq!(
+        client.try_admin_withdraw_token(&xlm_token.address, &recipient, &0),
+        Err(Ok(contract_error(Error::InvalidAmount)))
+    );
+
+    client.admin_withdraw_token(&xlm_token.address, &recipient, &30);
+    assert_eq!(count_events(&env, &client.address, "stranded_tokens_withdrawn"), 1);
+    assert_eq!(xlm_token.balance(&recipient), 30);
+    assert_eq!(client.get_free_balance(&xlm_token.address), 20);
+
+    // Paying out a pool shrinks its liability along with the balance
+    env.as_contract(&client.address, || {
+        crate::accounting::release_liability(&env, Pool::Escrow, &xlm_token.address, 80);
+    });
+    xlm_token.burn(&client.address, &80);
+    assert_eq!(client.get_liability(&Pool::Escrow, &xlm_token.address), 0);
+    assert_eq!(client.get_free_balance(&xlm_token.address), 20);
+
+    client.admin_withdraw_token(&xlm_token.address, &recipient, &20);
+    assert_eq!(xlm_token.balance(&client.address), 220);
+    assert_eq!(client.get_free_balance(&xlm_token.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #107)")]
+fn test_release_beyond_liability_rejected() {
+    use crate::Pool;
+
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    env.as_contract(&client.address, || {
+        crate::accounting::add_liability(&env, Pool::BurnRewards, &xlm_token.address, 50);
+        crate::accounting::release_liability(&env, Pool::BurnRewards, &xlm_token.address, 51);
+    });
+}
+
+#[test]
+fn test_solvency_report() {
+    use crate::{Pool, PoolLiability};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // Fund the insurance and refund pools, then escrow a payment
+    xlm_token.mint(&client.address, &1_000);
+    env.as_contract(&client.address, || {
+        crate::accounting::add_liability(&env, Pool::Insurance, &xlm_token.address, 400);
+        crate::accounting::add_liability(&env, Pool::Refund, &xlm_token.address, 150);
+    });
+    xlm_token.mint(&client.address, &250);
+    env.as_contract(&client.address, || {
+        crate::accounting::add_liability(&env, Pool::Escrow, &xlm_token.address, 250);
+    });
+
+    let report = client.get_solvency();
+    assert_eq!(report.balance, 1_250);
+    assert_eq!(
+        report.liabilities,
+        soroban_sdk::vec![
+            &env,
+            PoolLiability { pool: Pool::Insurance, amount: 400 },
+            PoolLiability { pool: Pool::Bounty, amount: 0 },
+            PoolLiability { pool: Pool::Escrow, amount: 250 },
+            PoolLiability { pool: Pool::Refund, amount: 150 },
+            PoolLiability { pool: Pool::BurnRewards, amount: 0 },
+        ]
+    );
+    assert_eq!(report.total_liabilities, 800);
+    assert_eq!(report.free_balance, 450);
+    assert_eq!(report.free_balance, client.get_free_balance(&xlm_token.address));
+
+    // Withdrawal is bounded by the reported free balance
+    let recipient = Address::generate(&env);
+    assert_eq!(
+        client.try_admin_withdraw_token(&xlm_token.address, &recipient, &451),
+        Err(Ok(contract_error(AccountingError::InsufficientFreeBalance)))
+    );
+    client.admin_withdraw_token(&xlm_token.address, &recipient, &450);
+    let report = client.get_solvency();
+    assert_eq!((report.balance, report.free_balance), (800, 0));
+}
+
+#[test]
+fn test_admin_withdraw_token_unaccounted_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let stray_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // A token no pool tracks is entirely free
+    stray_token.mint(&client.address, &1_000);
+    assert_eq!(client.get_total_liabilities(&stray_token.address), 0);
+
+    client.admin_withdraw_token(&stray_token.address, &recipient, &1_000);
+    assert_eq!(stray_token.balance(&recipient), 1_000);
+    assert_eq!(stray_token.balance(&client.address), 0);
+}
+
+// ===== Capabilities table tests =====
+
+/// Collect every `pub fn name(env: Env, ...)` entrypoint declared in the crate's sources
+fn declared_entrypoints() -> std::vec::Vec<std::string::String> {
+    let src_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut names = std::vec::Vec::new();
+    for entry in std::fs::read_dir(src_dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.file_name().unwrap() == "test.rs" {
+            continue;
+        }
+        let source = std::fs::read_to_string(&path).unwrap();
+        for (i, _) in source.match_indices("\n    pub fn ") {
+            let rest = &source[i + "\n    pub fn ".len()..];
+            let open = rest.find('(').unwrap();
+            let args = rest[open + 1..].trim_start();
+            if args.starts_with("env: Env") || args.starts_with("_env: Env") {
+                names.push(std::string::String::from(&rest[..open]));
+            }
+        }
+    }
+    names.sort();
+    names
+}
+
+#[test]
+fn test_capabilities_cover_every_entrypoint() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let listed = client.get_capabilities();
+    let declared = declared_entrypoints();
+
+    // Every entrypoint is listed, and nothing else is
+    for name in declared.iter() {
+        let symbol = soroban_sdk::Symbol::new(&env, name);
+        assert!(
+            listed.iter().any(|c| c.name == symbol),
+            "{} is missing from the capabilities table",
+            name
+        );
+    }
+    assert_eq!(listed.len() as usize, declared.len());
+}
+
+#[test]
+fn test_capabilities_flags() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let find = |name: &str| {
+        client
+            .get_capabilities()
+            .iter()
+            .find(|c| c.name == soroban_sdk::Symbol::new(&env, name))
+            .unwrap()
+    };
+
+    let splice = find("splice_genome");
+    assert!(splice.user_auth && splice.moves_tokens && splice.mutates_ownership);
+    assert!(!splice.admin_auth);
+
+    let withdraw = find("admin_withdraw_token");
+    assert!(withdraw.admin_auth && withdraw.moves_tokens);
+    assert!(!withdraw.user_auth && !withdraw.mutates_ownership);
+
+    let view = find("get_cartridge");
+    assert!(!view.user_auth && !view.admin_auth && !view.moves_tokens && !view.mutates_ownership);
+}
+
+// ===== Interface hash tests =====
+
+/// Interface hash of the current entrypoints and contract types. Update it together
+/// with the frontend bindings whenever a signature or a contract type changes
+const PINNED_INTERFACE_HASH: &str = "dabaeed99b0e81d4b10e84eacef419dbedb277901cef944f22db743b053aca17";
+
+#[test]
+fn test_interface_hash_pinned() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let hash = client.interface_hash();
+    assert_eq!(
+        hex::encode(hash.to_array()),
+        PINNED_INTERFACE_HASH,
+        "the contract interface changed; regenerate the bindings and update the pinned hash"
+    );
+    assert_eq!(hash.to_array(), crate::INTERFACE_HASH);
+}
+
+#[test]
+fn test_assert_interface() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.assert_interface(&client.interface_hash());
+
+    let mut stale = client.interface_hash().to_array();
+    stale[0] ^= 1;
+    assert_eq!(
+        client.try_assert_interface(&soroban_sdk::BytesN::from_array(&env, &stale)),
+        Err(Ok(contract_error(Error::InterfaceMismatch)))
+    );
+}
+
+// ===== Derivation version tests =====
+
+#[test]
+fn test_derivation_version_fixed_at_mint() {
+    use crate::{derive_v1, derive_v2, LATEST_DERIVATION_VERSION};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // New deployments default to the latest rules
+    assert_eq!(client.get_derivation_version(), LATEST_DERIVATION_VERSION);
+
+    client.set_derivation_version(&1);
+    let old_id = client.splice_genome(&user);
+    client.set_derivation_version(&2);
+    let new_id = client.splice_genome(&user);
+
+    assert_eq!(client.get_derivation_version(), 2);
+    assert_eq!(client.get_cartridge_derivation_version(&old_id), 1);
+    assert_eq!(client.get_cartridge_derivation_version(&new_id), 2);
+
+    // Finalize both after the upgrade; the old cartridge still derives with v1
+    let old = client.get_cartridge(&old_id).unwrap();
+    let new = client.get_cartridge(&new_id).unwrap();
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_splice(&old_id, &old.splice_round, &r, &sc, &su, &None);
+    client.finalize_splice(&new_id, &new.splice_round, &r, &sc, &su, &None);
+
+    let randomness: Bytes = env.crypto().sha256(&sc).into();
+    let [head, body, legs] = derive_v1(&randomness);
+    let creature = client.get_creature(&old_id).unwrap();
+    assert_eq!((creature.head_gene, creature.body_gene, creature.legs_gene), (head, body, legs));
+
+    let [head, body, legs] = derive_v2(&env, &randomness, new_id);
+    let creature = client.get_creature(&new_id).unwrap();
+    assert_eq!((creature.head_gene, creature.body_gene, creature.legs_gene), (head, body, legs));
+}
+
+#[test]
+fn test_derivation_v2_mixes_cartridge_id() {
+    use crate::derive_v2;
+
+    let env = Env::default();
+    let randomness = Bytes::from_array(&env, &[0x42; 32]);
+
+    // Same randomness, different cartridges — at least one gene must differ somewhere
+    let reference = derive_v2(&env, &randomness, 1);
+    assert!((2..10).any(|id| derive_v2(&env, &randomness, id) != reference));
+    assert_eq!(derive_v2(&env, &randomness, 1), reference);
+}
+
+fn has_duplicate_ids(genes: &[crate::Gene; 3]) -> bool {
+    genes[0].id == genes[1].id || genes[0].id == genes[2].id || genes[1].id == genes[2].id
+}
+
+#[test]
+fn test_derivation_v3_remaps_duplicate_slots() {
+    use crate::{derive_v2, derive_v3};
+
+    let env = Env::default();
+    // (randomness byte, v2 IDs, v3 IDs) for cartridge 1
+    let cases = [
+        (0u8, [12, 12, 9], [12, 13, 9]),   // body moves to the next Common
+        (4, [9, 9, 10], [9, 10, 11]),      // body takes legs' ID, so legs moves on too
+        (17, [8, 2, 2], [8, 2, 0]),        // Dark Oracle wraps from 2 to 0
+        (53, [14, 7, 14], [14, 7, 6]),     // Common wraps from 14 to 6
+        (125, [1, 1, 1], [1, 2, 0]),       // all three slots share a Rare ID
+    ];
+    for (byte, v2_ids, v3_ids) in cases {
+        let randomness = Bytes::from_array(&env, &[byte; 32]);
+        let v2 = derive_v2(&env, &randomness, 1);
+        let v3 = derive_v3(&env, &randomness, 1);
+        assert_eq!(v2.clone().map(|gene| gene.id), v2_ids);
+        assert_eq!(v3.clone().map(|gene| gene.id), v3_ids);
+        assert_eq!(v2.map(|gene| gene.rarity), v3.map(|gene| gene.rarity));
+    }
+
+    // Without a collision v3 is exactly v2
+    for byte in 0..=255u8 {
+        let randomness = Bytes::from_array(&env, &[byte; 32]);
+        let v2 = derive_v2(&env, &randomness, 1);
+        let v3 = derive_v3(&env, &randomness, 1);
+        assert!(!has_duplicate_ids(&v3));
+        if !has_duplicate_ids(&v2) {
+            assert_eq!(v2, v3);
+        }
+    }
+}
+
+#[test]
+fn test_finalized_creatures_never_repeat_a_gene_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &1_000_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let mut collisions_avoided = 0;
+    for byte in 0..40u8 {
+        let seed = Bytes::from_array(&env, &[byte; 8]);
+        let id = client.dev_splice_and_finalize(&user, &seed);
+        let creature = client.get_creature(&id).unwrap();
+        assert!(!has_duplicate_ids(&[creature.head_gene, creature.body_gene, creature.legs_gene]));
+
+        let randomness: Bytes = env.crypto().sha256(&seed).into();
+        if has_duplicate_ids(&crate::derive_v2(&env, &randomness, id)) {
+            collisions_avoided += 1;
+        }
+    }
+    assert!(collisions_avoided > 0);
+}
+
+#[test]
+fn test_set_derivation_version_rejects_unknown() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    for version in [0, crate::LATEST_DERIVATION_VERSION + 1] {
+        assert_eq!(
+            client.try_set_derivation_version(&version),
+            Err(Ok(contract_error(Error::UnsupportedDerivationVersion)))
+        );
+    }
+}
+
+// ===== Dev finalize tests =====
+
+#[test]
+fn test_dev_finalize_is_instant_and_marked() {
+    use crate::derive_v4;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let cartridge_id = client.splice_genome(&user);
+
+    // No waiting for the assigned round and no beacon
+    let seed = Bytes::from_array(&env, b"local-dev-seed");
+    client.dev_finalize(&cartridge_id, &seed);
+
+    let creature = client.get_creature(&cartridge_id).unwrap();
+    assert!(creature.dev_minted);
+    assert_eq!(creature.owner, user);
+    let randomness: Bytes = env.crypto().sha256(&seed).into();
+    let [head, body, legs] = derive_v4(&env, &randomness, cartridge_id);
+    assert_eq!((creature.head_gene, creature.body_gene, creature.legs_gene), (head, body, legs));
+    assert!(client.get_cartridge(&cartridge_id).unwrap().finalized);
+
+    assert_eq!(
+        client.try_dev_finalize(&cartridge_id, &seed),
+        Err(Ok(contract_error(Error::AlreadyFinalized)))
+    );
+}
+
+#[test]
+fn test_verified_finalize_not_dev_minted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+
+    let cartridge_id = client.splice_genome(&user);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let (r, sc, su) = create_mock_entropy(&env);
+    wait_for_round(&env, round);
+    client.finalize_splice(&cartridge_id, &round, &r, &sc, &su, &None);
+
+    assert!(!client.get_creature(&cartridge_id).unwrap().dev_minted);
+}
+
+#[test]
+fn test_dev_finalize_refused_without_dev_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+    past_drand_genesis(&env);
+
+    let cartridge_id = client.splice_genome(&user);
+    assert_eq!(
+        client.try_dev_finalize(&cartridge_id, &Bytes::from_array(&env, &[1; 32])),
+        Err(Ok(contract_error(Error::DevModeDisabled)))
+    );
+    assert!(!client.has_creature(&cartridge_id));
+}
+
+// ===== Drand genesis tests =====
+
+#[test]
+fn test_mint_before_drand_genesis_refused_without_dev_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+
+    // The default test ledger clock (0) is long before genesis
+    assert_eq!(
+        client.try_splice_genome(&user),
+        Err(Ok(contract_error(Error::ClockBeforeDrandGenesis)))
+    );
+    assert_eq!(xlm_token.balance(&user), 100_000_000);
+}
+
+#[test]
+fn test_mint_before_drand_genesis_flagged_in_dev_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let cartridge_id = client.splice_genome(&user);
+    let cartridge = client.get_cartridge(&cartridge_id).unwrap();
+    assert!(cartridge.dev_entropy);
+    assert_eq!(cartridge.splice_round, 3);
+
+    // Even a beacon that passes verification can't make these genes trustworthy
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_splice(&cartridge_id, &3, &r, &sc, &su, &None);
+    assert!(client.get_creature(&cartridge_id).unwrap().dev_minted);
+}
+
+#[test]
+fn test_mint_just_after_drand_genesis() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+
+    // Genesis itself is round 1, emitted at the genesis time
+    env.ledger().with_mut(|li| li.timestamp = DRAND_GENESIS);
+    let cartridge = client.get_cartridge(&client.splice_genome(&user)).unwrap();
+    assert_eq!(cartridge.splice_round, 3);
+    assert!(!cartridge.dev_entropy);
+
+    // Round 2 is out at genesis + 3s, so the earliest future round is 4
+    env.ledger().with_mut(|li| li.timestamp = DRAND_GENESIS + 4);
+    let cartridge = client.get_cartridge(&client.splice_genome(&user)).unwrap();
+    assert_eq!(cartridge.splice_round, 4);
+    assert!(!cartridge.dev_entropy);
+}
+
+#[test]
+fn test_dev_splice_and_finalize() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let seed = Bytes::from_array(&env, &[3; 32]);
+    let creature_id = client.dev_splice_and_finalize(&user, &seed);
+    assert_eq!(count_events(&env, &client.address, "cartridge_minted"), 1);
+    assert_eq!(count_events(&env, &client.address, "creature_finalized"), 1);
+
+    // The fee is charged as for splice_genome
+    assert_eq!(xlm_token.balance(&user), 90_000_000);
+    assert_eq!(xlm_token.balance(&admin), 10_000_000);
+
+    let creature = client.get_creature(&creature_id).unwrap();
+    assert_eq!(creature.owner, user);
+    assert!(creature.dev_minted);
+    assert!(client.get_cartridge(&creature_id).unwrap().finalized);
+
+    // Genes come from SHA256(seed), as with dev_finalize
+    let randomness: Bytes = env.crypto().sha256(&seed).into();
+    let [head, body, legs] = crate::derive_v4(&env, &randomness, creature_id);
+    assert_eq!((creature.head_gene, creature.body_gene, creature.legs_gene), (head, body, legs));
+}
+
+#[test]
+fn test_dev_splice_and_finalize_refused_without_dev_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+    past_drand_genesis(&env);
+
+    assert_eq!(
+        client.try_dev_splice_and_finalize(&user, &Bytes::from_array(&env, &[1; 32])),
+        Err(Ok(contract_error(Error::DevModeDisabled)))
+    );
+    assert_eq!(xlm_token.balance(&user), 100_000_000);
+    assert_eq!(client.get_total_cartridges(), 0);
+}
+
+// ===== Pending index tests =====
+
+fn pending_ids(client: &GeneSplicerClient) -> std::vec::Vec<u32> {
+    let (page, next) = client.get_pending_details(&0, &crate::MAX_PENDING_PAGE);
+    assert_eq!(next, 0);
+    page.iter().map(|p| p.id).collect()
+}
+
+#[test]
+fn test_pending_index_tracks_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&alice, &100_000_000);
+    xlm_token.mint(&bob, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    assert!(pending_ids(&client).is_empty());
+
+    let a1 = client.splice_genome(&alice);
+    let b1 = client.splice_genome(&bob);
+    let a2 = client.splice_genome(&alice);
+    assert_eq!(pending_ids(&client), [a1, b1, a2]);
+
+    // Details match the stored cartridge
+    let (page, _) = client.get_pending_details(&b1, &1);
+    let cartridge = client.get_cartridge(&b1).unwrap();
+    assert_eq!(
+        page.get(0).unwrap(),
+        crate::PendingCartridge {
+            id: b1,
+            splice_round: cartridge.splice_round,
+            owner: bob.clone(),
+            created_at: cartridge.created_at,
+        }
+    );
+
+    // Verified finalization removes it
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_splice(&b1, &cartridge.splice_round, &r, &sc, &su, &None);
+    assert_eq!(pending_ids(&client), [a1, a2]);
+
+    // So does dev finalization
+    client.dev_finalize(&a1, &Bytes::from_array(&env, &[7; 32]));
+    assert_eq!(pending_ids(&client), [a2]);
+
+    let b2 = client.splice_genome(&bob);
+    assert_eq!(pending_ids(&client), [a2, b2]);
+}
+
+#[test]
+fn test_pending_details_pagination() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    for _ in 0..5 {
+        client.splice_genome(&user);
+    }
+    client.dev_finalize(&3, &Bytes::from_array(&env, &[7; 32]));
+
+    // Pages of two over pending IDs 1, 2, 4, 5
+    let (page, next) = client.get_pending_details(&0, &2);
+    assert_eq!(page.iter().map(|p| p.id).collect::<std::vec::Vec<_>>(), [1, 2]);
+    assert_eq!(next, 4);
+    let (page, next) = client.get_pending_details(&next, &2);
+    assert_eq!(page.iter().map(|p| p.id).collect::<std::vec::Vec<_>>(), [4, 5]);
+    assert_eq!(next, 0);
+
+    // A cursor on a finalized ID starts at the next pending one
+    let (page, _) = client.get_pending_details(&3, &1);
+    assert_eq!(page.get(0).unwrap().id, 4);
+
+    // Limit is capped at Limits.max_pending_page
+    assert_eq!(client.get_limits().max_pending_page, crate::MAX_PENDING_PAGE);
+    let (page, _) = client.get_pending_details(&0, &u32::MAX);
+    assert_eq!(page.len(), 4);
+
+    // An indexed ID whose record is gone is skipped rather than failing the page
+    env.as_contract(&client.address, || {
+        env.storage().persistent().remove(&crate::DataKey::Cartridge(2));
+    });
+    let (page, next) = client.get_pending_details(&0, &2);
+    assert_eq!(page.iter().map(|p| p.id).collect::<std::vec::Vec<_>>(), [1]);
+    assert_eq!(next, 4);
+}
+
+#[test]
+fn test_pending_index_is_chunked_by_id_range() {
+    use crate::{DataKey, PENDING_INDEX_CHUNK};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_mint_fee(&0);
+
+    // IDs 1..=99 land in chunk 0, 100..=102 in chunk 1
+    for _ in 0..PENDING_INDEX_CHUNK + 2 {
+        client.splice_genome(&user);
+    }
+    let chunk_len = |chunk: u32| {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .get::<_, soroban_sdk::Vec<u32>>(&DataKey::PendingChunk(chunk))
+                .map_or(0, |ids| ids.len())
+        })
+    };
+    let bounds = || {
+        env.as_contract(&client.address, || {
+            env.storage().persistent().get::<_, (u32, u32)>(&DataKey::PendingBounds)
+        })
+    };
+    assert_eq!((chunk_len(0), chunk_len(1)), (99, 3));
+    assert_eq!(bounds(), Some((0, 2)));
+
+    // Pages run across the chunk boundary
+    let (page, next) = client.get_pending_details(&98, &3);
+    assert_eq!(page.iter().map(|p| p.id).collect::<std::vec::Vec<_>>(), [98, 99, 100]);
+    assert_eq!(next, 101);
+
+    // Finalizing touches only its chunk; emptying the tail chunk pulls the tail back
+    client.dev_finalize(&50, &Bytes::from_array(&env, &[1; 32]));
+    assert_eq!((chunk_len(0), chunk_len(1)), (98, 3));
+    for id in 100..=102 {
+        client.dev_finalize(&id, &Bytes::from_array(&env, &[1; 32]));
+    }
+    assert_eq!(chunk_len(1), 0);
+    assert_eq!(bounds(), Some((0, 1)));
+
+    // Emptying every chunk drops the bounds; the next mint starts a fresh range
+    env.as_contract(&client.address, || {
+        for id in 1..PENDING_INDEX_CHUNK {
+            crate::pending::set_pending(&env, id, false);
+        }
+    });
+    assert_eq!(bounds(), None);
+    assert_eq!(client.get_pending_details(&0, &10), (soroban_sdk::Vec::new(&env), 0));
+    let id = client.splice_genome(&user);
+    assert_eq!(bounds(), Some((1, 2)));
+    assert_eq!(client.get_pending_details(&0, &10).0.get(0).unwrap().id, id);
+}
+
+#[test]
+fn test_private_owners_redacted_from_pending_details() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&alice, &100_000_000);
+    xlm_token.mint(&bob, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let hidden = client.splice_genome(&alice);
+    client.splice_genome(&bob);
+    client.splice_genome(&alice);
+    client.set_privacy(&alice, &true);
+    assert!(client.is_private(&alice));
+    assert!(!client.is_private(&bob));
+
+    let owners = |client: &GeneSplicerClient| -> std::vec::Vec<Address> {
+        let (page, _) = client.get_pending_details(&0, &crate::MAX_PENDING_PAGE);
+        page.iter().map(|p| p.owner).collect()
+    };
+    assert_eq!(owners(&client), [client.address.clone(), bob.clone(), client.address.clone()]);
+
+    // Per-asset getters still tell the truth
+    assert_eq!(client.get_cartridge(&hidden).unwrap().owner, alice);
+    assert_eq!(client.get_user_cartridges(&alice).len(), 2);
+
+    client.set_privacy(&alice, &false);
+    assert_eq!(owners(&client), [alice.clone(), bob, alice.clone()]);
+
+    env.set_auths(&[]);
+    assert!(client.try_set_privacy(&alice, &true).is_err());
+}
+
+// ===== Role tests =====
+
+/// Mock only `signer`'s auth, for the next `fn_name(args)` call on the contract
+fn as_signer<'a>(
+    env: &Env,
+    client: &GeneSplicerClient<'a>,
+    signer: &Address,
+    fn_name: &'a str,
+    args: soroban_sdk::Vec<soroban_sdk::Val>,
+) {
+    use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+    env.mock_auths(&[MockAuth {
+        address: signer,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name,
+            args,
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+/// Smallest WASM the host accepts as a contract: just the env interface-version meta section
+fn minimal_contract_wasm(env: &Env) -> Bytes {
+    let mut wasm = std::vec![0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+    let name = b"contractenvmetav0";
+    // ScEnvMetaEntry::InterfaceVersion { protocol: 20, pre_release: 0 }
+    let meta = [0u32, 20, 0].map(u32::to_be_bytes).concat();
+    wasm.push(0x00); // custom section
+    wasm.push((1 + name.len() + meta.len()) as u8);
+    wasm.push(name.len() as u8);
+    wasm.extend_from_slice(name);
+    wasm.extend_from_slice(&meta);
+    Bytes::from_slice(env, &wasm)
+}
+
+#[test]
+fn test_operator_limited_to_operational_functions() {
+    use soroban_sdk::{BytesN, IntoVal};
+
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &owner);
+    let client = setup_contract(&env, &owner, &xlm_token.address, true);
+
+    as_signer(&env, &client, &owner, "set_operator", (Some(operator.clone()),).into_val(&env));
+    client.set_operator(&Some(operator.clone()));
+    assert_eq!(client.operator(), Some(operator.clone()));
+
+    // Operational: the Operator can pause
+    as_signer(&env, &client, &operator, "set_paused", (true,).into_val(&env));
+    client.set_paused(&true);
+    assert!(client.is_mint_paused());
+
+    // Owner-only: the Operator can't rotate keys, upgrade, or reassign roles
+    let key = create_mock_drand_pubkey(&env);
+    as_signer(&env, &client, &operator, "set_drand_public_key", (key.clone(),).into_val(&env));
+    assert!(client.try_set_drand_public_key(&key).is_err());
+
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    as_signer(&env, &client, &operator, "upgrade", (wasm_hash.clone(),).into_val(&env));
+    assert!(client.try_upgrade(&wasm_hash).is_err());
+
+    as_signer(&env, &client, &operator, "set_admin", (operator.clone(),).into_val(&env));
+    assert!(client.try_set_admin(&operator).is_err());
+    assert_eq!(client.admin(), owner);
+}
+
+#[test]
+fn test_owner_rotates_keys_and_upgrades() {
+    use soroban_sdk::IntoVal;
+
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &owner);
+    let client = setup_contract(&env, &owner, &xlm_token.address, true);
+
+    as_signer(&env, &client, &owner, "set_operator", (Some(operator.clone()),).into_val(&env));
+    client.set_operator(&Some(operator.clone()));
+
+    let mut key = [0u8; 192];
+    key[0] = 1;
+    let key = Bytes::from_array(&env, &key);
+    as_signer(&env, &client, &owner, "set_drand_public_key", (key.clone(),).into_val(&env));
+    client.set_drand_public_key(&key);
+    assert_eq!(client.get_drand_public_key(), key);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(minimal_contract_wasm(&env));
+    as_signer(&env, &client, &owner, "upgrade", (wasm_hash.clone(),).into_val(&env));
+    client.upgrade(&wasm_hash);
+    assert_eq!(count_events(&env, &client.address, "contract_upgraded"), 1);
+}
+
+#[test]
+fn test_operator_falls_back_to_owner() {
+    use soroban_sdk::IntoVal;
+
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &owner);
+    let client = setup_contract(&env, &owner, &xlm_token.address, true);
+
+    // With no Operator assigned the Owner runs operations
+    as_signer(&env, &client, &owner, "set_mint_paused", (true,).into_val(&env));
+    client.set_mint_paused(&true);
+
+    as_signer(&env, &client, &owner, "set_operator", (Some(operator.clone()),).into_val(&env));
+    client.set_operator(&Some(operator.clone()));
+
+    // Once an Operator is assigned, operational calls need its auth
+    as_signer(&env, &client, &owner, "set_paused", (true,).into_val(&env));
+    assert!(client.try_set_paused(&true).is_err());
+
+    // Clearing the Operator hands operations back to the Owner
+    as_signer(&env, &client, &owner, "set_operator", (None::<Address>,).into_val(&env));
+    client.set_operator(&None);
+    assert_eq!(client.operator(), None);
+    as_signer(&env, &client, &owner, "set_paused", (true,).into_val(&env));
+    client.set_paused(&true);
+    assert!(client.is_finalize_paused());
+}
+
+#[test]
+fn test_role_change_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let owner = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &owner);
+    let client = setup_contract(&env, &owner, &xlm_token.address, true);
+
+    client.set_operator(&Some(Address::generate(&env)));
+    assert_eq!(count_events(&env, &client.address, "operator_changed"), 1);
+
+    client.set_admin(&new_owner);
+    assert_eq!(count_events(&env, &client.address, "owner_changed"), 1);
+    assert_eq!(client.admin(), new_owner);
+}
+
+// ===== Two-phase verification tests =====
+
+const REAL_ROUND: u64 = 27448023;
+
+/// drand API response for quicknet round 27448023
+const REAL_BEACON_JSON: &str = r#"{
+    "round": 27448023,
+    "randomness": "f22d19a3d8cd3a181fe8155d051fe006a726b1fe0b18043bda3a2fe4c6c1e5d8",
+    "signature": "967e8a7aa839aa8f672800bb50b1ee29dfa4757d120112c7b858b1f625193a41fb156ad7c69fefc644b9719f88d60313"
+}"#;
+
+/// Real drand quicknet round 27448023: (randomness, compressed sig, uncompressed sig)
+fn real_beacon(env: &Env) -> (Bytes, Bytes, Bytes) {
+    let beacon = splicers_drand_client::Beacon::from_json(REAL_BEACON_JSON).unwrap();
+    assert_eq!(beacon.round, REAL_ROUND);
+    (
+        Bytes::from_array(env, &beacon.randomness),
+        Bytes::from_array(env, &beacon.signature),
+        Bytes::from_array(env, &beacon.signature_uncompressed().unwrap()),
+    )
+}
+
+/// The off-chain helper's round schedule is the one the contract assigns rounds with
+#[test]
+fn test_drand_client_round_schedule_matches_contract() {
+    assert_eq!(splicers_drand_client::QUICKNET_GENESIS, DRAND_GENESIS);
+    for round in [1, 2, REAL_ROUND] {
+        assert_eq!(splicers_drand_client::round_time(round), crate::drand_round_time(round));
+    }
+}
+
+/// Production-mode contract with `count` cartridges assigned to REAL_ROUND
+fn setup_real_round_cartridges<'a>(env: &Env, count: u32) -> GeneSplicerClient<'a> {
+    let admin = Address::generate(env);
+    let user = Address::generate(env);
+    let xlm_token = create_xlm_token(env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(env), mock_provenance_hash(env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(env, &contract_id);
+    past_drand_genesis(env);
+
+    for _ in 0..count {
+        let cartridge_id = client.splice_genome(&user);
+        move_to_real_round(env, &client, cartridge_id);
+    }
+    client
+}
+
+/// Reassign an existing cartridge to REAL_ROUND and advance the clock to when it was emitted
+fn move_to_real_round(env: &Env, client: &GeneSplicerClient, cartridge_id: u32) {
+    wait_for_round(env, REAL_ROUND);
+    let mut cartridge = client.get_cartridge(&cartridge_id).unwrap();
+    cartridge.splice_round = REAL_ROUND;
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::Cartridge(cartridge_id), &cartridge);
+    });
+}
+
+#[test]
+fn test_two_phase_verification_makes_finalize_cheap() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 2);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+    let verifier = Address::generate(&env);
+
+    // Cartridge 1 pays for full inline verification, which caches the round
+    client.finalize_splice(&1, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None);
+    let full_cost = env.cost_estimate().budget().cpu_instruction_cost();
+    assert!(client.is_round_verified(&REAL_ROUND, &sig_uncompressed));
+
+    // Once that cache entry expires, verify again in two phases
+    env.ledger().with_mut(|li| li.sequence_number += 17_281);
+    let entry = client.begin_verification(&REAL_ROUND, &sig_uncompressed);
+    assert!(!client.is_round_verified(&REAL_ROUND, &sig_uncompressed));
+    client.complete_verification(&verifier, &REAL_ROUND, &entry);
+    assert_eq!(count_events(&env, &client.address, "round_verified"), 1);
+    assert!(client.is_round_verified(&REAL_ROUND, &sig_uncompressed));
+
+    // Cartridge 2 finalizes against the cache
+    client.finalize_splice(&2, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None);
+    let cached_cost = env.cost_estimate().budget().cpu_instruction_cost();
+    assert!(cached_cost * 2 < full_cost);
+    assert!(client.has_creature(&2));
+
+    // The pending state was consumed
+    assert_eq!(
+        client.try_complete_verification(&verifier, &REAL_ROUND, &entry),
+        Err(Ok(contract_error(Error::NoPendingVerification)))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_two_phase_verification_rejects_swapped_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 1);
+    let (_, _, sig_uncompressed) = real_beacon(&env);
+
+    let entry = client.begin_verification(&REAL_ROUND, &sig_uncompressed);
+    client.complete_verification(&Address::generate(&env), &REAL_ROUND, &entry);
+
+    // A different signature whose compressed form and randomness are self-consistent
+    // must not ride on the cached verification of the real one
+    let mut forged = [0x11u8; 96];
+    forged[0] = 0x01;
+    let forged_uncompressed = Bytes::from_array(&env, &forged);
+    let mut forged_compressed = [0x11u8; 48];
+    forged_compressed[0] = 0x80 | 0x01;
+    let forged_compressed = Bytes::from_array(&env, &forged_compressed);
+    let forged_randomness: Bytes = env.crypto().sha256(&forged_compressed).into();
+    assert!(!client.is_round_verified(&REAL_ROUND, &forged_uncompressed));
+
+    client.finalize_splice(
+        &1,
+        &REAL_ROUND,
+        &forged_randomness,
+        &forged_compressed,
+        &forged_uncompressed,
+        &None,
+    );
+}
+
+#[test]
+fn test_complete_verification_without_begin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 0);
+    let verifier = Address::generate(&env);
+
+    let entry = soroban_sdk::BytesN::from_array(&env, &[0; 32]);
+    assert_eq!(
+        client.try_complete_verification(&verifier, &REAL_ROUND, &entry),
+        Err(Ok(contract_error(Error::NoPendingVerification)))
+    );
+}
+
+#[test]
+fn test_verification_of_wrong_round_reports_pairing_failure() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 0);
+    let (_, _, sig_uncompressed) = real_beacon(&env);
+
+    // A genuine signature, claimed for the next round
+    let entry = client.begin_verification(&(REAL_ROUND + 1), &sig_uncompressed);
+    assert_eq!(
+        client.try_complete_verification(&Address::generate(&env), &(REAL_ROUND + 1), &entry),
+        Err(Ok(contract_error(Error::PairingCheckFailed)))
+    );
+}
+
+#[test]
+fn test_pending_verification_cannot_be_replaced() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 0);
+    let (_, _, sig_uncompressed) = real_beacon(&env);
+    let verifier = Address::generate(&env);
+
+    // An attacker begins with another subgroup-valid point (the G1 generator) between
+    // the honest caller's phases; it lands in its own entry
+    let entry = client.begin_verification(&REAL_ROUND, &sig_uncompressed);
+    let generator = Bytes::from_slice(&env, &hex::decode(G1_GENERATOR).unwrap());
+    let attacker_entry = client.begin_verification(&REAL_ROUND, &generator);
+    assert_ne!(attacker_entry, entry);
+
+    client.complete_verification(&verifier, &REAL_ROUND, &entry);
+    assert!(client.is_round_verified(&REAL_ROUND, &sig_uncompressed));
+    assert_eq!(
+        client.try_complete_verification(&verifier, &REAL_ROUND, &attacker_entry),
+        Err(Ok(contract_error(Error::PairingCheckFailed)))
+    );
+    assert!(!client.is_round_verified(&REAL_ROUND, &generator));
+}
+
+#[test]
+fn test_finalize_binds_randomness_to_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 1);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+    let hashed: Bytes = env.crypto().sha256(&sig_compressed).into();
+    assert_eq!(randomness, hashed);
+
+    // A valid signature can't carry randomness of the caller's choosing
+    let mut chosen = randomness.clone();
+    chosen.set(31, chosen.get(31).unwrap() ^ 1);
+    assert_eq!(
+        client.try_finalize_splice(&1, &REAL_ROUND, &chosen, &sig_compressed, &sig_uncompressed, &None),
+        Err(Ok(contract_error(Error::RandomnessMismatch)))
+    );
+    assert_eq!(
+        client.finalize_splice(&1, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None),
+        1
+    );
+}
+
+#[test]
+fn test_dev_mode_derives_genes_from_signature_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // The mock randomness isn't the signature's hash; dev mode accepts it but ignores it
+    let cartridge_id = client.splice_genome(&user);
+    let cartridge = client.get_cartridge(&cartridge_id).unwrap();
+    let (randomness, sig_compressed, sig_uncompressed) = create_mock_entropy(&env);
+    let hashed: Bytes = env.crypto().sha256(&sig_compressed).into();
+    assert_ne!(randomness, hashed);
+    client.finalize_splice(
+        &cartridge_id,
+        &cartridge.splice_round,
+        &randomness,
+        &sig_compressed,
+        &sig_uncompressed,
+        &None,
+    );
+
+    let creature = client.get_creature(&cartridge_id).unwrap();
+    let expected = env.as_contract(&client.address, || {
+        crate::derivation::derive(&env, &cartridge, &hashed)
+    });
+    let ids = |genes: [&crate::Gene; 3]| genes.map(|gene| gene.id);
+    assert_eq!(
+        ids([&creature.head_gene, &creature.body_gene, &creature.legs_gene]),
+        ids([&expected[0], &expected[1], &expected[2]])
+    );
+}
+
+#[test]
+fn test_pending_verification_expires() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 0);
+    let (_, _, sig_uncompressed) = real_beacon(&env);
+    let verifier = Address::generate(&env);
+
+    let entry = client.begin_verification(&REAL_ROUND, &sig_uncompressed);
+    env.ledger().with_mut(|li| li.sequence_number += 721);
+
+    assert_eq!(
+        client.try_complete_verification(&verifier, &REAL_ROUND, &entry),
+        Err(Ok(contract_error(Error::NoPendingVerification)))
+    );
+}
+
+#[test]
+fn test_shared_round_verification_share() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+    past_drand_genesis(&env);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+
+    // 0.3 XLM per cached finalization, at most 0.5 XLM per round
+    client.set_verification_share(&3_000_000, &5_000_000);
+
+    let users: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+    for user in &users {
+        xlm_token.mint(user, &100_000_000);
+        let cartridge_id = client.splice_genome(user);
+        move_to_real_round(&env, &client, cartridge_id);
+    }
+    let finalize = |id: u32| {
+        client.finalize_splice(&id, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None)
+    };
+
+    // The first finalizer runs the pairing and becomes the round's verifier
+    finalize(1);
+    assert_eq!(client.get_round_verifier(&REAL_ROUND), Some(users[0].clone()));
+    assert_eq!(client.get_round_reimbursed(&REAL_ROUND), 0);
+    assert_eq!(xlm_token.balance(&users[0]), 90_000_000);
+
+    // Each cached finalization pays the share to the verifier
+    finalize(2);
+    assert_eq!(count_events(&env, &client.address, "verification_share_paid"), 1);
+    assert_eq!(xlm_token.balance(&users[1]), 87_000_000);
+    assert_eq!(xlm_token.balance(&users[0]), 93_000_000);
+
+    // The third only pays what is left under the cap
+    finalize(3);
+    assert_eq!(xlm_token.balance(&users[2]), 88_000_000);
+    assert_eq!(xlm_token.balance(&users[0]), 95_000_000);
+    assert_eq!(client.get_round_reimbursed(&REAL_ROUND), 5_000_000);
+    assert_eq!(client.get_round_verifier(&REAL_ROUND), Some(users[0].clone()));
+
+    // The verifier is notified of each payout it received
+    let kinds: std::vec::Vec<_> = client
+        .read_inbox(&users[0])
+        .iter()
+        .map(|entry| (entry.kind, entry.id))
+        .collect();
+    let share = soroban_sdk::Symbol::new(&env, "verification_share");
+    assert_eq!(
+        kinds,
+        [(soroban_sdk::Symbol::new(&env, "finalized"), 1), (share.clone(), 2), (share, 3)]
+    );
+
+    assert_eq!(
+        client.try_set_verification_share(&-1, &5_000_000),
+        Err(Ok(contract_error(Error::InvalidAmount)))
+    );
+}
+
+// ===== Free first mint tests =====
+
+fn setup_free_mint<'a>(env: &Env) -> (GeneSplicerClient<'a>, token::StellarAssetClient<'a>) {
+    env.mock_all_auths();
+    let admin = Address::generate(env);
+    let xlm_token = create_xlm_token(env, &admin);
+    let client = setup_contract(env, &admin, &xlm_token.address, true);
+    client.set_free_mint_enabled(&true);
+    (client, xlm_token)
+}
+
+fn sign_free_mint(
+    env: &Env,
+    client: &GeneSplicerClient,
+    key: &ed25519_dalek::SigningKey,
+    user: &Address,
+) -> soroban_sdk::BytesN<64> {
+    use ed25519_dalek::Signer;
+
+    let message = client.free_mint_message(user);
+    let mut message_bytes = std::vec![0u8; message.len() as usize];
+    message.copy_into_slice(&mut message_bytes);
+    soroban_sdk::BytesN::from_array(env, &key.sign(&message_bytes).to_bytes())
+}
+
+#[test]
+fn test_free_mint_without_attestation() {
+    let env = Env::default();
+    let (client, xlm_token) = setup_free_mint(&env);
+    let user = Address::generate(&env);
+
+    // No XLM needed
+    assert!(client.is_free_mint_eligible(&user));
+    let cartridge_id = client.claim_free_mint(&user, &None);
+    assert_eq!(count_events(&env, &client.address, "free_mint_claimed"), 1);
+    assert_eq!(client.get_cartridge(&cartridge_id).unwrap().owner, user);
+    assert_eq!(xlm_token.balance(&user), 0);
+    assert_eq!(client.get_free_mint_count(), 1);
+
+    // Consumed
+    assert!(!client.is_free_mint_eligible(&user));
+    assert_eq!(
+        client.try_claim_free_mint(&user, &None),
+        Err(Ok(contract_error(FreeMintError::FreeMintAlreadyUsed)))
+    );
+
+    // Accounts that already paid for a mint don't get a free one
+    let payer = Address::generate(&env);
+    xlm_token.mint(&payer, &100_000_000);
+    client.splice_genome(&payer);
+    assert_eq!(
+        client.try_claim_free_mint(&payer, &None),
+        Err(Ok(contract_error(FreeMintError::FreeMintAlreadyUsed)))
+    );
+    assert_eq!(client.get_free_mint_count(), 1);
+}
+
+#[test]
+fn test_free_mint_with_attestation() {
+    let env = Env::default();
+    let (client, _) = setup_free_mint(&env);
+    let backend = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    let attestor = soroban_sdk::BytesN::from_array(&env, &backend.verifying_key().to_bytes());
+    client.set_free_mint_attestor(&Some(attestor), &(30 * 24 * 60 * 60));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    assert_eq!(
+        client.try_claim_free_mint(&alice, &None),
+        Err(Ok(contract_error(FreeMintError::AttestationRequired)))
+    );
+
+    let attestation = sign_free_mint(&env, &client, &backend, &alice);
+    client.claim_free_mint(&alice, &Some(attestation.clone()));
+    assert_eq!(client.get_free_mint_count(), 1);
+
+    // Replays fail: the same account is already seen, another account's message differs
+    assert_eq!(
+        client.try_claim_free_mint(&alice, &Some(attestation.clone())),
+        Err(Ok(contract_error(FreeMintError::FreeMintAlreadyUsed)))
+    );
+    assert!(client.try_claim_free_mint(&bob, &Some(attestation)).is_err());
+
+    // Raising the age threshold invalidates attestations signed for the old one
+    let stale = sign_free_mint(&env, &client, &backend, &bob);
+    client.set_free_mint_attestor(
+        &Some(soroban_sdk::BytesN::from_array(&env, &backend.verifying_key().to_bytes())),
+        &(60 * 24 * 60 * 60),
+    );
+    assert!(client.try_claim_free_mint(&bob, &Some(stale)).is_err());
+    assert_eq!(client.get_free_mint_count(), 1);
+}
+
+#[test]
+fn test_free_mint_program_disabled() {
+    let env = Env::default();
+    let (client, _) = setup_free_mint(&env);
+    let user = Address::generate(&env);
+
+    client.set_free_mint_enabled(&false);
+    assert!(!client.is_free_mint_enabled());
+    assert!(!client.is_free_mint_eligible(&user));
+    assert_eq!(
+        client.try_claim_free_mint(&user, &None),
+        Err(Ok(contract_error(FreeMintError::FreeMintDisabled)))
+    );
+    assert_eq!(client.get_free_mint_count(), 0);
+}
+
+// ===== Creature thumbnail tests =====
+
+#[test]
+fn test_creature_thumbs_match_full_structs() {
+    use crate::GeneRarity::{Legendary, Normal, Rare};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    store_creature(&env, &client.address, 1, &owner, [Normal, Normal, Normal]);
+    store_creature(&env, &client.address, 2, &owner, [Normal, Rare, Normal]);
+    store_creature(&env, &client.address, 4, &owner, [Rare, Normal, Legendary]);
+
+    // Missing ID 3 is skipped
+    let ids = soroban_sdk::vec![&env, 1, 2, 3, 4];
+    let thumbs = client.get_creature_thumbs(&ids);
+    assert_eq!(thumbs.len(), 3);
+
+    let expected_top = [Normal, Rare, Legendary];
+    for (thumb, top) in thumbs.iter().zip(expected_top) {
+        let creature = client.get_creature(&thumb.id).unwrap();
+        assert_eq!(thumb.skin_id, creature.skin_id);
+        assert_eq!(thumb.head_gene_id, creature.head_gene.id);
+        assert_eq!(thumb.body_gene_id, creature.body_gene.id);
+        assert_eq!(thumb.legs_gene_id, creature.legs_gene.id);
+        assert_eq!(thumb.top_rarity, top);
+    }
+}
+
+#[test]
+fn test_creature_thumbs_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let limit = client.get_limits().max_thumb_read;
+    assert_eq!(limit, crate::MAX_THUMB_READ);
+
+    let mut ids = soroban_sdk::Vec::new(&env);
+    for id in 1..=limit {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .set(&crate::DataKey::Creature(id), &mock_creature(id, &owner));
+        });
+        ids.push_back(id);
+    }
+    // Exactly at the limit is accepted, all present creatures returned
+    assert_eq!(client.get_creature_thumbs(&ids).len(), limit);
+
+    // One over is rejected
+    ids.push_back(limit + 1);
+    assert_eq!(
+        client.try_get_creature_thumbs(&ids),
+        Err(Ok(contract_error(ReadLimitError::MaxThumbReadExceeded)))
+    );
+}
+
+// ===== Bootstrap tests =====
+
+#[test]
+fn test_bootstrap_matches_individual_getters() {
+    use crate::GeneRarity::Rare;
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &1_000_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // An unknown account gets empty sections and defaults
+    let empty = client.bootstrap(&user);
+    assert_eq!((empty.creatures.len(), empty.creatures_cursor), (0, 0));
+    assert_eq!((empty.pending.len(), empty.pending_cursor), (0, 0));
+    assert_eq!((empty.points, empty.inbox_count), (0, 0));
+    assert_eq!((empty.maintenance_start, empty.maintenance_end), (None, None));
+
+    // 32 cartridges, the first one finalized, plus 40 more creatures and some points and mail
+    let cap = client.get_limits().max_bootstrap_entries;
+    assert_eq!(cap, crate::MAX_BOOTSTRAP_ENTRIES);
+    for _ in 0..32 {
+        client.splice_genome(&user);
+    }
+    let round = client.get_cartridge(&1).unwrap().splice_round;
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_splice(&1, &round, &r, &sc, &su, &None);
+    for id in 100..140 {
+        store_creature(&env, &client.address, id, &user, [Rare, Rare, Rare]);
+    }
+    env.as_contract(&client.address, || {
+        crate::points::credit(&env, &user, 250);
+        crate::inbox::notify(&env, &user, "right_sold", 7);
+    });
+    let now = env.ledger().timestamp();
+    client.schedule_maintenance(&(now + 10), &(now + 20), &soroban_sdk::symbol_short!("upgrade"));
+    client.set_mint_paused(&true);
+
+    let boot = client.bootstrap(&user);
+    assert_eq!(boot.points, client.get_points(&user));
+    assert_eq!(boot.inbox_count, client.read_inbox(&user).len());
+    assert_eq!(boot.gene_table_epoch, client.get_gene_table_epoch());
+    let window = client.get_maintenance().unwrap();
+    assert_eq!((boot.maintenance_start, boot.maintenance_end), (Some(window.start), Some(window.end)));
+    assert_eq!((boot.mint_paused, boot.finalize_paused), (true, false));
+
+    // Half the budget scans cartridges 32 down to 3; 1 and 2 are left behind the cursor
+    let cartridge_ids = client.get_user_cartridges(&user);
+    assert_eq!(boot.pending_cursor, 2);
+    assert_eq!(boot.pending.len(), cap / 2);
+    for (action, position) in boot.pending.iter().zip((2..32).rev()) {
+        let cartridge_id = cartridge_ids.get(position).unwrap();
+        assert_eq!(action.cartridge_id, cartridge_id);
+        assert_eq!(action.status, client.get_cartridge_status(&cartridge_id));
+    }
+
+    // Creatures get the other half, newest first; the cursor marks what's left
+    let creature_ids = client.get_user_creatures(&user);
+    assert_eq!(creature_ids.len(), 41);
+    assert_eq!(boot.creatures.len(), cap / 2);
+    assert_eq!(boot.creatures_cursor, 41 - cap / 2);
+    let mut newest_first = soroban_sdk::Vec::new(&env);
+    for position in (boot.creatures_cursor..41).rev() {
+        newest_first.push_back(creature_ids.get(position).unwrap());
+    }
+    assert_eq!(boot.creatures, client.get_creature_thumbs(&newest_first));
+
+    // A short cartridge scan leaves the rest of the budget to creatures; finalized ones aren't listed
+    env.as_contract(&client.address, || {
+        let key = crate::DataKey::UserCartridges(user.clone());
+        env.storage().persistent().set(&key, &soroban_sdk::vec![&env, 1u32]);
+    });
+    let boot = client.bootstrap(&user);
+    assert_eq!((boot.pending.len(), boot.pending_cursor), (0, 0));
+    assert_eq!((boot.creatures.len(), boot.creatures_cursor), (41, 0));
+}
+
+// ===== Drawing tests =====
+
+/// Off-chain re-implementation of the drawing selection, from the documented algorithm
+fn recompute_drawing(
+    env: &Env,
+    drawing_id: u32,
+    randomness: &Bytes,
+    population: u64,
+    winners: u32,
+    score: impl Fn(u32) -> u64,
+) -> soroban_sdk::Vec<u32> {
+    let uniform = |value: u64, range: u64| (value < u64::MAX - u64::MAX % range).then(|| value % range);
+    let mut preimage = Bytes::from_slice(env, crate::DRAWING_DOMAIN);
+    preimage.extend_from_array(&drawing_id.to_be_bytes());
+    preimage.append(randomness);
+    let mut state = env.crypto().sha256(&preimage).to_array();
+    let mut selected = soroban_sdk::Vec::new(env);
+    while selected.len() < winners {
+        state = env.crypto().sha256(&Bytes::from_array(env, &state)).to_array();
+        let index = uniform(u64::from_be_bytes(state[0..8].try_into().unwrap()), population);
+        let ticket = uniform(u64::from_be_bytes(state[8..16].try_into().unwrap()), crate::MAX_RARITY_SCORE);
+        if let (Some(index), Some(ticket)) = (index, ticket) {
+            let id = index as u32 + 1;
+            if !selected.contains(id) && ticket < score(id) {
+                selected.push_back(id);
+            }
+        }
+    }
+    selected
+}
+
+#[test]
+fn test_drawing_pins_winners_for_real_beacon() {
+    use crate::GeneRarity::{Legendary, Normal, Rare};
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+    past_drand_genesis(&env);
+
+    // Eight cartridges; 1-6 finalized with mixed rarities, 4 retired, 7-8 still pending
+    for _ in 0..8 {
+        client.splice_genome(&user);
+    }
+    let rarities = [
+        [Normal, Normal, Normal],
+        [Rare, Normal, Normal],
+        [Legendary, Legendary, Legendary],
+        [Legendary, Rare, Rare],
+        [Rare, Rare, Rare],
+        [Normal, Legendary, Normal],
+    ];
+    for (id, genes) in (1..=6).zip(rarities) {
+        store_creature(&env, &client.address, id, &user, genes);
+    }
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&crate::DataKey::Retired(4), &true);
+    });
+
+    assert_eq!(client.try_create_drawing(&0, &REAL_ROUND), Err(Ok(contract_error(DrawingError::InvalidDrawing))));
+    let drawing_id = client.create_drawing(&3, &REAL_ROUND);
+    assert_eq!(client.get_drawing(&drawing_id).unwrap().population, 8);
+    let every_eligible = client.create_drawing(&5, &REAL_ROUND);
+
+    wait_for_round(&env, REAL_ROUND);
+    assert_eq!(client.try_create_drawing(&3, &REAL_ROUND), Err(Ok(contract_error(DrawingError::InvalidDrawing))));
+    let (randomness, _, sig_uncompressed) = real_beacon(&env);
+    let mut wrong = randomness.clone();
+    wrong.set(0, wrong.get(0).unwrap() ^ 1);
+    assert_eq!(
+        client.try_resolve_drawing(&drawing_id, &wrong, &sig_uncompressed),
+        Err(Ok(contract_error(Error::RandomnessMismatch)))
+    );
+
+    // Three winners are found within a single call's attempts
+    assert!(client.resolve_drawing(&drawing_id, &randomness, &sig_uncompressed));
+    assert_eq!(count_events(&env, &client.address, "drawing_winner"), 3);
+    assert_eq!(count_events(&env, &client.address, "drawing_resolved"), 1);
+    let drawing = client.get_drawing(&drawing_id).unwrap();
+    assert!(drawing.resolved);
+    assert_eq!(drawing.winner_ids, soroban_sdk::vec![&env, 6, 5, 3]);
+    assert_eq!(
+        recompute_drawing(&env, drawing_id, &randomness, 8, 3, |id| match id {
+            1..=6 if id != 4 => crate::rarity_score(&client.get_creature(&id).unwrap()),
+            _ => 0,
+        }),
+        drawing.winner_ids
+    );
+
+    // Asking for every eligible creature takes several calls, continuing the same chain;
+    // the retired creature and the unfinalized IDs never win
+    let drawing_id = every_eligible;
+    let mut calls = 1;
+    while !client.resolve_drawing(&drawing_id, &randomness, &sig_uncompressed) {
+        calls += 1;
+    }
+    let drawing = client.get_drawing(&drawing_id).unwrap();
+    assert!(calls > 1);
+    assert!(drawing.attempts > crate::MAX_ATTEMPTS_PER_CALL && drawing.attempts < crate::MAX_DRAWING_ATTEMPTS);
+    let mut winners: std::vec::Vec<u32> = drawing.winner_ids.iter().collect();
+    winners.sort();
+    assert_eq!(winners, [1, 2, 3, 5, 6]);
+    assert_eq!(
+        client.try_resolve_drawing(&drawing_id, &randomness, &sig_uncompressed),
+        Err(Ok(contract_error(DrawingError::DrawingResolved)))
+    );
+    assert_eq!(
+        client.try_resolve_drawing(&99, &randomness, &sig_uncompressed),
+        Err(Ok(contract_error(DrawingError::DrawingNotFound)))
+    );
+}
+
+// ===== Provenance tests =====
+
+/// Independent RFC 6962-style tree: leaves padded to a power of two, returns every level bottom-up
+fn provenance_tree(env: &Env, content_hashes: &[[u8; 32]]) -> std::vec::Vec<std::vec::Vec<[u8; 32]>> {
+    let sha256 = |prefix: u8, parts: &[&[u8; 32]]| -> [u8; 32] {
+        let mut preimage = Bytes::from_array(env, &[prefix]);
+        for part in parts {
+            preimage.extend_from_array(part);
+        }
+        env.crypto().sha256(&preimage).to_array()
+    };
+    let width = content_hashes.len().next_power_of_two();
+    let mut level: std::vec::Vec<[u8; 32]> = (0..width)
+        .map(|i| sha256(0x00, &[content_hashes.get(i).unwrap_or(&[0; 32])]))
+        .collect();
+    let mut levels = std::vec![level.clone()];
+    while level.len() > 1 {
+        level = level.chunks(2).map(|pair| sha256(0x01, &[&pair[0], &pair[1]])).collect();
+        levels.push(level.clone());
+    }
+    levels
+}
+
+fn provenance_proof(
+    env: &Env,
+    levels: &[std::vec::Vec<[u8; 32]>],
+    index: usize,
+) -> soroban_sdk::Vec<soroban_sdk::BytesN<32>> {
+    let mut proof = soroban_sdk::Vec::new(env);
+    for (depth, level) in levels[..levels.len() - 1].iter().enumerate() {
+        proof.push_back(soroban_sdk::BytesN::from_array(env, &level[(index >> depth) ^ 1]));
+    }
+    proof
+}
+
+#[test]
+fn test_provenance_segments_verify_against_committed_root() {
+    use soroban_sdk::BytesN;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+
+    // Five fake assets pad to an eight-leaf tree
+    let assets: std::vec::Vec<[u8; 32]> = (1..=5u8).map(|i| [i; 32]).collect();
+    let levels = provenance_tree(&env, &assets);
+    let root = BytesN::from_array(&env, &levels.last().unwrap()[0]);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, true, create_mock_drand_pubkey(&env), root.clone(), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+    assert_eq!(client.get_provenance_hash(), root);
+
+    for (index, asset) in assets.iter().enumerate() {
+        let proof = provenance_proof(&env, &levels, index);
+        assert!(client.verify_provenance_segment(&(index as u32), &BytesN::from_array(&env, asset), &proof));
+    }
+
+    let asset = BytesN::from_array(&env, &assets[2]);
+    let proof = provenance_proof(&env, &levels, 2);
+    // Swapped art, wrong position, a tampered sibling, an index past the tree
+    assert!(!client.verify_provenance_segment(&2, &BytesN::from_array(&env, &assets[3]), &proof));
+    assert!(!client.verify_provenance_segment(&3, &asset, &proof));
+    let mut tampered = proof.clone();
+    tampered.set(1, BytesN::from_array(&env, &[0xff; 32]));
+    assert!(!client.verify_provenance_segment(&2, &asset, &tampered));
+    assert!(!client.verify_provenance_segment(&(2 + 8), &asset, &proof));
+    assert!(!client.verify_provenance_segment(&2, &asset, &proof.slice(0..2)));
+}
+
+// ===== Randomness age tests =====
+
+#[test]
+fn test_randomness_older_than_cartridge_is_refused() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 1);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+
+    // The normal path: a cartridge minted before its round finalizes as usual
+    assert_eq!(client.can_finalize(&1, &REAL_ROUND), (true, 0));
+
+    // A cartridge minted two hours after REAL_ROUND, then rebound to it by remediation
+    let user = client.get_cartridge(&1).unwrap().owner;
+    let round_time = crate::drand_round_time(REAL_ROUND);
+    env.ledger().with_mut(|li| li.timestamp = round_time + 7_200);
+    let late_id = client.splice_genome(&user);
+    let mut late = client.get_cartridge(&late_id).unwrap();
+    late.substituted_from = Some(late.splice_round);
+    late.splice_round = REAL_ROUND;
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::Cartridge(late_id), &late);
+    });
+
+    // REAL_ROUND's beacon is verified and cached by the first cartridge
+    client.finalize_splice(&1, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None);
+    assert!(client.is_round_verified(&REAL_ROUND, &sig_uncompressed));
+
+    let too_old = contract_error(Error::EntropyTooOldForCartridge);
+    assert_eq!(client.can_finalize(&late_id, &REAL_ROUND), (false, Error::EntropyTooOldForCartridge as u32));
+    assert_eq!(
+        client.try_finalize_splice(&late_id, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None),
+        Err(Ok(too_old))
+    );
+
+    // The setter is bounded, and a wider window admits the pairing again
+    assert_eq!(
+        client.try_set_max_randomness_age(&(crate::MIN_RANDOMNESS_AGE - 1)),
+        Err(Ok(contract_error(Error::InvalidRandomnessAge)))
+    );
+    assert_eq!(
+        client.try_set_max_randomness_age(&(crate::MAX_RANDOMNESS_AGE + 1)),
+        Err(Ok(contract_error(Error::InvalidRandomnessAge)))
+    );
+    assert_eq!(client.get_max_randomness_age(), crate::DEFAULT_MAX_RANDOMNESS_AGE);
+    client.set_max_randomness_age(&10_800);
+    assert_eq!(client.can_finalize(&late_id, &REAL_ROUND), (true, 0));
+}
+
+// ===== Finalization queue tests =====
+
+#[test]
+fn test_finalization_queue_processes_rounds_in_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    // dev_mode skips the pairing, so mock beacons stand in for two real rounds
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+    client.set_max_assignments_per_round(&3);
+
+    let ids: std::vec::Vec<u32> = (0..5).map(|_| client.splice_genome(&user)).collect();
+    let first_round = client.get_cartridge(&ids[0]).unwrap().splice_round;
+    let second_round = client.get_cartridge(&ids[3]).unwrap().splice_round;
+    assert_eq!(second_round, first_round + 1);
+
+    let positions: std::vec::Vec<u32> = ids
+        .iter()
+        .map(|id| client.request_finalization(&user, id))
+        .collect();
+    assert_eq!(positions, [0, 1, 2, 0, 1]);
+    assert_eq!(client.get_queue_length(&first_round), 3);
+    assert_eq!(client.get_queue_length(&second_round), 2);
+    assert_eq!(
+        client.try_request_finalization(&user, &ids[0]),
+        Err(Ok(contract_error(Error::AlreadyQueued)))
+    );
+    assert_eq!(
+        client.try_request_finalization(&Address::generate(&env), &ids[0]),
+        Err(Ok(contract_error(Error::NotCartridgeOwner)))
+    );
+
+    // Before the round is emitted the crank moves the head of the queue to the back
+    let (randomness, sig_compressed, sig_uncompressed) = create_mock_entropy(&env);
+    assert_eq!(client.process_queue(&first_round, &randomness, &sig_uncompressed, &1), 0);
+    assert_eq!(client.get_queue_position(&ids[0]), Some(2));
+    assert_eq!(client.get_queue_position(&ids[1]), Some(0));
+
+    // A cartridge finalized directly while queued is dropped when its turn comes
+    wait_for_round(&env, second_round);
+    client.finalize_splice(&ids[2], &first_round, &randomness, &sig_compressed, &sig_uncompressed, &None);
+
+    assert_eq!(client.process_queue(&first_round, &randomness, &sig_uncompressed, &2), 1);
+    assert_eq!(count_events(&env, &client.address, "queue_item_skipped"), 1);
+    assert!(client.get_cartridge(&ids[1]).unwrap().finalized);
+    assert!(!client.get_cartridge(&ids[0]).unwrap().finalized);
+    assert_eq!(client.get_queue_position(&ids[2]), None);
+    assert_eq!(client.get_queue_position(&ids[0]), Some(0));
+
+    // Later calls finish the first round; the second round's queue is separate
+    assert_eq!(client.process_queue(&first_round, &randomness, &sig_uncompressed, &10), 1);
+    assert_eq!(client.get_queue_length(&first_round), 0);
+    assert_eq!(client.process_queue(&first_round, &randomness, &sig_uncompressed, &10), 0);
+    assert_eq!(client.get_queue_length(&second_round), 2);
+    assert_eq!(client.process_queue(&second_round, &randomness, &sig_uncompressed, &10), 2);
+
+    for id in &ids {
+        assert!(client.get_cartridge(id).unwrap().finalized);
+        assert_eq!(client.get_queue_position(id), None);
+        assert!(client.get_creature(id).is_some());
+    }
+    assert_eq!(
+        client.try_request_finalization(&user, &ids[0]),
+        Err(Ok(contract_error(Error::AlreadyFinalized)))
+    );
+}
+
+#[test]
+fn test_finalization_queue_skips_items_that_fail_invariants() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+    // Record a version history so forged stamps can be told apart
+    client.set_derivation_version(&crate::LATEST_DERIVATION_VERSION);
+    env.ledger().with_mut(|li| li.timestamp += 100);
+
+    let ids: std::vec::Vec<u32> = (0..3).map(|_| client.splice_genome(&user)).collect();
+    let round = client.get_cartridge(&ids[0]).unwrap().splice_round;
+    for id in &ids {
+        client.request_finalization(&user, id);
+    }
+
+    // One stamp is forged and one record vanishes while queued
+    forge_derivation_version(&env, &client, ids[0], 1);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&crate::DataKey::Cartridge(ids[1]));
+    });
+
+    // Both are dropped with an event; the crank still finalizes the rest
+    wait_for_round(&env, round);
+    let (randomness, _, sig_uncompressed) = create_mock_entropy(&env);
+    assert_eq!(client.process_queue(&round, &randomness, &sig_uncompressed, &10), 1);
+    assert_eq!(count_events(&env, &client.address, "queue_item_skipped"), 2);
+    assert_eq!(client.get_queue_length(&round), 0);
+    assert!(!client.get_cartridge(&ids[0]).unwrap().finalized);
+    assert!(client.get_cartridge(&ids[2]).unwrap().finalized);
+}
+
+// ===== Configuration bootstrap tests =====
+
+fn empty_bootstrap() -> crate::FullBootstrap {
+    crate::FullBootstrap {
+        skin_count: None,
+        skin_caps: None,
+        drand_chain_hash: None,
+        derivation_version: None,
+        max_assignments_per_round: None,
+        round_substitution_delay: None,
+        round_substitution_max_offset: None,
+        max_finalization_lag: None,
+        max_randomness_age: None,
+        bulk_discounts: None,
+        points_rate: None,
+        consumer_read_fee: None,
+        verification_share: None,
+        verification_cost_cap: None,
+        layer_table: None,
+        reveal_time: None,
+        game_operator: None,
+        metadata_operator: None,
+        operator: None,
+    }
+}
+
+#[test]
+fn test_bootstrap_config_applies_every_section_once() {
+    use crate::{LayerRule, LayerSource};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let game = Address::generate(&env);
+    let curator = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let chain_hash = soroban_sdk::BytesN::from_array(&env, &[7; 32]);
+    let layers = soroban_sdk::vec![
+        &env,
+        LayerRule {
+            category: soroban_sdk::symbol_short!("bg"),
+            source: LayerSource::Skin,
+            asset_offset: 500,
+            z_index: 0,
+        },
+    ];
+    let config = crate::FullBootstrap {
+        skin_count: Some(4),
+        skin_caps: Some(soroban_sdk::vec![&env, (0, Some(10)), (3, Some(0))]),
+        drand_chain_hash: Some(chain_hash.clone()),
+        derivation_version: Some(2),
+        max_assignments_per_round: Some(25),
+        round_substitution_delay: Some(3_600),
+        round_substitution_max_offset: Some(20),
+        max_finalization_lag: Some(86_400),
+        max_randomness_age: Some(600),
+        bulk_discounts: Some(soroban_sdk::vec![&env, (3, 1_000)]),
+        points_rate: Some(5),
+        consumer_read_fee: Some(1_000),
+        verification_share: Some(100),
+        verification_cost_cap: Some(5_000),
+        layer_table: Some(layers.clone()),
+        reveal_time: Some(2_000_000_000),
+        game_operator: Some(game.clone()),
+        metadata_operator: Some(curator.clone()),
+        operator: Some(operator.clone()),
+    };
+
+    // A section failing validation reverts the sections before it
+    let mut invalid = config.clone();
+    invalid.derivation_version = Some(99);
+    assert_eq!(
+        client.try_bootstrap_config(&invalid),
+        Err(Ok(contract_error(Error::UnsupportedDerivationVersion)))
+    );
+    assert_ne!(client.get_skin_count(), 4);
+
+    client.bootstrap_config(&config);
+    assert_eq!(count_events(&env, &client.address, "bootstrap_applied"), 1);
+
+    assert_eq!(client.get_skin_count(), 4);
+    assert_eq!(client.get_skin_supply(&0), (0, Some(10)));
+    assert_eq!(client.get_available_skins(), soroban_sdk::vec![&env, 0, 1, 2]);
+    assert_eq!(client.get_drand_chain_hash(), chain_hash);
+    assert_eq!(client.get_derivation_version(), 2);
+    assert_eq!(client.get_max_assignments_per_round(), 25);
+    assert_eq!(client.get_round_substitution_delay(), 3_600);
+    assert_eq!(client.get_max_round_substitution(), 20);
+    assert_eq!(client.get_max_finalization_lag(), 86_400);
+    assert_eq!(client.get_max_randomness_age(), 600);
+    assert_eq!(client.get_bulk_discounts(), soroban_sdk::vec![&env, (3, 1_000)]);
+    assert_eq!(client.get_points_rate(), 5);
+    assert_eq!(client.get_consumer_read_fee(), 1_000);
+    assert_eq!(client.get_verification_share(), 100);
+    assert_eq!(client.get_verification_cost_cap(), 5_000);
+    assert_eq!(client.get_layer_table(), layers);
+    assert_eq!(client.get_reveal_time(), Some(2_000_000_000));
+    assert_eq!(client.get_game_operator(), Some(game));
+    assert_eq!(client.get_metadata_operator(), Some(curator));
+    assert_eq!(client.operator(), Some(operator));
+
+    // Each section went through its setter's config log entry, one per skin cap
+    assert_eq!(client.get_config_log(&0, &crate::MAX_CONFIG_LOG).len(), 18);
+
+    // Closed for good once a cartridge exists
+    client.splice_genome(&user);
+    assert_eq!(
+        client.try_bootstrap_config(&empty_bootstrap()),
+        Err(Ok(contract_error(Error::BootstrapClosed)))
+    );
+}
+
+#[test]
+fn test_bootstrap_config_requires_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let mut config = empty_bootstrap();
+    config.points_rate = Some(3);
+    env.set_auths(&[]);
+    assert!(client.try_bootstrap_config(&config).is_err());
+    env.mock_all_auths();
+    client.bootstrap_config(&config);
+    assert_eq!(client.get_points_rate(), 3);
+}
+
+// ===== Feature switch tests =====
+
+#[test]
+fn test_disabled_feature_refuses_its_entrypoints() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&seller, &100_000_000);
+    xlm_token.mint(&buyer, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // Everything is on until a switch is flipped
+    assert_eq!(client.get_features().len(), crate::FEATURES.len() as u32);
+    let marketplace = soroban_sdk::Symbol::new(&env, "marketplace");
+    let listed = client.splice_genome(&seller);
+    client.sell_finalization_right(&seller, &listed, &10);
+
+    client.set_feature_enabled(&marketplace, &false);
+    assert_eq!(count_events(&env, &client.address, "feature_toggled"), 1);
+    assert!(!client.get_features().contains(&marketplace));
+    assert_eq!(client.get_features().len(), crate::FEATURES.len() as u32 - 1);
+
+    let disabled = contract_error(FeatureError::FeatureDisabled);
+    let other = client.splice_genome(&seller);
+    assert_eq!(client.try_sell_finalization_right(&seller, &other, &10), Err(Ok(disabled)));
+    assert_eq!(client.try_splice_and_list(&seller, &10), Err(Ok(disabled)));
+    assert_eq!(client.try_buy_finalization_right(&buyer, &listed), Err(Ok(disabled)));
+    // Other subsystems and plain mints are unaffected
+    client.create_proposal();
+
+    client.set_feature_enabled(&marketplace, &true);
+    assert!(client.get_features().contains(&marketplace));
+    client.buy_finalization_right(&buyer, &listed);
+    assert_eq!(client.get_cartridge(&listed).unwrap().finalize_beneficiary, Some(buyer));
+    client.splice_and_list(&seller, &10);
+
+    assert_eq!(
+        client.try_set_feature_enabled(&soroban_sdk::Symbol::new(&env, "staking"), &false),
+        Err(Ok(contract_error(FeatureError::UnknownFeature)))
+    );
+    env.set_auths(&[]);
+    assert!(client.try_set_feature_enabled(&marketplace, &false).is_err());
+}
+
+// ===== Config log tests =====
+
+fn xdr_hash<T: soroban_sdk::xdr::ToXdr>(env: &Env, value: T) -> soroban_sdk::BytesN<32> {
+    env.crypto().sha256(&value.to_xdr(env)).into()
+}
+
+#[test]
+fn test_config_log_records_changes_in_order() {
+    use soroban_sdk::Symbol;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.set_skin_count(&12);
+    client.set_skin_cap(&3, &Some(5));
+    client.set_paused(&true);
+    assert_eq!(count_events(&env, &client.address, "config_changed"), 2);
+
+    let log = client.get_config_log(&0, &10);
+    assert_eq!(log.len(), 4);
+    let changes: std::vec::Vec<Symbol> = log.iter().map(|e| e.change).collect();
+    assert_eq!(
+        changes,
+        [
+            Symbol::new(&env, "skin_count"),
+            Symbol::new(&env, "skin_cap"),
+            Symbol::new(&env, "mint_paused"),
+            Symbol::new(&env, "finalize_paused"),
+        ]
+    );
+    assert!(log.iter().all(|e| e.actor == admin));
+
+    // Summary hashes match a recomputation of the new values
+    assert_eq!(log.get(0).unwrap().summary_hash, xdr_hash(&env, 12u64));
+    assert_eq!(log.get(1).unwrap().summary_hash, xdr_hash(&env, (3u32, Some(5u32))));
+    assert_eq!(log.get(2).unwrap().summary_hash, xdr_hash(&env, true));
+
+    // Paging
+    let page = client.get_config_log(&1, &2);
+    assert_eq!(page, log.slice(1..3));
+    assert_eq!(client.get_config_log(&10, &5).len(), 0);
+}
+
+#[test]
+fn test_config_log_is_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let total = crate::MAX_CONFIG_LOG as u64 + 5;
+    for count in 1..=total {
+        client.set_skin_count(&count);
+    }
+
+    let log = client.get_config_log(&0, &u32::MAX);
+    assert_eq!(log.len(), crate::MAX_CONFIG_LOG);
+    // The five oldest changes were dropped
+    assert_eq!(log.get(0).unwrap().summary_hash, xdr_hash(&env, 6u64));
+    assert_eq!(log.last().unwrap().summary_hash, xdr_hash(&env, total));
+}
+
+// ===== Parameter parsing tests =====
+
+/// `bytes` with `extra` trailing garbage bytes appended
+fn with_trailing(bytes: &Bytes, extra: u32) -> Bytes {
+    let mut padded = bytes.clone();
+    for _ in 0..extra {
+        padded.push_back(0xff);
+    }
+    padded
+}
+
+#[test]
+fn test_finalize_splice_rejects_malformed_params() {
+    let env = Env::default();
+    let (client, _, cartridge_id, _) = setup_permit(&env);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let (r, sc, su) = create_mock_entropy(&env);
+
+    let finalize = |r: &Bytes, sc: &Bytes, su: &Bytes, hash: &Option<Bytes>| {
+        client.try_finalize_splice(&cartridge_id, &round, r, sc, su, hash)
+    };
+
+    // Over-length inputs are rejected, not truncated
+    let long_r = with_trailing(&r, 1);
+    let long_sc = with_trailing(&sc, 1);
+    let long_su = with_trailing(&su, 1);
+    let short_hash = Some(Bytes::from_array(&env, &[0u8; 31]));
+    assert_eq!(
+        finalize(&long_r, &sc, &su, &None),
+        Err(Ok(contract_error(Error::InvalidRandomnessLength)))
+    );
+    assert_eq!(
+        finalize(&r, &long_sc, &su, &None),
+        Err(Ok(contract_error(Error::InvalidSignatureLength)))
+    );
+    assert_eq!(
+        finalize(&r, &sc, &long_su, &None),
+        Err(Ok(contract_error(Error::InvalidSignatureLength)))
+    );
+    assert_eq!(
+        finalize(&r, &sc, &su, &short_hash),
+        Err(Ok(contract_error(Error::WrongDrandNetwork)))
+    );
+
+    // Lengths are checked before the cartridge is looked up
+    assert_eq!(
+        client.try_finalize_splice(&999, &round, &long_r, &sc, &su, &None),
+        Err(Ok(contract_error(Error::InvalidRandomnessLength)))
+    );
+
+    assert!(finalize(&r, &sc, &su, &None).is_ok());
+}
+
+#[test]
+fn test_finalize_with_permit_rejects_malformed_params() {
+    let env = Env::default();
+    let (client, _, cartridge_id, key) = setup_permit(&env);
+    let relayer = Address::generate(&env);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let permit = sign_permit(&env, &client, &key, cartridge_id, 1_000);
+    let (r, sc, su) = create_mock_entropy(&env);
+
+    let finalize = |r: &Bytes, sc: &Bytes, su: &Bytes| {
+        client.try_finalize_with_permit(&relayer, &cartridge_id, &round, r, sc, su, &None, &Some(permit.clone()))
+    };
+
+    let short_r = r.slice(0..31);
+    let short_sc = sc.slice(0..47);
+    let short_su = su.slice(0..95);
+    assert_eq!(
+        finalize(&short_r, &sc, &su),
+        Err(Ok(contract_error(Error::InvalidRandomnessLength)))
+    );
+    assert_eq!(
+        finalize(&r, &short_sc, &su),
+        Err(Ok(contract_error(Error::InvalidSignatureLength)))
+    );
+    assert_eq!(
+        finalize(&r, &sc, &short_su),
+        Err(Ok(contract_error(Error::InvalidSignatureLength)))
+    );
+
+    // Rejected calls did not consume the permit
+    assert!(finalize(&r, &sc, &su).is_ok());
+}
+
+#[test]
+fn test_verification_entrypoints_reject_malformed_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 1);
+    let (_, _, su) = real_beacon(&env);
+
+    for bad in [su.slice(0..95), with_trailing(&su, 1)] {
+        assert_eq!(
+            client.try_begin_verification(&REAL_ROUND, &bad),
+            Err(Ok(contract_error(Error::InvalidSignatureLength)))
+        );
+        assert_eq!(
+            client.try_is_round_verified(&REAL_ROUND, &bad),
+            Err(Ok(contract_error(Error::InvalidSignatureLength)))
+        );
+    }
+    assert!(!client.is_round_verified(&REAL_ROUND, &su));
+}
+
+#[test]
+fn test_drand_key_setters_reject_malformed_length() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let key = real_drand_pubkey(&env);
+    for bad in [key.slice(0..191), with_trailing(&key, 1)] {
+        assert_eq!(
+            client.try_set_drand_public_key(&bad),
+            Err(Ok(contract_error(Error::InvalidPublicKeyLength)))
+        );
+    }
+    assert_eq!(client.get_drand_public_key(), create_mock_drand_pubkey(&env));
+
+    client.set_drand_public_key(&key);
+    assert_eq!(client.get_drand_public_key(), key);
+}
+
+// ===== Gene index tests =====
+
+#[test]
+fn test_gene_index_tracks_finalized_creatures() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let mut creatures = std::vec::Vec::new();
+    for seed in 0u8..6 {
+        let creature_id = client.dev_splice_and_finalize(&user, &Bytes::from_array(&env, &[seed; 32]));
+        creatures.push(client.get_creature(&creature_id).unwrap());
+    }
+
+    for creature in &creatures {
+        let genes = [&creature.head_gene, &creature.body_gene, &creature.legs_gene];
+        for (slot, gene) in (0u32..).zip(genes) {
+            // Every creature carrying this gene, in finalization order
+            let expected: std::vec::Vec<u32> = creatures
+                .iter()
+                .filter(|c| [&c.head_gene, &c.body_gene, &c.legs_gene][slot as usize].id == gene.id)
+                .map(|c| c.id)
+                .collect();
+            let count = client.get_creatures_by_gene_count(&slot, &gene.id);
+            assert_eq!(count as usize, expected.len());
+            let page = client.get_creatures_by_gene(&slot, &gene.id, &0, &100);
+            assert_eq!(page.iter().collect::<std::vec::Vec<u32>>(), expected);
+        }
+    }
+
+    // Each slot indexes every creature exactly once
+    for slot in 0u32..3 {
+        let total: u32 = (0u32..15).map(|gene_id| client.get_creatures_by_gene_count(&slot, &gene_id)).sum();
+        assert_eq!(total, 6);
+    }
+
+    assert_eq!(
+        client.try_get_creatures_by_gene_count(&3, &0),
+        Err(Ok(contract_error(Error::InvalidGeneSlot)))
+    );
+}
+
+#[test]
+fn test_gene_index_chunk_rollover() {
+    use crate::GENE_INDEX_CHUNK;
+
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // mock_creature carries head gene 6
+    let total = 2 * GENE_INDEX_CHUNK + 5;
+    for id in 1..=total {
+        env.as_contract(&client.address, || {
+            crate::gene_index::index_creature(&env, &mock_creature(id, &admin));
+        });
+    }
+    assert_eq!(client.get_creatures_by_gene_count(&0, &6), total);
+    env.as_contract(&client.address, || {
+        let chunk = |index| {
+            env.storage()
+                .persistent()
+                .get::<_, soroban_sdk::Vec<u32>>(&crate::DataKey::CreaturesByGeneChunk(0, 6, index))
+        };
+        assert_eq!(chunk(0).unwrap().len(), GENE_INDEX_CHUNK);
+        assert_eq!(chunk(1).unwrap().len(), GENE_INDEX_CHUNK);
+        assert_eq!(chunk(2).unwrap().len(), 5);
+        assert!(chunk(3).is_none());
+    });
+
+    // A page straddling a chunk boundary is contiguous
+    let page = client.get_creatures_by_gene(&0, &6, &(GENE_INDEX_CHUNK - 2), &5);
+    let expected: std::vec::Vec<u32> = (GENE_INDEX_CHUNK - 1..GENE_INDEX_CHUNK + 4).collect();
+    assert_eq!(page.iter().collect::<std::vec::Vec<u32>>(), expected);
+
+    // Pages are capped and stop at the end of the list
+    let limit = client.get_limits().max_gene_index_page;
+    assert_eq!(client.get_creatures_by_gene(&0, &6, &0, &u32::MAX).len(), limit);
+    assert_eq!(client.get_creatures_by_gene(&0, &6, &(total - 3), &10).len(), 3);
+    assert_eq!(client.get_creatures_by_gene(&0, &6, &total, &10).len(), 0);
+}
+
+// ===== Metadata anchoring tests =====
+
+#[test]
+fn test_anchor_metadata_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let renderer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let cartridge_id = client.splice_genome(&user);
+    let hash = soroban_sdk::BytesN::from_array(&env, &[0x11; 32]);
+
+    // No operator configured yet
+    assert_eq!(
+        client.try_anchor_metadata(&renderer, &cartridge_id, &hash),
+        Err(Ok(contract_error(MetadataError::NotMetadataOperator)))
+    );
+    client.set_metadata_operator(&Some(renderer.clone()));
+
+    // Only finalized creatures can be anchored
+    assert_eq!(
+        client.try_anchor_metadata(&renderer, &cartridge_id, &hash),
+        Err(Ok(contract_error(Error::CreatureNotFound)))
+    );
+    client.dev_finalize(&cartridge_id, &Bytes::from_array(&env, b"seed"));
+
+    assert_eq!(
+        client.try_anchor_metadata(&user, &cartridge_id, &hash),
+        Err(Ok(contract_error(MetadataError::NotMetadataOperator)))
+    );
+    client.anchor_metadata(&renderer, &cartridge_id, &hash);
+    assert_eq!(count_events(&env, &client.address, "metadata_anchored"), 1);
+    assert_eq!(client.get_metadata_hash(&cartridge_id), Some(hash.clone()));
+
+    // Anchoring is once-only, even for the operator
+    let other = soroban_sdk::BytesN::from_array(&env, &[0x22; 32]);
+    assert_eq!(
+        client.try_anchor_metadata(&renderer, &cartridge_id, &other),
+        Err(Ok(contract_error(MetadataError::MetadataAlreadyAnchored)))
+    );
+    assert_eq!(client.get_metadata_hash(&cartridge_id), Some(hash));
+}
+
+#[test]
+fn test_metadata_correction_flow() {
+    use soroban_sdk::{testutils::Ledger, IntoVal};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let renderer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_metadata_operator(&Some(renderer.clone()));
+
+    let creature_id = client.splice_genome(&user);
+    let original = soroban_sdk::BytesN::from_array(&env, &[0x11; 32]);
+    let fixed = soroban_sdk::BytesN::from_array(&env, &[0x22; 32]);
+
+    assert_eq!(
+        client.try_request_metadata_correction(&renderer, &creature_id, &fixed),
+        Err(Ok(contract_error(MetadataError::MetadataNotAnchored)))
+    );
+    client.dev_finalize(&creature_id, &Bytes::from_array(&env, b"seed"));
+    client.anchor_metadata(&renderer, &creature_id, &original);
+    assert_eq!(
+        client.try_approve_metadata_correction(&creature_id),
+        Err(Ok(contract_error(MetadataError::NoMetadataCorrection)))
+    );
+
+    // A request alone changes nothing
+    client.request_metadata_correction(&renderer, &creature_id, &fixed);
+    assert_eq!(client.get_metadata_correction(&creature_id), Some(fixed.clone()));
+    assert_eq!(client.get_metadata_hash(&creature_id), Some(original.clone()));
+
+    // Only the Owner can approve
+    as_signer(&env, &client, &renderer, "approve_metadata_correction", (creature_id,).into_val(&env));
+    assert!(client.try_approve_metadata_correction(&creature_id).is_err());
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    client.approve_metadata_correction(&creature_id);
+    assert_eq!(count_events(&env, &client.address, "metadata_corrected"), 1);
+    assert_eq!(client.get_metadata_hash(&creature_id), Some(fixed));
+    assert_eq!(client.get_metadata_correction(&creature_id), None);
+    assert_eq!(
+        client.get_metadata_provenance(&creature_id),
+        soroban_sdk::vec![
+            &env,
+            crate::MetadataRevision { metadata_hash: original, replaced_at: 5_000 }
+        ]
+    );
+}
+
+// ===== Finalized bitmap tests =====
+
+#[test]
+fn test_finalized_bitmap_across_chunk_boundary() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // Start minting just below the first chunk boundary (IDs 254..=257)
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&crate::DataKey::NextCartridgeId, &254u32);
+    });
+    let ids: std::vec::Vec<u32> = (0..4).map(|_| client.splice_genome(&user)).collect();
+    assert_eq!(ids, [254, 255, 256, 257]);
+    for id in [255u32, 256] {
+        client.dev_finalize(&id, &Bytes::from_array(&env, b"seed"));
+    }
+
+    for id in ids.iter() {
+        assert_eq!(client.is_finalized_fast(id), client.is_finalized(id));
+    }
+    let mut low = [0u8; 32];
+    low[31] = 0b1000_0000; // ID 255
+    let mut high = [0u8; 32];
+    high[0] = 0b0000_0001; // ID 256
+    assert_eq!(client.get_finalized_bitmap(&0).to_array(), low);
+    assert_eq!(client.get_finalized_bitmap(&1).to_array(), high);
+    assert_eq!(client.get_finalized_bitmap(&2).to_array(), [0u8; 32]);
+
+    // A corrupted index is rebuilt from the cartridges' own flags
+    env.as_contract(&client.address, || {
+        let garbage = soroban_sdk::BytesN::from_array(&env, &[0xff; 32]);
+        env.storage().persistent().set(&crate::DataKey::FinalizedBitmapChunk(0), &garbage);
+        env.storage().persistent().remove(&crate::DataKey::FinalizedBitmapChunk(1));
+    });
+    assert!(client.is_finalized_fast(&254));
+    assert!(!client.is_finalized_fast(&256));
+    client.repair_finalized_bitmap(&254, &4);
+    for id in ids.iter() {
+        assert_eq!(client.is_finalized_fast(id), client.is_finalized(id));
+    }
+    // Bits outside the repaired range are left alone
+    assert!(client.is_finalized_fast(&1));
+
+    let limit = client.get_limits().max_batch_read;
+    assert_eq!(
+        client.try_repair_finalized_bitmap(&0, &(limit + 1)),
+        Err(Ok(contract_error(ReadLimitError::MaxBatchReadExceeded)))
+    );
+}
+
+// ===== Round assignment cap tests =====
+
+#[test]
+fn test_round_assignment_cap_spreads_rounds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.set_max_assignments_per_round(&2);
+    assert_eq!(client.get_max_assignments_per_round(), 2);
+
+    // Five mints in one ledger: two per round, spilling forward
+    let rounds: std::vec::Vec<u64> = (0..5)
+        .map(|_| {
+            let id = client.splice_genome(&user);
+            client.get_cartridge(&id).unwrap().splice_round
+        })
+        .collect();
+    let first = rounds[0];
+    assert_eq!(rounds, [first, first, first + 1, first + 1, first + 2]);
+    assert_eq!(client.get_round_assignments(&first), 2);
+    assert_eq!(client.get_round_assignments(&(first + 2)), 1);
+}
+
+#[test]
+fn test_round_assignment_search_is_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &200_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.set_max_assignments_per_round(&1);
+    for _ in 0..crate::MAX_ROUND_SEARCH {
+        client.splice_genome(&user);
+    }
+    assert_eq!(
+        client.try_splice_genome(&user),
+        Err(Ok(contract_error(Error::RoundCapacityExhausted)))
+    );
+
+    // Lifting the cap lets mints share a round again
+    client.set_max_assignments_per_round(&0);
+    client.splice_genome(&user);
+}
+
+// ===== Render layer tests =====
+
+#[test]
+fn test_render_layers_follow_layer_table() {
+    use crate::{GeneRarity, LayerRef, LayerRule, LayerSource};
+    use soroban_sdk::{symbol_short, vec};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // Skin 0, genes head 6 / body 7 / legs 8, one Legendary gene
+    store_creature(
+        &env,
+        &client.address,
+        1,
+        &owner,
+        [GeneRarity::Normal, GeneRarity::Legendary, GeneRarity::Normal],
+    );
+    let layer = |category, asset_id, z_index| LayerRef { category, asset_id, z_index };
+
+    assert_eq!(
+        client.get_render_layers(&1),
+        vec![
+            &env,
+            layer(symbol_short!("bg"), 0, 0),
+            layer(symbol_short!("legs"), 1_008, 10),
+            layer(symbol_short!("torso"), 2_007, 20),
+            layer(symbol_short!("head"), 3_006, 30),
+        ]
+    );
+
+    // Reorganized art: new offsets, a rarity frame on top, no separate legs layer
+    let rule = |category, source, asset_offset, z_index| LayerRule { category, source, asset_offset, z_index };
+    client.set_layer_table(&vec![
+        &env,
+        rule(symbol_short!("bg"), LayerSource::Skin, 500, 0),
+        rule(symbol_short!("torso"), LayerSource::BodyGene, 7_000, 5),
+        rule(symbol_short!("head"), LayerSource::HeadGene, 8_000, 6),
+        rule(symbol_short!("frame"), LayerSource::TopRarity, 9_000, 7),
+    ]);
+    assert_eq!(
+        client.get_render_layers(&1),
+        vec![
+            &env,
+            layer(symbol_short!("bg"), 500, 0),
+            layer(symbol_short!("torso"), 7_007, 5),
+            layer(symbol_short!("head"), 8_006, 6),
+            layer(symbol_short!("frame"), 9_002, 7),
+        ]
+    );
+
+    // Tables must be in strictly ascending z order
+    let unordered = vec![
+        &env,
+        rule(symbol_short!("head"), LayerSource::HeadGene, 0, 3),
+        rule(symbol_short!("bg"), LayerSource::Skin, 0, 3),
+    ];
+    assert_eq!(
+        client.try_set_layer_table(&unordered),
+        Err(Ok(contract_error(Error::InvalidLayerTable)))
+    );
+    assert_eq!(
+        client.try_get_render_layers(&2),
+        Err(Ok(contract_error(Error::CreatureNotFound)))
+    );
+}
+
+// ===== Time-locked reveal tests =====
+
+/// `head_gene_id` carried by the last `creature_finalized` event
+fn finalized_event_head_gene(env: &Env, contract_id: &Address) -> u32 {
+    use soroban_sdk::xdr::{ContractEventBody, ScSymbol, ScVal};
+
+    let topic = ScVal::Symbol(ScSymbol("creature_finalized".try_into().unwrap()));
+    let field = ScVal::Symbol(ScSymbol("head_gene_id".try_into().unwrap()));
+    let event = env
+        .events()
+        .all()
+        .filter_by_contract(contract_id)
+        .events()
+        .iter()
+        .rev()
+        .find(|event| {
+            let ContractEventBody::V0(body) = &event.body;
+            body.topics.first() == Some(&topic)
+        })
+        .cloned()
+        .unwrap();
+    let ContractEventBody::V0(body) = event.body;
+    let ScVal::Map(Some(map)) = body.data else {
+        panic!("event data is not a map");
+    };
+    match map.iter().find(|entry| entry.key == field).unwrap().val {
+        ScVal::U32(id) => id,
+        _ => panic!("head_gene_id is not a u32"),
+    }
+}
+
+#[test]
+fn test_genes_hidden_until_reveal_time() {
+    use crate::HIDDEN_GENE_ID;
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.set_reveal_time(&Some(5_000));
+    assert!(!client.is_revealed());
+
+    // Genes are derived and stored at finalization, but reads and events hide them
+    let id = client.splice_genome(&user);
+    client.dev_finalize(&id, &Bytes::from_array(&env, b"seed"));
+    assert_eq!(finalized_event_head_gene(&env, &client.address), HIDDEN_GENE_ID);
+    let stored: crate::Creature = env.as_contract(&client.address, || {
+        env.storage().persistent().get(&crate::DataKey::Creature(id)).unwrap()
+    });
+    assert_ne!(stored.head_gene.id, HIDDEN_GENE_ID);
+
+    let sealed = client.get_creature(&id).unwrap();
+    assert_eq!(
+        (sealed.head_gene.id, sealed.body_gene.id, sealed.legs_gene.id),
+        (HIDDEN_GENE_ID, HIDDEN_GENE_ID, HIDDEN_GENE_ID)
+    );
+    assert_eq!(sealed.skin_id, stored.skin_id);
+    let batch = client.get_creatures_batch(&soroban_sdk::vec![&env, id]);
+    assert_eq!(batch.get(0).unwrap().unwrap(), sealed);
+    let thumb = client.get_creature_thumbs(&soroban_sdk::vec![&env, id]).get(0).unwrap();
+    assert_eq!(thumb.head_gene_id, HIDDEN_GENE_ID);
+    assert_eq!(
+        client.try_get_render_layers(&id),
+        Err(Ok(contract_error(RevealError::NotRevealed)))
+    );
+    assert_eq!(
+        client.try_get_creatures_by_gene_count(&0, &stored.head_gene.id),
+        Err(Ok(contract_error(RevealError::NotRevealed)))
+    );
+
+    // Reaching the reveal time is enough, no transaction needed
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    assert!(client.is_revealed());
+    assert_eq!(client.get_creature(&id).unwrap(), stored);
+    assert_eq!(client.get_creatures_by_gene_count(&0, &stored.head_gene.id), 1);
+}
+
+#[test]
+fn test_trigger_reveal_announces_once() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.set_reveal_time(&Some(u64::MAX));
+    let id = client.splice_genome(&user);
+    client.dev_finalize(&id, &Bytes::from_array(&env, b"seed"));
+    assert_eq!(client.get_creature(&id).unwrap().head_gene.id, crate::HIDDEN_GENE_ID);
+
+    // The Owner can reveal ahead of schedule with one collection-level event
+    client.trigger_reveal();
+    assert_eq!(count_events(&env, &client.address, "collection_revealed"), 1);
+    assert!(client.is_revealed());
+    assert_ne!(client.get_creature(&id).unwrap().head_gene.id, crate::HIDDEN_GENE_ID);
+
+    // Creatures finalized after the reveal emit their real genes
+    let next = client.splice_genome(&user);
+    client.dev_finalize(&next, &Bytes::from_array(&env, b"seed"));
+    assert_eq!(
+        finalized_event_head_gene(&env, &client.address),
+        client.get_creature(&next).unwrap().head_gene.id
+    );
+
+    assert_eq!(
+        client.try_trigger_reveal(),
+        Err(Ok(contract_error(RevealError::AlreadyRevealed)))
+    );
+}
+
+// ===== Inbox tests =====
+
+#[test]
+fn test_inbox_collects_notifications() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&seller, &100_000_000);
+    xlm_token.mint(&buyer, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    let (r, sc, su) = create_mock_entropy(&env);
+
+    // The seller finalizes one cartridge and sells the right to another
+    let kept = client.splice_genome(&seller);
+    let sold = client.splice_genome(&seller);
+    let round = client.get_cartridge(&kept).unwrap().splice_round;
+    client.finalize_splice(&kept, &round, &r, &sc, &su, &None);
+    client.sell_finalization_right(&seller, &sold, &25_000_000);
+    client.buy_finalization_right(&buyer, &sold);
+    client.finalize_splice(&sold, &round, &r, &sc, &su, &None);
+
+    let entry = |kind: &str, id: u32| crate::InboxEntry {
+        kind: soroban_sdk::Symbol::new(&env, kind),
+        id,
+        timestamp: 1_000,
+    };
+    assert_eq!(
+        client.read_inbox(&seller),
+        soroban_sdk::vec![&env, entry("finalized", kept), entry("right_sold", sold)]
+    );
+    assert_eq!(client.read_inbox(&buyer), soroban_sdk::vec![&env, entry("finalized", sold)]);
+
+    client.clear_inbox(&seller);
+    assert_eq!(client.read_inbox(&seller).len(), 0);
+    assert_eq!(client.read_inbox(&buyer).len(), 1);
+}
+
+#[test]
+fn test_inbox_evicts_oldest() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &1_000_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let total = crate::MAX_INBOX + 3;
+    for _ in 0..total {
+        client.dev_splice_and_finalize(&user, &Bytes::from_array(&env, &[7; 32]));
+    }
+
+    let inbox = client.read_inbox(&user);
+    assert_eq!(inbox.len(), crate::MAX_INBOX);
+    assert_eq!(inbox.first().unwrap().id, 4);
+    assert_eq!(inbox.last().unwrap().id, total);
+}
+
+#[test]
+fn test_clear_inbox_requires_owner_auth() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    assert!(client.try_clear_inbox(&user).is_err());
+}
+
+// ===== Points checkout tests =====
+
+/// Credit `user` with points by writing their balance directly
+fn grant_points(env: &Env, client: &GeneSplicerClient, user: &Address, points: u64) {
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::Points(user.clone()), &points);
+    });
+}
+
+#[test]
+fn test_points_checkout_full_and_partial() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_points_rate(&2);
+    grant_points(&env, &client, &user, 30_000_000);
+
+    // Applying more points than the fee needs only spends the fee's worth
+    let cartridge_id = client.splice_genome_with_points(&user, &25_000_000);
+    assert_eq!(count_events(&env, &client.address, "points_checkout"), 1);
+    assert_eq!(client.get_cartridge(&cartridge_id).unwrap().owner, user);
+    assert_eq!(client.get_points(&user), 10_000_000);
+    assert_eq!(xlm_token.balance(&user), 100_000_000);
+
+    // 4M points buy 2M stroops; an odd leftover point isn't spent
+    client.splice_genome_with_points(&user, &4_000_001);
+    assert_eq!(client.get_points(&user), 6_000_000);
+    assert_eq!(xlm_token.balance(&user), 92_000_000);
+    assert_eq!(xlm_token.balance(&admin), 8_000_000);
+    assert_eq!(client.get_user_cartridges(&user).len(), 2);
+}
+
+#[test]
+fn test_points_checkout_rejected_without_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    grant_points(&env, &client, &user, 1_000);
+
+    assert_eq!(
+        client.try_splice_genome_with_points(&user, &1_000),
+        Err(Ok(contract_error(PointsError::PointsDisabled)))
+    );
+
+    client.set_points_rate(&1);
+    assert_eq!(
+        client.try_splice_genome_with_points(&user, &1_001),
+        Err(Ok(contract_error(PointsError::InsufficientPoints)))
+    );
+
+    // A failed mint leaves the points untouched
+    client.set_mint_paused(&true);
+    assert_eq!(
+        client.try_splice_genome_with_points(&user, &1_000),
+        Err(Ok(contract_error(Error::MintPaused)))
+    );
+    assert_eq!(client.get_points(&user), 1_000);
+    assert_eq!(xlm_token.balance(&user), 100_000_000);
+    assert_eq!(client.get_total_cartridges(), 0);
+}
+
+// ===== Round substitution tests =====
+
+#[test]
+fn test_substitute_round_window_and_delay() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+
+    let cartridge_id = client.splice_genome(&user);
+    let original = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let set_time = |time: u64| env.ledger().with_mut(|li| li.timestamp = time);
+    let substitute = |round: u64| client.try_substitute_round(&cartridge_id, &round);
+
+    // Off by default, even for a long-overdue round
+    set_time(crate::drand_round_time(original) + 10 * crate::DEFAULT_SUBSTITUTION_DELAY);
+    assert_eq!(substitute(original + 1), Err(Ok(contract_error(SubstitutionError::SubstitutionNotAllowed))));
+
+    client.set_round_substitution(&3_600, &2);
+    set_time(crate::drand_round_time(original) + 3_599);
+    assert_eq!(substitute(original + 1), Err(Ok(contract_error(SubstitutionError::SubstitutionTooEarly))));
+
+    set_time(crate::drand_round_time(original) + 3_600);
+    for outside in [original - 1, original, original + 3] {
+        assert_eq!(substitute(outside), Err(Ok(contract_error(SubstitutionError::SubstitutionNotAllowed))));
+    }
+    client.substitute_round(&cartridge_id, &(original + 1));
+    assert_eq!(count_events(&env, &client.address, "round_substituted"), 1);
+    let cartridge = client.get_cartridge(&cartridge_id).unwrap();
+    assert_eq!(cartridge.splice_round, original + 1);
+    assert_eq!(cartridge.substituted_from, Some(original));
+
+    // A further move waits on the new round and stays within the original's window
+    assert_eq!(substitute(original + 2), Err(Ok(contract_error(SubstitutionError::SubstitutionTooEarly))));
+    set_time(crate::drand_round_time(original + 1) + 3_600);
+    client.substitute_round(&cartridge_id, &(original + 2));
+    assert_eq!(client.get_cartridge(&cartridge_id).unwrap().substituted_from, Some(original));
+
+    // The cartridge now finalizes against its substitute round
+    let (r, sc, su) = create_mock_entropy(&env);
+    client.finalize_splice(&cartridge_id, &(original + 2), &r, &sc, &su, &None);
+    assert_eq!(client.get_creature(&cartridge_id).unwrap().entropy_round, original + 2);
+    assert_eq!(substitute(original + 1), Err(Ok(contract_error(Error::AlreadyFinalized))));
+}
+
+#[test]
+fn test_substitute_round_requires_finalizer_auth() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+    let cartridge_id = client.splice_genome(&user);
+    client.set_round_substitution(&0, &1);
+
+    let original = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    env.ledger().with_mut(|li| li.timestamp = crate::drand_round_time(original));
+    env.set_auths(&[]);
+    assert!(client.try_substitute_round(&cartridge_id, &(original + 1)).is_err());
+
+    env.mock_all_auths();
+    client.substitute_round(&cartridge_id, &(original + 1));
+}
+
+// ===== Configuration freeze tests =====
+
+#[test]
+fn test_finalize_configuration_freezes_every_setter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    let key = client.get_drand_public_key();
+    let chain_hash = client.get_drand_chain_hash();
+    let layers = client.get_layer_table();
+    let wasm_hash = soroban_sdk::BytesN::from_array(&env, &[0x22; 32]);
+
+    assert!(!client.is_configuration_final());
+    client.finalize_configuration(&false);
+    assert_eq!(count_events(&env, &client.address, "configuration_frozen"), 1);
+    assert!(client.is_configuration_final());
+    assert_eq!(client.get_configuration_finalized_at(), Some(env.ledger().timestamp()));
+
+    let frozen = || Err(Ok(contract_error(Error::ConfigurationFinalized)));
+    assert_eq!(client.try_set_skin_count(&20), frozen(), "skin_count");
+    assert_eq!(client.try_set_skin_cap(&0, &Some(5)), frozen(), "skin_cap");
+    assert_eq!(client.try_set_drand_public_key(&key), frozen(), "drand_key");
+    assert_eq!(client.try_set_drand_chain_hash(&chain_hash), frozen(), "drand_chain_hash");
+    assert_eq!(client.try_set_derivation_version(&1), frozen(), "derivation_version");
+    assert_eq!(client.try_set_free_mint_attestor(&None, &0), frozen(), "free_mint_attestor");
+    assert_eq!(client.try_set_verification_share(&0, &0), frozen(), "verification_share");
+    assert_eq!(client.try_set_metadata_operator(&None), frozen(), "metadata_operator");
+    assert_eq!(client.try_set_max_assignments_per_round(&1), frozen(), "max_assignments");
+    assert_eq!(client.try_set_layer_table(&layers), frozen(), "layer_table");
+    assert_eq!(client.try_set_reveal_time(&None), frozen(), "reveal_time");
+    assert_eq!(client.try_set_points_rate(&1), frozen(), "points_rate");
+    assert_eq!(client.try_set_round_substitution(&0, &1), frozen(), "round_substitution");
+    assert_eq!(client.try_upgrade(&wasm_hash), frozen(), "upgrade");
+    assert_eq!(client.try_finalize_configuration(&true), frozen(), "finalize_configuration");
+
+    // Operations stay available, and a new Owner can't lift the freeze
+    client.set_paused(&true);
+    client.set_free_mint_enabled(&true);
+    client.set_admin(&new_admin);
+    assert_eq!(client.admin(), new_admin);
+    assert_eq!(client.try_set_skin_count(&20), frozen());
+    assert_eq!(client.try_finalize_configuration(&false), frozen());
+    assert!(client.is_configuration_final());
+}
+
+#[test]
+fn test_finalize_configuration_can_lock_operations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.finalize_configuration(&true);
+    assert!(client.are_operations_locked());
+    for result in [
+        client.try_set_mint_paused(&true),
+        client.try_set_finalize_paused(&true),
+        client.try_set_paused(&true),
+        client.try_set_free_mint_enabled(&true),
+    ] {
+        assert_eq!(result, Err(Ok(contract_error(Error::ConfigurationFinalized))));
+    }
+
+    // Roles can still rotate
+    client.set_operator(&Some(operator.clone()));
+    assert_eq!(client.operator(), Some(operator));
+}
+
+#[test]
+fn test_finalize_configuration_requires_owner() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    assert!(client.try_finalize_configuration(&false).is_err());
+    assert!(!client.is_configuration_final());
+}
+
+// ===== Finalize error report tests =====
+
+#[test]
+fn test_finalize_or_report_records_and_clears_errors() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+
+    let cartridge_id = client.splice_genome(&user);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let (r, sc, su) = create_mock_entropy(&env);
+    wait_for_round(&env, round);
+    assert_eq!(client.get_last_finalize_error(&cartridge_id), None);
+
+    // A wrong round is recorded instead of trapping
+    let failed_at = env.ledger().timestamp();
+    assert_eq!(client.finalize_splice_or_report(&cartridge_id, &(round + 1), &r, &sc, &su, &None), None);
+    assert_eq!(
+        client.get_last_finalize_error(&cartridge_id),
+        Some((Error::RoundMismatch as u32, failed_at))
+    );
+
+    // A second failure overwrites the first
+    env.ledger().with_mut(|li| li.timestamp += 60);
+    let short = su.slice(0..95);
+    assert_eq!(client.finalize_splice_or_report(&cartridge_id, &round, &r, &sc, &short, &None), None);
+    assert_eq!(
+        client.get_last_finalize_error(&cartridge_id),
+        Some((Error::InvalidSignatureLength as u32, failed_at + 60))
+    );
+    assert!(!client.has_creature(&cartridge_id));
+
+    // Success clears the record
+    assert_eq!(
+        client.finalize_splice_or_report(&cartridge_id, &round, &r, &sc, &su, &None),
+        Some(cartridge_id)
+    );
+    assert!(client.has_creature(&cartridge_id));
+    assert_eq!(client.get_last_finalize_error(&cartridge_id), None);
+
+    assert_eq!(client.finalize_splice_or_report(&cartridge_id, &round, &r, &sc, &su, &None), None);
+    assert_eq!(
+        client.get_last_finalize_error(&cartridge_id).map(|(code, _)| code),
+        Some(Error::AlreadyFinalized as u32)
+    );
+}
+
+#[test]
+fn test_finalize_or_report_beacon_mismatches() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 1);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+    let last_code = || client.get_last_finalize_error(&1).map(|(code, _)| code);
+
+    let mut wrong_randomness = randomness.clone();
+    wrong_randomness.set(0, wrong_randomness.get(0).unwrap() ^ 1);
+    client.finalize_splice_or_report(&1, &REAL_ROUND, &wrong_randomness, &sig_compressed, &sig_uncompressed, &None);
+    assert_eq!(last_code(), Some(Error::RandomnessMismatch as u32));
+
+    let mut other_point = sig_uncompressed.clone();
+    other_point.set(10, other_point.get(10).unwrap() ^ 1);
+    client.finalize_splice_or_report(&1, &REAL_ROUND, &randomness, &sig_compressed, &other_point, &None);
+    assert_eq!(last_code(), Some(Error::SignatureCompressionMismatch as u32));
+
+    let wrong_network = Some(Bytes::from_array(&env, &[0xab; 32]));
+    client.finalize_splice_or_report(&1, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &wrong_network);
+    assert_eq!(last_code(), Some(Error::WrongDrandNetwork as u32));
+
+    // An ordinary finalization also clears the record
+    client.finalize_splice(&1, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None);
+    assert_eq!(last_code(), None);
+}
+
+#[test]
+fn test_finalize_or_report_requires_finalizer_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    let cartridge_id = client.splice_genome(&user);
+    let (r, sc, su) = create_mock_entropy(&env);
+
+    env.set_auths(&[]);
+    assert!(client.try_finalize_splice_or_report(&cartridge_id, &0, &r, &sc, &su, &None).is_err());
+    assert_eq!(client.get_last_finalize_error(&cartridge_id), None);
+}
+
+// ===== Bulk pricing tests =====
+
+#[test]
+fn test_bulk_discount_thresholds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let unit_price = |count: u32| client.quote_batch_fee(&count).unit_price;
+    assert_eq!(unit_price(1), 10_000_000);
+    assert_eq!(unit_price(4), 10_000_000);
+    assert_eq!(unit_price(5), 9_000_000);
+    assert_eq!(unit_price(14), 9_000_000);
+    assert_eq!(unit_price(15), 8_000_000);
+    assert_eq!(unit_price(100), 8_000_000);
+
+    let quote = client.quote_batch_fee(&15);
+    assert_eq!(quote.discount_bps, 2_000);
+    assert_eq!(quote.total, 15 * 8_000_000);
+
+    // A custom table replaces the defaults
+    client.set_bulk_discounts(&soroban_sdk::vec![&env, (2, 500), (3, 500), (10, 5_000)]);
+    assert_eq!(unit_price(1), 10_000_000);
+    assert_eq!(unit_price(2), 9_500_000);
+    assert_eq!(unit_price(9), 9_500_000);
+    assert_eq!(unit_price(10), 5_000_000);
+
+    client.set_bulk_discounts(&soroban_sdk::vec![&env]);
+    assert_eq!(unit_price(50), 10_000_000);
+}
+
+#[test]
+fn test_bulk_discount_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    for tiers in [
+        soroban_sdk::vec![&env, (5, 1_000), (5, 2_000)],  // counts not increasing
+        soroban_sdk::vec![&env, (15, 2_000), (5, 1_000)], // unsorted
+        soroban_sdk::vec![&env, (5, 2_000), (15, 1_000)], // discount decreases
+        soroban_sdk::vec![&env, (5, 10_001)],             // over 100%
+        soroban_sdk::vec![&env, (0, 1_000)],              // zero count
+    ] {
+        assert_eq!(
+            client.try_set_bulk_discounts(&tiers),
+            Err(Ok(contract_error(BulkPricingError::InvalidDiscountTiers)))
+        );
+    }
+
+    let mut too_many = soroban_sdk::Vec::new(&env);
+    for i in 0..=crate::MAX_BULK_TIERS {
+        too_many.push_back((i + 1, i * 100));
+    }
+    assert_eq!(
+        client.try_set_bulk_discounts(&too_many),
+        Err(Ok(contract_error(BulkPricingError::InvalidDiscountTiers)))
+    );
+    assert_eq!(client.get_bulk_discounts(), soroban_sdk::vec![&env, (5, 1_000), (15, 2_000)]);
+}
+
+// ===== Derivation consistency tests =====
+
+/// Overwrite a stored cartridge's derivation stamp, as a buggy migration or forged write would
+fn forge_derivation_version(env: &Env, client: &GeneSplicerClient, cartridge_id: u32, version: u32) {
+    let mut cartridge = client.get_cartridge(&cartridge_id).unwrap();
+    cartridge.derivation_version = version;
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::Cartridge(cartridge_id), &cartridge);
+    });
+}
+
+#[test]
+fn test_forged_derivation_version_rejected() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+    let (r, sc, su) = create_mock_entropy(&env);
+    let seed = Bytes::from_array(&env, &[9; 32]);
+
+    // Minted under the latest version, then the Owner switches new mints to v1
+    let old = client.splice_genome(&user);
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    client.set_derivation_version(&1);
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    let new = client.splice_genome(&user);
+    assert_eq!(client.get_cartridge_derivation_version(&new), 1);
+
+    // Stamps that don't match the version live at mint time are refused on every path
+    forge_derivation_version(&env, &client, old, 1);
+    forge_derivation_version(&env, &client, new, 2);
+    for id in [old, new] {
+        let round = client.get_cartridge(&id).unwrap().splice_round;
+        let refused = Err(Ok(contract_error(Error::InconsistentDerivation)));
+        assert_eq!(client.try_finalize_splice(&id, &round, &r, &sc, &su, &None), refused);
+        assert_eq!(client.try_dev_finalize(&id, &seed), refused);
+    }
+
+    // The genuine stamps finalize normally
+    forge_derivation_version(&env, &client, old, crate::LATEST_DERIVATION_VERSION);
+    forge_derivation_version(&env, &client, new, 1);
+    client.dev_finalize(&old, &seed);
+    client.dev_finalize(&new, &seed);
+}
+
+#[test]
+fn test_forged_gene_table_epoch_rejected() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+    let seed = Bytes::from_array(&env, &[9; 32]);
+
+    // Minted under epoch 0, then a new table goes live before the next mint
+    let old = client.splice_genome(&user);
+    let table = crate::GeneTable {
+        legendary_odds: 500,
+        rare_odds: 500,
+    };
+    client.commit_gene_table(&gene_table_hash(&env, &table));
+    env.ledger().with_mut(|li| li.timestamp += crate::GENE_TABLE_DELAY);
+    assert_eq!(client.apply_gene_table(&table), 1);
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    let new = client.splice_genome(&user);
+    assert_eq!(client.get_cartridge(&new).unwrap().gene_table_epoch, 1);
+
+    // An epoch that wasn't live at mint time would pick other odds, so it is refused
+    let forge_epoch = |id: u32, epoch: u32| {
+        let mut cartridge = client.get_cartridge(&id).unwrap();
+        cartridge.gene_table_epoch = epoch;
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .set(&crate::DataKey::Cartridge(id), &cartridge);
+        });
+    };
+    forge_epoch(old, 1);
+    forge_epoch(new, 0);
+    for id in [old, new] {
+        assert_eq!(
+            client.try_dev_finalize(&id, &seed),
+            Err(Ok(contract_error(Error::InconsistentDerivation)))
+        );
+    }
+
+    forge_epoch(old, 0);
+    forge_epoch(new, 1);
+    client.dev_finalize(&old, &seed);
+    client.dev_finalize(&new, &seed);
+}
+
+#[test]
+fn test_derivation_change_in_mint_ledger_accepts_both_versions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    let seed = Bytes::from_array(&env, &[9; 32]);
+
+    // A mint and a version change sharing a timestamp can't be ordered from history alone
+    let first = client.splice_genome(&user);
+    client.set_derivation_version(&1);
+    let second = client.splice_genome(&user);
+    forge_derivation_version(&env, &client, first, 1);
+    forge_derivation_version(&env, &client, second, crate::LATEST_DERIVATION_VERSION);
+    client.dev_finalize(&first, &seed);
+    client.dev_finalize(&second, &seed);
+}
+
+// ===== Collection tests =====
+
+fn collection_params(fee: i128, skin_count: u64, supply_cap: u32) -> crate::CollectionParams {
+    crate::CollectionParams {
+        fee,
+        skin_count,
+        supply_cap,
+    }
+}
+
+#[test]
+fn test_collections_mint_with_their_own_params() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let partner = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let halloween = client.create_collection(&admin, &collection_params(5_000_000, 2, 0));
+    let partner_drop = client.create_collection(&partner, &collection_params(0, 1, 3));
+    assert_eq!((halloween, partner_drop), (1, 2));
+    assert_eq!(client.get_collection_count(), 2);
+    assert_eq!(client.get_collection(&partner_drop).unwrap().admin, partner);
+    assert_eq!(client.get_collection(&0), None);
+
+    // IDs stay global across collections; each cartridge records its own
+    let original = client.splice_genome(&user);
+    let spooky = client.splice_collection(&user, &halloween);
+    let free = client.splice_collection(&user, &partner_drop);
+    let also_original = client.splice_collection(&user, &0);
+    assert_eq!((original, spooky, free, also_original), (1, 2, 3, 4));
+    assert_eq!(client.get_cartridge(&original).unwrap().collection_id, 0);
+    assert_eq!(client.get_cartridge(&spooky).unwrap().collection_id, halloween);
+    assert_eq!(client.get_cartridge(&free).unwrap().collection_id, partner_drop);
+    assert_eq!(client.get_cartridge(&also_original).unwrap().collection_id, 0);
+
+    // Fees: 1 XLM twice for the original, 0.5 XLM for Halloween, nothing for the partner drop
+    assert_eq!(xlm_token.balance(&admin), 25_000_000);
+    assert_eq!(client.get_cartridge(&free).unwrap().skin_id, 0);
+    assert!(client.get_cartridge(&spooky).unwrap().skin_id < 2);
+
+    // Per-collection counters and indexes
+    assert_eq!(client.get_collection_minted(&0), 2);
+    assert_eq!(client.get_collection_minted(&halloween), 1);
+    assert_eq!(client.get_collection_minted(&partner_drop), 1);
+    assert_eq!(
+        client.get_collection_cartridges(&partner_drop, &0, &10),
+        soroban_sdk::vec![&env, free]
+    );
+
+    // The creature inherits its cartridge's collection
+    client.dev_finalize(&spooky, &Bytes::from_array(&env, &[3; 32]));
+    assert_eq!(client.get_creature(&spooky).unwrap().collection_id, halloween);
+}
+
+#[test]
+fn test_collection_supply_cap_and_index_paging() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let capped = client.create_collection(&admin, &collection_params(0, 3, 3));
+    let other = client.create_collection(&admin, &collection_params(0, 3, 0));
+    let mut minted = soroban_sdk::Vec::new(&env);
+    for _ in 0..3 {
+        minted.push_back(client.splice_collection(&user, &capped));
+        client.splice_collection(&user, &other);
+    }
+    assert_eq!(
+        client.try_splice_collection(&user, &capped),
+        Err(Ok(contract_error(CollectionError::CollectionSoldOut)))
+    );
+
+    // Pages walk the collection's own IDs, skipping the other collection's
+    assert_eq!(client.get_collection_cartridges(&capped, &0, &10), minted);
+    assert_eq!(
+        client.get_collection_cartridges(&capped, &1, &1),
+        soroban_sdk::vec![&env, minted.get(1).unwrap()]
+    );
+    assert_eq!(client.get_collection_cartridges(&capped, &3, &10).len(), 0);
+    assert_eq!(client.get_collection_minted(&0), 0);
+
+    // Raising the cap reopens the collection
+    client.update_collection(&capped, &collection_params(0, 3, 4));
+    client.splice_collection(&user, &capped);
+    assert_eq!(client.get_collection_minted(&capped), 4);
+}
+
+#[test]
+fn test_collection_validation_and_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let partner = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    assert_eq!(
+        client.try_create_collection(&partner, &collection_params(-1, 3, 0)),
+        Err(Ok(contract_error(CollectionError::InvalidCollectionParams)))
+    );
+    assert_eq!(
+        client.try_create_collection(&partner, &collection_params(0, 0, 0)),
+        Err(Ok(contract_error(CollectionError::InvalidCollectionParams)))
+    );
+
+    let drop = client.create_collection(&partner, &collection_params(0, 2, 0));
+    client.splice_collection(&user, &drop);
+    client.splice_collection(&user, &drop);
+    assert_eq!(
+        client.try_update_collection(&drop, &collection_params(0, 2, 1)),
+        Err(Ok(contract_error(CollectionError::InvalidCollectionParams)))
+    );
+
+    assert_eq!(
+        client.try_splice_collection(&user, &9),
+        Err(Ok(contract_error(CollectionError::CollectionNotFound)))
+    );
+    assert_eq!(
+        client.try_update_collection(&9, &collection_params(0, 1, 0)),
+        Err(Ok(contract_error(CollectionError::CollectionNotFound)))
+    );
+    assert_eq!(
+        client.try_get_collection_cartridges(&0, &0, &10),
+        Err(Ok(contract_error(CollectionError::CollectionNotFound)))
+    );
+
+    // The collection admin retunes it; the Owner creates collections
+    client.update_collection(&drop, &collection_params(1_000, 2, 5));
+    assert_eq!(client.get_collection(&drop).unwrap().params.fee, 1_000);
+    let entry = client.get_config_log(&0, &crate::MAX_CONFIG_LOG).last().unwrap();
+    assert_eq!(entry.actor, partner);
+
+    env.set_auths(&[]);
+    assert!(client.try_update_collection(&drop, &collection_params(0, 2, 5)).is_err());
+    assert!(client.try_create_collection(&partner, &collection_params(0, 2, 0)).is_err());
+}
+
+// ===== Randomness consumer tests =====
+
+mod example_consumer {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env};
+
+    /// Minimal partner contract rolling a die from a round the splicer has verified
+    #[contract]
+    pub struct ExampleConsumer;
+
+    #[contractimpl]
+    impl ExampleConsumer {
+        pub fn roll(env: Env, splicer: Address, round: u64) -> u32 {
+            let randomness: BytesN<32> = crate::GeneSplicerClient::new(&env, &splicer)
+                .consume_randomness(
+                    &env.current_contract_address(),
+                    &round,
+                    &symbol_short!("dice"),
+                    &0,
+                );
+            randomness.get(0).unwrap() as u32 % 6 + 1
+        }
+    }
+}
+
+#[test]
+fn test_compress_g1_matches_drand_encoding() {
+    let env = Env::default();
+    let (_, sig_compressed, sig_uncompressed) = real_beacon(&env);
+    let uncompressed = soroban_sdk::BytesN::<96>::try_from(sig_uncompressed).unwrap();
+
+    let compressed = crate::compress_g1(&env, &uncompressed);
+    assert_eq!(Bytes::from(compressed.clone()), sig_compressed);
+
+    // The negated point differs only in the sign flag
+    let negated = crate::compress_g1(&env, &crate::negate_g1_bytes(&env, &uncompressed));
+    let mut expected = compressed.to_array();
+    expected[0] |= 0x20;
+    assert_eq!(negated.to_array(), expected);
+}
+
+#[test]
+fn test_non_canonical_signature_flags_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 1);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+
+    // Each flipped flag bit, with randomness to match it, would be a second roll of the same beacon
+    for flag in [0x20, 0x40, 0x80] {
+        let mut flipped = sig_compressed.to_buffer::<48>().as_slice().to_vec();
+        flipped[0] ^= flag;
+        let flipped = Bytes::from_slice(&env, &flipped);
+        let ground: Bytes = env.crypto().sha256(&flipped).into();
+        assert_eq!(
+            client.try_finalize_splice(&1, &REAL_ROUND, &ground, &flipped, &sig_uncompressed, &None),
+            Err(Ok(contract_error(Error::SignatureCompressionMismatch)))
+        );
+    }
+
+    client.finalize_splice(&1, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None);
+}
+
+#[test]
+fn test_registered_consumer_reads_verified_randomness() {
+    use soroban_sdk::symbol_short;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 1);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+    let consumer_id = env.register(example_consumer::ExampleConsumer, ());
+    let consumer = example_consumer::ExampleConsumerClient::new(&env, &consumer_id);
+
+    let dice = symbol_short!("dice");
+
+    // Nothing to read before the round is verified, and nobody may read unregistered
+    assert_eq!(
+        client.try_consume_randomness(&consumer_id, &REAL_ROUND, &dice, &0),
+        Err(Ok(contract_error(ConsumerError::ConsumerNotRegistered)))
+    );
+    client.register_consumer(&consumer_id);
+    assert!(client.is_consumer_registered(&consumer_id));
+    assert_eq!(
+        client.try_consume_randomness(&consumer_id, &REAL_ROUND, &dice, &0),
+        Err(Ok(contract_error(ConsumerError::RandomnessNotVerified)))
+    );
+
+    client.finalize_splice(&1, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None);
+
+    // A direct call from the consumer contract carries its own auth
+    env.set_auths(&[]);
+    let drand_randomness = soroban_sdk::BytesN::<32>::try_from(randomness.clone()).unwrap();
+    let derived = crate::derive_from_round(&env, &drand_randomness, &dice, 0);
+    let expected_roll = derived.get(0).unwrap() as u32 % 6 + 1;
+    assert_eq!(consumer.roll(&client.address, &REAL_ROUND), expected_roll);
+    assert_eq!(count_events(&env, &client.address, "randomness_consumed"), 1);
+    env.mock_all_auths();
+
+    // Reads get a per-context value, never the raw randomness
+    assert_eq!(client.consume_randomness(&consumer_id, &REAL_ROUND, &dice, &0), derived);
+    assert_ne!(Bytes::from(derived.clone()), randomness);
+    assert_ne!(client.consume_randomness(&consumer_id, &REAL_ROUND, &dice, &1), derived);
+    assert_ne!(
+        client.consume_randomness(&consumer_id, &REAL_ROUND, &crate::GENE_CONTEXT, &1),
+        derived
+    );
+}
+
+#[test]
+fn test_consumer_read_fee_and_unregister() {
+    let env = Env::default();
+    // The fee transfer is a sub-call the consumer contract must authorize
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+    let (_, _, sig_uncompressed) = real_beacon(&env);
+    let entry = client.begin_verification(&REAL_ROUND, &sig_uncompressed);
+    client.complete_verification(&Address::generate(&env), &REAL_ROUND, &entry);
+
+    let consumer_id = env.register(example_consumer::ExampleConsumer, ());
+    let consumer = example_consumer::ExampleConsumerClient::new(&env, &consumer_id);
+    xlm_token.mint(&consumer_id, &1_000);
+    client.register_consumer(&consumer_id);
+    client.set_consumer_read_fee(&300);
+    assert_eq!(
+        client.try_set_consumer_read_fee(&-1),
+        Err(Ok(contract_error(Error::InvalidAmount)))
+    );
+
+    consumer.roll(&client.address, &REAL_ROUND);
+    consumer.roll(&client.address, &REAL_ROUND);
+    assert_eq!(xlm_token.balance(&admin), 600);
+    assert_eq!(xlm_token.balance(&consumer_id), 400);
+
+    client.unregister_consumer(&consumer_id);
+    assert!(!client.is_consumer_registered(&consumer_id));
+    assert!(consumer.try_roll(&client.address, &REAL_ROUND).is_err());
+}
+
+// ===== Coordinate range tests =====
+
+/// Field elements equal to and just past the BLS12-381 base field modulus
+fn out_of_range_coordinates() -> [[u8; 48]; 2] {
+    let p = crate::BLS12_381_P;
+    let mut p_plus_one = p;
+    p_plus_one[47] += 1;
+    [p, p_plus_one]
+}
+
+#[test]
+fn test_signature_coordinates_out_of_range() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 1);
+    let (randomness, _, real_uncompressed) = real_beacon(&env);
+    let real = real_uncompressed.to_buffer::<96>();
+    let real = real.as_slice();
+
+    for coordinate in out_of_range_coordinates() {
+        for position in [0, 48] {
+            let mut signature = [0u8; 96];
+            signature.copy_from_slice(real);
+            signature[position..position + 48].copy_from_slice(&coordinate);
+            let uncompressed = Bytes::from_array(&env, &signature);
+
+            assert_eq!(
+                client.try_begin_verification(&REAL_ROUND, &uncompressed),
+                Err(Ok(contract_error(Error::SignatureCoordinateOutOfRange)))
+            );
+
+            // finalize_splice reports it too, once the compressed form is the point's encoding
+            let compressed = crate::compress_g1(&env, &soroban_sdk::BytesN::from_array(&env, &signature));
+            assert_eq!(
+                client.try_finalize_splice(
+                    &1,
+                    &REAL_ROUND,
+                    &randomness,
+                    &Bytes::from(compressed),
+                    &uncompressed,
+                    &None,
+                ),
+                Err(Ok(contract_error(Error::SignatureCoordinateOutOfRange)))
+            );
+        }
+    }
+}
+
+#[test]
+fn test_pubkey_coordinates_out_of_range() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let contract_id = env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, real_drand_pubkey(&env), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+    let client = GeneSplicerClient::new(&env, &contract_id);
+    let real = real_drand_pubkey(&env).to_buffer::<192>();
+    let real = real.as_slice();
+
+    // x_c1 || x_c0 || y_c1 || y_c0; cover both coordinates of x and of y
+    for coordinate in out_of_range_coordinates() {
+        for position in [0, 48, 96, 144] {
+            let mut key = [0u8; 192];
+            key.copy_from_slice(real);
+            key[position..position + 48].copy_from_slice(&coordinate);
+            assert_eq!(
+                client.try_set_drand_public_key(&Bytes::from_array(&env, &key)),
+                Err(Ok(contract_error(Error::PubkeyCoordinateOutOfRange)))
+            );
+        }
+    }
+    assert_eq!(client.get_drand_public_key(), real_drand_pubkey(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #56)")]
+fn test_constructor_rejects_pubkey_coordinate_out_of_range() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+
+    let mut key = [0u8; 192];
+    key.copy_from_slice(real_drand_pubkey(&env).to_buffer::<192>().as_slice());
+    key[96..144].copy_from_slice(&crate::BLS12_381_P);
+    env.register(
+        GeneSplicer,
+        (&admin, &xlm_token.address, 10u64, false, Bytes::from_array(&env, &key), mock_provenance_hash(&env), MINT_FEE, &admin),
+    );
+}
+
+// ===== Cartridge status tests =====
+
+#[test]
+fn test_cartridge_status_sweeps_every_state() {
+    use crate::{CartridgeState, CartridgeStatus};
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+    client.set_max_finalization_lag(&3_600);
+
+    let cartridge_id = client.splice_genome(&user);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let ready_at = crate::drand_round_time(round);
+    let (r, sc, su) = create_mock_entropy(&env);
+    let status_at = |timestamp: u64| {
+        env.ledger().with_mut(|li| li.timestamp = timestamp);
+        client.get_cartridge_status(&cartridge_id)
+    };
+
+    // Before the round: finalization is refused for the same reason the getter gives
+    assert_eq!(
+        status_at(ready_at - 4),
+        CartridgeStatus {
+            state: CartridgeState::NotReady,
+            ready_since: None,
+            seconds_until_ready: Some(4),
+            seconds_overdue: None,
+        }
+    );
+    assert_eq!(
+        client.try_finalize_splice(&cartridge_id, &round, &r, &sc, &su, &None),
+        Err(Ok(contract_error(Error::RoundNotReady)))
+    );
+    assert_eq!(client.finalize_splice_or_report(&cartridge_id, &round, &r, &sc, &su, &None), None);
+    assert_eq!(
+        client.get_last_finalize_error(&cartridge_id).map(|(code, _)| code),
+        Some(Error::RoundNotReady as u32)
+    );
+
+    // Ready from the round's emission, overdue only past the lag
+    let ready = |seconds_overdue| CartridgeStatus {
+        state: CartridgeState::Ready,
+        ready_since: Some(ready_at),
+        seconds_until_ready: None,
+        seconds_overdue,
+    };
+    assert_eq!(status_at(ready_at), ready(None));
+    assert_eq!(status_at(ready_at + 3_600), ready(None));
+    assert_eq!(status_at(ready_at + 3_605), ready(Some(5)));
+
+    // Overdue cartridges still finalize
+    client.finalize_splice(&cartridge_id, &round, &r, &sc, &su, &None);
+    assert_eq!(
+        status_at(ready_at + 10_000),
+        CartridgeStatus {
+            state: CartridgeState::Finalized,
+            ready_since: Some(ready_at),
+            seconds_until_ready: None,
+            seconds_overdue: None,
+        }
+    );
+    assert_eq!(
+        client.try_get_cartridge_status(&99),
+        Err(Ok(contract_error(Error::CartridgeNotFound)))
+    );
+}
+
+#[test]
+fn test_dev_entropy_cartridge_ready_at_mint() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // Minted before drand genesis, so the round is already public
+    let cartridge_id = client.splice_genome(&user);
+    let status = client.get_cartridge_status(&cartridge_id);
+    assert_eq!(status.state, crate::CartridgeState::Ready);
+    assert_eq!(status.ready_since, Some(0));
+    assert_eq!(client.get_max_finalization_lag(), crate::DEFAULT_MAX_FINALIZATION_LAG);
+}
+
+// ===== Storefront tests =====
+
+#[test]
+fn test_storefront_credit_limit_boundary_and_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let storefront = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&storefront, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    assert_eq!(
+        client.try_storefront_mint(&storefront, &customer),
+        Err(Ok(contract_error(StorefrontError::StorefrontNotRegistered)))
+    );
+
+    // Room for exactly two mints on credit
+    client.register_storefront(&storefront, &(2 * crate::MINT_FEE));
+    let first = client.storefront_mint(&storefront, &customer);
+    client.storefront_mint(&storefront, &customer);
+    assert_eq!(client.get_cartridge(&first).unwrap().owner, customer);
+    assert_eq!(client.get_storefront_debt(&storefront), 2 * crate::MINT_FEE);
+    assert_eq!(xlm_token.balance(&admin), 0);
+    assert_eq!(
+        client.try_storefront_mint(&storefront, &customer),
+        Err(Ok(contract_error(StorefrontError::CreditLimitExceeded)))
+    );
+
+    // Settling frees credit; paying more than is owed is refused
+    assert_eq!(
+        client.try_settle_storefront(&storefront, &(2 * crate::MINT_FEE + 1)),
+        Err(Ok(contract_error(Error::InvalidAmount)))
+    );
+    client.settle_storefront(&storefront, &crate::MINT_FEE);
+    assert_eq!(count_events(&env, &client.address, "storefront_settled"), 1);
+    assert_eq!(client.get_storefront_debt(&storefront), crate::MINT_FEE);
+    assert_eq!(xlm_token.balance(&admin), crate::MINT_FEE);
+    client.storefront_mint(&storefront, &customer);
+    assert_eq!(client.get_user_cartridges(&customer).len(), 3);
+
+    env.set_auths(&[]);
+    assert!(client.try_storefront_mint(&storefront, &customer).is_err());
+    assert!(client.try_register_storefront(&storefront, &0).is_err());
+}
+
+#[test]
+fn test_storefront_suspension() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let storefront = Address::generate(&env);
+    let customer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&storefront, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.register_storefront(&storefront, &(3 * crate::MINT_FEE));
+    client.storefront_mint(&storefront, &customer);
+    client.storefront_mint(&storefront, &customer);
+
+    // Lowering the limit below the debt leaves it standing; the Owner suspends the storefront
+    client.register_storefront(&storefront, &crate::MINT_FEE);
+    client.suspend_storefront(&storefront, &true);
+    assert_eq!(
+        client.get_storefront(&storefront),
+        Some(crate::Storefront {
+            credit_limit: crate::MINT_FEE,
+            suspended: true,
+        })
+    );
+    assert_eq!(
+        client.try_storefront_mint(&storefront, &customer),
+        Err(Ok(contract_error(StorefrontError::StorefrontSuspended)))
+    );
+
+    // A suspended storefront can still settle, and a new limit keeps the suspension
+    client.settle_storefront(&storefront, &(2 * crate::MINT_FEE));
+    client.register_storefront(&storefront, &(5 * crate::MINT_FEE));
+    assert!(client.get_storefront(&storefront).unwrap().suspended);
+
+    client.suspend_storefront(&storefront, &false);
+    client.storefront_mint(&storefront, &customer);
+    assert_eq!(client.get_storefront_debt(&storefront), crate::MINT_FEE);
+
+    // Suspension stays available after the configuration freeze
+    client.finalize_configuration(&false);
+    client.suspend_storefront(&storefront, &true);
+    assert_eq!(
+        client.try_register_storefront(&storefront, &0),
+        Err(Ok(contract_error(Error::ConfigurationFinalized)))
+    );
+}
+
+// ===== Retirement tests =====
+
+#[test]
+fn test_retire_creature_credits_points_and_drops_voting_weight() {
+    use crate::GeneRarity::{Legendary, Normal, Rare};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    store_creature(&env, &client.address, 1, &user, [Normal, Rare, Legendary]);
+    store_creature(&env, &client.address, 2, &user, [Normal, Normal, Normal]);
+    assert_eq!(client.get_voting_weight(&user), 4);
+
+    let points = client.retire_creature(&user, &1);
+    assert_eq!(
+        points,
+        crate::RETIREMENT_POINTS_NORMAL
+            + crate::RETIREMENT_POINTS_RARE
+            + crate::RETIREMENT_POINTS_LEGENDARY
+    );
+    assert_eq!(count_events(&env, &client.address, "creature_retired"), 1);
+    assert_eq!(client.get_points(&user), points);
+    assert!(client.is_creature_retired(&1));
+    assert!(!client.is_creature_retired(&2));
+
+    // The trophy stays with its owner but no longer votes
+    assert_eq!(client.get_creature(&1).unwrap().owner, user);
+    assert_eq!(client.get_voting_weight(&user), 1);
+}
+
+#[test]
+fn test_retire_creature_is_owner_only_and_irreversible() {
+    use crate::GeneRarity::Normal;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    store_creature(&env, &client.address, 1, &user, [Normal, Normal, Normal]);
+    assert_eq!(
+        client.try_retire_creature(&user, &2),
+        Err(Ok(contract_error(Error::CreatureNotFound)))
+    );
+    assert_eq!(
+        client.try_retire_creature(&other, &1),
+        Err(Ok(contract_error(Error::NotCreatureOwner)))
+    );
+
+    client.retire_creature(&user, &1);
+    assert_eq!(
+        client.try_retire_creature(&user, &1),
+        Err(Ok(contract_error(Error::CreatureRetired)))
+    );
+    assert_eq!(client.get_points(&user), 3 * crate::RETIREMENT_POINTS_NORMAL);
+
+    env.set_auths(&[]);
+    store_creature(&env, &client.address, 2, &user, [Normal, Normal, Normal]);
+    assert!(client.try_retire_creature(&user, &2).is_err());
+}
+
+// ===== Gene table commitment tests =====
+
+fn gene_table_hash(env: &Env, table: &crate::GeneTable) -> soroban_sdk::BytesN<32> {
+    xdr_hash(env, table.clone())
+}
+
+#[test]
+fn test_gene_table_apply_requires_delay_and_matching_hash() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let table = crate::GeneTable {
+        legendary_odds: 200,
+        rare_odds: 300,
+    };
+    assert_eq!(
+        client.try_apply_gene_table(&table),
+        Err(Ok(contract_error(GeneTableError::GeneTableNotCommitted)))
+    );
+
+    client.commit_gene_table(&gene_table_hash(&env, &table));
+    assert_eq!(count_events(&env, &client.address, "gene_table_committed"), 1);
+    assert_eq!(
+        client.try_apply_gene_table(&table),
+        Err(Ok(contract_error(GeneTableError::GeneTableDelayActive)))
+    );
+
+    let apply_after = client.get_gene_table_commitment().unwrap().apply_after;
+    env.ledger().set_timestamp(apply_after);
+    let tweaked = crate::GeneTable {
+        legendary_odds: 150,
+        rare_odds: 300,
+    };
+    assert_eq!(
+        client.try_apply_gene_table(&tweaked),
+        Err(Ok(contract_error(GeneTableError::GeneTableHashMismatch)))
+    );
+    assert_eq!(client.get_gene_table_epoch(), 0);
+
+    // Odds past the scale are refused even when committed
+    let overfull = crate::GeneTable {
+        legendary_odds: 600,
+        rare_odds: 500,
+    };
+    client.commit_gene_table(&gene_table_hash(&env, &overfull));
+    env.ledger().set_timestamp(env.ledger().timestamp() + crate::GENE_TABLE_DELAY);
+    assert_eq!(
+        client.try_apply_gene_table(&overfull),
+        Err(Ok(contract_error(GeneTableError::InvalidGeneTable)))
+    );
+
+    env.set_auths(&[]);
+    assert!(client
+        .try_commit_gene_table(&gene_table_hash(&env, &table))
+        .is_err());
+}
+
+#[test]
+fn test_gene_table_applies_only_to_cartridges_minted_after() {
+    use crate::{derive_v4, GeneRarity};
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let all_legendary = crate::GeneTable {
+        legendary_odds: crate::GENE_TABLE_SCALE,
+        rare_odds: 0,
+    };
+    client.commit_gene_table(&gene_table_hash(&env, &all_legendary));
+
+    // Minted while the commitment waits: keeps the original table
+    let before = client.splice_genome(&user);
+    env.ledger().set_timestamp(env.ledger().timestamp() + crate::GENE_TABLE_DELAY);
+    assert_eq!(client.apply_gene_table(&all_legendary), 1);
+    assert_eq!(count_events(&env, &client.address, "gene_table_applied"), 1);
+    assert_eq!(client.get_gene_table(&1), Some(all_legendary));
+    assert_eq!(client.get_gene_table_commitment(), None);
+    let after = client.splice_genome(&user);
+
+    assert_eq!(client.get_cartridge(&before).unwrap().gene_table_epoch, 0);
+    assert_eq!(client.get_cartridge(&after).unwrap().gene_table_epoch, 1);
+
+    let seed = Bytes::from_array(&env, b"gene-table-seed");
+    let randomness: Bytes = env.crypto().sha256(&seed).into();
+    client.dev_finalize(&before, &seed);
+    client.dev_finalize(&after, &seed);
+
+    let old = client.get_creature(&before).unwrap();
+    let [head, body, legs] = derive_v4(&env, &randomness, before);
+    assert_eq!((old.head_gene, old.body_gene, old.legs_gene), (head, body, legs));
+
+    let new = client.get_creature(&after).unwrap();
+    for gene in [new.head_gene, new.body_gene, new.legs_gene] {
+        assert_eq!(gene.rarity, GeneRarity::Legendary);
+        assert!((3..=5).contains(&gene.id));
+    }
+}
+
+// ===== Event nonce tests =====
+
+/// Nonces of the events `contract_id` emitted in the last invocation, in emission order
+fn event_nonces(env: &Env, contract_id: &Address) -> soroban_sdk::Vec<u64> {
+    use soroban_sdk::xdr::{ContractEventBody, ScSymbol, ScVal};
+
+    let key = ScVal::Symbol(ScSymbol("nonce".try_into().unwrap()));
+    let mut nonces = soroban_sdk::Vec::new(env);
+    for event in env.events().all().filter_by_contract(contract_id).events() {
+        let ContractEventBody::V0(body) = &event.body;
+        let ScVal::Map(Some(data)) = &body.data else {
+            panic!("event data is not a map");
+        };
+        let entry = data.iter().find(|entry| entry.key == key).unwrap();
+        let ScVal::U64(nonce) = entry.val else {
+            panic!("nonce is not a u64");
+        };
+        nonces.push_back(nonce);
+    }
+    nonces
+}
+
+#[test]
+fn test_event_nonces_strictly_increase_across_calls() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let mut last = client.get_event_nonce();
+    let mut check = |env: &Env| {
+        let nonces = event_nonces(env, &client.address);
+        assert!(!nonces.is_empty());
+        for nonce in nonces.iter() {
+            assert_eq!(nonce, last + 1, "nonces must have no gaps");
+            last = nonce;
+        }
+        assert_eq!(client.get_event_nonce(), last);
+    };
+
+    let first = client.splice_genome(&user);
+    check(&env);
+    let second = client.splice_genome(&user);
+    check(&env);
+    client.dev_finalize(&first, &Bytes::from_array(&env, b"nonce-seed"));
+    check(&env);
+    client.set_mint_paused(&true);
+    check(&env);
+    client.dev_finalize(&second, &Bytes::from_array(&env, b"nonce-seed"));
+    check(&env);
+    client.retire_creature(&user, &first);
+    check(&env);
+}
+
+// ===== Finalize pre-flight tests =====
+
+#[test]
+fn test_can_finalize_reports_each_failing_precondition() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    past_drand_genesis(&env);
+
+    let cartridge_id = client.splice_genome(&user);
+    let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+    let refused = |error: Error| (false, error as u32);
+
+    assert_eq!(client.can_finalize(&99, &round), refused(Error::CartridgeNotFound));
+    assert_eq!(client.can_finalize(&cartridge_id, &round), refused(Error::RoundNotReady));
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = crate::drand_round_time(round));
+    assert_eq!(client.can_finalize(&cartridge_id, &round), (true, 0));
+    assert_eq!(
+        client.can_finalize(&cartridge_id, &(round + 1)),
+        refused(Error::RoundMismatch)
+    );
+
+    // finalize_splice fails with the code the pre-flight reported
+    client.set_finalize_paused(&true);
+    assert_eq!(client.can_finalize(&cartridge_id, &round), refused(Error::FinalizePaused));
+    let (r, sc, su) = create_mock_entropy(&env);
+    assert_eq!(
+        client.try_finalize_splice(&cartridge_id, &round, &r, &sc, &su, &None),
+        Err(Ok(contract_error(Error::FinalizePaused)))
+    );
+    client.set_finalize_paused(&false);
+
+    client.finalize_splice(&cartridge_id, &round, &r, &sc, &su, &None);
+    assert_eq!(client.can_finalize(&cartridge_id, &round), refused(Error::AlreadyFinalized));
+}
+
+// ===== Experience tests =====
+
+#[test]
+fn test_grant_xp_is_operator_only_and_once_per_match() {
+    use crate::GeneRarity::Normal;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    store_creature(&env, &client.address, 1, &user, [Normal, Normal, Normal]);
+
+    assert_eq!(
+        client.try_grant_xp(&operator, &1, &50, &7),
+        Err(Ok(contract_error(ExperienceError::NotGameOperator)))
+    );
+    client.set_game_operator(&Some(operator.clone()));
+    assert_eq!(
+        client.try_grant_xp(&operator, &2, &50, &7),
+        Err(Ok(contract_error(Error::CreatureNotFound)))
+    );
+
+    assert_eq!(client.grant_xp(&operator, &1, &50, &7), 1);
+    assert_eq!(count_events(&env, &client.address, "xp_granted"), 1);
+    assert_eq!(
+        client.try_grant_xp(&operator, &1, &50, &7),
+        Err(Ok(contract_error(ExperienceError::MatchAlreadyGranted)))
+    );
+    assert_eq!(client.get_creature_xp(&1), 50);
+
+    // Retired creatures are out of play
+    client.retire_creature(&user, &1);
+    assert_eq!(
+        client.try_grant_xp(&operator, &1, &50, &8),
+        Err(Ok(contract_error(Error::CreatureRetired)))
+    );
+
+    env.set_auths(&[]);
+    assert!(client.try_grant_xp(&operator, &1, &50, &9).is_err());
+}
+
+#[test]
+fn test_level_curve_boundaries_and_caps() {
+    use crate::{GeneRarity::Normal, LEVEL_XP, MAX_LEVEL, MAX_XP_PER_GRANT};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_game_operator(&Some(operator.clone()));
+    store_creature(&env, &client.address, 1, &user, [Normal, Normal, Normal]);
+
+    assert_eq!(client.get_level(&1), 1);
+    assert_eq!(
+        client.try_grant_xp(&operator, &1, &(MAX_XP_PER_GRANT + 1), &1),
+        Err(Ok(contract_error(Error::InvalidAmount)))
+    );
+    assert_eq!(
+        client.try_grant_xp(&operator, &1, &0, &1),
+        Err(Ok(contract_error(Error::InvalidAmount)))
+    );
+
+    // One short of level 2, then exactly on the threshold
+    client.grant_xp(&operator, &1, &(LEVEL_XP[1] - 1), &1);
+    assert_eq!(count_events(&env, &client.address, "creature_leveled_up"), 0);
+    assert_eq!(client.get_level(&1), 1);
+    assert_eq!(client.grant_xp(&operator, &1, &1, &2), 2);
+    assert_eq!(count_events(&env, &client.address, "creature_leveled_up"), 1);
+
+    // A single grant can cross several thresholds
+    assert_eq!(client.grant_xp(&operator, &1, &MAX_XP_PER_GRANT, &3), 4);
+
+    // Experience stops at the last threshold and the level at MAX_LEVEL
+    let mut match_id = 4;
+    while client.get_level(&1) < MAX_LEVEL {
+        client.grant_xp(&operator, &1, &MAX_XP_PER_GRANT, &match_id);
+        match_id += 1;
+    }
+    assert_eq!(client.get_creature_xp(&1), LEVEL_XP[LEVEL_XP.len() - 1]);
+    assert_eq!(client.grant_xp(&operator, &1, &MAX_XP_PER_GRANT, &match_id), MAX_LEVEL);
+    assert_eq!(client.get_creature_xp(&1), LEVEL_XP[LEVEL_XP.len() - 1]);
+    assert_eq!(count_events(&env, &client.address, "creature_leveled_up"), 0);
+}
+
+// ===== Reentrancy tests =====
+
+mod reentrant_token {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    /// Token whose transfer calls back into the splicer's splice_genome
+    #[contract]
+    pub struct ReentrantToken;
+
+    #[contractimpl]
+    impl ReentrantToken {
+        pub fn set_target(env: Env, splicer: Address) {
+            env.storage().instance().set(&symbol_short!("target"), &splicer);
+        }
+
+        pub fn balance(_env: Env, _id: Address) -> i128 {
+            i128::MAX
+        }
+
+        pub fn transfer(env: Env, from: Address, _to: Address, _amount: i128) {
+            let splicer: Address = env.storage().instance().get(&symbol_short!("target")).unwrap();
+            crate::GeneSplicerClient::new(&env, &splicer).splice_genome(&from);
+        }
+    }
+}
+
+#[test]
+fn test_token_callback_into_splice_genome_is_refused() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_id = env.register(reentrant_token::ReentrantToken, ());
+    let client = setup_contract(&env, &admin, &token_id, true);
+    reentrant_token::ReentrantTokenClient::new(&env, &token_id).set_target(&client.address);
+
+    // Soroban's host rejects the re-entry before the guard can; either way nothing is minted
+    assert!(client.try_splice_genome(&user).is_err());
+    assert_eq!(client.get_total_cartridges(), 0);
+}
+
+#[test]
+fn test_guarded_entrypoints_refuse_nested_entry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // The guard is released after every call, so calls in sequence go through
+    client.splice_genome(&user);
+    client.splice_genome(&user);
+
+    // While a guarded entrypoint is running, every other one refuses to start
+    env.as_contract(&client.address, || {
+        env.storage()
+            .temporary()
+            .set(&crate::DataKey::Reentrancy, &true);
+    });
+    assert_eq!(
+        client.try_splice_genome(&user),
+        Err(Ok(contract_error(Error::ReentrancyDetected)))
+    );
+    assert_eq!(
+        client.try_dev_splice_and_finalize(&user, &Bytes::from_array(&env, &[1; 32])),
+        Err(Ok(contract_error(Error::ReentrancyDetected)))
+    );
+    assert_eq!(
+        client.try_buy_finalization_right(&user, &1),
+        Err(Ok(contract_error(Error::ReentrancyDetected)))
+    );
+    assert_eq!(client.get_total_cartridges(), 2);
+}
+
+// ===== Finalization receipt tests =====
+
+#[test]
+fn test_finalization_receipt_digest_is_byte_exact() {
+    use crate::GeneRarity::Normal;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    assert_eq!(
+        client.try_get_finalization_receipt(&1),
+        Err(Ok(contract_error(Error::CreatureNotFound)))
+    );
+
+    // Cartridge 1 is stamped v4 / epoch 0; the creature has genes 6, 7, 8 from round 42
+    client.splice_genome(&user);
+    store_creature(&env, &client.address, 1, &user, [Normal, Normal, Normal]);
+    let receipt = client.get_finalization_receipt(&1);
+    assert_eq!(receipt.gene_seed, None);
+
+    let preimage = hex::decode(
+        "53504c49434552535f524543454950545f5631\
+         00000001\
+         000000000000002a\
+         00000004\
+         00000000\
+         00000006\
+         00000007\
+         00000008\
+         00",
+    )
+    .unwrap();
+    assert_eq!(&preimage[..crate::RECEIPT_DOMAIN.len()], crate::RECEIPT_DOMAIN);
+    let digest: soroban_sdk::BytesN<32> = env.crypto().sha256(&Bytes::from_slice(&env, &preimage)).into();
+    assert_eq!(receipt.digest, digest);
+    assert_eq!(
+        hex::encode(receipt.digest.to_array()),
+        "a97d0e6cfc4e32dfa917b3c7aeb5e5d57f022d077b7aee4fef4dae8996ac367e"
+    );
+}
+
+#[test]
+fn test_finalization_receipt_rederives_genes_from_cached_randomness() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 1);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+    client.finalize_splice(&1, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None);
+
+    let receipt = client.get_finalization_receipt(&1);
+    assert_eq!(receipt.entropy_round, REAL_ROUND);
+    assert!(!receipt.dev_minted);
+    let drand_randomness = soroban_sdk::BytesN::<32>::try_from(randomness.clone()).unwrap();
+    assert_eq!(
+        receipt.gene_seed,
+        Some(crate::derive_from_round(&env, &drand_randomness, &crate::GENE_CONTEXT, 1))
+    );
+
+    // Anyone can re-run the public derivation from the receipt and drand's randomness
+    assert_eq!(receipt.derivation_version, crate::LATEST_DERIVATION_VERSION);
+    let [head, body, legs] = crate::derive_v4(&env, &randomness, receipt.creature_id);
+    assert_eq!(
+        (receipt.head_gene_id, receipt.body_gene_id, receipt.legs_gene_id),
+        (head.id, body.id, legs.id)
+    );
+}
+
+// ===== Creature lock tests =====
+
+#[test]
+fn test_creature_locks_conflict_matrix_and_expiry() {
+    use crate::locks::{acquire_lock, release_lock};
+    use crate::GeneRarity::Normal;
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let market = Address::generate(&env);
+    let pool = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    store_creature(&env, &client.address, 1, &user, [Normal, Normal, Normal]);
+    let now = env.ledger().timestamp();
+
+    env.as_contract(&client.address, || {
+        assert_eq!(acquire_lock(&env, 1, "listed", &market, 0), Ok(()));
+        assert_eq!(
+            acquire_lock(&env, 1, "staked", &pool, 0),
+            Err(Error::LockConflict)
+        );
+        assert_eq!(
+            acquire_lock(&env, 1, "listed", &pool, 0),
+            Err(Error::LockConflict)
+        );
+        assert_eq!(
+            acquire_lock(&env, 1, "delegated", &delegate, now + 100),
+            Ok(())
+        );
+    });
+    assert_eq!(client.get_locks(&1).len(), 2);
+    assert_eq!(
+        client.try_retire_creature(&user, &1),
+        Err(Ok(contract_error(Error::CreatureLocked)))
+    );
+
+    // Only the holder releases its own lock
+    env.as_contract(&client.address, || {
+        assert!(!release_lock(&env, 1, "listed", &pool));
+        assert!(release_lock(&env, 1, "listed", &market));
+        assert!(!release_lock(&env, 1, "listed", &market));
+        assert_eq!(acquire_lock(&env, 1, "staked", &pool, now + 50), Ok(()));
+        assert_eq!(
+            acquire_lock(&env, 1, "swap", &market, 0),
+            Err(Error::LockConflict)
+        );
+    });
+
+    // Both remaining locks lapse on their own
+    env.ledger().with_mut(|li| li.timestamp = now + 100);
+    assert_eq!(client.get_locks(&1).len(), 0);
+    env.as_contract(&client.address, || {
+        assert_eq!(acquire_lock(&env, 1, "swap", &market, 0), Ok(()));
+        assert!(release_lock(&env, 1, "swap", &market));
+    });
+    client.retire_creature(&user, &1);
+    assert!(client.is_creature_retired(&1));
+}
+
+// ===== Migration tests =====
+
+#[test]
+fn test_export_and_import_migrates_a_creature_between_deployments() {
+    use crate::GeneRarity::{Legendary, Normal, Rare};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let v1 = setup_contract(&env, &admin, &xlm_token.address, true);
+    let v2 = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    store_creature(&env, &v1.address, 1, &user, [Normal, Rare, Legendary]);
+    let original = v1.get_creature(&1).unwrap();
+    assert_eq!(v1.get_voting_weight(&user), 3);
+    assert_eq!(
+        v1.try_export_asset(&other, &1),
+        Err(Ok(contract_error(Error::NotCreatureOwner)))
+    );
+
+    let voucher = v1.export_asset(&user, &1);
+    assert_eq!(count_events(&env, &v1.address, "asset_exported"), 1);
+    assert_eq!(voucher.source, v1.address);
+    assert_eq!(voucher.creature, original);
+    assert!(v1.confirm_export(&1, &voucher.commitment));
+    assert!(!v1.confirm_export(&2, &voucher.commitment));
+
+    // The creature stays behind, locked for good and without voting weight
+    assert_eq!(v1.get_voting_weight(&user), 0);
+    assert_eq!(
+        v1.try_export_asset(&user, &1),
+        Err(Ok(contract_error(Error::LockConflict)))
+    );
+    assert_eq!(
+        v1.try_retire_creature(&user, &1),
+        Err(Ok(contract_error(Error::CreatureLocked)))
+    );
+
+    assert_eq!(
+        v2.try_import_asset(&user, &voucher, &v1.address),
+        Err(Ok(contract_error(MigrationError::UntrustedSource)))
+    );
+    v2.set_trusted_source(&v1.address, &true);
+    assert!(v2.is_trusted_source(&v1.address));
+    assert_eq!(
+        v2.try_import_asset(&other, &voucher, &v1.address),
+        Err(Ok(contract_error(Error::NotCreatureOwner)))
+    );
+
+    // Rewriting the genes breaks the commitment, and recommitting the forgery
+    // still fails the source's confirmation
+    let mut forged = voucher.clone();
+    forged.creature.head_gene = original.legs_gene.clone();
+    assert_eq!(
+        v2.try_import_asset(&user, &forged, &v1.address),
+        Err(Ok(contract_error(MigrationError::InvalidExportVoucher)))
+    );
+    forged.commitment = xdr_hash(
+        &env,
+        (
+            forged.source.clone(),
+            forged.creature.clone(),
+            forged.exported_at,
+        ),
+    );
+    assert_eq!(
+        v2.try_import_asset(&user, &forged, &v1.address),
+        Err(Ok(contract_error(MigrationError::InvalidExportVoucher)))
+    );
+
+    let id = v2.import_asset(&user, &voucher, &v1.address);
+    assert_eq!(id, crate::IMPORTED_ID_BASE);
+    assert_eq!(count_events(&env, &v2.address, "asset_imported"), 1);
+    let imported = v2.get_creature(&id).unwrap();
+    assert_eq!(imported.owner, user);
+    assert_eq!(imported.head_gene, original.head_gene);
+    assert_eq!(imported.body_gene, original.body_gene);
+    assert_eq!(imported.legs_gene, original.legs_gene);
+    assert_eq!(imported.skin_id, original.skin_id);
+    assert_eq!(imported.entropy_round, original.entropy_round);
+    assert_eq!(imported.finalized_at, original.finalized_at);
+    assert_eq!(v2.get_user_creatures(&user), soroban_sdk::vec![&env, id]);
+    assert_eq!(v2.get_voting_weight(&user), 3);
+    let origin = v2.get_import_origin(&id).unwrap();
+    assert_eq!(origin.source, v1.address);
+    assert_eq!(origin.source_creature_id, 1);
+    assert_eq!(origin.commitment, voucher.commitment);
+
+    assert_eq!(
+        v2.try_import_asset(&user, &voucher, &v1.address),
+        Err(Ok(contract_error(MigrationError::AlreadyImported)))
+    );
+    v2.set_trusted_source(&v1.address, &false);
+    assert!(!v2.is_trusted_source(&v1.address));
+}
+
+// ===== Uninitialized getter tests =====
+
+#[test]
+fn test_getters_report_not_initialized_instead_of_trapping() {
+    use crate::DataKey;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // Strip everything the constructor stores, as a partial bootstrap would leave it
+    env.as_contract(&client.address, || {
+        for key in [
+            DataKey::Admin,
+            DataKey::XlmToken,
+            DataKey::CartridgeSkinCount,
+            DataKey::NextCartridgeId,
+            DataKey::DrandPublicKey,
+            DataKey::DrandChainHash,
+            DataKey::ProvenanceHash,
+        ] {
+            env.storage().instance().remove(&key);
+        }
+    });
+
+    let not_initialized = Error::NotInitialized;
+    assert_eq!(client.try_admin(), Err(Ok(not_initialized)));
+    assert_eq!(client.try_get_skin_count(), Err(Ok(not_initialized)));
+    assert_eq!(client.try_get_available_skins(), Err(Ok(not_initialized)));
+    assert_eq!(client.try_get_drand_public_key(), Err(Ok(not_initialized)));
+    assert_eq!(client.try_get_drand_chain_hash(), Err(Ok(not_initialized)));
+    assert_eq!(client.try_get_provenance_hash(), Err(Ok(not_initialized)));
+    assert_eq!(
+        client.try_verify_provenance_segment(
+            &0,
+            &mock_provenance_hash(&env),
+            &soroban_sdk::Vec::new(&env)
+        ),
+        Err(Ok(not_initialized))
+    );
+    assert_eq!(client.try_get_solvency(), Err(Ok(not_initialized)));
+    // Optional values and counters fall back to their unset state
+    assert_eq!(client.operator(), None);
+    assert_eq!(client.get_total_cartridges(), 0);
+
+    // Entrypoints that need the missing values fail with the same code
+    let not_initialized = contract_error(Error::NotInitialized);
+    assert_eq!(
+        client.try_set_admin(&Address::generate(&env)),
+        Err(Ok(not_initialized))
+    );
+    assert_eq!(
+        client.try_splice_genome(&Address::generate(&env)),
+        Err(Ok(not_initialized))
+    );
+}
+
+// ===== Verification diagnosis tests =====
+
+/// G1 point (x = 4) on the curve but outside the prime-order subgroup
+const NON_SUBGROUP_G1: &str = "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004\
+0a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c";
+
+/// The G1 generator: in the subgroup, so it passes begin_verification, but no round's signature
+const G1_GENERATOR: &str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb\
+08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+
+#[test]
+fn test_diagnose_verification_reports_the_failing_stage() {
+    use crate::VerificationStage::*;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 0);
+    let (_, _, signature) = real_beacon(&env);
+    let key = real_drand_pubkey(&env);
+    let diagnose = |round: u64, signature: &Bytes, key: Option<Bytes>| {
+        client.diagnose_verification(&round, signature, &key).stage
+    };
+
+    let passed = client.diagnose_verification(&REAL_ROUND, &signature, &None);
+    assert_eq!(passed.stage, Passed);
+    assert_eq!(
+        passed.message,
+        env.crypto()
+            .sha256(&Bytes::from_array(&env, &REAL_ROUND.to_be_bytes()))
+            .to_bytes()
+    );
+    assert_eq!(passed.dst, Bytes::from_slice(&env, crate::DRAND_DST));
+    assert_eq!(diagnose(REAL_ROUND, &signature, Some(key.clone())), Passed);
+
+    assert_eq!(diagnose(REAL_ROUND, &signature.slice(0..95), None), SignatureLength);
+    let mut out_of_range = signature.to_buffer::<96>().as_slice().to_vec();
+    out_of_range[48..].copy_from_slice(&crate::BLS12_381_P);
+    let out_of_range = Bytes::from_slice(&env, &out_of_range);
+    assert_eq!(diagnose(REAL_ROUND, &out_of_range, None), SignatureCoordinates);
+    let outside_g1 = Bytes::from_slice(&env, &hex::decode(NON_SUBGROUP_G1).unwrap());
+    assert_eq!(diagnose(REAL_ROUND, &outside_g1, None), SignatureSubgroup);
+
+    assert_eq!(diagnose(REAL_ROUND, &signature, Some(key.slice(0..191))), PublicKeyLength);
+    let mut key_out_of_range = key.to_buffer::<192>().as_slice().to_vec();
+    key_out_of_range[..48].copy_from_slice(&crate::BLS12_381_P);
+    let key_out_of_range = Bytes::from_slice(&env, &key_out_of_range);
+    assert_eq!(
+        diagnose(REAL_ROUND, &signature, Some(key_out_of_range)),
+        PublicKeyCoordinates
+    );
+    assert_eq!(
+        diagnose(REAL_ROUND, &signature, Some(create_mock_drand_pubkey(&env))),
+        PublicKey
+    );
+
+    // A genuine signature for another round fails only the pairing
+    assert_eq!(diagnose(REAL_ROUND + 1, &signature, None), Pairing);
+}
+
+/// The enforcing path fails on the same stages the diagnosis reports
+#[test]
+fn test_verification_failures_match_diagnosis() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 0);
+    let (_, _, signature) = real_beacon(&env);
+    let verifier = Address::generate(&env);
+
+    let outside_g1 = Bytes::from_slice(&env, &hex::decode(NON_SUBGROUP_G1).unwrap());
+    assert!(client.try_begin_verification(&REAL_ROUND, &outside_g1).is_err());
+
+    let entry = client.begin_verification(&(REAL_ROUND + 1), &signature);
+    assert!(client
+        .try_complete_verification(&verifier, &(REAL_ROUND + 1), &entry)
+        .is_err());
+    let entry = client.begin_verification(&REAL_ROUND, &signature);
+    client.complete_verification(&verifier, &REAL_ROUND, &entry);
+    assert!(client.is_round_verified(&REAL_ROUND, &signature));
+}
+
+// ===== Burn reward tests =====
+
+/// Dev-mode contract whose burn reward pool holds `pool` stroops, plus the XLM token
+fn setup_burn_rewards<'a>(
+    env: &Env,
+    pool: i128,
+) -> (GeneSplicerClient<'a>, token::StellarAssetClient<'a>) {
+    let admin = Address::generate(env);
+    let xlm_token = create_xlm_token(env, &admin);
+    let client = setup_contract(env, &admin, &xlm_token.address, true);
+    if pool > 0 {
+        xlm_token.mint(&admin, &pool);
+        client.fund_burn_rewards(&admin, &pool);
+    }
+    (client, xlm_token)
+}
+
+#[test]
+fn test_burn_rewards_scale_with_rarity() {
+    use crate::GeneRarity::{Legendary, Normal, Rare};
+    use crate::{
+        Pool, DEFAULT_BURN_REWARD_LEGENDARY as LEGENDARY, DEFAULT_BURN_REWARD_NORMAL as NORMAL,
+        DEFAULT_BURN_REWARD_RARE as RARE,
+    };
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let user = Address::generate(&env);
+    let (client, xlm_token) = setup_burn_rewards(&env, 1_000_000_000);
+    let balance = || token::Client::new(&env, &xlm_token.address).balance(&user);
+    assert_eq!(count_events(&env, &client.address, "burn_rewards_funded"), 1);
+
+    store_creature(&env, &client.address, 1, &user, [Normal, Normal, Normal]);
+    store_creature(&env, &client.address, 2, &user, [Normal, Rare, Legendary]);
+    store_creature(&env, &client.address, 3, &user, [Legendary, Legendary, Legendary]);
+
+    client.retire_creature(&user, &1);
+    assert_eq!(count_events(&env, &client.address, "burn_reward_paid"), 1);
+    assert_eq!(balance(), 3 * NORMAL);
+    client.retire_creature(&user, &2);
+    assert_eq!(balance(), 3 * NORMAL + NORMAL + RARE + LEGENDARY);
+    client.retire_creature(&user, &3);
+    let paid = 4 * NORMAL + RARE + 4 * LEGENDARY;
+    assert_eq!(balance(), paid);
+
+    let epoch = client.get_burn_reward_epoch();
+    assert_eq!(client.get_burn_reward_spent(&epoch), paid);
+    assert_eq!(
+        client.get_liability(&Pool::BurnRewards, &xlm_token.address),
+        1_000_000_000 - paid
+    );
+    assert_eq!(client.get_solvency().free_balance, 0);
+}
+
+#[test]
+fn test_burn_rewards_capped_per_epoch() {
+    use crate::GeneRarity::{Legendary, Normal};
+    use crate::{BurnRewardSchedule, BURN_REWARD_EPOCH};
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let user = Address::generate(&env);
+    let (client, xlm_token) = setup_burn_rewards(&env, 1_000_000_000);
+    let balance = || token::Client::new(&env, &xlm_token.address).balance(&user);
+
+    let schedule = BurnRewardSchedule {
+        normal: 1_000_000,
+        rare: 3_000_000,
+        legendary: 10_000_000,
+        epoch_budget: 20_000_000,
+    };
+    assert_eq!(
+        client.try_set_burn_reward_schedule(&BurnRewardSchedule {
+            rare: -1,
+            ..schedule.clone()
+        }),
+        Err(Ok(contract_error(Error::InvalidAmount)))
+    );
+    client.set_burn_reward_schedule(&schedule);
+    assert_eq!(client.get_burn_reward_schedule(), schedule);
+
+    for id in 1..=3 {
+        store_creature(&env, &client.address, id, &user, [Legendary, Normal, Normal]);
+    }
+    client.retire_creature(&user, &1);
+    assert_eq!(balance(), 12_000_000);
+
+    // A second 12 XLM reward would overrun the 20 XLM budget: skipped, burn kept
+    client.retire_creature(&user, &2);
+    assert_eq!(count_events(&env, &client.address, "burn_reward_skipped"), 1);
+    assert!(client.is_creature_retired(&2));
+    assert_eq!(balance(), 12_000_000);
+
+    // The next epoch starts with a fresh budget
+    env.ledger().with_mut(|li| li.timestamp += BURN_REWARD_EPOCH);
+    client.retire_creature(&user, &3);
+    assert_eq!(balance(), 24_000_000);
+    assert_eq!(client.get_burn_reward_spent(&client.get_burn_reward_epoch()), 12_000_000);
+}
+
+#[test]
+fn test_burn_reward_skipped_when_pool_is_short() {
+    use crate::GeneRarity::{Legendary, Normal};
+    use crate::Pool;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let user = Address::generate(&env);
+    let (client, xlm_token) = setup_burn_rewards(&env, 5_000_000);
+    // Stray XLM at the contract is free balance, not reward pool
+    xlm_token.mint(&client.address, &100_000_000);
+
+    store_creature(&env, &client.address, 1, &user, [Legendary, Normal, Normal]);
+    store_creature(&env, &client.address, 2, &user, [Normal, Normal, Normal]);
+    client.retire_creature(&user, &1);
+    assert_eq!(count_events(&env, &client.address, "burn_reward_skipped"), 1);
+    assert!(client.is_creature_retired(&1));
+    assert_eq!(token::Client::new(&env, &xlm_token.address).balance(&user), 0);
+
+    // A reward the pool can cover still pays
+    client.retire_creature(&user, &2);
+    assert_eq!(token::Client::new(&env, &xlm_token.address).balance(&user), 3_000_000);
+    assert_eq!(
+        client.get_liability(&Pool::BurnRewards, &xlm_token.address),
+        2_000_000
+    );
+}
+
+// ===== On-chain metadata JSON tests =====
+
+#[test]
+fn test_metadata_json_is_byte_exact() {
+    use crate::GeneRarity::{Legendary, Normal, Rare};
+    use soroban_sdk::{BytesN, String};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    store_creature(&env, &client.address, 1, &owner, [Normal, Rare, Legendary]);
+    let expected = concat!(
+        r#"{"name":"Creature #1","id":1,"collection_id":0,"entropy_round":42,"#,
+        r#""finalized_at":0,"dev_minted":false,"attributes":["#,
+        r#"{"trait_type":"Skin","value":0},"#,
+        r#"{"trait_type":"Head","value":6,"rarity":"Normal"},"#,
+        r#"{"trait_type":"Body","value":7,"rarity":"Rare"},"#,
+        r#"{"trait_type":"Legs","value":8,"rarity":"Legendary"}]}"#,
+    );
+    assert_eq!(client.get_metadata(&1), String::from_str(&env, expected));
+
+    // Anchoring a hash appends it as lowercase hex
+    client.set_metadata_operator(&Some(operator.clone()));
+    client.anchor_metadata(&operator, &1, &BytesN::from_array(&env, &[0xa5; 32]));
+    let expected = concat!(
+        r#"{"name":"Creature #1","id":1,"collection_id":0,"entropy_round":42,"#,
+        r#""finalized_at":0,"dev_minted":false,"attributes":["#,
+        r#"{"trait_type":"Skin","value":0},"#,
+        r#"{"trait_type":"Head","value":6,"rarity":"Normal"},"#,
+        r#"{"trait_type":"Body","value":7,"rarity":"Rare"},"#,
+        r#"{"trait_type":"Legs","value":8,"rarity":"Legendary"}],"#,
+        r#""metadata_hash":"a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5"}"#,
+    );
+    assert_eq!(client.get_metadata(&1), String::from_str(&env, expected));
+
+    assert_eq!(
+        client.try_get_metadata(&2),
+        Err(Ok(contract_error(Error::CreatureNotFound)))
+    );
+    client.set_reveal_time(&Some(u64::MAX));
+    assert_eq!(
+        client.try_get_metadata(&1),
+        Err(Ok(contract_error(RevealError::NotRevealed)))
+    );
+}
+
+#[test]
+fn test_metadata_json_fits_max_width_fields() {
+    use crate::{GeneRarity::Legendary, MAX_METADATA_LEN};
+    use soroban_sdk::{BytesN, String};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let mut creature = mock_creature(u32::MAX, &owner);
+    creature.skin_id = u32::MAX;
+    creature.collection_id = u32::MAX;
+    creature.entropy_round = u64::MAX;
+    creature.finalized_at = u64::MAX;
+    creature.dev_minted = true;
+    for gene in [
+        &mut creature.head_gene,
+        &mut creature.body_gene,
+        &mut creature.legs_gene,
+    ] {
+        gene.id = u32::MAX;
+        gene.rarity = Legendary;
+    }
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&crate::DataKey::Creature(u32::MAX), &creature);
+    });
+    client.set_metadata_operator(&Some(operator.clone()));
+    client.anchor_metadata(&operator, &u32::MAX, &BytesN::from_array(&env, &[0xff; 32]));
+
+    let expected = concat!(
+        r#"{"name":"Creature #4294967295","id":4294967295,"collection_id":4294967295,"#,
+        r#""entropy_round":18446744073709551615,"finalized_at":18446744073709551615,"#,
+        r#""dev_minted":true,"attributes":["#,
+        r#"{"trait_type":"Skin","value":4294967295},"#,
+        r#"{"trait_type":"Head","value":4294967295,"rarity":"Legendary"},"#,
+        r#"{"trait_type":"Body","value":4294967295,"rarity":"Legendary"},"#,
+        r#"{"trait_type":"Legs","value":4294967295,"rarity":"Legendary"}],"#,
+        r#""metadata_hash":"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"}"#,
+    );
+    let metadata = client.get_metadata(&u32::MAX);
+    assert_eq!(metadata, String::from_str(&env, expected));
+    assert!(metadata.len() as usize <= MAX_METADATA_LEN);
+}
+
+// ===== Round context derivation tests =====
+
+#[test]
+fn test_derive_from_round_separates_contexts_and_entities() {
+    use crate::{derive_from_round, GENE_CONTEXT};
+    use soroban_sdk::{symbol_short, BytesN};
+
+    let env = Env::default();
+    let randomness = BytesN::from_array(&env, &[0x11; 32]);
+    let genes = derive_from_round(&env, &randomness, &GENE_CONTEXT, 7);
+
+    // SHA256(randomness || XDR(context) || entity_id u64 BE)
+    let preimage = hex::decode(
+        "1111111111111111111111111111111111111111111111111111111111111111\
+         0000000f0000000567656e6573000000\
+         0000000000000007",
+    )
+    .unwrap();
+    let digest: BytesN<32> = env.crypto().sha256(&Bytes::from_slice(&env, &preimage)).into();
+    assert_eq!(genes, digest);
+
+    // Same round, different context or entity: unrelated values
+    let drawing = derive_from_round(&env, &randomness, &symbol_short!("drawing"), 7);
+    let other_cartridge = derive_from_round(&env, &randomness, &GENE_CONTEXT, 8);
+    assert_ne!(genes, drawing);
+    assert_ne!(genes, other_cartridge);
+    assert_ne!(drawing, other_cartridge);
+    assert_eq!(derive_from_round(&env, &randomness, &GENE_CONTEXT, 7), genes);
+}
+
+#[test]
+fn test_v4_genes_come_from_the_gene_context() {
+    use crate::{derive_from_round, derive_v3, derive_v4, GENE_CONTEXT};
+    use soroban_sdk::BytesN;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    client.set_derivation_version(&3);
+    let v3_id = client.splice_genome(&user);
+    client.set_derivation_version(&4);
+    let v4_id = client.splice_genome(&user);
+    assert_eq!(client.get_cartridge_derivation_version(&v4_id), 4);
+
+    let seed = Bytes::from_array(&env, b"context-seed");
+    let randomness: Bytes = env.crypto().sha256(&seed).into();
+    client.dev_finalize(&v3_id, &seed);
+    client.dev_finalize(&v4_id, &seed);
+
+    // v3 cartridges keep reading the raw randomness
+    let creature = client.get_creature(&v3_id).unwrap();
+    let [head, body, legs] = derive_v3(&env, &randomness, v3_id);
+    assert_eq!((creature.head_gene, creature.body_gene, creature.legs_gene), (head, body, legs));
+
+    let creature = client.get_creature(&v4_id).unwrap();
+    let [head, body, legs] = derive_v4(&env, &randomness, v4_id);
+    assert_eq!(
+        (creature.head_gene, creature.body_gene, creature.legs_gene),
+        (head.clone(), body, legs)
+    );
+
+    // v4 slot values are the first three big-endian u32s of the gene context value
+    let raw = BytesN::<32>::try_from(randomness).unwrap();
+    let value = derive_from_round(&env, &raw, &GENE_CONTEXT, v4_id.into()).to_array();
+    let head_value = u32::from_be_bytes([value[0], value[1], value[2], value[3]]);
+    let expected_rarity = match head_value % 10 {
+        0 => crate::GeneRarity::Legendary,
+        1..=3 => crate::GeneRarity::Rare,
+        _ => crate::GeneRarity::Normal,
+    };
+    assert_eq!(head.rarity, expected_rarity);
+}
+
+// ===== Client mint reference tests =====
+
+#[test]
+fn test_splice_genome_ref_records_lookup_until_expiry() {
+    use crate::CLIENT_REF_TTL;
+    use soroban_sdk::{
+        testutils::{storage::Temporary as _, Ledger},
+        BytesN,
+    };
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let client_ref = BytesN::from_array(&env, &[0x42; 32]);
+    assert_eq!(client.lookup_by_ref(&client_ref), None);
+
+    client.splice_genome(&user);
+    let cartridge_id = client.splice_genome_ref(&user, &client_ref);
+    assert_eq!(cartridge_id, 2);
+    assert_eq!(client.lookup_by_ref(&client_ref), Some(cartridge_id));
+    assert_eq!(client.get_cartridge(&cartridge_id).unwrap().owner, user);
+    assert_eq!(xlm_token.balance(&user), 80_000_000);
+    env.as_contract(&client.address, || {
+        let key = crate::DataKey::ClientRef(client_ref.clone());
+        assert_eq!(env.storage().temporary().get_ttl(&key), CLIENT_REF_TTL);
+    });
+
+    // A live reference can't be reused, and the refused mint charges nothing
+    assert_eq!(
+        client.try_splice_genome_ref(&user, &client_ref),
+        Err(Ok(contract_error(Error::ClientRefInUse)))
+    );
+    assert_eq!(xlm_token.balance(&user), 80_000_000);
+    let other_ref = BytesN::from_array(&env, &[0x43; 32]);
+    assert_eq!(client.splice_genome_ref(&user, &other_ref), 3);
+
+    // Still recorded on its last ledger, gone after it
+    env.ledger().with_mut(|li| li.sequence_number += CLIENT_REF_TTL);
+    assert_eq!(client.lookup_by_ref(&client_ref), Some(cartridge_id));
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    assert_eq!(client.lookup_by_ref(&client_ref), None);
+    assert_eq!(client.splice_genome_ref(&user, &client_ref), 4);
+    assert_eq!(client.lookup_by_ref(&client_ref), Some(4));
+}
+
+// ===== Garbage collection tests =====
+
+#[test]
+fn test_gc_removes_expired_permit_markers() {
+    use soroban_sdk::{symbol_short, testutils::Ledger, BytesN};
+
+    let env = Env::default();
+    let (client, user, first, key) = setup_permit(&env);
+    let second = client.splice_genome(&user);
+    let relayer = Address::generate(&env);
+    let (r, sc, su) = create_mock_entropy(&env);
+    for (cartridge_id, expires_at) in [(first, 1_000), (second, 5_000)] {
+        let round = client.get_cartridge(&cartridge_id).unwrap().splice_round;
+        let permit = sign_permit(&env, &client, &key, cartridge_id, expires_at);
+        let permit = Some(permit);
+        client.finalize_with_permit(&relayer, &cartridge_id, &round, &r, &sc, &su, &None, &permit);
+    }
+    let marker = |cartridge_id: u32, expires_at: u64| {
+        let message = client.permit_message(&cartridge_id, &expires_at);
+        let hash: BytesN<32> = env.crypto().sha256(&message).into();
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .has(&crate::DataKey::PermitConsumed(hash))
+        })
+    };
+    let permits = soroban_sdk::vec![&env, symbol_short!("permit")];
+
+    // Nothing has expired yet
+    assert_eq!(client.gc(&permits, &10), 0);
+    assert!(marker(first, 1_000) && marker(second, 5_000));
+
+    // Past the first permit's expiry only its marker goes
+    env.ledger().set_timestamp(1_001);
+    assert_eq!(client.gc(&permits, &10), 1);
+    assert!(!marker(first, 1_000));
+    assert!(marker(second, 5_000));
+    assert_eq!(client.gc(&permits, &10), 0);
+
+    assert_eq!(
+        client.try_gc(&soroban_sdk::vec![&env, symbol_short!("nonsense")], &10),
+        Err(Ok(contract_error(Error::UnknownGcKind)))
+    );
+}
+
+#[test]
+fn test_gc_removes_temporary_entries_at_logical_expiry() {
+    use crate::CLIENT_REF_TTL;
+    use soroban_sdk::{testutils::Ledger, BytesN, Symbol};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let refs = [1u8, 2, 3].map(|byte| BytesN::from_array(&env, &[byte; 32]));
+    for client_ref in refs.iter() {
+        client.splice_genome_ref(&user, client_ref);
+    }
+    // Someone extends the first reference's TTL well past its logical expiry
+    env.as_contract(&client.address, || {
+        let key = crate::DataKey::ClientRef(refs[0].clone());
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, CLIENT_REF_TTL * 4, CLIENT_REF_TTL * 4);
+    });
+    let kinds = soroban_sdk::vec![&env, Symbol::new(&env, "client_ref")];
+    assert_eq!(client.gc(&kinds, &10), 0);
+
+    env.ledger().with_mut(|li| li.sequence_number += CLIENT_REF_TTL + 1);
+    let live_ref = BytesN::from_array(&env, &[4; 32]);
+    client.splice_genome_ref(&user, &live_ref);
+    let live_expiry = u64::from(env.ledger().sequence() + CLIENT_REF_TTL);
+    assert_eq!(client.lookup_by_ref(&refs[0]), Some(1));
+
+    // The limit bounds the walk; the extended entry goes at its logical expiry
+    assert_eq!(client.gc(&kinds, &1), 1);
+    assert_eq!(client.lookup_by_ref(&refs[0]), None);
+
+    // The others already expired on their own, and the live reference survives
+    assert_eq!(client.gc(&kinds, &10), 0);
+    assert_eq!(client.lookup_by_ref(&live_ref), Some(4));
+    let tracked: soroban_sdk::Vec<(crate::DataKey, u64)> = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&crate::DataKey::GcRing(kinds.get(0).unwrap()))
+            .unwrap()
+    });
+    assert_eq!(tracked.len(), 1);
+    assert_eq!(tracked.get(0).unwrap().1, live_expiry);
+}
+
+// ===== Companion tests =====
+
+#[test]
+fn test_companion_is_one_per_parent_and_linked_both_ways() {
+    use crate::DEFAULT_COMPANION_FEE;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let parent = client.splice_genome(&user);
+    assert_eq!(
+        client.try_mint_companion(&user, &parent),
+        Err(Ok(contract_error(Error::CreatureNotFound)))
+    );
+    client.dev_finalize(&parent, &Bytes::from_array(&env, b"parent"));
+    assert_eq!(
+        client.try_mint_companion(&other, &parent),
+        Err(Ok(contract_error(Error::NotCreatureOwner)))
+    );
+
+    // Any live lock in the registry, not only an export, keeps companions out
+    env.as_contract(&client.address, || {
+        crate::locks::acquire_lock(&env, parent, "listed", &other, 0).unwrap();
+    });
+    assert_eq!(
+        client.try_mint_companion(&user, &parent),
+        Err(Ok(contract_error(Error::CreatureLocked)))
+    );
+    env.as_contract(&client.address, || {
+        assert!(crate::locks::release_lock(&env, parent, "listed", &other));
+    });
+
+    let companion = client.mint_companion(&user, &parent);
+    assert_eq!(count_events(&env, &client.address, "companion_minted"), 1);
+    assert_eq!(count_events(&env, &client.address, "cartridge_minted"), 1);
+    assert_eq!(client.get_cartridge(&companion).unwrap().owner, user);
+    assert_eq!(xlm_token.balance(&user), 90_000_000 - DEFAULT_COMPANION_FEE);
+    assert_eq!(xlm_token.balance(&admin), 10_000_000 + DEFAULT_COMPANION_FEE);
+
+    assert_eq!(client.get_companion(&parent), Some(companion));
+    assert_eq!(client.get_companion_parent(&companion), Some(parent));
+    assert_eq!(client.get_companion_parent(&parent), None);
+    assert_eq!(client.get_companion(&companion), None);
+
+    assert_eq!(
+        client.try_mint_companion(&user, &parent),
+        Err(Ok(contract_error(CompanionError::CompanionAlreadyMinted)))
+    );
+    client.dev_finalize(&companion, &Bytes::from_array(&env, b"companion"));
+    assert_eq!(
+        client.try_mint_companion(&user, &companion),
+        Err(Ok(contract_error(CompanionError::CompanionCannotBeParent)))
+    );
+}
+
+#[test]
+fn test_companion_link_survives_parent_changing_hands() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    xlm_token.mint(&buyer, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_companion_fee(&0);
+    assert_eq!(client.get_companion_fee(), 0);
+    assert_eq!(
+        client.try_set_companion_fee(&-1),
+        Err(Ok(contract_error(Error::InvalidAmount)))
+    );
+
+    let parent = client.splice_genome(&user);
+    client.dev_finalize(&parent, &Bytes::from_array(&env, b"parent"));
+    let companion = client.mint_companion(&user, &parent);
+    assert_eq!(xlm_token.balance(&user), 90_000_000);
+
+    client.transfer_creature(&user, &buyer, &parent);
+
+    assert_eq!(client.get_companion(&parent), Some(companion));
+    assert_eq!(client.get_companion_parent(&companion), Some(parent));
+    assert_eq!(client.get_cartridge(&companion).unwrap().owner, user);
+    assert_eq!(
+        client.try_mint_companion(&buyer, &parent),
+        Err(Ok(contract_error(CompanionError::CompanionAlreadyMinted)))
+    );
+}
+
+// ===== Fee rounding tests =====
+
+#[test]
+fn test_round_fee_invariants() {
+    use crate::{round_fee, FeeRounding};
+
+    let policies = [(1, 0), (1, 2_500_000), (1_000_000, 0), (1_000_000, 2_500_000), (7, 3)];
+    let fees = [
+        0,
+        1,
+        2,
+        999_999,
+        1_000_000,
+        1_000_001,
+        2_499_999,
+        6_666_667,
+        MINT_FEE * 9 / 10,
+        MINT_FEE - 1,
+        MINT_FEE,
+        MINT_FEE * 15,
+    ];
+    for (round_to, minimum) in policies {
+        let rounding = FeeRounding { round_to, minimum };
+        assert_eq!(round_fee(&rounding, 0), 0);
+        for fee in fees.into_iter().filter(|fee| *fee > 0) {
+            let rounded = round_fee(&rounding, fee);
+            assert_eq!(rounded % round_to, 0, "{fee} under {round_to}/{minimum}");
+            assert!(rounded >= fee && rounded >= minimum);
+            assert!(rounded - fee.max(minimum) < round_to);
+            assert_eq!(round_fee(&rounding, rounded), rounded);
+        }
+    }
+    let rounding = FeeRounding { round_to: 1_000_000, minimum: 2_500_000 };
+    assert_eq!(round_fee(&rounding, 1), 3_000_000);
+    assert_eq!(round_fee(&rounding, 6_666_667), 7_000_000);
+    assert_eq!(round_fee(&rounding, MINT_FEE), MINT_FEE);
+}
+
+#[test]
+fn test_fee_rounding_applies_wherever_fees_are_charged() {
+    use crate::FeeRounding;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    // The default policy changes nothing
+    assert_eq!(client.get_fee_rounding(), FeeRounding { round_to: 1, minimum: 0 });
+    for rounding in [
+        FeeRounding { round_to: 0, minimum: 0 },
+        FeeRounding { round_to: 1, minimum: -1 },
+    ] {
+        assert_eq!(
+            client.try_set_fee_rounding(&rounding),
+            Err(Ok(contract_error(Error::InvalidAmount)))
+        );
+    }
+    let rounding = FeeRounding { round_to: 1_000_000, minimum: 2_500_000 };
+    client.set_fee_rounding(&rounding);
+    assert_eq!(client.get_fee_rounding(), rounding);
+
+    // Points: 3_333_333 stroops off leaves 6_666_667, charged as 7_000_000
+    client.set_points_rate(&1);
+    grant_points(&env, &client, &user, 13_333_333);
+    client.splice_genome_with_points(&user, &3_333_333);
+    assert_eq!(xlm_token.balance(&user), 93_000_000);
+    // A fully discounted fee stays free despite the minimum
+    client.splice_genome_with_points(&user, &10_000_000);
+    assert_eq!(xlm_token.balance(&user), 93_000_000);
+
+    // Bulk tiers: 33.33% off quotes 6_667_000 per cartridge, rounded up to 7_000_000
+    client.set_bulk_discounts(&soroban_sdk::vec![&env, (1, 3_333)]);
+    let quote = client.quote_batch_fee(&3);
+    assert_eq!((quote.unit_price, quote.total), (7_000_000, 21_000_000));
+
+    // A 1-stroop companion fee is raised to the minimum, rounded up
+    let parent = client.splice_genome(&user);
+    client.dev_finalize(&parent, &Bytes::from_array(&env, b"parent"));
+    client.set_companion_fee(&1);
+    client.mint_companion(&user, &parent);
+    assert_eq!(xlm_token.balance(&user), 93_000_000 - 10_000_000 - 3_000_000);
+    assert_eq!(xlm_token.balance(&admin), 7_000_000 + 10_000_000 + 3_000_000);
+}
+
+// ===== Transfer tests =====
+
+#[test]
+fn test_transfers_move_ownership_lists_and_refuse_edge_cases() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let kept = client.splice_genome(&user);
+    let sent = client.splice_genome(&user);
+    client.transfer_cartridge(&user, &buyer, &sent);
+    assert_eq!(count_events(&env, &client.address, "cartridge_transferred"), 1);
+    assert_eq!(client.get_cartridge(&sent).unwrap().owner, buyer);
+    assert_eq!(client.get_user_cartridges(&user), soroban_sdk::vec![&env, kept]);
+    assert_eq!(client.get_user_cartridges(&buyer), soroban_sdk::vec![&env, sent]);
+
+    assert_eq!(
+        client.try_transfer_cartridge(&user, &buyer, &sent),
+        Err(Ok(contract_error(Error::NotCartridgeOwner)))
+    );
+    assert_eq!(
+        client.try_transfer_cartridge(&buyer, &buyer, &sent),
+        Err(Ok(contract_error(TransferError::SelfTransfer)))
+    );
+    assert_eq!(
+        client.try_transfer_cartridge(&user, &buyer, &99),
+        Err(Ok(contract_error(Error::CartridgeNotFound)))
+    );
+    assert_eq!(
+        client.try_transfer_creature(&user, &buyer, &kept),
+        Err(Ok(contract_error(Error::CreatureNotFound)))
+    );
+
+    // Once finalized, the creature is the transferable asset
+    client.dev_finalize(&sent, &Bytes::from_array(&env, b"sent"));
+    assert_eq!(
+        client.try_transfer_cartridge(&buyer, &user, &sent),
+        Err(Ok(contract_error(Error::AlreadyFinalized)))
+    );
+    client.transfer_creature(&buyer, &user, &sent);
+    assert_eq!(count_events(&env, &client.address, "creature_transferred"), 1);
+    assert_eq!(client.get_creature(&sent).unwrap().owner, user);
+    assert_eq!(client.get_user_creatures(&buyer).len(), 0);
+    assert_eq!(client.get_user_creatures(&user), soroban_sdk::vec![&env, sent]);
+    assert_eq!(
+        client.try_transfer_creature(&user, &user, &sent),
+        Err(Ok(contract_error(TransferError::SelfTransfer)))
+    );
+
+    // A live lock pins the creature to its owner
+    env.as_contract(&client.address, || {
+        crate::locks::acquire_lock(&env, sent, "listed", &admin, 0).unwrap();
+    });
+    assert_eq!(
+        client.try_transfer_creature(&user, &buyer, &sent),
+        Err(Ok(contract_error(Error::CreatureLocked)))
+    );
+}
+
+#[test]
+fn test_approved_spender_transfers_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let market = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let cartridge = client.splice_genome(&user);
+    assert_eq!(
+        client.try_transfer_from(&market, &user, &buyer, &cartridge),
+        Err(Ok(contract_error(TransferError::NotApproved)))
+    );
+    assert_eq!(
+        client.try_approve(&buyer, &market, &cartridge),
+        Err(Ok(contract_error(Error::NotCartridgeOwner)))
+    );
+    client.approve(&user, &market, &cartridge);
+    assert_eq!(count_events(&env, &client.address, "approval_set"), 1);
+    assert_eq!(client.get_approved(&cartridge), Some(market.clone()));
+    client.revoke_approval(&user, &cartridge);
+    assert_eq!(client.get_approved(&cartridge), None);
+
+    // A direct transfer clears the approval
+    client.approve(&user, &market, &cartridge);
+    client.transfer_cartridge(&user, &buyer, &cartridge);
+    assert_eq!(client.get_approved(&cartridge), None);
+
+    // After finalization the approval covers the creature, and is spent by transfer_from
+    client.dev_finalize(&cartridge, &Bytes::from_array(&env, b"seed"));
+    client.approve(&buyer, &market, &cartridge);
+    client.transfer_from(&market, &buyer, &user, &cartridge);
+    assert_eq!(count_events(&env, &client.address, "creature_transferred"), 1);
+    assert_eq!(client.get_creature(&cartridge).unwrap().owner, user);
+    assert_eq!(client.get_cartridge(&cartridge).unwrap().owner, buyer);
+    assert_eq!(client.get_approved(&cartridge), None);
+    assert_eq!(
+        client.try_transfer_from(&market, &user, &buyer, &cartridge),
+        Err(Ok(contract_error(TransferError::NotApproved)))
+    );
+}
+
+#[test]
+fn test_transfers_notify_recipient_inbox() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let cartridge = client.splice_genome(&user);
+    let creature = client.splice_genome(&user);
+    client.dev_finalize(&creature, &Bytes::from_array(&env, b"seed"));
+    client.clear_inbox(&user);
+
+    client.transfer_cartridge(&user, &buyer, &cartridge);
+    client.transfer_creature(&user, &buyer, &creature);
+
+    let entry = |kind: &str, id: u32| crate::InboxEntry {
+        kind: soroban_sdk::Symbol::new(&env, kind),
+        id,
+        timestamp: 1_000,
+    };
+    assert_eq!(
+        client.read_inbox(&buyer),
+        soroban_sdk::vec![
+            &env,
+            entry("cartridge_received", cartridge),
+            entry("creature_received", creature)
+        ]
+    );
+    // Sending doesn't notify the sender
+    assert_eq!(client.read_inbox(&user).len(), 0);
+}
+
+// ===== Integrity tests =====
+
+fn invariant_names(env: &Env, names: &[&str]) -> soroban_sdk::Vec<soroban_sdk::Symbol> {
+    let mut symbols = soroban_sdk::Vec::new(env);
+    for name in names {
+        symbols.push_back(soroban_sdk::Symbol::new(env, name));
+    }
+    symbols
+}
+
+#[test]
+fn test_integrity_detects_and_repairs_derived_indexes() {
+    use crate::DataKey;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    let creature_id = client.splice_genome(&user);
+    client.dev_finalize(&creature_id, &Bytes::from_array(&env, b"seed"));
+    let pending_id = client.splice_genome(&user);
+    for id in [creature_id, pending_id, 99] {
+        assert_eq!(client.check_integrity(&id), invariant_names(&env, &[]));
+    }
+    assert_eq!(client.repair(&creature_id), invariant_names(&env, &[]));
+
+    let head = client.get_creature(&creature_id).unwrap().head_gene.id;
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::UserCartridges(user.clone()), &soroban_sdk::vec![&env, creature_id]);
+        let duplicated = soroban_sdk::vec![&env, creature_id, creature_id];
+        storage.set(&DataKey::UserCreatures(user.clone()), &duplicated);
+        storage.remove(&DataKey::FinalizedBitmapChunk(0));
+        storage.remove(&DataKey::PendingChunk(0));
+        storage.remove(&DataKey::CreaturesByGeneChunk(0, head, 0));
+    });
+    assert_eq!(
+        client.check_integrity(&creature_id),
+        invariant_names(&env, &["creature_listed", "finalized_bit", "gene_indexed"])
+    );
+    assert_eq!(
+        client.check_integrity(&pending_id),
+        invariant_names(&env, &["cartridge_listed", "pending_index"])
+    );
+
+    assert_eq!(
+        client.repair(&creature_id),
+        invariant_names(&env, &["creature_listed", "finalized_bit", "gene_indexed"])
+    );
+    assert_eq!(count_events(&env, &client.address, "integrity_repaired"), 1);
+    assert_eq!(
+        client.repair(&pending_id),
+        invariant_names(&env, &["cartridge_listed", "pending_index"])
+    );
+    for id in [creature_id, pending_id] {
+        assert_eq!(client.check_integrity(&id), invariant_names(&env, &[]));
+    }
+    assert_eq!(client.get_user_creatures(&user), soroban_sdk::vec![&env, creature_id]);
+    assert_eq!(client.get_user_cartridges(&user), soroban_sdk::vec![&env, creature_id, pending_id]);
+    assert!(client.is_finalized_fast(&creature_id));
+    assert_eq!(client.get_pending_details(&0, &10).0.len(), 1);
+}
+
+#[test]
+fn test_integrity_reports_links_it_cannot_repair() {
+    use crate::DataKey;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_companion_fee(&0);
+
+    let parent = client.splice_genome(&user);
+    client.dev_finalize(&parent, &Bytes::from_array(&env, b"parent"));
+    let companion = client.mint_companion(&user, &parent);
+    let queued = client.splice_genome(&user);
+    let round = client.get_cartridge(&queued).unwrap().splice_round;
+    client.request_finalization(&user, &queued);
+    for id in [parent, companion, queued] {
+        assert_eq!(client.check_integrity(&id), invariant_names(&env, &[]));
+    }
+
+    env.as_contract(&client.address, || {
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::CompanionParent(companion), &queued);
+        storage.set(&DataKey::QueuedCartridge(queued), &(round, 5u32));
+        storage.remove(&DataKey::Creature(parent));
+    });
+    assert_eq!(
+        client.check_integrity(&parent),
+        invariant_names(&env, &["creature_matches_cartridge", "companion_link"])
+    );
+    assert_eq!(
+        client.check_integrity(&companion),
+        invariant_names(&env, &["companion_link"])
+    );
+    assert_eq!(client.check_integrity(&queued), invariant_names(&env, &["queue_entry"]));
+
+    // Which side of a broken link is right isn't knowable, so repair leaves them reported
+    for id in [parent, companion, queued] {
+        assert_eq!(client.repair(&id), invariant_names(&env, &[]));
+    }
+    assert_eq!(client.check_integrity(&queued), invariant_names(&env, &["queue_entry"]));
+}
+
+// ===== Stored entropy tests =====
+
+#[test]
+fn test_stored_entropy_finalizes_every_cartridge_of_its_round() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 3);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+    let submitter = Address::generate(&env);
+
+    assert_eq!(client.get_entropy(&REAL_ROUND), None);
+    assert_eq!(
+        client.try_finalize_with_entropy(&1),
+        Err(Ok(contract_error(EntropyError::EntropyNotAvailable)))
+    );
+    let mut chosen = randomness.clone();
+    chosen.set(0, chosen.get(0).unwrap() ^ 1);
+    assert_eq!(
+        client.try_submit_entropy(&submitter, &REAL_ROUND, &chosen, &sig_uncompressed, &None),
+        Err(Ok(contract_error(Error::RandomnessMismatch)))
+    );
+
+    let wrong_network = Some(Bytes::from_array(&env, &[0xAA; 32]));
+    assert_eq!(
+        client.try_submit_entropy(&submitter, &REAL_ROUND, &randomness, &sig_uncompressed, &wrong_network),
+        Err(Ok(contract_error(Error::WrongDrandNetwork)))
+    );
+
+    let quicknet = Some(Bytes::from_array(&env, &splicers_drand_client::QUICKNET_CHAIN_HASH));
+    client.submit_entropy(&submitter, &REAL_ROUND, &randomness, &sig_uncompressed, &quicknet);
+    let verify_cost = env.cost_estimate().budget().cpu_instruction_cost();
+    assert_eq!(count_events(&env, &client.address, "entropy_submitted"), 1);
+    let entropy = client.get_entropy(&REAL_ROUND).unwrap();
+    assert_eq!(
+        (entropy.round, Bytes::from(entropy.randomness), Bytes::from(entropy.signature)),
+        (REAL_ROUND, randomness.clone(), sig_uncompressed.clone())
+    );
+    assert_eq!(entropy.submitter, submitter);
+    assert_eq!(
+        client.try_submit_entropy(&submitter, &REAL_ROUND, &randomness, &sig_uncompressed, &None),
+        Err(Ok(contract_error(EntropyError::EntropyAlreadySubmitted)))
+    );
+
+    client.finalize_with_entropy(&1);
+
+    // Stored entropy outlives the verified-round cache and still skips the pairing
+    env.ledger().with_mut(|li| li.sequence_number += 17_281);
+    assert!(!client.is_round_verified(&REAL_ROUND, &sig_uncompressed));
+    client.finalize_with_entropy(&2);
+    assert!(env.cost_estimate().budget().cpu_instruction_cost() * 2 < verify_cost);
+
+    // finalize_splice given the stored beacon takes the same path
+    client.finalize_splice(&3, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None);
+    assert!(env.cost_estimate().budget().cpu_instruction_cost() * 2 < verify_cost);
+
+    // The cartridge ID is mixed in, so a shared round doesn't mean shared genes
+    let genes = |id: u32| {
+        let creature = client.get_creature(&id).unwrap();
+        (creature.head_gene.id, creature.body_gene.id, creature.legs_gene.id)
+    };
+    assert_ne!(genes(1), genes(2));
+}
+
+#[test]
+fn test_stored_entropy_refuses_v1_cartridges() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup_real_round_cartridges(&env, 0);
+    let (randomness, sig_compressed, sig_uncompressed) = real_beacon(&env);
+    let user = Address::generate(&env);
+
+    // v1 ignores the cartridge ID, so two v1 cartridges on one round would share genes
+    client.set_mint_fee(&0);
+    client.set_derivation_version(&1);
+    for _ in 0..2 {
+        let cartridge_id = client.splice_genome(&user);
+        move_to_real_round(&env, &client, cartridge_id);
+    }
+    client.submit_entropy(&user, &REAL_ROUND, &randomness, &sig_uncompressed, &None);
+    for id in [1, 2] {
+        assert_eq!(
+            client.try_finalize_with_entropy(&id),
+            Err(Ok(contract_error(EntropyError::SharedEntropyUnsupported)))
+        );
+    }
+
+    // finalize_splice with their own beacon still works
+    client.finalize_splice(&1, &REAL_ROUND, &randomness, &sig_compressed, &sig_uncompressed, &None);
+    assert_eq!(client.get_creature(&1).unwrap().entropy_round, REAL_ROUND);
+}
+
+// ===== Batch mint tests =====
+
+/// `unit_price` carried by the last `batch_cartridge_minted` event
+fn batch_event_unit_price(env: &Env, contract_id: &Address) -> i128 {
+    use soroban_sdk::xdr::{ContractEventBody, ScSymbol, ScVal};
+
+    let topic = ScVal::Symbol(ScSymbol("batch_cartridge_minted".try_into().unwrap()));
+    let field = ScVal::Symbol(ScSymbol("unit_price".try_into().unwrap()));
+    let events = env.events().all().filter_by_contract(contract_id).events().to_vec();
+    let event = events
+        .iter()
+        .rev()
+        .find(|event| {
+            let ContractEventBody::V0(body) = &event.body;
+            body.topics.first() == Some(&topic)
+        })
+        .unwrap();
+    let ContractEventBody::V0(body) = &event.body;
+    let ScVal::Map(Some(map)) = &body.data else {
+        panic!("event data is not a map");
+    };
+    match &map.iter().find(|entry| entry.key == field).unwrap().val {
+        ScVal::I128(parts) => ((parts.hi as i128) << 64) | parts.lo as i128,
+        _ => panic!("unit_price is not an i128"),
+    }
+}
+
+#[test]
+fn test_splice_genome_batch_mints_at_quoted_total() {
+    use crate::MAX_SPLICE_BATCH;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    assert_eq!(client.get_limits().max_splice_batch, MAX_SPLICE_BATCH);
+    for count in [0, MAX_SPLICE_BATCH + 1] {
+        assert_eq!(
+            client.try_splice_genome_batch(&user, &count),
+            Err(Ok(contract_error(BulkPricingError::InvalidBatchSize)))
+        );
+    }
+
+    // Below every tier: full price, and per-item events
+    let first = client.splice_genome(&user);
+    let pair = client.splice_genome_batch(&user, &2);
+    assert_eq!(pair, soroban_sdk::vec![&env, first + 1, first + 2]);
+    assert_eq!(count_events(&env, &client.address, "cartridge_minted"), 2);
+    assert_eq!(xlm_token.balance(&user), 70_000_000);
+
+    // Five reach the 10% tier: 45 XLM in one transfer, one aggregated event
+    let quote = client.quote_batch_fee(&5);
+    let batch = client.splice_genome_batch(&user, &5);
+    assert_eq!(count_events(&env, &client.address, "batch_cartridge_minted"), 1);
+    assert_eq!(batch_event_unit_price(&env, &client.address), quote.unit_price);
+    assert_eq!(quote.total, 45_000_000);
+    assert_eq!(xlm_token.balance(&user), 70_000_000 - quote.total);
+    assert_eq!(xlm_token.balance(&admin), 30_000_000 + quote.total);
+    assert_eq!(client.get_total_cartridges(), 8);
+    assert_eq!(client.get_user_cartridges(&user).len(), 8);
+    assert_eq!(client.get_user_cartridges(&user).slice(3..), batch);
+
+    let round = client.get_cartridge(&batch.get_unchecked(0)).unwrap().splice_round;
+    for (offset, id) in batch.iter().enumerate() {
+        assert_eq!(id, first + 3 + offset as u32);
+        let cartridge = client.get_cartridge(&id).unwrap();
+        assert_eq!((cartridge.owner, cartridge.splice_round), (user.clone(), round));
+    }
+}
+
+#[test]
+fn test_splice_genome_batch_needs_one_round_with_room_for_all() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_max_assignments_per_round(&3);
+
+    assert_eq!(
+        client.try_splice_genome_batch(&user, &4),
+        Err(Ok(contract_error(Error::RoundCapacityExhausted)))
+    );
+    let first = client.splice_genome(&user);
+    let batch = client.splice_genome_batch(&user, &3);
+    let round = |id: u32| client.get_cartridge(&id).unwrap().splice_round;
+    assert_eq!(round(batch.get_unchecked(0)), round(first) + 1);
+    assert_eq!(client.get_round_assignments(&round(first)), 1);
+    assert_eq!(client.get_round_assignments(&round(first + 1)), 3);
+}
+
+// ===== Mint fee and treasury tests =====
+
+/// Helper: register a dev-mode contract charging `mint_fee` and paying `treasury`
+fn setup_with_fee<'a>(
+    env: &Env,
+    admin: &Address,
+    xlm_token_address: &Address,
+    mint_fee: i128,
+    treasury: &Address,
+) -> GeneSplicerClient<'a> {
+    let pubkey = create_mock_drand_pubkey(env);
+    let contract_id = env.register(
+        GeneSplicer,
+        (admin, xlm_token_address, 10u64, true, pubkey, mock_provenance_hash(env), mint_fee, treasury),
+    );
+    GeneSplicerClient::new(env, &contract_id)
+}
+
+#[test]
+fn test_zero_mint_fee_skips_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_with_fee(&env, &admin, &xlm_token.address, 0, &treasury);
+
+    // No balance needed and nothing moves
+    assert_eq!((client.get_mint_fee(), client.get_treasury()), (0, treasury.clone()));
+    assert_eq!(client.splice_genome(&user), 1);
+    assert_eq!(client.quote_batch_fee(&3).total, 0);
+    client.splice_genome_batch(&user, &3);
+    assert_eq!(client.get_user_cartridges(&user).len(), 4);
+    assert_eq!(xlm_token.balance(&treasury), 0);
+}
+
+#[test]
+fn test_mint_fee_change_applies_to_next_mint() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    xlm_token.mint(&user, &100_000_000);
+    let client = setup_with_fee(&env, &admin, &xlm_token.address, MINT_FEE, &treasury);
+
+    // Proceeds go to the treasury, not the admin
+    client.splice_genome(&user);
+    assert_eq!(xlm_token.balance(&treasury), MINT_FEE);
+    assert_eq!(xlm_token.balance(&admin), 0);
+
+    client.set_mint_fee(&25_000_000);
+    assert_eq!(count_events(&env, &client.address, "mint_fee_updated"), 1);
+    assert_eq!(client.get_mint_fee(), 25_000_000);
+    assert_eq!(client.quote_batch_fee(&1).unit_price, 25_000_000);
+    client.splice_genome(&user);
+    assert_eq!(xlm_token.balance(&treasury), MINT_FEE + 25_000_000);
+    assert_eq!(xlm_token.balance(&user), 100_000_000 - MINT_FEE - 25_000_000);
+
+    // A new treasury receives the following mint
+    let multisig = Address::generate(&env);
+    client.set_treasury(&multisig);
+    client.splice_genome(&user);
+    assert_eq!(xlm_token.balance(&multisig), 25_000_000);
+
+    assert_eq!(
+        client.try_set_mint_fee(&-1),
+        Err(Ok(contract_error(Error::InvalidAmount)))
+    );
+}
+
+#[test]
+fn test_mint_fee_setters_reject_non_owner() {
+    use soroban_sdk::IntoVal;
+
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+
+    as_signer(&env, &client, &stranger, "set_mint_fee", (0i128,).into_val(&env));
+    assert!(client.try_set_mint_fee(&0).is_err());
+    as_signer(&env, &client, &stranger, "set_treasury", (stranger.clone(),).into_val(&env));
+    assert!(client.try_set_treasury(&stranger).is_err());
+    assert_eq!((client.get_mint_fee(), client.get_treasury()), (MINT_FEE, admin));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_constructor_rejects_negative_mint_fee() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    setup_with_fee(&env, &admin, &xlm_token.address, -1, &admin);
+}
+
+// ===== Enumeration tests =====
+
+#[test]
+fn test_user_pages_cover_large_holdings() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_mint_fee(&0);
+    for _ in 0..12 {
+        client.splice_genome_batch(&user, &10);
+    }
+    client.splice_genome_batch(&user, &3);
+
+    // 123 cartridges: full pages of 50, then a partial page of 23
+    let all = client.get_user_cartridges(&user);
+    assert_eq!(client.get_user_cartridge_count(&user), 123);
+    let mut walked = soroban_sdk::Vec::new(&env);
+    for (start, expected) in [(0, 50), (50, 50), (100, 23), (123, 0)] {
+        let page = client.get_user_cartridges_page(&user, &start, &50);
+        assert_eq!(page.len(), expected);
+        walked.append(&page);
+    }
+    assert_eq!(walked, all);
+    assert_eq!(client.get_user_cartridges_page(&user, &99, &2), all.slice(99..101));
+    assert_eq!(client.get_user_cartridges_page(&user, &u32::MAX, &10).len(), 0);
+    assert_eq!(client.get_user_cartridges_page(&user, &0, &u32::MAX).len(), crate::MAX_OWNER_PAGE);
+
+    // Creatures page the same way
+    for id in 1..=3 {
+        client.dev_finalize(&id, &Bytes::from_array(&env, &[id as u8; 8]));
+    }
+    assert_eq!(client.get_user_creature_count(&user), 3);
+    assert_eq!(
+        client.get_user_creatures_page(&user, &1, &5),
+        soroban_sdk::vec![&env, 2u32, 3]
+    );
+    assert_eq!(client.get_user_creatures_page(&user, &3, &5).len(), 0);
+}
+
+#[test]
+fn test_cartridges_range_is_capped_and_clipped() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let xlm_token = create_xlm_token(&env, &admin);
+    let client = setup_contract(&env, &admin, &xlm_token.address, true);
+    client.set_mint_fee(&0);
+    for _ in 0..6 {
+        client.splice_genome_batch(&user, &10);
+    }
+
+    // Half-open, and at most Limits.max_batch_read IDs from start_id
+    let range = client.get_cartridges_range(&5, &8);
+    let ids: std::vec::Vec<u32> = range.iter().map(|cartridge| cartridge.id).collect();
+    assert_eq!(ids, [5, 6, 7]);
+    let capped = client.get_cartridges_range(&1, &u32::MAX);
+    assert_eq!(capped.len(), client.get_limits().max_batch_read);
+    assert_eq!(capped.last().unwrap().id, 50);
+
+    // ID 0 is never minted; the tail stops at the last cartridge; past the end is empty
+    assert_eq!(client.get_cartridges_range(&0, &3).len(), 2);
+    assert_eq!(client.get_cartridges_range(&55, &100).len(), 6);
+    assert_eq!(client.get_cartridges_range(&61, &100).len(), 0);
+    assert_eq!(client.get_cartridges_range(&9, &3).len(), 0);
 }
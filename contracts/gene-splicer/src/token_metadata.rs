@@ -0,0 +1,148 @@
+//! Creature metadata JSON built on-chain
+//!
+//! `get_metadata` derives a creature's metadata entirely from contract state,
+//! for holders who don't want token metadata to depend on a web server. The
+//! output is compact JSON with a fixed key order, so the same creature always
+//! yields the same bytes:
+//!
+//! ```text
+//! {"name":"Creature #<id>","id":<id>,"collection_id":<n>,"entropy_round":<n>,
+//!  "finalized_at":<n>,"dev_minted":<bool>,"attributes":[
+//!  {"trait_type":"Skin","value":<skin_id>},
+//!  {"trait_type":"Head","value":<gene_id>,"rarity":"<Normal|Rare|Legendary>"},
+//!  {"trait_type":"Body",...},{"trait_type":"Legs",...}],
+//!  "metadata_hash":"<64 hex chars>"}
+//! ```
+//!
+//! (shown wrapped; the real output has no whitespace). `metadata_hash` is the
+//! anchored hash of the off-chain metadata (see `metadata`) and is left out
+//! until one is anchored. Like the render layers, metadata is refused with
+//! `NotRevealed` until the collection reveal.
+
+use crate::{
+    reveal, Creature, DataKey, Error, Gene, GeneRarity, GeneSplicer, GeneSplicerArgs,
+    GeneSplicerClient,
+};
+use soroban_sdk::{contractimpl, panic_with_error, BytesN, Env, String};
+
+/// Longest metadata JSON get_metadata can produce, with every number at its widest
+pub const MAX_METADATA_LEN: usize = 512;
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Get a creature's metadata as compact JSON built from on-chain state
+    /// Refused with NotRevealed until the collection reveal
+    pub fn get_metadata(env: Env, creature_id: u32) -> String {
+        reveal::require_revealed(&env);
+        let creature: Creature = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Creature(creature_id))
+            .unwrap_or_else(|| panic_with_error!(&env, Error::CreatureNotFound));
+        let metadata_hash: Option<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MetadataHash(creature_id));
+
+        let mut json = JsonWriter::new();
+        json.raw("{\"name\":\"Creature #");
+        json.number(creature.id.into());
+        json.raw("\",\"id\":");
+        json.number(creature.id.into());
+        json.raw(",\"collection_id\":");
+        json.number(creature.collection_id.into());
+        json.raw(",\"entropy_round\":");
+        json.number(creature.entropy_round);
+        json.raw(",\"finalized_at\":");
+        json.number(creature.finalized_at);
+        json.raw(",\"dev_minted\":");
+        json.raw(if creature.dev_minted { "true" } else { "false" });
+        json.raw(",\"attributes\":[{\"trait_type\":\"Skin\",\"value\":");
+        json.number(creature.skin_id.into());
+        json.raw("}");
+        for (slot, gene) in [
+            ("Head", &creature.head_gene),
+            ("Body", &creature.body_gene),
+            ("Legs", &creature.legs_gene),
+        ] {
+            json.gene(slot, gene);
+        }
+        json.raw("]");
+        if let Some(hash) = metadata_hash {
+            json.raw(",\"metadata_hash\":\"");
+            json.hex(&hash.to_array());
+            json.raw("\"");
+        }
+        json.raw("}");
+        json.finish(&env)
+    }
+}
+
+/// Append-only JSON text in a fixed buffer; callers supply the structure
+struct JsonWriter {
+    buf: [u8; MAX_METADATA_LEN],
+    len: usize,
+}
+
+impl JsonWriter {
+    fn new() -> Self {
+        JsonWriter {
+            buf: [0; MAX_METADATA_LEN],
+            len: 0,
+        }
+    }
+
+    /// Append text verbatim; only ever called with literals that need no escaping
+    fn raw(&mut self, text: &str) {
+        self.bytes(text.as_bytes());
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        self.buf[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+    }
+
+    /// Append `value` in decimal
+    fn number(&mut self, mut value: u64) {
+        // u64::MAX has 20 digits
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        self.bytes(&digits[start..]);
+    }
+
+    /// Append `bytes` as lowercase hex
+    fn hex(&mut self, bytes: &[u8]) {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        for byte in bytes {
+            self.bytes(&[HEX[(byte >> 4) as usize], HEX[(byte & 0x0f) as usize]]);
+        }
+    }
+
+    /// Append one gene slot's attribute entry
+    fn gene(&mut self, slot: &str, gene: &Gene) {
+        self.raw(",{\"trait_type\":\"");
+        self.raw(slot);
+        self.raw("\",\"value\":");
+        self.number(gene.id.into());
+        self.raw(",\"rarity\":\"");
+        self.raw(match gene.rarity {
+            GeneRarity::Normal => "Normal",
+            GeneRarity::Rare => "Rare",
+            GeneRarity::Legendary => "Legendary",
+        });
+        self.raw("\"}");
+    }
+
+    fn finish(&self, env: &Env) -> String {
+        String::from_bytes(env, &self.buf[..self.len])
+    }
+}
@@ -0,0 +1,292 @@
+//! Cartridge and creature transfers, with single-token approvals
+//!
+//! A cartridge and the creature finalized from it share an ID, and only one of
+//! them is transferable at a time: the cartridge until it is finalized, the
+//! creature afterwards. A finalized cartridge stays with its original owner as
+//! mint provenance, so `transfer_cartridge` refuses it with AlreadyFinalized.
+//! `approve`, `get_approved` and `transfer_from` take a plain token ID and act
+//! on whichever of the two is transferable.
+//!
+//! Moving a cartridge drops any unsold finalization right offer (it was the
+//! previous owner's asking price), but a right that was already sold stays
+//! sold: the buyer still finalizes and receives the creature. Retired
+//! creatures, and creatures holding a live lock (listed, staked, exported,
+//! ...), can't move. Transferring to the current owner fails with
+//! SelfTransfer. The recipient gets an inbox entry for the token received.
+//!
+//! An owner approves at most one spender per token. The approval is cleared
+//! by any transfer and only counts while its grantor still owns the token.
+
+use crate::{
+    account_auth, events, inbox, locks, retirement, Creature, DataKey, Error, GeneSplicer,
+    GeneSplicerArgs, GeneSplicerClient, GenomeCartridge, TTL_EXTEND_TO, TTL_THRESHOLD,
+};
+use soroban_sdk::{
+    contracterror, contractevent, contractimpl, contracttype, panic_with_error, Address, Env, Vec,
+};
+
+/// Transfer failures
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TransferError {
+    SelfTransfer = 92, // Transfer to the address that already owns the token
+    NotApproved = 93,  // transfer_from by a spender the current owner hasn't approved
+}
+
+/// A spender allowed to move one token, and the owner who allowed it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenApproval {
+    pub owner: Address,
+    pub spender: Address,
+}
+
+/// Event emitted when a cartridge changes hands
+#[contractevent]
+pub struct CartridgeTransferred {
+    pub cartridge_id: u32,
+    pub from: Address,
+    pub to: Address,
+    pub nonce: u64,
+}
+
+/// Event emitted when a creature changes hands
+#[contractevent]
+pub struct CreatureTransferred {
+    pub creature_id: u32,
+    pub from: Address,
+    pub to: Address,
+    pub nonce: u64,
+}
+
+/// Event emitted when an owner approves a spender for a token, or revokes it (spender None)
+#[contractevent]
+pub struct ApprovalSet {
+    pub token_id: u32,
+    pub owner: Address,
+    pub spender: Option<Address>,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Send an unfinalized cartridge from its owner to `to`
+    pub fn transfer_cartridge(env: Env, from: Address, to: Address, cartridge_id: u32) {
+        account_auth::require_transfer(&env, &from, cartridge_id, &to);
+        move_cartridge(&env, &from, &to, cartridge_id);
+    }
+
+    /// Send a creature from its owner to `to`
+    pub fn transfer_creature(env: Env, from: Address, to: Address, creature_id: u32) {
+        account_auth::require_transfer(&env, &from, creature_id, &to);
+        move_creature(&env, &from, &to, creature_id);
+    }
+
+    /// Allow `spender` to transfer the token (creature, or cartridge before finalization)
+    /// Replaces any earlier approval for the token
+    pub fn approve(env: Env, owner: Address, spender: Address, token_id: u32) {
+        account_auth::require_approve(&env, &owner, token_id, &spender);
+        require_token_owner(&env, &owner, token_id);
+
+        let key = DataKey::Approval(token_id);
+        let approval = TokenApproval {
+            owner: owner.clone(),
+            spender: spender.clone(),
+        };
+        env.storage().persistent().set(&key, &approval);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        events::publish(&env, |nonce| ApprovalSet {
+            token_id,
+            owner,
+            spender: Some(spender),
+            nonce,
+        });
+    }
+
+    /// Withdraw the approval on a token
+    pub fn revoke_approval(env: Env, owner: Address, token_id: u32) {
+        owner.require_auth();
+        require_token_owner(&env, &owner, token_id);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Approval(token_id));
+
+        events::publish(&env, |nonce| ApprovalSet {
+            token_id,
+            owner,
+            spender: None,
+            nonce,
+        });
+    }
+
+    /// Get the spender approved for a token, while its grantor still owns it
+    pub fn get_approved(env: Env, token_id: u32) -> Option<Address> {
+        let approval: TokenApproval = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Approval(token_id))?;
+        let owner = try_token_owner(&env, token_id)?;
+        (approval.owner == owner).then_some(approval.spender)
+    }
+
+    /// Move a token from `from` to `to` as its approved spender
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32) {
+        spender.require_auth();
+        if Self::get_approved(env.clone(), token_id) != Some(spender) {
+            panic_with_error!(&env, TransferError::NotApproved);
+        }
+        if is_creature(&env, token_id) {
+            move_creature(&env, &from, &to, token_id);
+        } else {
+            move_cartridge(&env, &from, &to, token_id);
+        }
+    }
+}
+
+/// Check and perform a cartridge transfer once it has been authorized
+fn move_cartridge(env: &Env, from: &Address, to: &Address, cartridge_id: u32) {
+    let key = DataKey::Cartridge(cartridge_id);
+    let mut cartridge: GenomeCartridge = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| panic_with_error!(env, Error::CartridgeNotFound));
+    if cartridge.owner != *from {
+        panic_with_error!(env, Error::NotCartridgeOwner);
+    }
+    if cartridge.finalized {
+        panic_with_error!(env, Error::AlreadyFinalized);
+    }
+    if from == to {
+        panic_with_error!(env, TransferError::SelfTransfer);
+    }
+
+    cartridge.owner = to.clone();
+    env.storage().persistent().set(&key, &cartridge);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::FinalizationRightPrice(cartridge_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Approval(cartridge_id));
+    move_id(
+        env,
+        DataKey::UserCartridges(from.clone()),
+        DataKey::UserCartridges(to.clone()),
+        cartridge_id,
+    );
+    inbox::notify(env, to, "cartridge_received", cartridge_id);
+
+    events::publish(env, |nonce| CartridgeTransferred {
+        cartridge_id,
+        from: from.clone(),
+        to: to.clone(),
+        nonce,
+    });
+}
+
+/// Check and perform a creature transfer once it has been authorized
+fn move_creature(env: &Env, from: &Address, to: &Address, creature_id: u32) {
+    let key = DataKey::Creature(creature_id);
+    let mut creature: Creature = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| panic_with_error!(env, Error::CreatureNotFound));
+    if creature.owner != *from {
+        panic_with_error!(env, Error::NotCreatureOwner);
+    }
+    if from == to {
+        panic_with_error!(env, TransferError::SelfTransfer);
+    }
+    retirement::assert_active(env, creature_id);
+    if let Err(error) = locks::require_unlocked(env, creature_id) {
+        panic_with_error!(env, error);
+    }
+
+    creature.owner = to.clone();
+    env.storage().persistent().set(&key, &creature);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Approval(creature_id));
+    move_id(
+        env,
+        DataKey::UserCreatures(from.clone()),
+        DataKey::UserCreatures(to.clone()),
+        creature_id,
+    );
+    inbox::notify(env, to, "creature_received", creature_id);
+
+    events::publish(env, |nonce| CreatureTransferred {
+        creature_id,
+        from: from.clone(),
+        to: to.clone(),
+        nonce,
+    });
+}
+
+/// Remove `id` from the ID list under `from_key` and append it to the one under `to_key`
+fn move_id(env: &Env, from_key: DataKey, to_key: DataKey, id: u32) {
+    let mut from_ids: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&from_key)
+        .unwrap_or(Vec::new(env));
+    if let Some(index) = from_ids.first_index_of(id) {
+        from_ids.remove(index);
+    }
+    env.storage().persistent().set(&from_key, &from_ids);
+
+    let mut to_ids: Vec<u32> = env
+        .storage()
+        .persistent()
+        .get(&to_key)
+        .unwrap_or(Vec::new(env));
+    to_ids.push_back(id);
+    env.storage().persistent().set(&to_key, &to_ids);
+    for key in [from_key, to_key] {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+}
+
+/// Whether the token is a creature (its cartridge, if any, is no longer transferable)
+fn is_creature(env: &Env, token_id: u32) -> bool {
+    env.storage().persistent().has(&DataKey::Creature(token_id))
+}
+
+/// Current owner of the transferable token, if it exists
+fn try_token_owner(env: &Env, token_id: u32) -> Option<Address> {
+    if let Some(creature) = env
+        .storage()
+        .persistent()
+        .get::<_, Creature>(&DataKey::Creature(token_id))
+    {
+        return Some(creature.owner);
+    }
+    env.storage()
+        .persistent()
+        .get::<_, GenomeCartridge>(&DataKey::Cartridge(token_id))
+        .map(|cartridge| cartridge.owner)
+}
+
+/// Fail unless `owner` holds the transferable token
+fn require_token_owner(env: &Env, owner: &Address, token_id: u32) {
+    match try_token_owner(env, token_id) {
+        None => panic_with_error!(env, Error::CartridgeNotFound),
+        Some(current) if current == *owner => {}
+        Some(_) if is_creature(env, token_id) => panic_with_error!(env, Error::NotCreatureOwner),
+        Some(_) => panic_with_error!(env, Error::NotCartridgeOwner),
+    }
+}
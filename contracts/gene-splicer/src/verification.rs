@@ -0,0 +1,163 @@
+//! Two-phase drand beacon verification
+//!
+//! Full BLS verification inside finalize_splice can brush against per-transaction
+//! resource limits when bundled with other operations. Verification can instead
+//! be split across transactions:
+//!
+//! 1. `begin_verification` runs the signature subgroup check and keeps the exact
+//!    signature bytes in temporary storage, under the round and the SHA-256 of
+//!    the signature, which it returns
+//! 2. `complete_verification` names that entry by round and digest, runs
+//!    hash-to-curve and the pairing against the stored bytes and records the
+//!    round as verified
+//!
+//! Entries for different signatures never share a key, so nobody can swap the
+//! held signature between another caller's two phases.
+//!
+//! A finalization that runs the full check itself also caches its round. Later
+//! finalizations skip BLS verification when the submitted signature matches
+//! the verified-round cache. Cache entries are bound to the signature and the
+//! drand public key, so rotating the key invalidates them. Both kinds of entry
+//! live in temporary storage and simply expire; verifying again is always possible.
+//!
+//! Each verification also caches the round's randomness, derived from the
+//! verified signature itself. The raw value never leaves this module: callers
+//! get `derive_from_round` of it under their own context tag (see `derivation`).
+
+use crate::{
+    compress_g1, cost_sharing, derive_from_round, events, gc, negate_g1_bytes, params,
+    require_configured, DataKey, Error, GeneSplicer, GeneSplicerArgs, GeneSplicerClient,
+};
+use soroban_sdk::{
+    contractevent, contractimpl, crypto::bls12_381::G1Affine, panic_with_error, Address, Bytes,
+    BytesN, Env, Symbol,
+};
+
+/// Ledgers a begun verification waits for completion (~1 hour)
+const PENDING_VERIFICATION_TTL: u32 = 720;
+
+/// Ledgers a verified round stays cached for finalization (~1 day)
+pub(crate) const VERIFIED_ROUND_TTL: u32 = 17_280;
+
+/// Event emitted when a round's beacon signature passes the pairing check
+#[contractevent]
+pub struct RoundVerified {
+    pub round: u64,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl GeneSplicer {
+    /// Phase 1: subgroup-check a 96-byte uncompressed beacon signature and hold it for `round`
+    /// Permissionless; returns the signature digest that names the entry in phase 2
+    pub fn begin_verification(env: Env, round: u64, signature: Bytes) -> BytesN<32> {
+        let signature = params::require(&env, params::parse_signature_g1(signature));
+        GeneSplicer::negated_signature_point(&env, &signature);
+
+        let digest: BytesN<32> = env.crypto().sha256(&Bytes::from(signature.clone())).into();
+        let key = DataKey::PendingVerification(round, digest.clone());
+        env.storage().temporary().set(&key, &signature);
+        env.storage().temporary().extend_ttl(
+            &key,
+            PENDING_VERIFICATION_TTL,
+            PENDING_VERIFICATION_TTL,
+        );
+        let expiry = env.ledger().sequence() + PENDING_VERIFICATION_TTL;
+        gc::track(&env, "verification", key, expiry.into());
+        digest
+    }
+
+    /// Phase 2: run the pairing check on the signature begin_verification held under `digest`
+    /// On success the round is cached as verified for that exact signature, and
+    /// `verifier` is recorded for cost sharing if the round had no verifier yet
+    pub fn complete_verification(env: Env, verifier: Address, round: u64, digest: BytesN<32>) {
+        verifier.require_auth();
+
+        let key = DataKey::PendingVerification(round, digest);
+        let signature: BytesN<96> = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NoPendingVerification));
+
+        // Subgroup membership was checked in phase 1 on these same bytes
+        let negated = negate_g1_bytes(&env, &signature);
+        GeneSplicer::verify_beacon_pairing(&env, round, G1Affine::from_bytes(negated));
+
+        env.storage().temporary().remove(&key);
+        record_verified(&env, round, &signature, Some(&verifier));
+    }
+
+    /// Check whether `signature` is the cached verified beacon signature for `round`
+    pub fn is_round_verified(env: Env, round: u64, signature: Bytes) -> bool {
+        let signature = params::require(&env, params::parse_signature_g1(signature));
+        is_verified(&env, round, &signature)
+    }
+}
+
+/// Whether finalization can skip BLS verification for this round and signature
+pub(crate) fn is_verified(env: &Env, round: u64, signature: &BytesN<96>) -> bool {
+    let cached: Option<BytesN<32>> = env
+        .storage()
+        .temporary()
+        .get(&DataKey::VerifiedRound(round));
+    cached.is_some_and(|digest| digest == beacon_digest(env, signature))
+}
+
+/// Cache `signature` as the verified beacon for `round`, paid for by `verifier`
+/// A None verifier (the finalization queue crank) leaves the round without one to reimburse
+pub(crate) fn record_verified(
+    env: &Env,
+    round: u64,
+    signature: &BytesN<96>,
+    verifier: Option<&Address>,
+) {
+    let cache_key = DataKey::VerifiedRound(round);
+    env.storage()
+        .temporary()
+        .set(&cache_key, &beacon_digest(env, signature));
+    env.storage()
+        .temporary()
+        .extend_ttl(&cache_key, VERIFIED_ROUND_TTL, VERIFIED_ROUND_TTL);
+    if let Some(verifier) = verifier {
+        cost_sharing::record_verifier(env, round, verifier);
+    }
+
+    let randomness_key = DataKey::VerifiedRandomness(round);
+    let randomness: BytesN<32> = env
+        .crypto()
+        .sha256(&Bytes::from(compress_g1(env, signature)))
+        .into();
+    env.storage().temporary().set(&randomness_key, &randomness);
+    env.storage()
+        .temporary()
+        .extend_ttl(&randomness_key, VERIFIED_ROUND_TTL, VERIFIED_ROUND_TTL);
+
+    events::publish(env, |nonce| RoundVerified { round, nonce });
+}
+
+/// `derive_from_round` of a round verified within the last VERIFIED_ROUND_TTL ledgers
+pub(crate) fn derived_randomness(
+    env: &Env,
+    round: u64,
+    context: &Symbol,
+    entity_id: u64,
+) -> Option<BytesN<32>> {
+    verified_randomness(env, round)
+        .map(|randomness| derive_from_round(env, &randomness, context, entity_id))
+}
+
+/// Raw randomness of a round verified within the last VERIFIED_ROUND_TTL ledgers
+fn verified_randomness(env: &Env, round: u64) -> Option<BytesN<32>> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::VerifiedRandomness(round))
+}
+
+/// SHA256(signature || drand public key), binding a cache entry to both
+fn beacon_digest(env: &Env, signature: &BytesN<96>) -> BytesN<32> {
+    let public_key: Bytes = require_configured(env, &DataKey::DrandPublicKey);
+    let mut preimage = Bytes::from(signature.clone());
+    preimage.append(&public_key);
+    env.crypto().sha256(&preimage).into()
+}
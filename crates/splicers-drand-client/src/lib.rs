@@ -0,0 +1,233 @@
+//! Off-chain drand quicknet glue for the gene-splicer contract
+//!
+//! The contract takes beacons in the CAP-0059 encoding: big-endian field
+//! elements with no flag bits, G1 as `x || y` (96 bytes) and G2 as
+//! `x_c1 || x_c0 || y_c1 || y_c0` (192 bytes). drand publishes compressed
+//! points (48-byte G1 signatures, a 96-byte G2 public key) as hex, so every
+//! relayer and test harness needs the same decoding. This crate is the one
+//! place that does it.
+//!
+//! ```ignore
+//! let beacon = Beacon::from_json(&body)?; // GET {api}/{chain_hash}/public/{round}
+//! let uncompressed = beacon.signature_uncompressed()?;
+//! client.finalize_splice(&id, &beacon.round, &beacon.randomness, &beacon.signature, &uncompressed, &None);
+//! ```
+
+use ark_bls12_381::{G1Affine, G2Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use hex_literal::hex;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Unix timestamp of quicknet round 1
+pub const QUICKNET_GENESIS: u64 = 1692803367;
+
+/// Seconds between quicknet rounds
+pub const QUICKNET_PERIOD: u64 = 3;
+
+/// Quicknet chain hash, as accepted by `finalize_splice`'s `chain_hash`
+pub const QUICKNET_CHAIN_HASH: [u8; 32] =
+    hex!("52db9ba70e0cc0f6eaf7803dd07447a1f5477735fd3f661792ba94600c84e971");
+
+/// Quicknet group public key, compressed G2
+pub const QUICKNET_PUBLIC_KEY: [u8; 96] = hex!(
+    "83cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c"
+    "8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb"
+    "5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a"
+);
+
+/// Hash-to-curve domain separation tag quicknet signs with
+pub const QUICKNET_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Why a beacon or point couldn't be decoded
+#[derive(Debug)]
+pub enum Error {
+    Json(serde_json::Error),
+    Hex(hex::FromHexError),
+    Length { expected: usize, actual: usize },
+    InvalidPoint,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Json(error) => write!(f, "malformed beacon JSON: {error}"),
+            Error::Hex(error) => write!(f, "malformed hex: {error}"),
+            Error::Length { expected, actual } => {
+                write!(f, "expected {expected} bytes, got {actual}")
+            }
+            Error::InvalidPoint => write!(f, "not a BLS12-381 point in the prime-order subgroup"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A quicknet beacon as published by the drand HTTP API
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Beacon {
+    pub round: u64,
+    pub randomness: [u8; 32],
+    pub signature: [u8; 48], // Compressed G1
+}
+
+#[derive(Deserialize)]
+struct BeaconJson {
+    round: u64,
+    randomness: String,
+    signature: String,
+}
+
+impl Beacon {
+    /// Parse the body of `GET {api}/{chain_hash}/public/{round}`
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let raw: BeaconJson = serde_json::from_str(json).map_err(Error::Json)?;
+        Ok(Beacon {
+            round: raw.round,
+            randomness: decode_hex(&raw.randomness)?,
+            signature: decode_hex(&raw.signature)?,
+        })
+    }
+
+    /// The signature in the uncompressed form `finalize_splice` takes
+    pub fn signature_uncompressed(&self) -> Result<[u8; 96], Error> {
+        decompress_g1(&self.signature)
+    }
+
+    /// Whether the randomness is SHA256 of the signature, as the contract checks
+    pub fn randomness_matches(&self) -> bool {
+        let digest: [u8; 32] = Sha256::digest(self.signature).into();
+        digest == self.randomness
+    }
+}
+
+/// Decompress a 48-byte G1 point (a quicknet signature) to 96 bytes
+pub fn decompress_g1(compressed: &[u8; 48]) -> Result<[u8; 96], Error> {
+    let point =
+        G1Affine::deserialize_compressed(&compressed[..]).map_err(|_| Error::InvalidPoint)?;
+    let mut uncompressed = [0u8; 96];
+    point
+        .serialize_uncompressed(&mut uncompressed[..])
+        .map_err(|_| Error::InvalidPoint)?;
+    Ok(uncompressed)
+}
+
+/// Decompress a 96-byte G2 point (a drand public key) to 192 bytes
+pub fn decompress_g2(compressed: &[u8; 96]) -> Result<[u8; 192], Error> {
+    let point =
+        G2Affine::deserialize_compressed(&compressed[..]).map_err(|_| Error::InvalidPoint)?;
+    let mut uncompressed = [0u8; 192];
+    point
+        .serialize_uncompressed(&mut uncompressed[..])
+        .map_err(|_| Error::InvalidPoint)?;
+    Ok(uncompressed)
+}
+
+/// Latest quicknet round emitted at `unix` (0 before genesis)
+pub fn round_for_time(unix: u64) -> u64 {
+    if unix < QUICKNET_GENESIS {
+        return 0;
+    }
+    (unix - QUICKNET_GENESIS) / QUICKNET_PERIOD + 1
+}
+
+/// Unix timestamp at which quicknet emits `round`
+pub fn round_time(round: u64) -> u64 {
+    QUICKNET_GENESIS + round.saturating_sub(1) * QUICKNET_PERIOD
+}
+
+/// Message quicknet signs for `round`: SHA256 of the round as 8 big-endian bytes
+pub fn message_for_round(round: u64) -> [u8; 32] {
+    Sha256::digest(round.to_be_bytes()).into()
+}
+
+fn decode_hex<const N: usize>(text: &str) -> Result<[u8; N], Error> {
+    let bytes = hex::decode(text).map_err(Error::Hex)?;
+    let actual = bytes.len();
+    bytes.try_into().map_err(|_| Error::Length {
+        expected: N,
+        actual,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUND_27448023: &str = r#"{
+        "round": 27448023,
+        "randomness": "f22d19a3d8cd3a181fe8155d051fe006a726b1fe0b18043bda3a2fe4c6c1e5d8",
+        "signature": "967e8a7aa839aa8f672800bb50b1ee29dfa4757d120112c7b858b1f625193a41fb156ad7c69fefc644b9719f88d60313"
+    }"#;
+
+    #[test]
+    fn decompresses_real_signature_to_cap_0059_bytes() {
+        let beacon = Beacon::from_json(ROUND_27448023).unwrap();
+        assert!(beacon.randomness_matches());
+        assert_eq!(
+            beacon.signature_uncompressed().unwrap(),
+            hex!(
+                "167e8a7aa839aa8f672800bb50b1ee29dfa4757d120112c7b858b1f625193a41"
+                "fb156ad7c69fefc644b9719f88d603130165791da033fb75626a46b01aeb3e12"
+                "07d87423db1b5de2dabeb60ee4cc227f750d10de8ec1f77dedd4f311586e5c3e"
+            )
+        );
+    }
+
+    #[test]
+    fn decompresses_quicknet_public_key_to_cap_0059_bytes() {
+        let uncompressed = decompress_g2(&QUICKNET_PUBLIC_KEY).unwrap();
+        // x keeps drand's c1 || c0 order, with the compression flags cleared
+        assert_eq!(uncompressed[..96], {
+            let mut x = QUICKNET_PUBLIC_KEY;
+            x[0] &= 0x1f;
+            x
+        });
+        assert_eq!(
+            uncompressed[96..],
+            hex!(
+                "01a714f2edb74119a2f2b0d5a7c75ba902d163700a61bc224ededd8e63aef7be"
+                "1aaf8e93d7a9718b047ccddb3eb5d68b0e5db2b6bfbb01c867749cadffca88b3"
+                "6c24f3012ba09fc4d3022c5c37dce0f977d3adb5d183c7477c442b1f04515273"
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_beacons() {
+        let mut off_curve = [0u8; 48];
+        off_curve[0] = 0x80;
+        off_curve[47] = 1;
+        assert!(matches!(decompress_g1(&off_curve), Err(Error::InvalidPoint)));
+
+        let short = ROUND_27448023.replace("\"f22d", "\"");
+        assert!(matches!(
+            Beacon::from_json(&short),
+            Err(Error::Length {
+                expected: 32,
+                actual: 30
+            })
+        ));
+        assert!(matches!(Beacon::from_json("{}"), Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn round_timing_matches_quicknet_schedule() {
+        assert_eq!(round_for_time(QUICKNET_GENESIS - 1), 0);
+        assert_eq!(round_for_time(QUICKNET_GENESIS), 1);
+        assert_eq!(round_for_time(QUICKNET_GENESIS + 2), 1);
+        assert_eq!(round_for_time(QUICKNET_GENESIS + 3), 2);
+        for round in [1, 2, 27448023] {
+            assert_eq!(round_for_time(round_time(round)), round);
+        }
+    }
+
+    #[test]
+    fn message_is_sha256_of_big_endian_round() {
+        assert_eq!(
+            message_for_round(27448023),
+            hex!("9363135e3c33cb5ae5ef66eafcb5b346216c64e3940ff6dca5c20a698621bc65")
+        );
+    }
+}
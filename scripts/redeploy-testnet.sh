@@ -9,9 +9,15 @@
 #   - stellar CLI installed
 #   - Stellar identity "testnet-user" configured (stellar keys generate testnet-user --network testnet)
 #   - SSH access to server at 178.156.244.26
+#   - PROVENANCE_HASH: Merkle root over every gene and skin asset's content hash
+#     (see contracts/gene-splicer/src/provenance.rs); fixed forever at deploy
+#   - Optional MINT_FEE (stroops, default 10000000 = 1 XLM) and TREASURY
+#     (address receiving proceeds, default the testnet-user admin)
 
 set -euo pipefail
 
+PROVENANCE_HASH="${PROVENANCE_HASH:?Set PROVENANCE_HASH to the collection provenance root (64 hex chars)}"
+
 echo "=== Splicers Testnet Redeployment ==="
 echo ""
 
@@ -62,7 +68,10 @@ CONTRACT_ID=$(stellar contract deploy \
   --xlm_token "$XLM_TOKEN" \
   --cartridge_skin_count 10 \
   --dev_mode false \
-  --drand_public_key "$DRAND_PUBKEY" 2>&1 | tail -1)
+  --drand_public_key "$DRAND_PUBKEY" \
+  --provenance_hash "$PROVENANCE_HASH" \
+  --mint_fee "${MINT_FEE:-10000000}" \
+  --treasury "${TREASURY:-$TESTNET_USER_ADDR}" 2>&1 | tail -1)
 echo "  Contract ID: $CONTRACT_ID"
 
 # Step 5: Update .env